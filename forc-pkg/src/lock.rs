@@ -55,12 +55,18 @@ pub struct PkgLock {
 pub type PkgDepLine = String;
 
 impl PkgLock {
-    /// Construct a package lock given a package's entry in the package graph.
-    pub fn from_node(graph: &pkg::Graph, node: pkg::NodeIx, disambiguate: &HashSet<&str>) -> Self {
+    /// Construct a package lock given a package's entry in the package graph, for the lock file
+    /// in `lock_dir`.
+    pub fn from_node(
+        graph: &pkg::Graph,
+        node: pkg::NodeIx,
+        disambiguate: &HashSet<&str>,
+        lock_dir: &Path,
+    ) -> Self {
         let pinned = &graph[node];
         let name = pinned.name.clone();
         let version = pinned.source.semver();
-        let source = pinned.source.to_string();
+        let source = pinned.source.to_lock_string(lock_dir);
         // Collection of all dependencies, so this includes both contract-dependencies and
         // lib-dependencies
         let all_dependencies: Vec<(String, DepKind)> = graph
@@ -83,6 +89,7 @@ impl PkgLock {
                         &dep_pkg.source,
                         dep_kind,
                         disambiguate,
+                        lock_dir,
                     ),
                     dep_kind.clone(),
                 )
@@ -158,20 +165,21 @@ impl Lock {
     }
 
     /// Given a graph of pinned packages, create a `Lock` representing the `Forc.lock` file
-    /// structure.
-    pub fn from_graph(graph: &pkg::Graph) -> Self {
+    /// structure, for the lock file in `lock_dir`.
+    pub fn from_graph(graph: &pkg::Graph, lock_dir: &Path) -> Self {
         let names = graph.node_indices().map(|n| &graph[n].name[..]);
         let disambiguate: HashSet<_> = names_requiring_disambiguation(names).collect();
         // Collect the packages.
         let package: BTreeSet<_> = graph
             .node_indices()
-            .map(|node| PkgLock::from_node(graph, node, &disambiguate))
+            .map(|node| PkgLock::from_node(graph, node, &disambiguate, lock_dir))
             .collect();
         Self { package }
     }
 
-    /// Given a `Lock` loaded from a `Forc.lock` file, produce the graph of pinned dependencies.
-    pub fn to_graph(&self) -> Result<pkg::Graph> {
+    /// Given a `Lock` loaded from the `Forc.lock` file in `lock_dir`, produce the graph of pinned
+    /// dependencies.
+    pub fn to_graph(&self, lock_dir: &Path) -> Result<pkg::Graph> {
         let mut graph = pkg::Graph::new();
 
         // Track the names which need to be disambiguated in the dependency list.
@@ -186,7 +194,7 @@ impl Lock {
             // required.
             let key = pkg.name_disambiguated(&disambiguate).into_owned();
             let name = pkg.name.clone();
-            let source: source::Pinned = pkg.source.parse().map_err(|e| {
+            let source = source::Pinned::from_lock_str(&pkg.source, lock_dir).map_err(|e| {
                 anyhow!("invalid 'source' entry for package {} lock: {:?}", name, e)
             })?;
             let pkg = pkg::Pinned { name, source };
@@ -273,9 +281,10 @@ fn pkg_dep_line(
     source: &source::Pinned,
     dep_kind: &DepKind,
     disambiguate: bool,
+    lock_dir: &Path,
 ) -> PkgDepLine {
     // Only include the full unique string in the case that this dep requires disambiguation.
-    let source_string = source.to_string();
+    let source_string = source.to_lock_string(lock_dir);
     let pkg_string = pkg_name_disambiguated(name, &source_string, disambiguate);
     // Prefix the dependency name if it differs from the package name.
     let pkg_string = match dep_name {
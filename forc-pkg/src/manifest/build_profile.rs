@@ -40,6 +40,10 @@ pub struct BuildProfile {
     #[serde(default)]
     pub optimization_level: OptLevel,
     #[serde(default)]
+    pub ir_passes: Option<String>,
+    #[serde(default)]
+    pub arithmetic_safety_analysis: bool,
+    #[serde(default)]
     pub experimental: ExperimentalFlags,
 }
 
@@ -65,6 +69,8 @@ impl BuildProfile {
             error_on_warnings: false,
             reverse_results: false,
             optimization_level: OptLevel::Opt0,
+            ir_passes: None,
+            arithmetic_safety_analysis: false,
             experimental: ExperimentalFlags {
                 new_encoding: false,
             },
@@ -88,6 +94,8 @@ impl BuildProfile {
             error_on_warnings: false,
             reverse_results: false,
             optimization_level: OptLevel::Opt1,
+            ir_passes: None,
+            arithmetic_safety_analysis: false,
             experimental: ExperimentalFlags {
                 new_encoding: false,
             },
@@ -111,7 +119,7 @@ mod tests {
     fn test_build_profile_custom_release_section() {
         let manifest = PackageManifest::from_dir("./tests/sections").expect("manifest");
         let build_profiles = manifest.build_profile.expect("build profile");
-        assert_eq!(build_profiles.len(), 3);
+        assert_eq!(build_profiles.len(), 4);
 
         let expected = BuildProfile::debug();
         let profile = build_profiles.get("debug").expect("debug profile");
@@ -125,6 +133,14 @@ mod tests {
         let profile = build_profiles.get("custom").expect("custom profile");
         assert_eq!(*profile, expected);
 
+        let expected = BuildProfile {
+            name: "".into(),
+            optimization_level: OptLevel::OptSize,
+            ..BuildProfile::debug()
+        };
+        let profile = build_profiles.get("size").expect("size profile");
+        assert_eq!(*profile, expected);
+
         let expected = BuildProfile {
             name: "".into(),
             print_ast: true,
@@ -141,6 +157,8 @@ mod tests {
             error_on_warnings: true,
             reverse_results: true,
             optimization_level: OptLevel::Opt0,
+            ir_passes: None,
+            arithmetic_safety_analysis: true,
             experimental: ExperimentalFlags { new_encoding: true },
         };
         let profile = build_profiles.get("release").expect("release profile");
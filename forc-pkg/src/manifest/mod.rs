@@ -12,7 +12,10 @@ use std::{
     sync::Arc,
 };
 use sway_core::{fuel_prelude::fuel_tx, language::parsed::TreeType, parse_tree_type, BuildTarget};
-use sway_error::handler::Handler;
+use sway_error::{
+    handler::Handler,
+    lint::{Lint, LintLevel},
+};
 use sway_utils::{
     constants, find_nested_manifest_dir, find_parent_manifest_dir,
     find_parent_manifest_dir_with_check,
@@ -184,6 +187,9 @@ pub struct PackageManifest {
     pub build_target: Option<BTreeMap<String, BuildTarget>>,
     build_profile: Option<BTreeMap<String, BuildProfile>>,
     pub contract_dependencies: Option<BTreeMap<String, ContractDependency>>,
+    /// The levels of the lints for the whole package, overridden by `#[allow(..)]`,
+    /// `#[warn(..)]` and `#[deny(..)]` attributes in the code.
+    pub lints: Option<BTreeMap<Lint, LintLevel>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -240,6 +246,7 @@ pub struct DependencyDetails {
     pub(crate) package: Option<String>,
     pub(crate) rev: Option<String>,
     pub(crate) ipfs: Option<String>,
+    pub(crate) registry: Option<String>,
 }
 
 impl DependencyDetails {
@@ -248,18 +255,24 @@ impl DependencyDetails {
     ///
     /// Following dependency details sets are considered to be invalid:
     /// 1. A set of dependency details which declares `branch`, `tag` or `rev` without `git`.
+    /// 2. A set of dependency details which declares `registry` without `version`.
     pub fn validate(&self) -> anyhow::Result<()> {
         let DependencyDetails {
+            version,
             git,
             branch,
             tag,
             rev,
+            registry,
             ..
         } = self;
 
         if git.is_none() && (branch.is_some() || tag.is_some() || rev.is_some()) {
             bail!("Details reserved for git sources used without a git field");
         }
+        if version.is_none() && registry.is_some() {
+            bail!("Details reserved for registry sources used without a version field");
+        }
         Ok(())
     }
 }
@@ -1030,6 +1043,7 @@ mod tests {
             package: None,
             rev: None,
             ipfs: None,
+            registry: None,
         };
 
         let dependency_details_branch = DependencyDetails {
@@ -1052,6 +1066,7 @@ mod tests {
             package: None,
             rev: None,
             ipfs: None,
+            registry: None,
         };
 
         let dependency_details_tag = DependencyDetails {
@@ -1074,6 +1089,7 @@ mod tests {
             package: None,
             ipfs: None,
             rev: Some("9f35b8e".to_string()),
+            registry: None,
         };
 
         let dependency_details_rev = DependencyDetails {
@@ -1151,6 +1167,18 @@ mod tests {
                 .map(|e| e.to_string()),
             Some(expected_mismatch_error.to_string())
         );
+
+        let dependency_details_registry_no_version = DependencyDetails {
+            registry: Some("https://github.com/FuelLabs/example-index".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            dependency_details_registry_no_version
+                .validate()
+                .err()
+                .map(|e| e.to_string()),
+            Some("Details reserved for registry sources used without a version field".to_string())
+        );
     }
 
     #[test]
@@ -1187,6 +1215,31 @@ mod tests {
         assert_eq!(patches.len(), 1);
     }
 
+    #[test]
+    fn test_lints_section() {
+        let manifest = PackageManifest::from_dir("./tests/sections").expect("manifest");
+        let lints = manifest.lints.expect("lints");
+        assert_eq!(lints.len(), 2);
+        assert_eq!(lints.get(&Lint::DeadCode), Some(&LintLevel::Allow));
+        assert_eq!(
+            lints.get(&Lint::UncheckedArithmetic),
+            Some(&LintLevel::Deny)
+        );
+
+        let manifest: Result<PackageManifest, _> = toml::from_str(
+            r#"
+            [project]
+            authors = ["Fuel Labs <contact@fuel.sh>"]
+            license = "Apache-2.0"
+            name = "unknown_lint"
+
+            [lints]
+            no_such_lint = "warn"
+            "#,
+        );
+        assert!(manifest.is_err());
+    }
+
     #[test]
     fn test_valid_dependency_details() {
         let dependency_details_path = DependencyDetails {
@@ -1198,6 +1251,7 @@ mod tests {
             package: None,
             rev: None,
             ipfs: None,
+            registry: None,
         };
 
         let git_source_string = "https://github.com/FuelLabs/sway".to_string();
@@ -1210,6 +1264,7 @@ mod tests {
             package: None,
             rev: None,
             ipfs: None,
+            registry: None,
         };
         let dependency_details_git_branch = DependencyDetails {
             version: None,
@@ -1220,6 +1275,7 @@ mod tests {
             package: None,
             rev: None,
             ipfs: None,
+            registry: None,
         };
         let dependency_details_git_rev = DependencyDetails {
             version: None,
@@ -1230,6 +1286,7 @@ mod tests {
             package: None,
             rev: Some("9f35b8e".to_string()),
             ipfs: None,
+            registry: None,
         };
 
         let dependency_details_ipfs = DependencyDetails {
@@ -1241,6 +1298,19 @@ mod tests {
             package: None,
             rev: None,
             ipfs: Some("QmVxgEbiDDdHpG9AesCpZAqNvHYp1P3tWLFdrpUBWPMBcc".to_string()),
+            registry: None,
+        };
+
+        let dependency_details_registry = DependencyDetails {
+            version: Some("0.1".to_string()),
+            path: None,
+            git: None,
+            branch: None,
+            tag: None,
+            package: None,
+            rev: None,
+            ipfs: None,
+            registry: Some("https://github.com/FuelLabs/example-index".to_string()),
         };
 
         assert!(dependency_details_path.validate().is_ok());
@@ -1248,5 +1318,6 @@ mod tests {
         assert!(dependency_details_git_branch.validate().is_ok());
         assert!(dependency_details_git_rev.validate().is_ok());
         assert!(dependency_details_ipfs.validate().is_ok());
+        assert!(dependency_details_registry.validate().is_ok());
     }
 }
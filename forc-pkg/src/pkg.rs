@@ -11,8 +11,8 @@ use crate::{
 use anyhow::{anyhow, bail, Context, Error, Result};
 use forc_tracing::println_warning;
 use forc_util::{
-    default_output_directory, find_file_name, kebab_to_snake_case, print_compiling,
-    print_on_failure, print_warnings,
+    default_output_directory, dependency_cache_directory, find_file_name, kebab_to_snake_case,
+    print_compiling, print_on_failure, print_warnings,
 };
 use fuel_abi_types::abi::program as program_abi;
 use petgraph::{
@@ -22,7 +22,7 @@ use petgraph::{
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{hash_map, BTreeSet, HashMap, HashSet},
+    collections::{hash_map, BTreeMap, BTreeSet, HashMap, HashSet},
     fmt,
     fs::{self, File},
     hash::{Hash, Hasher},
@@ -39,19 +39,23 @@ use sway_core::{
     },
     asm_generation::ProgramABI,
     decl_engine::DeclRefFunction,
+    dependency_cache::{DependencyCache, DependencyCacheKey},
     fuel_prelude::{
         fuel_crypto,
         fuel_tx::{self, Contract, ContractId, StorageSlot},
     },
     language::{parsed::TreeType, Visibility},
-    semantic_analysis::namespace,
+    semantic_analysis::{
+        namespace,
+        storage_access_analysis::{self, StorageAccessReport},
+    },
     source_map::SourceMap,
     transform::AttributeKind,
-    BuildTarget, Engines, FinalizedEntry, LspConfig,
+    BuildTarget, Engines, FinalizedEntry, LspConfig, TypeId, TypeInfo,
 };
 use sway_error::{error::CompileError, handler::Handler, warning::CompileWarning};
 use sway_types::constants::{CORE, PRELUDE, STD};
-use sway_types::{Ident, Span, Spanned};
+use sway_types::{integer_bits::IntegerBits, Ident, Span, Spanned};
 use sway_utils::{constants, time_expr, PerformanceData, PerformanceMetric};
 use tracing::{debug, info};
 
@@ -106,6 +110,8 @@ pub struct BuiltPackage {
     pub descriptor: PackageDescriptor,
     pub program_abi: ProgramABI,
     pub storage_slots: Vec<StorageSlot>,
+    /// The storage fields read and written by each entry point, for contracts only.
+    pub storage_access: Option<StorageAccessReport>,
     pub warnings: Vec<CompileWarning>,
     pub source_map: SourceMap,
     pub tree_type: TreeType,
@@ -116,6 +122,8 @@ pub struct BuiltPackage {
     ///
     /// For non-contract members, this is always `None`.
     pub bytecode_without_tests: Option<BuiltPackageBytecode>,
+    /// The source map associated with `bytecode_without_tests`.
+    pub source_map_without_tests: Option<SourceMap>,
 }
 
 /// The package descriptors that a `BuiltPackage` holds so that the source used for building the
@@ -133,6 +141,9 @@ pub struct PackageDescriptor {
 pub struct BuiltPackageBytecode {
     pub bytes: Vec<u8>,
     pub entries: Vec<PkgEntry>,
+    /// The instruction offset at which each function begins within `bytes`, mapped to the
+    /// function's name.
+    pub fn_offsets: BTreeMap<u64, String>,
 }
 
 /// Represents a package entry point.
@@ -162,6 +173,36 @@ pub struct PkgTestEntry {
     pub pass_condition: TestPassCondition,
     pub span: Span,
     pub file_path: Arc<PathBuf>,
+    /// The parameters of the test function, for which arguments are generated when running
+    /// property-based tests.
+    pub params: Vec<TestParam>,
+    /// The number of runs specified with `#[test(runs = N)]`, if any.
+    pub runs: Option<u64>,
+}
+
+/// A parameter of a test function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestParam {
+    pub name: String,
+    pub ty: TestParamType,
+}
+
+/// The types supported as parameters of test functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestParamType {
+    Bool,
+    /// An unsigned integer with the given number of bits.
+    Uint(usize),
+    B256,
+    /// A string array of the given length.
+    StrArray(usize),
+    /// An array of the given element type and length.
+    Array(Box<TestParamType>, usize),
+    Tuple(Vec<TestParamType>),
+    Struct {
+        name: String,
+        fields: Vec<TestParam>,
+    },
 }
 
 /// The result of successfully compiling a workspace.
@@ -181,6 +222,7 @@ pub struct CompiledPackage {
     pub tree_type: TreeType,
     pub program_abi: ProgramABI,
     pub storage_slots: Vec<StorageSlot>,
+    pub storage_access: Option<StorageAccessReport>,
     pub bytecode: BuiltPackageBytecode,
     pub root_module: namespace::Module,
     pub warnings: Vec<CompileWarning>,
@@ -304,6 +346,8 @@ pub struct BuildOpts {
     pub release: bool,
     /// Output the time elapsed over each part of the compilation process.
     pub time_phases: bool,
+    /// If set, the textual IR pass pipeline to run instead of the one of the optimization level.
+    pub ir_passes: Option<String>,
     /// If set, outputs compilation metrics info in JSON format.
     pub metrics_outfile: Option<String>,
     /// Warnings must be treated as compiler errors.
@@ -508,6 +552,15 @@ impl BuiltPackage {
                 };
 
                 res?;
+
+                // And another one with the storage fields read and written by each entry point.
+                if let Some(storage_access) = &self.storage_access {
+                    let storage_access_stem = format!("{pkg_name}-storage_access");
+                    let storage_access_path =
+                        output_dir.join(storage_access_stem).with_extension("json");
+                    let storage_access_file = File::create(storage_access_path)?;
+                    serde_json::to_writer_pretty(&storage_access_file, storage_access)?;
+                }
             }
             TreeType::Predicate => {
                 // Get the root hash of the bytecode for predicates and store the result in a file in the output directory
@@ -665,7 +718,8 @@ impl BuildPlan {
         });
 
         // Next, construct the package graph from the lock.
-        let mut graph = lock.to_graph().unwrap_or_else(|e| {
+        let lock_dir = lock_path.parent().unwrap_or(lock_path);
+        let mut graph = lock.to_graph(lock_dir).unwrap_or_else(|e| {
             new_lock_cause = Some(anyhow!("Invalid lock: {}", e));
             Graph::default()
         });
@@ -705,7 +759,7 @@ impl BuildPlan {
         };
 
         // Construct the new lock and check the diff.
-        let new_lock = Lock::from_graph(plan.graph());
+        let new_lock = Lock::from_graph(plan.graph(), lock_dir);
         let lock_diff = new_lock.diff(&lock);
         if !lock_diff.removed.is_empty() || !lock_diff.added.is_empty() {
             new_lock_cause.get_or_insert(anyhow!("lock file did not match manifest"));
@@ -1566,6 +1620,7 @@ pub fn sway_build_config(
     .with_time_phases(build_profile.time_phases)
     .with_metrics(build_profile.metrics_outfile.clone())
     .with_optimization_level(build_profile.optimization_level)
+    .with_ir_passes(build_profile.ir_passes.clone())
     .with_experimental(sway_core::ExperimentalFlags {
         new_encoding: build_profile.experimental.new_encoding,
     });
@@ -1763,8 +1818,12 @@ pub fn compile(
     let mut metrics = PerformanceData::default();
 
     let entry_path = pkg.manifest_file.entry_path();
+    // Opt-in analyses are meant for the packages being worked on rather than their dependencies.
+    let is_member = pkg.pinned.source == source::Pinned::MEMBER;
     let sway_build_config =
-        sway_build_config(pkg.manifest_file.dir(), &entry_path, pkg.target, profile)?;
+        sway_build_config(pkg.manifest_file.dir(), &entry_path, pkg.target, profile)?
+            .with_arithmetic_safety_analysis(profile.arithmetic_safety_analysis && is_member)
+            .with_lint_levels(pkg.manifest_file.lints.clone().unwrap_or_default());
     let terse_mode = profile.terse;
     let reverse_results = profile.reverse_results;
     let fail = |handler: Handler| {
@@ -1813,6 +1872,7 @@ pub fn compile(
     }
 
     let storage_slots = typed_program.storage_slots.clone();
+    let storage_access = storage_access_analysis::storage_access_report(engines, typed_program);
     let tree_type = typed_program.kind.tree_type();
 
     let namespace = typed_program.root.namespace.clone();
@@ -1889,6 +1949,14 @@ pub fn compile(
         .iter()
         .map(|finalized_entry| PkgEntry::from_finalized_entry(finalized_entry, engines))
         .collect::<anyhow::Result<_>>()?;
+    let fn_offsets = asm_res
+        .as_ref()
+        .map(|asm| asm.0.fn_offsets.clone())
+        .unwrap_or_default();
+    metrics.spills = asm_res
+        .as_ref()
+        .map(|asm| asm.0.fn_spills.clone())
+        .unwrap_or_default();
 
     let asm = match asm_res {
         Err(_) => return fail(handler),
@@ -1933,11 +2001,13 @@ pub fn compile(
     let bytecode = BuiltPackageBytecode {
         bytes: compiled.bytecode,
         entries,
+        fn_offsets,
     };
     let compiled_package = CompiledPackage {
         source_map: source_map.clone(),
         program_abi,
         storage_slots,
+        storage_access,
         tree_type,
         bytecode,
         root_module: namespace.root_module().clone(),
@@ -1985,6 +2055,7 @@ impl PkgTestEntry {
         let test_function_decl = engines.de().get_function(&decl_ref);
 
         const FAILING_TEST_KEYWORD: &str = "should_revert";
+        const RUNS_KEYWORD: &str = "runs";
 
         let test_args: HashMap<String, Option<String>> = test_function_decl
             .attributes
@@ -2002,21 +2073,53 @@ impl PkgTestEntry {
             })
             .collect();
 
-        let pass_condition = if test_args.is_empty() {
-            anyhow::Ok(TestPassCondition::ShouldNotRevert)
-        } else if let Some(args) = test_args.get(FAILING_TEST_KEYWORD) {
-            let expected_revert_code = args
-                .as_ref()
-                .map(|arg| {
-                    let arg_str = arg.replace('"', "");
-                    arg_str.parse::<u64>()
+        let test_name = &test_function_decl.name;
+        let mut pass_condition = TestPassCondition::ShouldNotRevert;
+        let mut runs = None;
+        for (name, value) in &test_args {
+            match name.as_str() {
+                FAILING_TEST_KEYWORD => {
+                    let expected_revert_code = value
+                        .as_ref()
+                        .map(|arg| {
+                            let arg_str = arg.replace('"', "");
+                            arg_str.parse::<u64>()
+                        })
+                        .transpose()?;
+                    pass_condition = TestPassCondition::ShouldRevert(expected_revert_code);
+                }
+                RUNS_KEYWORD => {
+                    let value = value
+                        .as_ref()
+                        .ok_or_else(|| anyhow!("Missing number of runs for test: {test_name}."))?;
+                    runs = Some(value.replace('"', "").parse::<u64>()?);
+                }
+                _ => bail!("Invalid test argument(s) for test: {test_name}."),
+            }
+        }
+
+        let params = test_function_decl
+            .parameters
+            .iter()
+            .map(|param| {
+                let ty = TestParamType::from_type_id(param.type_argument.type_id, engines)
+                    .with_context(|| {
+                        format!("Invalid parameter `{}` for test: {test_name}.", param.name)
+                    })?;
+                Ok(TestParam {
+                    name: param.name.to_string(),
+                    ty,
                 })
-                .transpose()?;
-            anyhow::Ok(TestPassCondition::ShouldRevert(expected_revert_code))
-        } else {
-            let test_name = &test_function_decl.name;
-            bail!("Invalid test argument(s) for test: {test_name}.")
-        }?;
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if runs == Some(0) {
+            bail!("The number of runs must be greater than zero for test: {test_name}.");
+        }
+        if runs.is_some() && params.is_empty() {
+            bail!(
+                "The number of runs may only be specified for tests with parameters: {test_name}."
+            );
+        }
 
         let file_path = Arc::new(
             engines.se().get_path(
@@ -2028,10 +2131,59 @@ impl PkgTestEntry {
             pass_condition,
             span,
             file_path,
+            params,
+            runs,
         })
     }
 }
 
+impl TestParamType {
+    fn from_type_id(type_id: TypeId, engines: &Engines) -> Result<Self> {
+        let ty = match &*engines.te().get(type_id) {
+            TypeInfo::Boolean => Self::Bool,
+            TypeInfo::UnsignedInteger(bits) => Self::Uint(match bits {
+                IntegerBits::Eight => 8,
+                IntegerBits::Sixteen => 16,
+                IntegerBits::ThirtyTwo => 32,
+                IntegerBits::SixtyFour => 64,
+                IntegerBits::V256 => 256,
+            }),
+            TypeInfo::B256 => Self::B256,
+            TypeInfo::StringArray(length) => Self::StrArray(length.val()),
+            TypeInfo::Array(elem, length) => Self::Array(
+                Box::new(Self::from_type_id(elem.type_id, engines)?),
+                length.val(),
+            ),
+            TypeInfo::Tuple(fields) => Self::Tuple(
+                fields
+                    .iter()
+                    .map(|field| Self::from_type_id(field.type_id, engines))
+                    .collect::<Result<_>>()?,
+            ),
+            TypeInfo::Struct(decl_ref) => {
+                let decl = engines.de().get_struct(decl_ref);
+                let fields = decl
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        Ok(TestParam {
+                            name: field.name.to_string(),
+                            ty: Self::from_type_id(field.type_argument.type_id, engines)?,
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                Self::Struct {
+                    name: decl.call_path.suffix.to_string(),
+                    fields,
+                }
+            }
+            TypeInfo::Alias { ty, .. } => Self::from_type_id(ty.type_id, engines)?,
+            _ => bail!("unsupported type `{}`", engines.help_out(type_id)),
+        };
+        Ok(ty)
+    }
+}
+
 /// The suffix that helps identify the file which contains the hash of the binary file created when
 /// scripts are built_package.
 pub const SWAY_BIN_HASH_SUFFIX: &str = "-bin-hash";
@@ -2049,6 +2201,7 @@ fn build_profile_from_opts(
         pkg,
         print,
         time_phases,
+        ir_passes,
         build_profile,
         release,
         metrics_outfile,
@@ -2088,6 +2241,9 @@ fn build_profile_from_opts(
     profile.print_intermediate_asm |= print.intermediate_asm;
     profile.terse |= pkg.terse;
     profile.time_phases |= time_phases;
+    if ir_passes.is_some() {
+        profile.ir_passes = ir_passes.clone();
+    }
     if profile.metrics_outfile.is_none() {
         profile.metrics_outfile = metrics_outfile.clone();
     }
@@ -2121,6 +2277,15 @@ fn is_contract_dependency(graph: &Graph, node: NodeIx) -> bool {
 
 /// Builds a project with given BuildOptions.
 pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
+    let build_plan = BuildPlan::from_build_opts(&build_options)?;
+    build_with_plan(&build_options, &build_plan)
+}
+
+/// Builds a project with the given build plan, rather than creating a new one.
+///
+/// This allows for building a project repeatedly without resolving or fetching its dependencies
+/// each time.
+pub fn build_with_plan(build_options: &BuildOpts, build_plan: &BuildPlan) -> Result<Built> {
     let BuildOpts {
         minify,
         binary_outfile,
@@ -2130,7 +2295,7 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
         member_filter,
         experimental,
         ..
-    } = &build_options;
+    } = build_options;
 
     let current_dir = std::env::current_dir()?;
     let path = &build_options
@@ -2140,7 +2305,6 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
         .map(PathBuf::from)
         .unwrap_or_else(|| current_dir);
 
-    let build_plan = BuildPlan::from_build_opts(&build_options)?;
     let graph = build_plan.graph();
     let manifest_map = build_plan.manifest_map();
 
@@ -2151,7 +2315,7 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
         .find(|&pkg_manifest| pkg_manifest.dir() == path);
     let build_profiles: HashMap<String, BuildProfile> = build_plan.build_profiles().collect();
     // Get the selected build profile using build options
-    let build_profile = build_profile_from_opts(&build_profiles, &build_options)?;
+    let build_profile = build_profile_from_opts(&build_profiles, build_options)?;
     // If this is a workspace we want to have all members in the output.
     let outputs = match curr_manifest {
         Some(pkg_manifest) => std::iter::once(
@@ -2163,13 +2327,13 @@ pub fn build_with_options(build_options: BuildOpts) -> Result<Built> {
         None => build_plan.member_nodes().collect(),
     };
 
-    let outputs = member_filter.filter_outputs(&build_plan, outputs);
+    let outputs = member_filter.filter_outputs(build_plan, outputs);
 
     // Build it!
     let mut built_workspace = Vec::new();
     let build_start = std::time::Instant::now();
     let built_packages = build(
-        &build_plan,
+        build_plan,
         *build_target,
         &build_profile,
         &outputs,
@@ -2295,13 +2459,26 @@ pub fn build(
     // We will need this for `forc test`.
     let mut contract_id_value: Option<ContractIdConst> = None;
 
-    let mut lib_namespace_map = Default::default();
-    let mut compiled_contract_deps = HashMap::new();
-    for &node in plan
+    let nodes: Vec<_> = plan
         .compilation_order
         .iter()
+        .cloned()
         .filter(|node| required.contains(node))
-    {
+        .collect();
+    let dependency_cache = DependencyCache::new(dependency_cache_directory());
+    let dependency_cache_keys = dependency_cache_keys(plan, &nodes, |manifest| {
+        // Dependency libraries are always built with tests disabled, see below.
+        let profile = BuildProfile {
+            include_tests: false,
+            ..profile.clone()
+        };
+        sway_build_config(manifest.dir(), &manifest.entry_path(), target, &profile)
+    })?;
+    let cached_nodes = load_dependency_cache(&dependency_cache, &dependency_cache_keys, &engines);
+
+    let mut lib_namespace_map = Default::default();
+    let mut compiled_contract_deps = HashMap::new();
+    for (idx, &node) in nodes.iter().enumerate() {
         let mut source_map = SourceMap::new();
         let pkg = &plan.graph()[node];
         let manifest = &plan.manifest_map()[&pkg.id()];
@@ -2334,7 +2511,7 @@ pub fn build(
         let is_contract_dependency = is_contract_dependency(plan.graph(), node);
         // If we are building a contract and tests are enabled or we are building a contract
         // dependency, we need the tests exlcuded bytecode.
-        let bytecode_without_tests = if (include_tests
+        let (bytecode_without_tests, source_map_without_tests) = if (include_tests
             && matches!(manifest.program_type(), Ok(TreeType::Contract)))
             || is_contract_dependency
         {
@@ -2364,13 +2541,16 @@ pub fn build(
                 Err(errs) => return fail(&[], &errs),
             };
 
+            // The tests excluded bytecode has a distinct layout, so it gets its own source map.
+            let mut source_map_without_tests = SourceMap::new();
             let compiled_without_tests = compile(
                 &descriptor,
                 &profile,
                 &engines,
                 dep_namespace,
-                &mut source_map,
+                &mut source_map_without_tests,
             )?;
+            source_map_without_tests.insert_dependency(descriptor.manifest_file.dir());
 
             if let Some(outfile) = profile.metrics_outfile {
                 let path = Path::new(&outfile);
@@ -2399,9 +2579,12 @@ pub fn build(
                 // We finally set the contract ID value here to use for compilation later if tests are enabled.
                 contract_id_value = Some(format!("0x{contract_id}"));
             }
-            Some(compiled_without_tests.bytecode)
+            (
+                Some(compiled_without_tests.bytecode),
+                Some(source_map_without_tests),
+            )
         } else {
-            None
+            (None, None)
         };
 
         // Build all non member nodes with tests disabled by overriding the current profile.
@@ -2458,6 +2641,11 @@ pub fn build(
             lib_namespace_map.insert(node, root_module);
         }
         source_map.insert_dependency(descriptor.manifest_file.dir());
+        if idx >= cached_nodes {
+            if let Some(key) = dependency_cache_keys.get(idx) {
+                store_dependency_cache(&dependency_cache, key, &engines);
+            }
+        }
 
         // TODO: This should probably be in `fuel_abi_json::generate_json_abi_program`?
         if let ProgramABI::Fuel(ref mut program_abi) = compiled.program_abi {
@@ -2468,11 +2656,13 @@ pub fn build(
             descriptor,
             program_abi: compiled.program_abi,
             storage_slots: compiled.storage_slots,
+            storage_access: compiled.storage_access,
             source_map: compiled.source_map,
             tree_type: compiled.tree_type,
             bytecode: compiled.bytecode,
             warnings: compiled.warnings,
             bytecode_without_tests,
+            source_map_without_tests,
         };
 
         if outputs.contains(&node) {
@@ -2483,6 +2673,67 @@ pub fn build(
     Ok(built_packages)
 }
 
+/// Returns the [DependencyCacheKey]s of the longest prefix of `nodes` made of dependency
+/// libraries, where the key at index `i` identifies the state of the engines after compiling the
+/// first `i + 1` nodes using the build configs returned by `build_config`.
+fn dependency_cache_keys(
+    plan: &BuildPlan,
+    nodes: &[NodeIx],
+    build_config: impl Fn(&PackageManifestFile) -> Result<sway_core::BuildConfig>,
+) -> Result<Vec<DependencyCacheKey>> {
+    let mut keys: Vec<DependencyCacheKey> = vec![];
+    for &node in nodes {
+        let pkg = &plan.graph()[node];
+        let manifest = &plan.manifest_map()[&pkg.id()];
+        if pkg.source == source::Pinned::MEMBER
+            || !matches!(manifest.program_type(), Ok(TreeType::Library))
+        {
+            break;
+        }
+        // The namespace of a package depends on the names under which it imports its
+        // dependencies.
+        let mut deps: Vec<_> = plan
+            .graph()
+            .edges_directed(node, Direction::Outgoing)
+            .map(|edge| (&edge.weight().name, &plan.graph()[edge.target()].name))
+            .collect();
+        deps.sort();
+        let package = format!("{} {:?}", pkg.name, deps);
+        let key = keys
+            .last()
+            .cloned()
+            .unwrap_or_default()
+            .with_package(&package, &build_config(manifest)?);
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+/// Restores the state of the engines cached for the longest prefix of `keys` into `engines`.
+///
+/// Returns the number of nodes whose compilation results were restored.
+fn load_dependency_cache(
+    cache: &DependencyCache,
+    keys: &[DependencyCacheKey],
+    engines: &Engines,
+) -> usize {
+    let restored = keys
+        .iter()
+        .rposition(|key| cache.load(key, engines))
+        .map_or(0, |idx| idx + 1);
+    if restored > 0 {
+        debug!("Restored the compiler state of {restored} dependencies from the cache");
+    }
+    restored
+}
+
+/// Caches the state of `engines` for `key`. Failing to do so only costs a later build some time.
+fn store_dependency_cache(cache: &DependencyCache, key: &DependencyCacheKey, engines: &Engines) {
+    if let Err(err) = cache.store(key, engines) {
+        debug!("Failed to cache the compiler state of dependencies: {err}");
+    }
+}
+
 /// Standardize the JSON ABI data structure by eliminating duplicate types. This is an iterative
 /// process because every time two types are merged, new opportunities for more merging arise.
 fn standardize_json_abi_types(json_abi_program: &mut program_abi::ProgramABI) {
@@ -2626,12 +2877,80 @@ pub fn check(
     engines: &Engines,
     retrigger_compilation: Option<Arc<AtomicBool>>,
     experimental: sway_core::ExperimentalFlags,
+) -> anyhow::Result<Vec<(Option<Programs>, Handler)>> {
+    check_packages(
+        plan,
+        build_target,
+        terse_mode,
+        lsp_mode,
+        include_tests,
+        engines,
+        retrigger_compilation,
+        experimental,
+        false,
+    )
+}
+
+/// Like [check], but also runs the opt-in lints, e.g. the arithmetic safety analysis, on the
+/// members of the build plan.
+pub fn lint(
+    plan: &BuildPlan,
+    build_target: BuildTarget,
+    terse_mode: bool,
+    include_tests: bool,
+    engines: &Engines,
+    experimental: sway_core::ExperimentalFlags,
+) -> anyhow::Result<Vec<(Option<Programs>, Handler)>> {
+    check_packages(
+        plan,
+        build_target,
+        terse_mode,
+        None,
+        include_tests,
+        engines,
+        None,
+        experimental,
+        true,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_packages(
+    plan: &BuildPlan,
+    build_target: BuildTarget,
+    terse_mode: bool,
+    lsp_mode: Option<LspConfig>,
+    include_tests: bool,
+    engines: &Engines,
+    retrigger_compilation: Option<Arc<AtomicBool>>,
+    experimental: sway_core::ExperimentalFlags,
+    opt_in_lints: bool,
 ) -> anyhow::Result<Vec<(Option<Programs>, Handler)>> {
     let mut lib_namespace_map = Default::default();
     let mut source_map = SourceMap::new();
     // During `check`, we don't compile so this stays empty.
     let compiled_contract_deps = HashMap::new();
 
+    let profile = BuildProfile {
+        terse: terse_mode,
+        ..BuildProfile::debug()
+    };
+    let dependency_cache = DependencyCache::new(dependency_cache_directory());
+    let dependency_cache_keys = dependency_cache_keys(plan, &plan.compilation_order, |manifest| {
+        Ok(sway_build_config(
+            manifest.dir(),
+            &manifest.entry_path(),
+            build_target,
+            &profile,
+        )?
+        .with_include_tests(include_tests)
+        .with_lsp_mode(lsp_mode.clone()))
+    })?;
+    // Engines reused across checks, e.g. by the language server, already hold the compiled
+    // dependencies in memory and restore nothing. They still store the dependencies which aren't
+    // cached yet, so that later sessions can restore them.
+    load_dependency_cache(&dependency_cache, &dependency_cache_keys, engines);
+
     let mut results = vec![];
     for (idx, &node) in plan.compilation_order.iter().enumerate() {
         let pkg = &plan.graph[node];
@@ -2660,11 +2979,6 @@ pub fn check(
         )
         .expect("failed to create dependency namespace");
 
-        let profile = BuildProfile {
-            terse: terse_mode,
-            ..BuildProfile::debug()
-        };
-
         let build_config = sway_build_config(
             manifest.dir(),
             &manifest.entry_path(),
@@ -2672,7 +2986,9 @@ pub fn check(
             &profile,
         )?
         .with_include_tests(include_tests)
-        .with_lsp_mode(lsp_mode.clone());
+        .with_lsp_mode(lsp_mode.clone())
+        .with_arithmetic_safety_analysis(opt_in_lints && pkg.source == source::Pinned::MEMBER)
+        .with_lint_levels(manifest.lints.clone().unwrap_or_default());
 
         let input = manifest.entry_string()?;
         let handler = Handler::default();
@@ -2720,6 +3036,11 @@ pub fn check(
                 }
 
                 source_map.insert_dependency(manifest.dir());
+                if let Some(key) = dependency_cache_keys.get(idx) {
+                    if !dependency_cache.contains(key) {
+                        store_dependency_cache(&dependency_cache, key, engines);
+                    }
+                }
             }
             Err(_) => {
                 results.push((programs_res.ok(), handler));
@@ -2798,6 +3119,60 @@ mod test {
         .unwrap()
     }
 
+    #[test]
+    fn test_storage_access_report() {
+        let fixture_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("storage_access");
+        let core_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("sway-lib-core");
+        // Build a copy of the contract, so that its lock file and outputs are not written
+        // into the repository.
+        let pkg_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            pkg_dir.path().join("Forc.toml"),
+            format!(
+                "[project]\n\
+                authors = [\"Fuel Labs <contact@fuel.sh>\"]\n\
+                entry = \"main.sw\"\n\
+                license = \"Apache-2.0\"\n\
+                name = \"storage_access\"\n\
+                \n\
+                [dependencies]\n\
+                core = {{ path = {:?} }}\n",
+                core_dir.display().to_string()
+            ),
+        )
+        .unwrap();
+        fs::create_dir(pkg_dir.path().join("src")).unwrap();
+        fs::copy(
+            fixture_dir.join("src").join("main.sw"),
+            pkg_dir.path().join("src").join("main.sw"),
+        )
+        .unwrap();
+
+        let output_dir = pkg_dir.path().join("out");
+        build_with_options(BuildOpts {
+            pkg: PkgOpts {
+                path: Some(pkg_dir.path().display().to_string()),
+                output_directory: Some(output_dir.display().to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+
+        let read_json = |path: PathBuf| -> serde_json::Value {
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+        };
+        assert_eq!(
+            read_json(output_dir.join("storage_access-storage_access.json")),
+            read_json(fixture_dir.join("storage_access.json"))
+        );
+    }
+
     #[test]
     fn test_root_pkg_order() {
         let build_plan = setup_build_plan();
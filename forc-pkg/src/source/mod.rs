@@ -11,7 +11,7 @@ pub mod git;
 pub(crate) mod ipfs;
 mod member;
 pub mod path;
-mod reg;
+pub mod reg;
 
 use self::git::Url;
 use crate::manifest::GenericManifestFile;
@@ -153,12 +153,9 @@ impl Source {
     ) -> Result<Self> {
         let source = match dep {
             manifest::Dependency::Simple(ref ver_str) => {
-                bail!(
-                    "Unsupported dependency declaration in \"{}\": `{}` - \
-                    currently only `git` and `path` dependencies are supported",
-                    manifest_dir.display(),
-                    ver_str
-                )
+                let index = reg::Index::default_index(manifest_dir)?;
+                let version = parse_version_req(ver_str)?;
+                Source::Registry(reg::Source { index, version })
             }
             manifest::Dependency::Detailed(ref det) => {
                 match (&det.path, &det.version, &det.git, &det.ipfs) {
@@ -197,6 +194,14 @@ impl Source {
                         let source = ipfs::Source(cid);
                         Source::Ipfs(source)
                     }
+                    (_, Some(ver_str), _, _) => {
+                        let index = match det.registry {
+                            Some(ref index) => reg::Index::from_str_with_base(index, manifest_dir)?,
+                            None => reg::Index::default_index(manifest_dir)?,
+                        };
+                        let version = parse_version_req(ver_str)?;
+                        Source::Registry(reg::Source { index, version })
+                    }
                     _ => {
                         bail!("unsupported set of fields for dependency: {:?}", dep);
                     }
@@ -301,11 +306,32 @@ impl Pinned {
     /// Used soley for the package lock file.
     pub fn semver(&self) -> Option<semver::Version> {
         match self {
-            Self::Registry(reg) => Some(reg.source.version.clone()),
+            Self::Registry(reg) => Some(reg.version.clone()),
             _ => None,
         }
     }
 
+    /// The string representing this source within the lock file in `lock_dir`.
+    ///
+    /// This matches the `Display` implementation, other than for registry sources with a local
+    /// index whose path is written relative to `lock_dir`.
+    pub fn to_lock_string(&self, lock_dir: &Path) -> String {
+        match self {
+            Self::Registry(reg) => reg.to_lock_string(lock_dir),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Parse a source written by `to_lock_string` to the lock file in `lock_dir`.
+    pub fn from_lock_str(s: &str, lock_dir: &Path) -> Result<Self, PinnedParseError> {
+        match Self::from_str(s)? {
+            Self::Registry(_) => reg::Pinned::from_lock_str(s, lock_dir)
+                .map(Self::Registry)
+                .map_err(|_| PinnedParseError),
+            source => Ok(source),
+        }
+    }
+
     /// Wrap `self` in some type able to be formatted for the compiling output.
     ///
     /// This refers to `<source>` in the following:
@@ -356,7 +382,7 @@ impl fmt::Display for Pinned {
             Self::Path(src) => src.fmt(f),
             Self::Git(src) => src.fmt(f),
             Self::Ipfs(src) => src.fmt(f),
-            Self::Registry(_reg) => todo!("pkg registries not yet implemented"),
+            Self::Registry(src) => src.fmt(f),
         }
     }
 }
@@ -368,7 +394,7 @@ impl<'a> fmt::Display for DisplayCompiling<'a, Pinned> {
             Pinned::Path(_src) => self.manifest_dir.display().fmt(f),
             Pinned::Git(src) => src.fmt(f),
             Pinned::Ipfs(src) => src.fmt(f),
-            Pinned::Registry(_src) => todo!("registry dependencies not yet implemented"),
+            Pinned::Registry(src) => src.fmt(f),
         }
     }
 }
@@ -386,14 +412,21 @@ impl FromStr for Pinned {
             Self::Git(src)
         } else if let Ok(src) = ipfs::Pinned::from_str(s) {
             Self::Ipfs(src)
+        } else if let Ok(src) = reg::Pinned::from_str(s) {
+            Self::Registry(src)
         } else {
-            // TODO: Try parse registry source.
             return Err(PinnedParseError);
         };
         Ok(source)
     }
 }
 
+/// Parse the version requirement of a registry dependency.
+fn parse_version_req(ver_str: &str) -> Result<semver::VersionReq> {
+    semver::VersionReq::parse(ver_str)
+        .map_err(|e| anyhow!("invalid version requirement `{}`: {}", ver_str, e))
+}
+
 /// Produce a unique ID for a particular fetch pass.
 ///
 /// This is used in the temporary git directory and allows for avoiding contention over the git
@@ -1,40 +1,286 @@
-use crate::{manifest::PackageManifestFile, source};
-use anyhow::bail;
+use crate::manifest::GenericManifestFile;
+use crate::{
+    manifest::{self, PackageManifestFile},
+    source::{self, git},
+};
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::{Path, PathBuf};
+use sha2::{Digest, Sha256};
+use std::{
+    cmp::Ordering,
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tracing::info;
 
-/// A package from the official registry.
-#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+/// A package from a registry, resolved via the registry's package index.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
 pub struct Source {
-    /// The base version specified for the package.
-    pub version: semver::Version,
+    /// The index in which the package's published versions are listed.
+    pub index: Index,
+    /// The version requirement specified for the package.
+    pub version: semver::VersionReq,
 }
 
 /// A pinned instance of the registry source.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize)]
 pub struct Pinned {
-    /// The registry package with base version.
+    /// The registry package with its version requirement.
     pub source: Source,
     /// The pinned version.
     pub version: semver::Version,
 }
 
+/// The location of a registry's package index.
+///
+/// An index has the following layout:
+///
+/// ```ignore
+/// <index>/<name>/index.json      # A JSON array of `IndexEntry`s, one per published version.
+/// <index>/<name>/<version>/      # The package source with a `Forc.toml` at its root.
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Deserialize, Serialize)]
+pub enum Index {
+    /// An index within a directory on the local file system.
+    Path(PathBuf),
+    /// An index within a git repository, tracked at the repository's default branch.
+    Git(git::Url),
+}
+
+/// Describes a single published version of a package within an index.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct IndexEntry {
+    /// The published version.
+    pub version: semver::Version,
+    /// Yanked versions are never selected when pinning a new version requirement.
+    #[serde(default)]
+    pub yanked: bool,
+}
+
+/// Error returned upon failed parsing of `Pinned::from_str`.
+#[derive(Clone, Debug)]
+pub enum PinnedParseError {
+    Prefix,
+    Index,
+    VersionReq,
+    Version,
+}
+
+/// The environment variable used to specify the default registry index.
+pub const REGISTRY_INDEX_ENV_VAR: &str = "FORC_REGISTRY_INDEX";
+
+const REG_DIR_NAME: &str = "registry";
+const INDEX_DIR_NAME: &str = "index";
+const CACHE_DIR_NAME: &str = "cache";
+const INDEX_FILE_NAME: &str = "index.json";
+const INDEX_HEAD_FILE_NAME: &str = "HEAD";
+
+/// The package name used for git checkouts of registry indices.
+const GIT_INDEX_PKG_NAME: &str = "registry-index";
+
+impl Pinned {
+    pub const PREFIX: &'static str = "registry";
+
+    /// The string representing this pin within the lock file in `lock_dir`.
+    ///
+    /// Unlike the `Display` implementation, the path of a local index is written relative to
+    /// `lock_dir` so that the lock file does not depend on where the project is checked out.
+    pub fn to_lock_string(&self, lock_dir: &Path) -> String {
+        match self.source.index {
+            Index::Path(ref path) => {
+                let lock_dir = lock_dir
+                    .canonicalize()
+                    .unwrap_or_else(|_| lock_dir.to_path_buf());
+                let index = Index::Path(relative_path(path, &lock_dir));
+                let source = Source {
+                    index,
+                    version: self.source.version.clone(),
+                };
+                let pinned = Self {
+                    source,
+                    version: self.version.clone(),
+                };
+                pinned.to_string()
+            }
+            Index::Git(_) => self.to_string(),
+        }
+    }
+
+    /// Parse a pin written by `to_lock_string` to the lock file in `lock_dir`.
+    pub fn from_lock_str(s: &str, lock_dir: &Path) -> Result<Self, PinnedParseError> {
+        let mut pinned = Self::from_str(s)?;
+        if let Index::Path(ref mut path) = pinned.source.index {
+            if path.is_relative() {
+                let joined = lock_dir.join(&path);
+                *path = joined.canonicalize().unwrap_or(joined);
+            }
+        }
+        Ok(pinned)
+    }
+}
+
+impl Index {
+    /// Parse the index from the given string, resolving relative paths from `base_dir`.
+    ///
+    /// Strings that look like a URL, either with a scheme or in the scp-like `user@host:path`
+    /// form, produce a `Git` index. All others must refer to an existing local directory and
+    /// produce a `Path` index.
+    pub fn from_str_with_base(s: &str, base_dir: &Path) -> Result<Self> {
+        if !Path::new(s).is_absolute() && is_url(s) {
+            let url = git::Url::from_str(s)?;
+            return Ok(Self::Git(url));
+        }
+        let path = base_dir.join(s);
+        if !path.is_dir() {
+            bail!("registry index directory not found: {}", path.display());
+        }
+        let canonical_path = path.canonicalize().map_err(|e| {
+            anyhow!(
+                "Failed to canonicalize registry index path {:?}: {}",
+                path,
+                e
+            )
+        })?;
+        Ok(Self::Path(canonical_path))
+    }
+
+    /// The index used when a registry dependency does not specify one.
+    pub fn default_index(base_dir: &Path) -> Result<Self> {
+        let index = std::env::var(REGISTRY_INDEX_ENV_VAR).map_err(|_| {
+            anyhow!(
+                "no registry index specified: either add a `registry` field to the dependency \
+                or set the `{}` environment variable",
+                REGISTRY_INDEX_ENV_VAR
+            )
+        })?;
+        Self::from_str_with_base(&index, base_dir)
+    }
+
+    /// Returns the local directory containing the index.
+    ///
+    /// For git indices, the latest commit of the default branch is checked out unless `offline`
+    /// is `true`, in which case the most recently synced checkout is used.
+    fn local_path(&self, fetch_id: u64, offline: bool) -> Result<PathBuf> {
+        match self {
+            Self::Path(path) => Ok(path.clone()),
+            Self::Git(url) => {
+                let head_path = index_dir(self).join(INDEX_HEAD_FILE_NAME);
+                if offline {
+                    let commit_hash = fs::read_to_string(&head_path).map_err(|_| {
+                        anyhow!("Unable to fetch registry index {} in offline mode", url)
+                    })?;
+                    return Ok(git::commit_path(GIT_INDEX_PKG_NAME, url, &commit_hash));
+                }
+                let source = git::Source {
+                    repo: url.clone(),
+                    reference: git::Reference::DefaultBranch,
+                };
+                let pinned = git::pin(fetch_id, GIT_INDEX_PKG_NAME, source)?;
+                let path = git::commit_path(GIT_INDEX_PKG_NAME, url, &pinned.commit_hash);
+                let mut lock = forc_util::path_lock(&path)?;
+                {
+                    let _guard = lock.write()?;
+                    if !path.exists() {
+                        info!(
+                            "  {} registry index {}",
+                            ansi_term::Color::Green.bold().paint("Updating"),
+                            url
+                        );
+                        git::fetch(fetch_id, GIT_INDEX_PKG_NAME, &pinned)?;
+                    }
+                }
+                fs::create_dir_all(index_dir(self))?;
+                fs::write(&head_path, &pinned.commit_hash)?;
+                Ok(path)
+            }
+        }
+    }
+}
+
+impl Source {
+    /// Select the greatest non-yanked version within the given index that satisfies the version
+    /// requirement.
+    pub fn resolve(&self, index_path: &Path, name: &str) -> Result<semver::Version> {
+        read_index_entries(index_path, name)?
+            .into_iter()
+            .filter(|entry| !entry.yanked && self.version.matches(&entry.version))
+            .map(|entry| entry.version)
+            .max()
+            .ok_or_else(|| {
+                anyhow!(
+                    "no version of `{}` matching `{}` found in registry index {}",
+                    name,
+                    self.version,
+                    self.index
+                )
+            })
+    }
+}
+
 impl source::Pin for Source {
     type Pinned = Pinned;
-    fn pin(&self, _ctx: source::PinCtx) -> anyhow::Result<(Self::Pinned, PathBuf)> {
-        bail!("registry dependencies are not yet supported");
+    fn pin(&self, ctx: source::PinCtx) -> Result<(Self::Pinned, PathBuf)> {
+        let index_path = self.index.local_path(ctx.fetch_id(), ctx.offline())?;
+        let version = self.resolve(&index_path, ctx.name())?;
+        let path = pkg_cache_dir(&self.index, ctx.name(), &version);
+        let pinned = Pinned {
+            source: self.clone(),
+            version,
+        };
+        Ok((pinned, path))
     }
 }
 
 impl source::Fetch for Pinned {
-    fn fetch(&self, _ctx: source::PinCtx, _local: &Path) -> anyhow::Result<PackageManifestFile> {
-        bail!("registry dependencies are not yet supported");
+    fn fetch(&self, ctx: source::PinCtx, local: &Path) -> Result<PackageManifestFile> {
+        // Co-ordinate access to the cached package directory using an advisory file lock.
+        let mut lock = forc_util::path_lock(local)?;
+        {
+            let _guard = lock.write()?;
+            if !local.exists() {
+                info!(
+                    "  {} {} {}",
+                    ansi_term::Color::Green.bold().paint("Fetching"),
+                    ansi_term::Style::new().bold().paint(ctx.name),
+                    self
+                );
+                let index_path = self
+                    .source
+                    .index
+                    .local_path(ctx.fetch_id(), ctx.offline())?;
+                let pkg_path = index_path.join(ctx.name()).join(self.version.to_string());
+                if !pkg_path.is_dir() {
+                    bail!(
+                        "registry index {} does not contain sources for `{}` v{}",
+                        self.source.index,
+                        ctx.name(),
+                        self.version
+                    );
+                }
+                cache_dir(&pkg_path, local, ctx.fetch_id()).with_context(|| {
+                    format!("failed to cache `{}` v{}", ctx.name(), self.version)
+                })?;
+            }
+        }
+        let path = {
+            let _guard = lock.read()?;
+            manifest::find_within(local, ctx.name())
+                .ok_or_else(|| anyhow!("failed to find package `{}` in {}", ctx.name(), self))?
+        };
+        PackageManifestFile::from_file(path)
     }
 }
 
 impl source::DepPath for Pinned {
-    fn dep_path(&self, _name: &str) -> anyhow::Result<source::DependencyPath> {
-        bail!("registry dependencies are not yet supported");
+    fn dep_path(&self, name: &str) -> Result<source::DependencyPath> {
+        let pkg_path = pkg_cache_dir(&self.source.index, name, &self.version);
+        // Co-ordinate access to the cached package directory using an advisory file lock.
+        let lock = forc_util::path_lock(&pkg_path)?;
+        let _guard = lock.read()?;
+        let path = manifest::find_within(&pkg_path, name)
+            .ok_or_else(|| anyhow!("failed to find package `{}` in {}", name, self))?;
+        Ok(source::DependencyPath::ManifestPath(path))
     }
 }
 
@@ -43,3 +289,362 @@ impl From<Pinned> for source::Pinned {
         Self::Registry(p)
     }
 }
+
+// `semver::VersionReq` does not implement `Ord`, so we order by its string representation.
+impl PartialOrd for Source {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Source {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index
+            .cmp(&other.index)
+            .then_with(|| self.version.to_string().cmp(&other.version.to_string()))
+    }
+}
+
+impl fmt::Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Path(path) => path.display().fmt(f),
+            Self::Git(url) => url.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for Pinned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // registry+<index>?<version-req>#<version>
+        write!(
+            f,
+            "{}+{}?{}#{}",
+            Self::PREFIX,
+            self.source.index,
+            self.source.version,
+            self.version
+        )
+    }
+}
+
+impl FromStr for Pinned {
+    type Err = PinnedParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // registry+<index>?<version-req>#<version>
+        let s = s.trim();
+
+        // Check for "registry+" at the start.
+        let prefix_plus = format!("{}+", Self::PREFIX);
+        if s.find(&prefix_plus) != Some(0) {
+            return Err(PinnedParseError::Prefix);
+        }
+        let s = &s[prefix_plus.len()..];
+
+        // Neither the version requirement nor the version may contain `?` or `#`, so we split
+        // from the end to allow for these characters within the index URL.
+        let (s, version) = s.rsplit_once('#').ok_or(PinnedParseError::Version)?;
+        let version = semver::Version::parse(version).map_err(|_| PinnedParseError::Version)?;
+        let (index, version_req) = s.rsplit_once('?').ok_or(PinnedParseError::VersionReq)?;
+        let version_req =
+            semver::VersionReq::parse(version_req).map_err(|_| PinnedParseError::VersionReq)?;
+        // Relative index paths are only written by `to_lock_string` and always start with `.`.
+        let index = if Path::new(index).is_absolute() || index.starts_with('.') {
+            Index::Path(PathBuf::from(index))
+        } else {
+            Index::Git(git::Url::from_str(index).map_err(|_| PinnedParseError::Index)?)
+        };
+
+        let source = Source {
+            index,
+            version: version_req,
+        };
+        Ok(Self { source, version })
+    }
+}
+
+/// Whether the given registry index string refers to a git repository rather than a directory.
+fn is_url(s: &str) -> bool {
+    s.contains("://")
+        || s.split_once(':')
+            .is_some_and(|(host, _)| !host.is_empty() && !host.contains('/'))
+}
+
+/// The path to `path` from `base`, with both assumed to be canonical.
+///
+/// The result always starts with `.` or `..`, so that it can't be mistaken for a git URL.
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let common = path
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        // E.g. on different Windows drives, there is no relative path.
+        return path.to_path_buf();
+    }
+    let ups = base.components().count() - common;
+    let mut relative = match ups {
+        0 => PathBuf::from("."),
+        _ => PathBuf::new(),
+    };
+    for _ in 0..ups {
+        relative.push("..");
+    }
+    relative.extend(path.components().skip(common));
+    relative
+}
+
+/// Read the entries for the package with the given name from the index at `index_path`.
+fn read_index_entries(index_path: &Path, name: &str) -> Result<Vec<IndexEntry>> {
+    let path = index_path.join(name).join(INDEX_FILE_NAME);
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| anyhow!("failed to read registry index entry {:?}: {}", path, e))?;
+    let entries = serde_json::from_str(&contents)
+        .map_err(|e| anyhow!("failed to parse registry index entry {:?}: {}", path, e))?;
+    Ok(entries)
+}
+
+/// Copy the directory at `src` to `dst`, by way of a temporary directory next to `dst` which is
+/// then renamed, so that an interrupted copy never leaves a partial package in the cache.
+fn cache_dir(src: &Path, dst: &Path, fetch_id: u64) -> Result<()> {
+    let file_name = dst
+        .file_name()
+        .ok_or_else(|| anyhow!("invalid cache directory {}", dst.display()))?;
+    let tmp = dst.with_file_name(format!("{}.{fetch_id:x}.tmp", file_name.to_string_lossy()));
+    if tmp.exists() {
+        fs::remove_dir_all(&tmp)?;
+    }
+    let copied = copy_dir(src, &tmp).and_then(|()| Ok(fs::rename(&tmp, dst)?));
+    if copied.is_err() {
+        let _ = fs::remove_dir_all(&tmp);
+    }
+    copied
+}
+
+/// Recursively copy the contents of the directory at `src` to `dst`.
+fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
+    for entry in walkdir::WalkDir::new(src) {
+        let entry = entry?;
+        let target = dst.join(entry.path().strip_prefix(src)?);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// The name to use for an index's directories under the user's forc directory.
+///
+/// This is the SHA-256 hash of the index, which unlike `DefaultHasher` is the same for every
+/// version of forc, so that the cache is reused after upgrading.
+fn index_dir_name(index: &Index) -> String {
+    let kind = match index {
+        Index::Path(_) => "path",
+        Index::Git(_) => "git",
+    };
+    hex::encode(Sha256::digest(format!("{kind}+{index}")))
+}
+
+fn registry_dir() -> PathBuf {
+    forc_util::user_forc_directory().join(REG_DIR_NAME)
+}
+
+/// The directory in which state associated with the given index is stored.
+///
+/// ```ignore
+/// $HOME/.forc/registry/index/<index_hash>
+/// ```
+fn index_dir(index: &Index) -> PathBuf {
+    registry_dir()
+        .join(INDEX_DIR_NAME)
+        .join(index_dir_name(index))
+}
+
+/// The path at which a registry package is cached.
+///
+/// ```ignore
+/// $HOME/.forc/registry/cache/<index_hash>/<name>-<version>
+/// ```
+fn pkg_cache_dir(index: &Index, name: &str, version: &semver::Version) -> PathBuf {
+    registry_dir()
+        .join(CACHE_DIR_NAME)
+        .join(index_dir_name(index))
+        .join(format!("{name}-{version}"))
+}
+
+#[test]
+fn test_source_registry_pinned_parsing() {
+    let strings = [
+        "registry+https://github.com/foo/index?^0.1.0#0.1.2",
+        "registry+/path/to/index?>=1.0.0, <2.0.0#1.5.0",
+    ];
+
+    let expected = [
+        Pinned {
+            source: Source {
+                index: Index::Git(git::Url::from_str("https://github.com/foo/index").unwrap()),
+                version: semver::VersionReq::parse("^0.1.0").unwrap(),
+            },
+            version: semver::Version::new(0, 1, 2),
+        },
+        Pinned {
+            source: Source {
+                index: Index::Path(PathBuf::from("/path/to/index")),
+                version: semver::VersionReq::parse(">=1.0.0, <2.0.0").unwrap(),
+            },
+            version: semver::Version::new(1, 5, 0),
+        },
+    ];
+
+    for (&string, expected) in strings.iter().zip(&expected) {
+        let parsed = Pinned::from_str(string).unwrap();
+        assert_eq!(&parsed, expected);
+        let serialized = expected.to_string();
+        assert_eq!(&serialized, string);
+    }
+}
+
+#[test]
+fn test_index_dir_name() {
+    let path = Index::Path(PathBuf::from("/path/to/index"));
+    let git = Index::Git(git::Url::from_str("https://github.com/foo/index").unwrap());
+    // The names must not change, or existing caches are lost.
+    assert_eq!(
+        index_dir_name(&path),
+        hex::encode(Sha256::digest("path+/path/to/index"))
+    );
+    assert_eq!(index_dir_name(&path).len(), 64);
+    assert_ne!(index_dir_name(&path), index_dir_name(&git));
+}
+
+#[test]
+fn test_cache_dir() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("registry_index");
+    let cache = tempfile::tempdir().unwrap();
+    let dst = cache.path().join("pkg-0.1.0");
+    cache_dir(&src, &dst, 42).unwrap();
+    for entry in walkdir::WalkDir::new(&src) {
+        let entry = entry.unwrap();
+        assert!(dst.join(entry.path().strip_prefix(&src).unwrap()).exists());
+    }
+    // Only the package is left in the cache.
+    assert_eq!(fs::read_dir(cache.path()).unwrap().count(), 1);
+
+    // A failed copy leaves nothing behind.
+    let missing = cache.path().join("missing-0.1.0");
+    assert!(cache_dir(&src.join("missing"), &missing, 42).is_err());
+    assert!(!missing.exists());
+    assert_eq!(fs::read_dir(cache.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_source_registry_resolve() {
+    let index_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("registry_index");
+    let source = |req: &str| Source {
+        index: Index::Path(index_dir.clone()),
+        version: semver::VersionReq::parse(req).unwrap(),
+    };
+    let resolve = |req: &str| source(req).resolve(&index_dir, "foo");
+
+    // Yanked versions are skipped.
+    assert_eq!(resolve("0.1").unwrap(), semver::Version::new(0, 1, 2));
+    assert_eq!(resolve("=0.1.0").unwrap(), semver::Version::new(0, 1, 0));
+    assert_eq!(resolve("*").unwrap(), semver::Version::new(0, 2, 0));
+    assert!(resolve("=0.1.3").is_err());
+    assert!(resolve("1.0").is_err());
+    assert!(source("0.1").resolve(&index_dir, "bar").is_err());
+}
+
+#[test]
+fn test_index_from_str_with_base() {
+    let base_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let index = Index::from_str_with_base("registry_index", &base_dir).unwrap();
+    assert_eq!(
+        index,
+        Index::Path(base_dir.join("registry_index").canonicalize().unwrap())
+    );
+    for url in ["https://github.com/foo/index", "git@github.com:foo/index"] {
+        let index = Index::from_str_with_base(url, &base_dir).unwrap();
+        assert!(matches!(index, Index::Git(_)));
+    }
+    let err = Index::from_str_with_base("missing_index", &base_dir).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("registry index directory not found"));
+}
+
+#[test]
+fn test_registry_dependency_lock() {
+    let fixture_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("registry_index");
+    let dir = tempfile::tempdir().unwrap();
+    let index_dir = dir.path().join("index");
+    copy_dir(&fixture_dir, &index_dir).unwrap();
+    let pkg_dir = dir.path().join("pkg");
+    fs::create_dir_all(pkg_dir.join("src")).unwrap();
+    fs::write(
+        pkg_dir.join("Forc.toml"),
+        "[project]\n\
+        authors = [\"Fuel Labs <contact@fuel.sh>\"]\n\
+        entry = \"lib.sw\"\n\
+        license = \"Apache-2.0\"\n\
+        name = \"pkg\"\n\
+        implicit-std = false\n\
+        \n\
+        [dependencies]\n\
+        foo = { version = \"0.1\", registry = \"../index\" }\n",
+    )
+    .unwrap();
+    fs::write(pkg_dir.join("src").join("lib.sw"), "library;\n").unwrap();
+
+    // Fetch into a temporary `.forc` directory, rather than the user's.
+    let forc_dir = dir.path().join(".forc");
+    forc_util::with_user_forc_directory(&forc_dir, || {
+        let manifest = manifest::ManifestFile::from_dir(&pkg_dir).unwrap();
+        let member_manifests = manifest.member_manifests().unwrap();
+        let lock_path = manifest.lock_path().unwrap();
+        let plan = crate::BuildPlan::from_lock_and_manifests(
+            &lock_path,
+            &member_manifests,
+            false,
+            false,
+            Default::default(),
+        )
+        .unwrap();
+
+        // The package is fetched into the cache, within the temporary `.forc` directory.
+        let index = Index::Path(index_dir.canonicalize().unwrap());
+        let cache_dir = pkg_cache_dir(&index, "foo", &semver::Version::new(0, 1, 2));
+        assert!(cache_dir.starts_with(&forc_dir));
+        assert!(cache_dir.is_dir());
+        let foo = plan
+            .graph()
+            .node_indices()
+            .find(|&node| plan.graph()[node].name == "foo")
+            .unwrap();
+        let foo_manifest = &plan.manifest_map()[&plan.graph()[foo].id()];
+        assert!(foo_manifest.dir().starts_with(&cache_dir));
+
+        // The lock file records the index relative to the project.
+        let lock = fs::read_to_string(&lock_path).unwrap();
+        assert!(lock.contains("source = \"registry+../index?^0.1#0.1.2\""));
+
+        // The lock file is read back as is.
+        crate::BuildPlan::from_lock_and_manifests(
+            &lock_path,
+            &member_manifests,
+            true,
+            false,
+            Default::default(),
+        )
+        .unwrap();
+    });
+}
@@ -0,0 +1,64 @@
+contract;
+
+storage {
+    owner: b256 = 0x0000000000000000000000000000000000000000000000000000000000000000,
+    count: u64 = 0,
+    total: u64 = 0,
+}
+
+#[storage(read)]
+fn read_word(key: StorageKey<u64>) -> u64 {
+    __state_load_word(key.slot())
+}
+
+#[storage(write)]
+fn write_word(key: StorageKey<u64>, value: u64) {
+    let _ = __state_store_word(key.slot(), value);
+}
+
+#[storage(read, write)]
+fn add(key: StorageKey<u64>, amount: u64) {
+    write_word(key, read_word(key) + amount);
+}
+
+#[storage(read)]
+fn read_quad(key: StorageKey<b256>) -> b256 {
+    let mut value = 0x0000000000000000000000000000000000000000000000000000000000000000;
+    let _ = __state_load_quad(key.slot(), __addr_of(value), 1);
+    value
+}
+
+#[storage(read)]
+fn only_owner(owner: b256) {
+    if read_quad(storage.owner) != owner {
+        __revert(0);
+    }
+}
+
+abi Counter {
+    #[storage(read)]
+    fn count() -> u64;
+    #[storage(read, write)]
+    fn increment(owner: b256, amount: u64);
+    #[storage(write)]
+    fn reset();
+}
+
+impl Counter for Contract {
+    #[storage(read)]
+    fn count() -> u64 {
+        read_word(storage.count)
+    }
+
+    #[storage(read, write)]
+    fn increment(owner: b256, amount: u64) {
+        only_owner(owner);
+        add(storage.count, 1);
+        add(storage.total, amount);
+    }
+
+    #[storage(write)]
+    fn reset() {
+        write_word(storage.count, 0);
+    }
+}
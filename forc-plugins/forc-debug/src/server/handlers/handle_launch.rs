@@ -29,10 +29,15 @@ impl DapServer {
                 if test_entry.file_path.as_path() != self.state.program_path.as_path() {
                     return None;
                 }
+                // Tests with parameters require generated arguments, which aren't supported here.
+                if !test_entry.params.is_empty() {
+                    return None;
+                }
 
                 Some(TestExecutor::new(
                     &pkg_to_debug.bytecode.bytes,
                     offset,
+                    vec![],
                     test_setup.clone(),
                     test_entry,
                     name.clone(),
@@ -0,0 +1,315 @@
+//! Code coverage collection and reporting for unit tests.
+//!
+//! Coverage is recorded by single-stepping the VM during test execution and collecting the
+//! instruction offsets that were executed. These are then mapped back to source locations using
+//! the package's `SourceMap`.
+//!
+//! The tests of a contract are executed as scripts calling a deployment of the contract built
+//! without tests, so instructions executed within the deployed contract's call frames are
+//! recorded separately and mapped through the source map of the contract's bytecode.
+
+use crate::{source::SourceFiles, TestedPackage};
+use forc_pkg::manifest::GenericManifestFile;
+use fuel_tx as tx;
+use fuel_vm::{prelude::Instruction, state::Breakpoint};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The execution counts for each instruction executed during a single test.
+///
+/// Instructions are identified by their index within the bytecode, i.e. the byte offset divided by
+/// the instruction size.
+#[derive(Debug, Clone, Default)]
+pub struct TestCoverage {
+    /// Instructions executed within the test's own bytecode.
+    pub script: BTreeMap<usize, u64>,
+    /// Instructions executed within the deployed contract under test, if any.
+    pub contract: BTreeMap<usize, u64>,
+}
+
+/// The coverage of all source files belonging to a tested package.
+#[derive(Debug, Clone, Default)]
+pub struct PackageCoverage {
+    pub files: Vec<FileCoverage>,
+}
+
+/// The coverage of a single source file.
+#[derive(Debug, Clone)]
+pub struct FileCoverage {
+    /// The path to the source file.
+    pub path: PathBuf,
+    /// The execution count for each line with at least one associated instruction.
+    ///
+    /// Line numbers start at `1`.
+    pub lines: BTreeMap<usize, u64>,
+    /// The coverage of each function declared within the file.
+    pub functions: Vec<FunctionCoverage>,
+}
+
+/// The coverage of a single function.
+#[derive(Debug, Clone)]
+pub struct FunctionCoverage {
+    /// The name of the function, prefixed with the implementing type for methods.
+    pub name: String,
+    /// The line on which the function is declared.
+    pub line: usize,
+    /// The greatest execution count of any instruction within the function.
+    pub hits: u64,
+}
+
+/// A summary of the number of lines and functions covered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CoverageSummary {
+    pub lines_hit: usize,
+    pub lines_found: usize,
+    pub functions_hit: usize,
+    pub functions_found: usize,
+}
+
+impl TestCoverage {
+    /// Record that the instruction at the given breakpoint was executed.
+    ///
+    /// Instructions executed within contracts other than the contract under test are ignored.
+    pub fn record(&mut self, breakpoint: &Breakpoint, root_contract_id: Option<tx::ContractId>) {
+        let contract = *breakpoint.contract();
+        let instructions = if contract == tx::ContractId::zeroed() {
+            &mut self.script
+        } else if Some(contract) == root_contract_id {
+            &mut self.contract
+        } else {
+            return;
+        };
+        let ix = breakpoint.pc() as usize / Instruction::SIZE;
+        *instructions.entry(ix).or_default() += 1;
+    }
+
+    /// Merge the execution counts from `other` into `self`.
+    pub fn merge(&mut self, other: &TestCoverage) {
+        for (instructions, other) in [
+            (&mut self.script, &other.script),
+            (&mut self.contract, &other.contract),
+        ] {
+            for (ix, count) in other {
+                *instructions.entry(*ix).or_default() += count;
+            }
+        }
+    }
+}
+
+impl PackageCoverage {
+    /// Collect the coverage of the given tested package's source files from the coverage
+    /// recorded for each of its tests.
+    ///
+    /// Only source files within the package's directory are included, and instructions belonging
+    /// to the test functions themselves are excluded.
+    pub fn from_tested_pkg(tested: &TestedPackage) -> anyhow::Result<Self> {
+        let built = &tested.built;
+        let pkg_dir = built.descriptor.manifest_file.dir();
+
+        let mut executed = TestCoverage::default();
+        for test in &tested.tests {
+            if let Some(coverage) = &test.coverage {
+                executed.merge(coverage);
+            }
+        }
+
+        // The spans of each test function, which we exclude from the report.
+        let test_spans: Vec<(Arc<PathBuf>, usize, usize)> = built
+            .bytecode
+            .entries
+            .iter()
+            .filter_map(|entry| entry.kind.test())
+            .map(|test| (test.file_path.clone(), test.span.start(), test.span.end()))
+            .collect();
+        let is_test_code = |path: &Path, start: usize, end: usize| {
+            test_spans
+                .iter()
+                .any(|(p, s, e)| **p == *path && *s <= start && end <= *e)
+        };
+
+        let mut source_files = SourceFiles::default();
+        let mut files: BTreeMap<PathBuf, BTreeMap<usize, u64>> = BTreeMap::new();
+        let mut fn_hits: BTreeMap<PathBuf, HashMap<usize, u64>> = BTreeMap::new();
+        let source_maps = [
+            (Some(&built.source_map), &executed.script),
+            (built.source_map_without_tests.as_ref(), &executed.contract),
+        ];
+        for (source_map, instructions) in source_maps {
+            let Some(source_map) = source_map else {
+                continue;
+            };
+            for (ix, span) in &source_map.map {
+                let path = &source_map.paths[span.path.0];
+                let (start, end) = (span.range.start, span.range.end);
+                if !path.starts_with(pkg_dir) || is_test_code(path, start, end) {
+                    continue;
+                }
+                let source_file = source_files.get(path)?;
+                let hits = instructions.get(ix).copied().unwrap_or_default();
+                let line = source_file.line(start);
+                let line_hits = files
+                    .entry(path.clone())
+                    .or_default()
+                    .entry(line)
+                    .or_default();
+                *line_hits = (*line_hits).max(hits);
+                if let Some(fn_ix) = source_file.enclosing_fn(start, end) {
+                    let fn_hits = fn_hits.entry(path.clone()).or_default().entry(fn_ix);
+                    let fn_hits = fn_hits.or_default();
+                    *fn_hits = (*fn_hits).max(hits);
+                }
+            }
+        }
+
+        let files = files
+            .into_iter()
+            .map(|(path, lines)| {
+                let source_file = source_files.get(&path)?;
+                let hits = fn_hits.remove(&path).unwrap_or_default();
+                let functions = source_file
+                    .fns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, f)| !is_test_code(&path, f.start, f.end))
+                    .map(|(fn_ix, f)| FunctionCoverage {
+                        name: f.name.clone(),
+                        line: source_file.line(f.start),
+                        hits: hits.get(&fn_ix).copied().unwrap_or_default(),
+                    })
+                    .collect();
+                Ok(FileCoverage {
+                    path,
+                    lines,
+                    functions,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { files })
+    }
+
+    /// Summarise the coverage across all files.
+    pub fn summary(&self) -> CoverageSummary {
+        self.files.iter().map(FileCoverage::summary).fold(
+            CoverageSummary::default(),
+            |acc, summary| CoverageSummary {
+                lines_hit: acc.lines_hit + summary.lines_hit,
+                lines_found: acc.lines_found + summary.lines_found,
+                functions_hit: acc.functions_hit + summary.functions_hit,
+                functions_found: acc.functions_found + summary.functions_found,
+            },
+        )
+    }
+
+    /// Write the coverage in the `lcov` tracefile format.
+    pub fn write_lcov<W: Write>(&self, test_name: &str, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "TN:{test_name}")?;
+        for file in &self.files {
+            let summary = file.summary();
+            writeln!(writer, "SF:{}", file.path.display())?;
+            for function in &file.functions {
+                writeln!(writer, "FN:{},{}", function.line, function.name)?;
+            }
+            for function in &file.functions {
+                writeln!(writer, "FNDA:{},{}", function.hits, function.name)?;
+            }
+            writeln!(writer, "FNF:{}", summary.functions_found)?;
+            writeln!(writer, "FNH:{}", summary.functions_hit)?;
+            for (line, hits) in &file.lines {
+                writeln!(writer, "DA:{line},{hits}")?;
+            }
+            writeln!(writer, "LF:{}", summary.lines_found)?;
+            writeln!(writer, "LH:{}", summary.lines_hit)?;
+            writeln!(writer, "end_of_record")?;
+        }
+        Ok(())
+    }
+}
+
+impl FileCoverage {
+    /// Summarise the coverage of this file.
+    pub fn summary(&self) -> CoverageSummary {
+        CoverageSummary {
+            lines_hit: self.lines.values().filter(|&&hits| hits > 0).count(),
+            lines_found: self.lines.len(),
+            functions_hit: self.functions.iter().filter(|f| f.hits > 0).count(),
+            functions_found: self.functions.len(),
+        }
+    }
+}
+
+impl CoverageSummary {
+    /// The percentage of lines covered, or `None` if there are no lines.
+    pub fn lines_percent(&self) -> Option<f64> {
+        percent(self.lines_hit, self.lines_found)
+    }
+
+    /// The percentage of functions covered, or `None` if there are no functions.
+    pub fn functions_percent(&self) -> Option<f64> {
+        percent(self.functions_hit, self.functions_found)
+    }
+}
+
+fn percent(hit: usize, found: usize) -> Option<f64> {
+    (found != 0).then(|| hit as f64 / found as f64 * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lcov_output() {
+        let coverage = PackageCoverage {
+            files: vec![FileCoverage {
+                path: PathBuf::from("/pkg/src/lib.sw"),
+                lines: BTreeMap::from([(6, 2), (7, 2), (12, 0)]),
+                functions: vec![
+                    FunctionCoverage {
+                        name: "Foo::bar".to_string(),
+                        line: 6,
+                        hits: 2,
+                    },
+                    FunctionCoverage {
+                        name: "baz".to_string(),
+                        line: 11,
+                        hits: 0,
+                    },
+                ],
+            }],
+        };
+        let summary = coverage.summary();
+        assert_eq!(
+            summary,
+            CoverageSummary {
+                lines_hit: 2,
+                lines_found: 3,
+                functions_hit: 1,
+                functions_found: 2,
+            }
+        );
+        assert_eq!(summary.functions_percent(), Some(50.0));
+
+        let mut lcov = vec![];
+        coverage.write_lcov("pkg", &mut lcov).unwrap();
+        let expected = "TN:pkg
+SF:/pkg/src/lib.sw
+FN:6,Foo::bar
+FN:11,baz
+FNDA:2,Foo::bar
+FNDA:0,baz
+FNF:2
+FNH:1
+DA:6,2
+DA:7,2
+DA:12,0
+LF:3
+LH:2
+end_of_record
+";
+        assert_eq!(String::from_utf8(lcov).unwrap(), expected);
+    }
+}
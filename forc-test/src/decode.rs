@@ -0,0 +1,908 @@
+//! Decoding of the values logged by unit tests.
+//!
+//! Every `Log` and `LogData` receipt carries the log ID of the logged value's type, which the
+//! program ABI maps to a type declaration. The values logged by a test itself are decoded using
+//! that declaration, either from their in-memory layout or, for programs compiled with the new
+//! encoding, from their ABI encoding. Values that cannot be decoded, such as those logged by
+//! called contracts whose log IDs belong to another ABI, are kept as raw bytes.
+
+use fuel_abi_types::{
+    abi::{full_program::FullTypeApplication, program::ProgramABI},
+    utils::{
+        extract_array_len, extract_custom_type_name, extract_generic_name, extract_str_len,
+        has_tuple_format,
+    },
+};
+use fuel_tx::{ContractId, Receipt};
+use std::{collections::HashMap, fmt};
+
+/// The size of a word in bytes.
+const WORD_SIZE: usize = 8;
+
+/// Decodes the values logged by a program using the types described by its ABI.
+#[derive(Debug, Clone, Default)]
+pub struct LogDecoder {
+    /// The type of the logged values for each log ID.
+    logged_types: HashMap<u64, LogType>,
+    /// Whether values are logged in their ABI encoding rather than in their in-memory layout.
+    encoded: bool,
+}
+
+/// A logged value, decoded if its type is known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedLog {
+    pub log_id: u64,
+    /// The type of the logged value, or `None` if the value could not be decoded.
+    pub ty: Option<LogType>,
+    pub value: LogValue,
+}
+
+/// The type of a logged value, as described by the program ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogType {
+    Unit,
+    Bool,
+    /// An unsigned integer with the given number of bits.
+    Uint(usize),
+    B256,
+    StrArray(usize),
+    StrSlice,
+    RawPtr,
+    RawSlice,
+    Array(Box<LogType>, usize),
+    Tuple(Vec<LogType>),
+    Struct {
+        name: String,
+        type_args: Vec<LogType>,
+        fields: Vec<(String, LogType)>,
+    },
+    Enum {
+        name: String,
+        type_args: Vec<LogType>,
+        variants: Vec<(String, LogType)>,
+    },
+}
+
+/// A decoded logged value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogValue {
+    Unit,
+    Bool(bool),
+    Uint(u64),
+    /// A `u256` in big-endian byte order.
+    U256([u8; 32]),
+    B256([u8; 32]),
+    /// A string array, a string slice or a `String`.
+    Str(String),
+    /// A raw slice or `Bytes`.
+    Bytes(Vec<u8>),
+    RawPtr(u64),
+    /// An array or a `Vec`.
+    Array(Vec<LogValue>),
+    Tuple(Vec<LogValue>),
+    Struct {
+        name: String,
+        fields: Vec<(String, LogValue)>,
+    },
+    Enum {
+        name: String,
+        variant: String,
+        value: Box<LogValue>,
+    },
+    /// The data of a value that could not be decoded.
+    Raw(Vec<u8>),
+}
+
+impl LogDecoder {
+    /// Create a decoder for the values logged by the program with the given ABI.
+    pub fn new(abi: &ProgramABI) -> Self {
+        let types = abi
+            .types
+            .iter()
+            .map(|decl| (decl.type_id, decl.clone()))
+            .collect();
+        let logged_types = abi
+            .logged_types
+            .iter()
+            .flatten()
+            .filter_map(|logged_type| {
+                let application =
+                    FullTypeApplication::from_counterpart(&logged_type.application, &types);
+                let ty = LogType::from_application(&application, &HashMap::new())?;
+                Some((logged_type.log_id, ty))
+            })
+            .collect();
+        Self {
+            logged_types,
+            encoded: abi.encoding.is_some(),
+        }
+    }
+
+    /// Decode the values logged by the given `Log` and `LogData` receipts, in order. All other
+    /// receipts are skipped.
+    pub fn decode_logs(&self, receipts: &[Receipt]) -> Vec<DecodedLog> {
+        receipts
+            .iter()
+            .filter_map(|receipt| match receipt {
+                Receipt::Log { id, ra, rb, .. } => {
+                    let ty = self.logged_type(id, *rb);
+                    let value = ty.and_then(|ty| ty.decode_word(*ra));
+                    Some(DecodedLog::new(*rb, ty, value, || {
+                        ra.to_be_bytes().to_vec()
+                    }))
+                }
+                Receipt::LogData { id, rb, data, .. } => {
+                    let data = data.as_deref().unwrap_or_default();
+                    let ty = self.logged_type(id, *rb);
+                    let value = ty.and_then(|ty| match self.encoded {
+                        true => ty.decode_encoded(data),
+                        false => ty.decode_logged_data(data),
+                    });
+                    Some(DecodedLog::new(*rb, ty, value, || data.to_vec()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The type of a value logged with the given log ID.
+    ///
+    /// Log IDs are only known for the values logged by the program itself, as the values logged
+    /// by called contracts are described by their own ABIs.
+    fn logged_type(&self, contract_id: &ContractId, log_id: u64) -> Option<&LogType> {
+        if *contract_id != ContractId::zeroed() {
+            return None;
+        }
+        self.logged_types.get(&log_id)
+    }
+}
+
+impl DecodedLog {
+    fn new(
+        log_id: u64,
+        ty: Option<&LogType>,
+        value: Option<LogValue>,
+        raw: impl FnOnce() -> Vec<u8>,
+    ) -> Self {
+        match (ty, value) {
+            (Some(ty), Some(value)) => Self {
+                log_id,
+                ty: Some(ty.clone()),
+                value,
+            },
+            _ => Self {
+                log_id,
+                ty: None,
+                value: LogValue::Raw(raw()),
+            },
+        }
+    }
+}
+
+impl LogType {
+    /// Resolve the type of an ABI type application, substituting the given types for generic
+    /// type parameters.
+    ///
+    /// Returns `None` for types that cannot be logged, or for unbound type parameters.
+    fn from_application(
+        application: &FullTypeApplication,
+        generics: &HashMap<String, LogType>,
+    ) -> Option<Self> {
+        let decl = &application.type_decl;
+        let type_field = decl.type_field.as_str();
+        if let Some(generic_name) = extract_generic_name(type_field) {
+            return generics.get(&generic_name).cloned();
+        }
+        let resolve_all = |applications: &[FullTypeApplication],
+                           generics: &HashMap<String, LogType>| {
+            applications
+                .iter()
+                .map(|application| Self::from_application(application, generics))
+                .collect::<Option<Vec<_>>>()
+        };
+        let ty = match type_field {
+            "()" => Self::Unit,
+            "bool" => Self::Bool,
+            "u8" => Self::Uint(8),
+            "u16" => Self::Uint(16),
+            "u32" => Self::Uint(32),
+            "u64" => Self::Uint(64),
+            "u256" => Self::Uint(256),
+            "b256" => Self::B256,
+            "str" => Self::StrSlice,
+            "raw untyped ptr" => Self::RawPtr,
+            "raw untyped slice" => Self::RawSlice,
+            _ => {
+                if let Some(len) = extract_str_len(type_field) {
+                    Self::StrArray(len)
+                } else if let Some(len) = extract_array_len(type_field) {
+                    let elem = Self::from_application(decl.components.first()?, generics)?;
+                    Self::Array(Box::new(elem), len)
+                } else if has_tuple_format(type_field) {
+                    Self::Tuple(resolve_all(&decl.components, generics)?)
+                } else if let Some(name) = extract_custom_type_name(type_field) {
+                    // The declaration's type parameters are bound to the application's type
+                    // arguments within the declaration's fields or variants.
+                    let type_args = resolve_all(&application.type_arguments, generics)?;
+                    let decl_generics = decl
+                        .type_parameters
+                        .iter()
+                        .zip(&type_args)
+                        .filter_map(|(param, arg)| {
+                            Some((extract_generic_name(&param.type_field)?, arg.clone()))
+                        })
+                        .collect();
+                    let members = decl
+                        .components
+                        .iter()
+                        .map(|component| {
+                            let ty = Self::from_application(component, &decl_generics)?;
+                            Some((component.name.clone(), ty))
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+                    if type_field.starts_with("enum") {
+                        Self::Enum {
+                            name,
+                            type_args,
+                            variants: members,
+                        }
+                    } else {
+                        Self::Struct {
+                            name,
+                            type_args,
+                            fields: members,
+                        }
+                    }
+                } else {
+                    return None;
+                }
+            }
+        };
+        Some(ty)
+    }
+
+    /// Decode a value logged with a `Log` receipt, which holds the value itself in a register.
+    fn decode_word(&self, word: u64) -> Option<LogValue> {
+        let value = match self {
+            Self::Unit => LogValue::Unit,
+            Self::Bool => LogValue::Bool(word != 0),
+            Self::Uint(bits) if *bits <= 64 => LogValue::Uint(word),
+            Self::RawPtr => LogValue::RawPtr(word),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Decode a value logged with a `LogData` receipt in its in-memory layout.
+    fn decode_logged_data(&self, data: &[u8]) -> Option<LogValue> {
+        // Slices are logged as the data they refer to, rather than as a pointer and a length.
+        let value = match self {
+            Self::StrSlice => LogValue::Str(String::from_utf8(data.to_vec()).ok()?),
+            Self::RawSlice => LogValue::Bytes(data.to_vec()),
+            _ => {
+                if data.len() != self.size_in_memory() {
+                    return None;
+                }
+                self.decode_in_memory(data)?
+            }
+        };
+        Some(value)
+    }
+
+    /// The size in bytes of a value of this type in memory.
+    fn size_in_memory(&self) -> usize {
+        match self {
+            Self::Unit | Self::Bool | Self::Uint(8) => 1,
+            Self::Uint(256) | Self::B256 => 32,
+            Self::Uint(_) | Self::RawPtr => WORD_SIZE,
+            Self::StrArray(len) => align_to_word(*len),
+            Self::StrSlice | Self::RawSlice => 2 * WORD_SIZE,
+            Self::Array(elem, len) => elem.size_in_memory() * len,
+            Self::Tuple(elems) => elems
+                .iter()
+                .map(|elem| align_to_word(elem.size_in_memory()))
+                .sum(),
+            Self::Struct { fields, .. } => fields
+                .iter()
+                .map(|(_, ty)| align_to_word(ty.size_in_memory()))
+                .sum(),
+            Self::Enum { variants, .. } => {
+                let union_size = variants
+                    .iter()
+                    .map(|(_, ty)| align_to_word(ty.size_in_memory()))
+                    .max()
+                    .unwrap_or(0);
+                WORD_SIZE + union_size
+            }
+        }
+    }
+
+    /// Decode a value of this type from its in-memory layout at the start of `bytes`.
+    ///
+    /// Struct and tuple fields are aligned to words, with smaller fields at the start of their
+    /// word, while array elements are packed. An enum is a word holding the variant's tag,
+    /// followed by the variant's value aligned to the end of the largest variant.
+    fn decode_in_memory(&self, bytes: &[u8]) -> Option<LogValue> {
+        let bytes = bytes.get(..self.size_in_memory())?;
+        let value = match self {
+            Self::Unit => LogValue::Unit,
+            Self::Bool => LogValue::Bool(bytes[0] != 0),
+            Self::Uint(8) => LogValue::Uint(bytes[0].into()),
+            Self::Uint(256) => LogValue::U256(bytes.try_into().ok()?),
+            Self::Uint(_) => LogValue::Uint(u64::from_be_bytes(bytes.try_into().ok()?)),
+            Self::B256 => LogValue::B256(bytes.try_into().ok()?),
+            Self::RawPtr => LogValue::RawPtr(u64::from_be_bytes(bytes.try_into().ok()?)),
+            Self::StrArray(len) => LogValue::Str(String::from_utf8(bytes[..*len].to_vec()).ok()?),
+            // A slice within another value is a pointer and a length, which are of no use
+            // outside of the VM.
+            Self::StrSlice | Self::RawSlice => LogValue::Raw(bytes.to_vec()),
+            Self::Array(elem, len) => {
+                let elem_size = elem.size_in_memory();
+                let elems = (0..*len)
+                    .map(|ix| elem.decode_in_memory(&bytes[ix * elem_size..]))
+                    .collect::<Option<_>>()?;
+                LogValue::Array(elems)
+            }
+            Self::Tuple(elems) => LogValue::Tuple(decode_fields_in_memory(elems, bytes)?),
+            Self::Struct { name, fields, .. } => {
+                let field_tys = fields.iter().map(|(_, ty)| ty.clone()).collect::<Vec<_>>();
+                let values = decode_fields_in_memory(&field_tys, bytes)?;
+                LogValue::Struct {
+                    name: name.clone(),
+                    fields: fields
+                        .iter()
+                        .map(|(field_name, _)| field_name.clone())
+                        .zip(values)
+                        .collect(),
+                }
+            }
+            Self::Enum { name, variants, .. } => {
+                let (tag, union) = bytes.split_at(WORD_SIZE);
+                let tag = usize::try_from(u64::from_be_bytes(tag.try_into().ok()?)).ok()?;
+                let (variant, ty) = variants.get(tag)?;
+                let offset = union.len() - ty.size_in_memory();
+                LogValue::Enum {
+                    name: name.clone(),
+                    variant: variant.clone(),
+                    value: Box::new(ty.decode_in_memory(&union[offset..])?),
+                }
+            }
+        };
+        Some(value)
+    }
+
+    /// Decode a value logged in its ABI encoding, which must make up the whole of `data`.
+    fn decode_encoded(&self, data: &[u8]) -> Option<LogValue> {
+        let mut reader = EncodingReader { bytes: data };
+        let value = reader.decode(self)?;
+        reader.bytes.is_empty().then_some(value)
+    }
+}
+
+/// Decode consecutive word-aligned values from their in-memory layout.
+fn decode_fields_in_memory(tys: &[LogType], bytes: &[u8]) -> Option<Vec<LogValue>> {
+    let mut offset = 0;
+    tys.iter()
+        .map(|ty| {
+            let value = ty.decode_in_memory(&bytes[offset..])?;
+            offset += align_to_word(ty.size_in_memory());
+            Some(value)
+        })
+        .collect()
+}
+
+/// Reads consecutive values from their ABI encoding.
+struct EncodingReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> EncodingReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.bytes.len() {
+            return None;
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Some(taken)
+    }
+
+    fn read_u64(&mut self) -> Option<u64> {
+        Some(u64::from_be_bytes(self.take(WORD_SIZE)?.try_into().ok()?))
+    }
+
+    /// Read a length prefix, which can't exceed the number of remaining bytes.
+    fn read_len(&mut self) -> Option<usize> {
+        let len = usize::try_from(self.read_u64()?).ok()?;
+        (len <= self.bytes.len()).then_some(len)
+    }
+
+    fn read_str(&mut self, len: usize) -> Option<String> {
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+
+    fn decode(&mut self, ty: &LogType) -> Option<LogValue> {
+        let value = match ty {
+            LogType::Unit => LogValue::Unit,
+            LogType::Bool => LogValue::Bool(self.take(1)?[0] != 0),
+            LogType::Uint(256) => LogValue::U256(self.take(32)?.try_into().ok()?),
+            LogType::Uint(bits) => {
+                let bytes = self.take(bits / 8)?;
+                let mut word = [0u8; WORD_SIZE];
+                word[WORD_SIZE - bytes.len()..].copy_from_slice(bytes);
+                LogValue::Uint(u64::from_be_bytes(word))
+            }
+            LogType::B256 => LogValue::B256(self.take(32)?.try_into().ok()?),
+            LogType::RawPtr => LogValue::RawPtr(self.read_u64()?),
+            LogType::StrArray(len) => LogValue::Str(self.read_str(*len)?),
+            LogType::StrSlice => {
+                let len = self.read_len()?;
+                LogValue::Str(self.read_str(len)?)
+            }
+            LogType::RawSlice => {
+                let len = self.read_len()?;
+                LogValue::Bytes(self.take(len)?.to_vec())
+            }
+            LogType::Array(elem, len) => LogValue::Array(
+                (0..*len)
+                    .map(|_| self.decode(elem))
+                    .collect::<Option<_>>()?,
+            ),
+            LogType::Tuple(elems) => LogValue::Tuple(
+                elems
+                    .iter()
+                    .map(|elem| self.decode(elem))
+                    .collect::<Option<_>>()?,
+            ),
+            // The heap types of the standard library are encoded as their length followed by
+            // their elements.
+            LogType::Struct {
+                name, type_args, ..
+            } if base_name(name) == "Vec" && type_args.len() == 1 => {
+                let len = self.read_len()?;
+                LogValue::Array(
+                    (0..len)
+                        .map(|_| self.decode(&type_args[0]))
+                        .collect::<Option<_>>()?,
+                )
+            }
+            LogType::Struct { name, .. } if base_name(name) == "Bytes" => {
+                let len = self.read_len()?;
+                LogValue::Bytes(self.take(len)?.to_vec())
+            }
+            LogType::Struct { name, .. } if base_name(name) == "String" => {
+                let len = self.read_len()?;
+                LogValue::Str(self.read_str(len)?)
+            }
+            LogType::Struct { name, fields, .. } => LogValue::Struct {
+                name: name.clone(),
+                fields: fields
+                    .iter()
+                    .map(|(field_name, ty)| Some((field_name.clone(), self.decode(ty)?)))
+                    .collect::<Option<_>>()?,
+            },
+            LogType::Enum { name, variants, .. } => {
+                let tag = usize::try_from(self.read_u64()?).ok()?;
+                let (variant, ty) = variants.get(tag)?;
+                LogValue::Enum {
+                    name: name.clone(),
+                    variant: variant.clone(),
+                    value: Box::new(self.decode(ty)?),
+                }
+            }
+        };
+        Some(value)
+    }
+}
+
+/// Round a size in bytes up to a multiple of the word size.
+fn align_to_word(size: usize) -> usize {
+    size.div_ceil(WORD_SIZE) * WORD_SIZE
+}
+
+/// The name of a type without its call path.
+fn base_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+impl fmt::Display for DecodedLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.ty {
+            Some(ty) => write!(f, "{ty}: ")?,
+            None => write!(f, "log id {}: ", self.log_id)?,
+        }
+        match f.alternate() {
+            true => write!(f, "{:#}", self.value),
+            false => write!(f, "{}", self.value),
+        }
+    }
+}
+
+impl fmt::Display for LogType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unit => write!(f, "()"),
+            Self::Bool => write!(f, "bool"),
+            Self::Uint(bits) => write!(f, "u{bits}"),
+            Self::B256 => write!(f, "b256"),
+            Self::StrArray(len) => write!(f, "str[{len}]"),
+            Self::StrSlice => write!(f, "str"),
+            Self::RawPtr => write!(f, "raw_ptr"),
+            Self::RawSlice => write!(f, "raw_slice"),
+            Self::Array(elem, len) => write!(f, "[{elem}; {len}]"),
+            Self::Tuple(elems) => {
+                write!(f, "(")?;
+                write_type_list(f, elems)?;
+                if elems.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Self::Struct {
+                name, type_args, ..
+            }
+            | Self::Enum {
+                name, type_args, ..
+            } => {
+                write!(f, "{}", base_name(name))?;
+                if !type_args.is_empty() {
+                    write!(f, "<")?;
+                    write_type_list(f, type_args)?;
+                    write!(f, ">")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_type_list(f: &mut fmt::Formatter<'_>, tys: &[LogType]) -> fmt::Result {
+    for (ix, ty) in tys.iter().enumerate() {
+        if ix > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{ty}")?;
+    }
+    Ok(())
+}
+
+/// Values are written using Sway syntax. The alternate flag (`{:#}`) writes the elements of
+/// aggregates on separate, indented lines.
+impl fmt::Display for LogValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unit => write!(f, "()"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Uint(value) => write!(f, "{value}"),
+            Self::U256(bytes) | Self::B256(bytes) => write_hex(f, bytes),
+            Self::Str(s) => write!(f, "{s:?}"),
+            Self::Bytes(bytes) | Self::Raw(bytes) => write_hex(f, bytes),
+            Self::RawPtr(ptr) => write!(f, "0x{ptr:x}"),
+            Self::Array(elems) => {
+                let elems = elems.iter().map(|elem| (None, elem));
+                write_elems(f, ("[", "]"), false, elems)
+            }
+            Self::Tuple(elems) if elems.len() == 1 && !f.alternate() => {
+                write!(f, "({},)", elems[0])
+            }
+            Self::Tuple(elems) => {
+                let elems = elems.iter().map(|elem| (None, elem));
+                write_elems(f, ("(", ")"), false, elems)
+            }
+            Self::Struct { name, fields } => {
+                write!(f, "{}", base_name(name))?;
+                if fields.is_empty() {
+                    return Ok(());
+                }
+                write!(f, " ")?;
+                let fields = fields
+                    .iter()
+                    .map(|(field_name, value)| (Some(field_name.as_str()), value));
+                write_elems(f, ("{", "}"), true, fields)
+            }
+            Self::Enum {
+                name,
+                variant,
+                value,
+            } => {
+                write!(f, "{}::{variant}", base_name(name))?;
+                if **value == Self::Unit {
+                    return Ok(());
+                }
+                write_elems(f, ("(", ")"), false, std::iter::once((None, &**value)))
+            }
+        }
+    }
+}
+
+/// Write the elements of an aggregate value between the given delimiters, each preceded by its
+/// name if it has one.
+fn write_elems<'a>(
+    f: &mut fmt::Formatter<'_>,
+    (open, close): (&str, &str),
+    padded: bool,
+    elems: impl Iterator<Item = (Option<&'a str>, &'a LogValue)>,
+) -> fmt::Result {
+    let pretty = f.alternate();
+    let elems = elems
+        .map(|(name, value)| {
+            let value = match pretty {
+                true => format!("{value:#}"),
+                false => value.to_string(),
+            };
+            match name {
+                Some(name) => format!("{name}: {value}"),
+                None => value,
+            }
+        })
+        .collect::<Vec<_>>();
+    if elems.is_empty() {
+        return write!(f, "{open}{close}");
+    }
+    if pretty {
+        writeln!(f, "{open}")?;
+        for elem in elems {
+            writeln!(f, "    {},", elem.replace('\n', "\n    "))?;
+        }
+        write!(f, "{close}")
+    } else {
+        let pad = if padded { " " } else { "" };
+        write!(f, "{open}{pad}{}{pad}{close}", elems.join(", "))
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    write!(f, "0x")?;
+    bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_abi_types::abi::program::{LoggedType, TypeApplication, TypeDeclaration, Version};
+    use fuel_tx::Bytes32;
+
+    fn decl(type_id: usize, type_field: &str) -> TypeDeclaration {
+        TypeDeclaration {
+            type_id,
+            type_field: type_field.to_string(),
+            components: None,
+            type_parameters: None,
+        }
+    }
+
+    fn app(name: &str, type_id: usize) -> TypeApplication {
+        TypeApplication {
+            name: name.to_string(),
+            type_id,
+            type_arguments: None,
+        }
+    }
+
+    /// An ABI with a `Point { x: u64, flag: bool }` struct, an `Option<T>` enum, and the given
+    /// logged types.
+    fn abi(logged_types: Vec<(u64, TypeApplication)>, encoding: Option<Version>) -> ProgramABI {
+        ProgramABI {
+            encoding,
+            types: vec![
+                decl(0, "()"),
+                decl(1, "bool"),
+                decl(2, "u64"),
+                TypeDeclaration {
+                    components: Some(vec![app("x", 2), app("flag", 1)]),
+                    ..decl(3, "struct Point")
+                },
+                decl(4, "generic T"),
+                TypeDeclaration {
+                    components: Some(vec![app("None", 0), app("Some", 4)]),
+                    type_parameters: Some(vec![4]),
+                    ..decl(5, "enum Option")
+                },
+                decl(6, "u8"),
+                TypeDeclaration {
+                    components: Some(vec![app("__array_element", 6)]),
+                    ..decl(7, "[_; 3]")
+                },
+                decl(8, "str[5]"),
+                TypeDeclaration {
+                    components: Some(vec![app("__tuple_element", 3), app("__tuple_element", 2)]),
+                    ..decl(9, "(_, _)")
+                },
+                decl(10, "raw untyped ptr"),
+                TypeDeclaration {
+                    components: Some(vec![app("ptr", 10), app("cap", 2)]),
+                    type_parameters: Some(vec![4]),
+                    ..decl(11, "struct RawVec")
+                },
+                TypeDeclaration {
+                    components: Some(vec![
+                        TypeApplication {
+                            type_arguments: Some(vec![app("", 4)]),
+                            ..app("buf", 11)
+                        },
+                        app("len", 2),
+                    ]),
+                    type_parameters: Some(vec![4]),
+                    ..decl(12, "struct Vec")
+                },
+            ],
+            functions: vec![],
+            logged_types: Some(
+                logged_types
+                    .into_iter()
+                    .map(|(log_id, application)| LoggedType {
+                        log_id,
+                        application,
+                    })
+                    .collect(),
+            ),
+            messages_types: None,
+            configurables: None,
+        }
+    }
+
+    fn option_of(type_id: usize) -> TypeApplication {
+        TypeApplication {
+            type_arguments: Some(vec![app("", type_id)]),
+            ..app("", 5)
+        }
+    }
+
+    fn log_data(log_id: u64, data: Vec<u8>) -> Receipt {
+        Receipt::LogData {
+            id: ContractId::zeroed(),
+            ra: 0,
+            rb: log_id,
+            ptr: 0,
+            len: data.len() as u64,
+            digest: Bytes32::zeroed(),
+            pc: 0,
+            is: 0,
+            data: Some(data),
+        }
+    }
+
+    fn word(value: u64) -> Vec<u8> {
+        value.to_be_bytes().to_vec()
+    }
+
+    #[test]
+    fn resolves_generic_types() {
+        let decoder = LogDecoder::new(&abi(
+            vec![
+                (0, option_of(3)),
+                (
+                    1,
+                    TypeApplication {
+                        type_arguments: Some(vec![option_of(2)]),
+                        ..app("", 12)
+                    },
+                ),
+            ],
+            None,
+        ));
+        assert_eq!(decoder.logged_types[&0].to_string(), "Option<Point>");
+        assert_eq!(decoder.logged_types[&1].to_string(), "Vec<Option<u64>>");
+        let LogType::Enum { variants, .. } = &decoder.logged_types[&0] else {
+            panic!("expected an enum");
+        };
+        assert!(matches!(&variants[1].1, LogType::Struct { name, .. } if name == "Point"));
+    }
+
+    #[test]
+    fn decodes_in_memory_layout() {
+        let decoder = LogDecoder::new(&abi(
+            vec![
+                (0, app("", 9)),
+                (1, option_of(1)),
+                (2, app("", 7)),
+                (3, app("", 8)),
+            ],
+            None,
+        ));
+        // `(Point { x: 42, flag: true }, 7)`, with the `bool` at the start of its word.
+        let mut tuple = word(42);
+        tuple.extend([1, 0, 0, 0, 0, 0, 0, 0]);
+        tuple.extend(word(7));
+        // `Option::Some(true)`, with the `bool` at the end of the variants' word.
+        let mut option = word(1);
+        option.extend([0, 0, 0, 0, 0, 0, 0, 1]);
+        let logs = decoder.decode_logs(&[
+            log_data(0, tuple),
+            log_data(1, option),
+            log_data(2, vec![1, 2, 3]),
+            log_data(3, b"hello\0\0\0".to_vec()),
+        ]);
+        let logs = logs.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            logs,
+            [
+                "(Point, u64): (Point { x: 42, flag: true }, 7)",
+                "Option<bool>: Option::Some(true)",
+                "[u8; 3]: [1, 2, 3]",
+                "str[5]: \"hello\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_encoded_values() {
+        let vec_of_options = TypeApplication {
+            type_arguments: Some(vec![option_of(2)]),
+            ..app("", 12)
+        };
+        let decoder = LogDecoder::new(&abi(
+            vec![(0, vec_of_options), (1, app("", 3)), (2, app("", 8))],
+            Some(Version("1".to_string())),
+        ));
+        // `vec![Option::None, Option::Some(5)]`, with a length prefix.
+        let mut vec = word(2);
+        vec.extend(word(0));
+        vec.extend(word(1));
+        vec.extend(word(5));
+        // `Point { x: 3, flag: false }`, without any padding.
+        let mut point = word(3);
+        point.push(0);
+        let logs = decoder.decode_logs(&[
+            log_data(0, vec),
+            log_data(1, point),
+            log_data(2, b"hello".to_vec()),
+        ]);
+        let logs = logs.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            logs,
+            [
+                "Vec<Option<u64>>: [Option::None, Option::Some(5)]",
+                "Point: Point { x: 3, flag: false }",
+                "str[5]: \"hello\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_undecodable_values_raw() {
+        let decoder = LogDecoder::new(&abi(vec![(0, app("", 2)), (1, app("", 3))], None));
+        let log = |id| Receipt::Log {
+            id,
+            ra: 42,
+            rb: 0,
+            rc: 0,
+            rd: 0,
+            pc: 0,
+            is: 0,
+        };
+        let logs = decoder.decode_logs(&[
+            log(ContractId::zeroed()),
+            log(ContractId::from([1; 32])),
+            log_data(1, vec![0xab; 3]),
+            log_data(7, vec![0xcd]),
+        ]);
+        let logs = logs.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            logs,
+            [
+                "u64: 42",
+                "log id 0: 0x000000000000002a",
+                "log id 1: 0xababab",
+                "log id 7: 0xcd",
+            ]
+        );
+    }
+
+    #[test]
+    fn pretty_prints_aggregates() {
+        let value = LogValue::Tuple(vec![
+            LogValue::Struct {
+                name: "Point".to_string(),
+                fields: vec![
+                    ("x".to_string(), LogValue::Uint(1)),
+                    ("flag".to_string(), LogValue::Bool(false)),
+                ],
+            },
+            LogValue::Array(vec![]),
+        ]);
+        assert_eq!(
+            format!("{value:#}"),
+            "(\n    Point {\n        x: 1,\n        flag: false,\n    },\n    [],\n)"
+        );
+    }
+}
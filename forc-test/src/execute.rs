@@ -7,6 +7,7 @@ use fuel_vm::error::InterpreterError;
 use fuel_vm::{
     self as vm,
     checked_transaction::builder::TransactionBuilderExt,
+    fuel_asm::RegId,
     interpreter::{Interpreter, NotSupportedEcal},
     prelude::{Instruction, SecretKey},
     storage::MemoryStorage,
@@ -14,7 +15,9 @@ use fuel_vm::{
 use rand::{Rng, SeedableRng};
 use tx::Receipt;
 
+use vm::state::Breakpoint;
 use vm::state::DebugEval;
+use vm::state::ExecuteState;
 use vm::state::ProgramState;
 
 /// An interface for executing a test within a VM [Interpreter] instance.
@@ -24,10 +27,27 @@ pub struct TestExecutor {
     pub tx_builder: tx::TransactionBuilder<tx::Script>,
     pub test_entry: PkgTestEntry,
     pub name: String,
+    /// The ID of the deployed contract under test, if the test belongs to a contract.
+    pub root_contract_id: Option<tx::ContractId>,
+}
+
+/// An instruction executed while single-stepping a test.
+#[derive(Debug, Clone)]
+pub struct ExecutedInstruction {
+    /// The location of the instruction.
+    ///
+    /// The contract is zeroed for instructions executed by the test itself, and is otherwise the
+    /// contract within whose call frame the instruction was executed.
+    pub breakpoint: Breakpoint,
+    /// The gas consumed by executing the instruction.
+    pub gas_used: u64,
+    /// The contract called by the instruction if it is a `CALL`.
+    pub called_contract: Option<tx::ContractId>,
 }
 
 /// The result of executing a test with breakpoints enabled.
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum DebugResult {
     // Holds the test result.
     TestComplete(TestResult),
@@ -39,17 +59,18 @@ impl TestExecutor {
     pub fn new(
         bytecode: &[u8],
         test_offset: u32,
+        script_data: Vec<u8>,
         test_setup: TestSetup,
         test_entry: &PkgTestEntry,
         name: String,
     ) -> Self {
         let storage = test_setup.storage().clone();
+        let root_contract_id = test_setup.root_contract_id();
 
         // Patch the bytecode to jump to the relevant test.
         let bytecode = patch_test_bytecode(bytecode, test_offset).into_owned();
 
         // Create a transaction to execute the test function.
-        let script_input_data = vec![];
         let rng = &mut rand::rngs::StdRng::seed_from_u64(TEST_METADATA_SEED);
 
         // Prepare the transaction metadata.
@@ -60,7 +81,7 @@ impl TestExecutor {
         let asset_id = rng.gen();
         let tx_pointer = rng.gen();
 
-        let mut tx_builder = tx::TransactionBuilder::script(bytecode, script_input_data)
+        let mut tx_builder = tx::TransactionBuilder::script(bytecode, script_data)
             .add_unsigned_coin_input(
                 secret_key,
                 utxo_id,
@@ -105,6 +126,7 @@ impl TestExecutor {
             tx_builder,
             test_entry: test_entry.clone(),
             name,
+            root_contract_id,
         }
     }
 
@@ -136,6 +158,9 @@ impl TestExecutor {
             condition,
             logs,
             gas_used,
+            coverage: None,
+            gas_profile: None,
+            fuzz: None,
         }))
     }
 
@@ -167,6 +192,9 @@ impl TestExecutor {
             condition,
             logs,
             gas_used,
+            coverage: None,
+            gas_profile: None,
+            fuzz: None,
         }))
     }
 
@@ -194,9 +222,140 @@ impl TestExecutor {
             condition,
             logs,
             gas_used,
+            coverage: None,
+            gas_profile: None,
+            fuzz: None,
         })
     }
 
+    /// Execute the test one instruction at a time, calling `on_step` after each instruction is
+    /// executed, including those executed within the call frames of contracts called by the test.
+    ///
+    /// This is considerably slower than [TestExecutor::execute], and is used to collect code
+    /// coverage and gas profiles.
+    pub fn execute_single_stepping(
+        &mut self,
+        mut on_step: impl FnMut(&ExecutedInstruction),
+    ) -> anyhow::Result<TestResult> {
+        let block_height = (u32::MAX >> 1).into();
+        let start = std::time::Instant::now();
+        // Break before the first instruction so that the transaction is initialized, then take
+        // over execution. The VM never returns control to its debugger from within a call
+        // frame, so each instruction is executed directly rather than by resuming to the next
+        // breakpoint.
+        self.interpreter.set_single_stepping(true);
+        let mut state = *self
+            .interpreter
+            .transact(self.tx_builder.finalize_checked(block_height))
+            .map_err(|err: InterpreterError<_>| anyhow::anyhow!(err))?
+            .state();
+        self.interpreter.set_single_stepping(false);
+
+        // The `ScriptResult` receipt is appended when `transact` breaks before the first
+        // instruction and is not updated as execution continues, so measure the gas consumed
+        // while stepping through the `$ggas` register instead.
+        let gas_start = self.interpreter.registers()[RegId::GGAS];
+        let mut panic_receipt = None;
+        while let ProgramState::RunProgram(_) = state {
+            let breakpoint = self.next_instruction();
+            let called_contract = self.called_contract();
+            let gas_before = self.interpreter.registers()[RegId::GGAS];
+            let in_call = *breakpoint.contract() != tx::ContractId::zeroed();
+
+            // Mirror the VM's own run loop, where only reverts end execution from within a call
+            // frame and panics revert the transaction with a `Panic` receipt.
+            state = match self.interpreter.execute() {
+                Ok(ExecuteState::Revert(ra)) => ProgramState::Revert(ra),
+                Ok(ExecuteState::Return(ra)) if !in_call => ProgramState::Return(ra),
+                Ok(ExecuteState::ReturnData(digest)) if !in_call => {
+                    ProgramState::ReturnData(digest)
+                }
+                Ok(_) => state,
+                Err(err) => match err.instruction_result() {
+                    Some(reason) => {
+                        let registers = self.interpreter.registers();
+                        panic_receipt = Some(Receipt::panic(
+                            *breakpoint.contract(),
+                            reason,
+                            registers[RegId::PC],
+                            registers[RegId::IS],
+                        ));
+                        ProgramState::Revert(0)
+                    }
+                    None => anyhow::bail!("VM failed to execute. {:?}", err),
+                },
+            };
+
+            on_step(&ExecutedInstruction {
+                breakpoint,
+                gas_used: gas_before.saturating_sub(self.interpreter.registers()[RegId::GGAS]),
+                called_contract,
+            });
+        }
+        let gas_used = gas_start.saturating_sub(self.interpreter.registers()[RegId::GGAS]);
+
+        let duration = start.elapsed();
+        // The VM's receipts can't be appended to from here, so the panic receipt is added to a
+        // copy of them.
+        let mut receipts = self.interpreter.receipts().to_vec();
+        receipts.extend(panic_receipt);
+        let (_, logs) = Self::get_gas_and_receipts(receipts)?;
+        let span = self.test_entry.span.clone();
+        let file_path = self.test_entry.file_path.clone();
+        let condition = self.test_entry.pass_condition.clone();
+        let name = self.name.clone();
+        Ok(TestResult {
+            name,
+            file_path,
+            duration,
+            span,
+            state,
+            condition,
+            logs,
+            gas_used,
+            coverage: None,
+            gas_profile: None,
+            fuzz: None,
+        })
+    }
+
+    /// The location of the instruction the VM is about to execute.
+    ///
+    /// Instructions executed within a call frame are identified by the called contract's ID and
+    /// their offset within its bytecode, while those executed by the test itself have a zeroed
+    /// contract ID.
+    fn next_instruction(&self) -> Breakpoint {
+        let registers = self.interpreter.registers();
+        let memory = self.interpreter.memory();
+        let pc = registers[RegId::PC].saturating_sub(registers[RegId::IS]);
+        // `$fp` is zero outside of any call, and otherwise points to the current call frame,
+        // which begins with the ID of the called contract.
+        let fp = registers[RegId::FP] as usize;
+        let contract = memory
+            .get(fp..fp + tx::ContractId::LEN)
+            .filter(|_| fp != 0)
+            .and_then(|contract| tx::ContractId::try_from(contract).ok())
+            .unwrap_or_else(tx::ContractId::zeroed);
+        Breakpoint::new(contract, pc / Instruction::SIZE as u64)
+    }
+
+    /// If the VM is about to execute a `CALL` instruction, returns the ID of the contract called.
+    fn called_contract(&self) -> Option<tx::ContractId> {
+        let registers = self.interpreter.registers();
+        let memory = self.interpreter.memory();
+        let pc = registers[RegId::PC] as usize;
+        let bytes = memory.get(pc..pc + Instruction::SIZE)?;
+        let Ok(Instruction::CALL(call)) = Instruction::try_from(<[u8; 4]>::try_from(bytes).ok()?)
+        else {
+            return None;
+        };
+        // The call's first register points to the call parameters, beginning with the contract ID.
+        let (params, _, _, _) = call.unpack();
+        let params = registers[params] as usize;
+        let contract_id = memory.get(params..params + tx::ContractId::LEN)?;
+        tx::ContractId::try_from(contract_id).ok()
+    }
+
     fn get_gas_and_receipts(receipts: Vec<Receipt>) -> anyhow::Result<(u64, Vec<Receipt>)> {
         let gas_used = *receipts
             .iter()
@@ -235,7 +394,7 @@ impl TestExecutor {
 /// [5] add  $$ds $$ds $is
 /// [6] <first-entry-point>           ; This is where we want to jump from to our test code!
 /// ```
-fn patch_test_bytecode(bytecode: &[u8], test_offset: u32) -> std::borrow::Cow<[u8]> {
+fn patch_test_bytecode(bytecode: &[u8], test_offset: u32) -> std::borrow::Cow<'_, [u8]> {
     // TODO: Standardize this or add metadata to bytecode.
     const PROGRAM_START_INST_OFFSET: u32 = 6;
     const PROGRAM_START_BYTE_OFFSET: usize = PROGRAM_START_INST_OFFSET as usize * Instruction::SIZE;
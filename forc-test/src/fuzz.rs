@@ -0,0 +1,554 @@
+//! Property-based testing for unit tests with parameters.
+//!
+//! Tests that declare parameters are run repeatedly with arguments generated from the parameters'
+//! types. The arguments are written to the script data using the layout the compiler expects for
+//! the arguments of an entry function. When a run fails, its arguments are repeatedly simplified
+//! while the test continues to fail, and the simplest failing arguments are reported as a
+//! counterexample.
+
+use crate::{coverage::TestCoverage, TestResult};
+use forc_pkg::{TestParam, TestParamType};
+use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+use std::fmt;
+
+/// The number of runs for tests with parameters when no number is specified.
+pub const DEFAULT_RUNS: u64 = 256;
+
+/// The seed from which arguments are generated when no seed is specified.
+pub const DEFAULT_SEED: u64 = 0;
+
+/// The maximum number of runs executed while shrinking a failing test's arguments.
+const MAX_SHRINK_RUNS: usize = 1024;
+
+/// The size of a word in bytes.
+const WORD_SIZE: usize = 8;
+
+/// The outcome of running a test with generated arguments.
+#[derive(Debug, Clone)]
+pub struct FuzzReport {
+    /// The number of runs with generated arguments, excluding those executed while shrinking.
+    pub runs: u64,
+    /// The seed from which arguments were generated.
+    pub seed: u64,
+    /// The simplest arguments found for which the test fails, along with the parameter names.
+    pub counterexample: Option<Vec<(String, TestValue)>>,
+}
+
+/// A value generated as an argument to a test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestValue {
+    Bool(bool),
+    /// An unsigned integer of at most 64 bits, along with its number of bits.
+    Uint(usize, u64),
+    /// A `u256` in big-endian byte order.
+    U256([u8; 32]),
+    B256([u8; 32]),
+    StrArray(String),
+    Array(Vec<TestValue>),
+    Tuple(Vec<TestValue>),
+    Struct {
+        name: String,
+        fields: Vec<(String, TestValue)>,
+    },
+}
+
+/// Run a test with arguments generated for the given parameters, shrinking the arguments of the
+/// first failing run.
+///
+/// Returns the result of the failing run with the simplest arguments if the test fails, or of the
+/// last run otherwise. The returned result's duration covers all runs, and its coverage is merged
+/// from all runs. The gas used by a passing test is averaged over all runs.
+pub(crate) fn run(
+    params: &[TestParam],
+    runs: u64,
+    seed: u64,
+    mut run_case: impl FnMut(&[TestValue]) -> anyhow::Result<TestResult>,
+) -> anyhow::Result<TestResult> {
+    let start = std::time::Instant::now();
+    let rng = &mut StdRng::seed_from_u64(seed);
+    let mut coverage: Option<TestCoverage> = None;
+    let mut run_case = |args: &[TestValue]| {
+        let result = run_case(args)?;
+        if let Some(case_coverage) = &result.coverage {
+            coverage
+                .get_or_insert_with(Default::default)
+                .merge(case_coverage);
+        }
+        anyhow::Ok(result)
+    };
+
+    let mut total_gas = 0u128;
+    let mut run = 0;
+    let (mut result, counterexample) = loop {
+        run += 1;
+        let args: Vec<_> = params
+            .iter()
+            .map(|param| TestValue::generate(&param.ty, rng))
+            .collect();
+        let mut result = run_case(&args)?;
+        if !result.passed() {
+            let (args, result) = shrink(args, result, &mut run_case)?;
+            let names = params.iter().map(|param| param.name.clone());
+            break (result, Some(names.zip(args).collect()));
+        }
+        total_gas += u128::from(result.gas_used);
+        if run >= runs {
+            result.gas_used = (total_gas / u128::from(run)) as u64;
+            break (result, None);
+        }
+    };
+
+    result.duration = start.elapsed();
+    result.coverage = coverage;
+    result.fuzz = Some(FuzzReport {
+        runs: run,
+        seed,
+        counterexample,
+    });
+    Ok(result)
+}
+
+/// Repeatedly replace the failing arguments with simpler ones for which the test still fails,
+/// until no simpler failing arguments are found.
+fn shrink(
+    mut args: Vec<TestValue>,
+    mut result: TestResult,
+    mut run_case: impl FnMut(&[TestValue]) -> anyhow::Result<TestResult>,
+) -> anyhow::Result<(Vec<TestValue>, TestResult)> {
+    let mut shrink_runs = 0;
+    'shrink: loop {
+        let candidates: Vec<(usize, TestValue)> = args
+            .iter()
+            .enumerate()
+            .flat_map(|(ix, arg)| arg.shrink().into_iter().map(move |arg| (ix, arg)))
+            .collect();
+        for (ix, arg) in candidates {
+            if shrink_runs == MAX_SHRINK_RUNS {
+                break 'shrink;
+            }
+            shrink_runs += 1;
+            let mut shrunk = args.clone();
+            shrunk[ix] = arg;
+            let shrunk_result = run_case(&shrunk)?;
+            if !shrunk_result.passed() {
+                args = shrunk;
+                result = shrunk_result;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+    Ok((args, result))
+}
+
+/// Encode the given arguments as script data.
+///
+/// A single argument that fits within a register is loaded from the script data as a word, while
+/// any other single argument is read from memory in place. Multiple arguments are laid out
+/// consecutively, each aligned to a word.
+pub fn encode_args(args: &[TestValue]) -> Vec<u8> {
+    let mut bytes = vec![];
+    match args {
+        [TestValue::Bool(value)] => bytes.extend(u64::from(*value).to_be_bytes()),
+        [TestValue::Uint(_, value)] => bytes.extend(value.to_be_bytes()),
+        args => {
+            for arg in args {
+                arg.write_aligned(&mut bytes);
+            }
+        }
+    }
+    bytes
+}
+
+impl TestValue {
+    /// Generate a random value of the given type.
+    ///
+    /// Edge cases such as zero and the maximum value are generated more often than they would be
+    /// by sampling uniformly, and integers are biased towards smaller magnitudes.
+    pub fn generate(ty: &TestParamType, rng: &mut impl Rng) -> Self {
+        match ty {
+            TestParamType::Bool => Self::Bool(rng.gen()),
+            TestParamType::Uint(256) => {
+                let mut bytes = [0; 32];
+                match rng.gen_range(0..8) {
+                    0 => (),
+                    1 => bytes[31] = 1,
+                    2 => bytes = [u8::MAX; 32],
+                    _ => {
+                        let len = rng.gen_range(1..=32);
+                        rng.fill(&mut bytes[32 - len..]);
+                    }
+                }
+                Self::U256(bytes)
+            }
+            TestParamType::Uint(bits) => {
+                let max = u64::MAX >> (64 - bits);
+                let value = match rng.gen_range(0..8) {
+                    0 => 0,
+                    1 => 1,
+                    2 => max,
+                    _ => rng.gen::<u64>() >> (64 - rng.gen_range(1..=*bits)),
+                };
+                Self::Uint(*bits, value)
+            }
+            TestParamType::B256 => {
+                let mut bytes = [0; 32];
+                if rng.gen_range(0..8) != 0 {
+                    rng.fill(&mut bytes);
+                }
+                Self::B256(bytes)
+            }
+            TestParamType::StrArray(len) => Self::StrArray(
+                (0..*len)
+                    .map(|_| rng.sample(Alphanumeric) as char)
+                    .collect(),
+            ),
+            TestParamType::Array(elem, len) => {
+                Self::Array((0..*len).map(|_| Self::generate(elem, rng)).collect())
+            }
+            TestParamType::Tuple(fields) => {
+                Self::Tuple(fields.iter().map(|ty| Self::generate(ty, rng)).collect())
+            }
+            TestParamType::Struct { name, fields } => Self::Struct {
+                name: name.clone(),
+                fields: fields
+                    .iter()
+                    .map(|field| (field.name.clone(), Self::generate(&field.ty, rng)))
+                    .collect(),
+            },
+        }
+    }
+
+    /// Simpler values of the same type, ordered from simplest.
+    pub fn shrink(&self) -> Vec<Self> {
+        match self {
+            Self::Bool(value) => match value {
+                true => vec![Self::Bool(false)],
+                false => vec![],
+            },
+            Self::Uint(bits, value) => {
+                // Try dropping leading decimal digits, which finds small values for properties that
+                // depend on a remainder, then approach zero by successively smaller steps so that
+                // the boundary at which the test begins to fail is found in few runs.
+                let truncated = (1..20)
+                    .map(|digits| 10u64.pow(digits))
+                    .take_while(|modulus| modulus <= value)
+                    .map(|modulus| value % modulus);
+                let steps = (1..64)
+                    .map(|shift| value >> shift)
+                    .take_while(|&step| step > 0)
+                    .map(|step| value - step);
+                let mut values: Vec<Self> = vec![];
+                for v in std::iter::once(0)
+                    .chain(truncated)
+                    .chain(steps)
+                    .chain(value.checked_sub(1))
+                {
+                    let shrunk = Self::Uint(*bits, v);
+                    if v != *value && !values.contains(&shrunk) {
+                        values.push(shrunk);
+                    }
+                }
+                values
+            }
+            Self::U256(bytes) => {
+                if *bytes == [0; 32] {
+                    return vec![];
+                }
+                let mut values = vec![[0; 32], shr1(bytes), decrement(bytes)];
+                values.dedup();
+                values
+                    .into_iter()
+                    .filter(|v| v != bytes)
+                    .map(Self::U256)
+                    .collect()
+            }
+            Self::B256(bytes) => {
+                if *bytes == [0; 32] {
+                    return vec![];
+                }
+                let mut values = vec![Self::B256([0; 32])];
+                for ix in (0..32).filter(|&ix| bytes[ix] != 0) {
+                    let mut value = *bytes;
+                    value[ix] = 0;
+                    values.push(Self::B256(value));
+                }
+                values
+            }
+            Self::StrArray(s) => {
+                let mut values = vec![];
+                if s.chars().any(|c| c != 'a') {
+                    values.push(Self::StrArray("a".repeat(s.len())));
+                }
+                for (ix, c) in s.char_indices().filter(|&(_, c)| c != 'a') {
+                    let mut value = s.clone();
+                    value.replace_range(ix..ix + c.len_utf8(), "a");
+                    values.push(Self::StrArray(value));
+                }
+                values
+            }
+            Self::Array(elems) => shrink_each(elems).into_iter().map(Self::Array).collect(),
+            Self::Tuple(elems) => shrink_each(elems).into_iter().map(Self::Tuple).collect(),
+            Self::Struct { name, fields } => {
+                let (names, values): (Vec<_>, Vec<_>) = fields.iter().cloned().unzip();
+                shrink_each(&values)
+                    .into_iter()
+                    .map(|values| Self::Struct {
+                        name: name.clone(),
+                        fields: names.iter().cloned().zip(values).collect(),
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Append the value's in-memory representation to `bytes`.
+    fn write(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Bool(value) => bytes.push(u8::from(*value)),
+            Self::Uint(8, value) => bytes.push(*value as u8),
+            Self::Uint(_, value) => bytes.extend(value.to_be_bytes()),
+            Self::U256(value) | Self::B256(value) => bytes.extend(value),
+            Self::StrArray(s) => {
+                let start = bytes.len();
+                bytes.extend(s.as_bytes());
+                pad_to_word(bytes, start);
+            }
+            // Array elements are packed, while the fields of tuples and structs are word aligned.
+            Self::Array(elems) => elems.iter().for_each(|elem| elem.write(bytes)),
+            Self::Tuple(elems) => elems.iter().for_each(|elem| elem.write_aligned(bytes)),
+            Self::Struct { fields, .. } => fields
+                .iter()
+                .for_each(|(_, field)| field.write_aligned(bytes)),
+        }
+    }
+
+    /// Append the value's in-memory representation to `bytes`, padded to a whole number of words.
+    fn write_aligned(&self, bytes: &mut Vec<u8>) {
+        let start = bytes.len();
+        self.write(bytes);
+        pad_to_word(bytes, start);
+    }
+}
+
+impl fmt::Display for TestValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Uint(_, value) => write!(f, "{value}"),
+            Self::U256(bytes) | Self::B256(bytes) => {
+                write!(f, "0x")?;
+                bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+            }
+            Self::StrArray(s) => write!(f, "{s:?}"),
+            Self::Array(elems) => {
+                write!(f, "[")?;
+                write_list(f, elems)?;
+                write!(f, "]")
+            }
+            Self::Tuple(elems) => {
+                write!(f, "(")?;
+                write_list(f, elems)?;
+                if elems.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Self::Struct { name, fields } if fields.is_empty() => write!(f, "{name} {{}}"),
+            Self::Struct { name, fields } => {
+                write!(f, "{name} {{ ")?;
+                for (ix, (field_name, value)) in fields.iter().enumerate() {
+                    if ix > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{field_name}: {value}")?;
+                }
+                write!(f, " }}")
+            }
+        }
+    }
+}
+
+/// Simpler versions of the given values, each with a single value shrunk.
+fn shrink_each(values: &[TestValue]) -> Vec<Vec<TestValue>> {
+    values
+        .iter()
+        .enumerate()
+        .flat_map(|(ix, value)| {
+            value.shrink().into_iter().map(move |shrunk| {
+                let mut values = values.to_vec();
+                values[ix] = shrunk;
+                values
+            })
+        })
+        .collect()
+}
+
+fn write_list(f: &mut fmt::Formatter<'_>, values: &[TestValue]) -> fmt::Result {
+    for (ix, value) in values.iter().enumerate() {
+        if ix > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{value}")?;
+    }
+    Ok(())
+}
+
+/// Pad the bytes written since `start` to a whole number of words.
+fn pad_to_word(bytes: &mut Vec<u8>, start: usize) {
+    let len = bytes.len() - start;
+    let padded_len = len.div_ceil(WORD_SIZE) * WORD_SIZE;
+    bytes.resize(start + padded_len, 0);
+}
+
+/// Halve a big-endian `u256`.
+fn shr1(bytes: &[u8; 32]) -> [u8; 32] {
+    let mut shifted = [0; 32];
+    let mut carry = 0;
+    for (ix, byte) in bytes.iter().enumerate() {
+        shifted[ix] = (byte >> 1) | carry;
+        carry = byte << 7;
+    }
+    shifted
+}
+
+/// Subtract one from a non-zero big-endian `u256`.
+fn decrement(bytes: &[u8; 32]) -> [u8; 32] {
+    let mut decremented = *bytes;
+    for byte in decremented.iter_mut().rev() {
+        let (value, borrowed) = byte.overflowing_sub(1);
+        *byte = value;
+        if !borrowed {
+            break;
+        }
+    }
+    decremented
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_single_and_multiple_args() {
+        assert_eq!(encode_args(&[]), Vec::<u8>::new());
+        assert_eq!(
+            encode_args(&[TestValue::Uint(8, 7)]),
+            [0, 0, 0, 0, 0, 0, 0, 7]
+        );
+        assert_eq!(
+            encode_args(&[TestValue::Bool(true), TestValue::Uint(64, 0x0102)]),
+            [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2]
+        );
+        let point = TestValue::Struct {
+            name: "Point".to_string(),
+            fields: vec![
+                ("x".to_string(), TestValue::Uint(8, 1)),
+                (
+                    "y".to_string(),
+                    TestValue::Array(vec![TestValue::Uint(8, 2), TestValue::Uint(8, 3)]),
+                ),
+            ],
+        };
+        assert_eq!(
+            encode_args(&[point]),
+            [1, 0, 0, 0, 0, 0, 0, 0, 2, 3, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            encode_args(&[TestValue::StrArray("abc".to_string())]),
+            [b'a', b'b', b'c', 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn generated_values_match_types() {
+        let rng = &mut StdRng::seed_from_u64(DEFAULT_SEED);
+        let ty = TestParamType::Struct {
+            name: "Foo".to_string(),
+            fields: vec![
+                TestParam {
+                    name: "a".to_string(),
+                    ty: TestParamType::Uint(8),
+                },
+                TestParam {
+                    name: "b".to_string(),
+                    ty: TestParamType::Array(Box::new(TestParamType::StrArray(3)), 2),
+                },
+            ],
+        };
+        for _ in 0..100 {
+            let TestValue::Struct { name, fields } = TestValue::generate(&ty, rng) else {
+                panic!("expected a struct");
+            };
+            assert_eq!(name, "Foo");
+            assert!(matches!(fields[0], (_, TestValue::Uint(8, a)) if a <= u64::from(u8::MAX)));
+            let (_, TestValue::Array(b)) = &fields[1] else {
+                panic!("expected an array");
+            };
+            assert!(b
+                .iter()
+                .all(|s| matches!(s, TestValue::StrArray(s) if s.len() == 3)));
+        }
+    }
+
+    #[test]
+    fn shrink_uint_towards_boundary() {
+        // Greedily shrink as the runner does, with a test that fails for values of at least 1000.
+        let mut value = TestValue::Uint(64, 123_456_789);
+        while let Some(shrunk) = value
+            .shrink()
+            .into_iter()
+            .find(|v| matches!(v, TestValue::Uint(_, v) if *v >= 1000))
+        {
+            value = shrunk;
+        }
+        assert_eq!(value, TestValue::Uint(64, 1000));
+
+        // With a test that fails for values with a remainder of 9 when divided by 10.
+        let mut value = TestValue::Uint(64, 82_658_261_567_968_319);
+        while let Some(shrunk) = value
+            .shrink()
+            .into_iter()
+            .find(|v| matches!(v, TestValue::Uint(_, v) if v % 10 == 9))
+        {
+            value = shrunk;
+        }
+        assert_eq!(value, TestValue::Uint(64, 9));
+    }
+
+    #[test]
+    fn shrink_u256() {
+        let mut bytes = [0; 32];
+        bytes[30] = 1;
+        let value = TestValue::U256(bytes);
+        let mut halved = [0; 32];
+        halved[31] = 0x80;
+        let mut decremented = [0; 32];
+        decremented[31] = 0xff;
+        assert_eq!(
+            value.shrink(),
+            [
+                TestValue::U256([0; 32]),
+                TestValue::U256(halved),
+                TestValue::U256(decremented),
+            ]
+        );
+        assert_eq!(TestValue::U256([0; 32]).shrink(), []);
+    }
+
+    #[test]
+    fn display_values() {
+        let value = TestValue::Tuple(vec![
+            TestValue::Bool(true),
+            TestValue::StrArray("ab".to_string()),
+            TestValue::Struct {
+                name: "Foo".to_string(),
+                fields: vec![(
+                    "x".to_string(),
+                    TestValue::Array(vec![TestValue::Uint(8, 1), TestValue::Uint(8, 2)]),
+                )],
+            },
+        ]);
+        assert_eq!(value.to_string(), r#"(true, "ab", Foo { x: [1, 2] })"#);
+    }
+}
@@ -0,0 +1,473 @@
+//! Per-function gas profiling for unit tests.
+//!
+//! Gas is profiled by single-stepping the VM during test execution and attributing the gas consumed
+//! by each instruction to the call stack within which it was executed. Call stacks are
+//! reconstructed from the offsets of the function labels within the compiled bytecode, while
+//! instructions that were inlined from other functions are attributed to their enclosing Sway
+//! function using the package's `SourceMap`.
+//!
+//! Instructions executed within a contract call are attributed to a frame representing the called
+//! contract. Within the deployed contract under test, call stacks are further reconstructed from
+//! the function offsets and source map of the contract's bytecode built without tests.
+
+use crate::{execute::ExecutedInstruction, source::SourceFiles, TestedPackage};
+use fuel_tx as tx;
+use fuel_vm::{prelude::Instruction, state::Breakpoint};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io::{self, Write},
+};
+use sway_core::source_map::SourceMap;
+
+/// A single frame of a reconstructed call stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Frame {
+    /// A function within the test's bytecode, identified by the instruction offset of its label.
+    Fn(u64),
+    /// A call to the contract with the given ID.
+    Contract(tx::ContractId),
+    /// A function within the bytecode of the contract under test, identified by the instruction
+    /// offset of its label.
+    ContractFn(u64),
+}
+
+/// The gas consumed during a single test, keyed by call stack and instruction.
+#[derive(Debug, Clone, Default)]
+pub struct TestGasProfile {
+    /// Each distinct call stack observed during execution.
+    pub stacks: Vec<Vec<Frame>>,
+    /// The gas consumed by each instruction, keyed by the index of its call stack within `stacks`
+    /// and the instruction's index within the bytecode executing at the top of the stack.
+    pub samples: HashMap<(usize, usize), u64>,
+    /// The ID of the deployed contract under test, if the test belongs to a contract.
+    pub root_contract_id: Option<tx::ContractId>,
+}
+
+/// Records the gas consumed by each instruction executed during a single test.
+#[derive(Debug)]
+pub struct GasProfiler<'a> {
+    fn_offsets: &'a BTreeMap<u64, String>,
+    /// The function offsets of the contract under test's bytecode.
+    contract_fn_offsets: Option<&'a BTreeMap<u64, String>>,
+    profile: TestGasProfile,
+    stack_ids: HashMap<Vec<Frame>, usize>,
+    /// The current call stack, along with the instruction index each frame returns to.
+    stack: Vec<(Frame, Option<usize>)>,
+    /// The index of the current call stack within `profile.stacks`, if known.
+    stack_id: Option<usize>,
+    /// The index of the previously executed instruction.
+    prev_ix: Option<usize>,
+    /// The contract called by the previously executed instruction, if it was a `CALL`.
+    pending_call: Option<tx::ContractId>,
+    /// The contract calls in progress.
+    calls: Vec<Call>,
+}
+
+/// A contract call in progress.
+#[derive(Debug)]
+struct Call {
+    /// The called contract.
+    contract_id: tx::ContractId,
+    /// The length of the call stack when the call was made.
+    stack_len: usize,
+    /// The index of the `CALL` instruction within the caller's bytecode.
+    call_ix: Option<usize>,
+}
+
+/// The gas consumed by the tests of a package, attributed to the functions that consumed it.
+#[derive(Debug, Clone, Default)]
+pub struct PackageGasProfile {
+    /// The gas consumed within each call stack, where each frame is named by its function.
+    pub stacks: BTreeMap<Vec<String>, u64>,
+}
+
+/// The gas consumed by a single function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionGas {
+    /// The name of the function.
+    pub name: String,
+    /// The gas consumed by the function's own instructions.
+    pub self_gas: u64,
+    /// The gas consumed by the function along with everything that it calls.
+    pub total_gas: u64,
+}
+
+impl<'a> GasProfiler<'a> {
+    /// Create a profiler for a test given the function offsets of the test's bytecode, along with
+    /// the ID and function offsets of the deployed contract under test if there is one.
+    pub fn new(
+        fn_offsets: &'a BTreeMap<u64, String>,
+        root_contract: Option<(tx::ContractId, &'a BTreeMap<u64, String>)>,
+    ) -> Self {
+        Self {
+            fn_offsets,
+            contract_fn_offsets: root_contract.map(|(_, fn_offsets)| fn_offsets),
+            profile: TestGasProfile {
+                root_contract_id: root_contract.map(|(contract_id, _)| contract_id),
+                ..Default::default()
+            },
+            stack_ids: HashMap::new(),
+            stack: vec![],
+            stack_id: None,
+            prev_ix: None,
+            pending_call: None,
+            calls: vec![],
+        }
+    }
+
+    /// Record the gas consumed by an executed instruction.
+    ///
+    /// Calls are recognised by jumps to the start of a function, and returns by jumps back to the
+    /// instruction following the call. Contract calls are recognised by the instruction following
+    /// a `CALL` executing within the called contract, and returns by execution leaving it.
+    pub fn record(&mut self, executed: &ExecutedInstruction) {
+        let contract_id = *executed.breakpoint.contract();
+        let ix = executed.breakpoint.pc() as usize / Instruction::SIZE;
+        if let Some(called) = self
+            .pending_call
+            .take()
+            .filter(|&called| called == contract_id)
+        {
+            self.calls.push(Call {
+                contract_id: called,
+                stack_len: self.stack.len(),
+                call_ix: self.prev_ix,
+            });
+            self.push(Frame::Contract(called), None);
+            self.prev_ix = None;
+        } else if contract_id != self.contract_id() {
+            if let Some(call) = self.calls.pop() {
+                self.stack.truncate(call.stack_len);
+                self.stack_id = None;
+                self.prev_ix = call.call_ix;
+            }
+        }
+
+        if let Some(prev_ix) = self.prev_ix.filter(|&prev_ix| ix != prev_ix + 1) {
+            let returned =
+                matches!(self.stack.last(), Some((_, Some(return_ix))) if *return_ix == ix);
+            if returned {
+                self.pop();
+            } else if let Some(frame) = self.fn_frame(&executed.breakpoint) {
+                self.push(frame, Some(prev_ix + 1));
+            }
+        }
+
+        self.sample(ix, executed.gas_used);
+        self.prev_ix = Some(ix);
+        self.pending_call = executed.called_contract;
+    }
+
+    /// Finish profiling, returning the recorded profile.
+    pub fn finish(self) -> TestGasProfile {
+        self.profile
+    }
+
+    /// The contract within which execution currently takes place, zeroed outside of any call.
+    fn contract_id(&self) -> tx::ContractId {
+        self.calls
+            .last()
+            .map_or_else(tx::ContractId::zeroed, |call| call.contract_id)
+    }
+
+    /// The frame of the function beginning at the given breakpoint, if any.
+    fn fn_frame(&self, breakpoint: &Breakpoint) -> Option<Frame> {
+        let offset = breakpoint.pc() / Instruction::SIZE as u64;
+        let contract_id = *breakpoint.contract();
+        if contract_id == tx::ContractId::zeroed() {
+            self.fn_offsets
+                .contains_key(&offset)
+                .then_some(Frame::Fn(offset))
+        } else if Some(contract_id) == self.profile.root_contract_id {
+            self.contract_fn_offsets?
+                .contains_key(&offset)
+                .then_some(Frame::ContractFn(offset))
+        } else {
+            None
+        }
+    }
+
+    fn push(&mut self, frame: Frame, return_ix: Option<usize>) {
+        self.stack.push((frame, return_ix));
+        self.stack_id = None;
+    }
+
+    fn pop(&mut self) {
+        self.stack.pop();
+        self.stack_id = None;
+    }
+
+    fn sample(&mut self, ix: usize, gas_used: u64) {
+        let stack_id = self.stack_id();
+        *self.profile.samples.entry((stack_id, ix)).or_default() += gas_used;
+    }
+
+    /// The index of the current call stack within the profile, interning it if necessary.
+    fn stack_id(&mut self) -> usize {
+        if let Some(stack_id) = self.stack_id {
+            return stack_id;
+        }
+        let frames: Vec<Frame> = self.stack.iter().map(|(frame, _)| *frame).collect();
+        let stacks = &mut self.profile.stacks;
+        let stack_id = *self.stack_ids.entry(frames.clone()).or_insert_with(|| {
+            stacks.push(frames);
+            stacks.len() - 1
+        });
+        self.stack_id = Some(stack_id);
+        stack_id
+    }
+}
+
+impl PackageGasProfile {
+    /// Collect the gas profile of the given tested package from the profiles recorded for each of
+    /// its tests.
+    pub fn from_tested_pkg(tested: &TestedPackage) -> anyhow::Result<Self> {
+        let built = &tested.built;
+        let fn_offsets = &built.bytecode.fn_offsets;
+        let no_fn_offsets = BTreeMap::new();
+        let contract_fn_offsets = built
+            .bytecode_without_tests
+            .as_ref()
+            .map_or(&no_fn_offsets, |bytecode| &bytecode.fn_offsets);
+        let mut source_files = SourceFiles::default();
+        let mut stacks = BTreeMap::new();
+        for test in &tested.tests {
+            let Some(profile) = &test.gas_profile else {
+                continue;
+            };
+            for (&(stack_ix, ix), &gas) in &profile.samples {
+                let frames = &profile.stacks[stack_ix];
+                let mut names: Vec<String> = frames
+                    .iter()
+                    .filter_map(|frame| match frame {
+                        Frame::Fn(offset) => fn_offsets.get(offset).cloned(),
+                        Frame::Contract(id) => Some(format!("<contract 0x{id}>")),
+                        Frame::ContractFn(offset) => contract_fn_offsets.get(offset).cloned(),
+                    })
+                    .collect();
+                if names.is_empty() {
+                    names.push(test.name.clone());
+                }
+                // The source map of the bytecode executing at the top of the stack, which is
+                // unknown within contracts other than the contract under test.
+                let source_map = match frames.iter().rev().find_map(|frame| match frame {
+                    Frame::Contract(id) => Some(*id),
+                    _ => None,
+                }) {
+                    None => Some(&built.source_map),
+                    Some(id) if Some(id) == profile.root_contract_id => {
+                        built.source_map_without_tests.as_ref()
+                    }
+                    Some(_) => None,
+                };
+                // Attribute instructions inlined from other functions to their source function.
+                if let Some(source_map) = source_map {
+                    if let Some(name) = enclosing_fn(&mut source_files, source_map, ix)? {
+                        let unqualified = name.rsplit("::").next().unwrap_or(&name);
+                        if names.last().map(String::as_str) != Some(unqualified) {
+                            names.push(name);
+                        }
+                    }
+                }
+                *stacks.entry(names).or_default() += gas;
+            }
+        }
+        Ok(Self { stacks })
+    }
+
+    /// The total gas consumed across all call stacks.
+    pub fn total_gas(&self) -> u64 {
+        self.stacks.values().sum()
+    }
+
+    /// The gas consumed by each function, sorted from the greatest to least self gas.
+    pub fn functions(&self) -> Vec<FunctionGas> {
+        let mut functions: HashMap<&str, (u64, u64)> = HashMap::new();
+        for (stack, gas) in &self.stacks {
+            // Count each function once per stack so that recursion isn't double counted.
+            let unique: HashSet<&str> = stack.iter().map(String::as_str).collect();
+            for name in unique {
+                functions.entry(name).or_default().1 += gas;
+            }
+            if let Some(leaf) = stack.last() {
+                functions.entry(leaf).or_default().0 += gas;
+            }
+        }
+        let mut functions: Vec<_> = functions
+            .into_iter()
+            .map(|(name, (self_gas, total_gas))| FunctionGas {
+                name: name.to_string(),
+                self_gas,
+                total_gas,
+            })
+            .collect();
+        functions.sort_by(|a, b| {
+            b.self_gas
+                .cmp(&a.self_gas)
+                .then(b.total_gas.cmp(&a.total_gas))
+                .then(a.name.cmp(&b.name))
+        });
+        functions
+    }
+
+    /// Write the profile in the folded stacks format accepted by flamegraph tools.
+    pub fn write_folded<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for (stack, gas) in &self.stacks {
+            writeln!(writer, "{} {gas}", stack.join(";"))?;
+        }
+        Ok(())
+    }
+}
+
+/// The name of the Sway function enclosing the source of the instruction at the given index.
+fn enclosing_fn(
+    source_files: &mut SourceFiles,
+    source_map: &SourceMap,
+    ix: usize,
+) -> anyhow::Result<Option<String>> {
+    let Some(span) = source_map.map.get(&ix) else {
+        return Ok(None);
+    };
+    let source_file = source_files.get(&source_map.paths[span.path.0])?;
+    let name = source_file
+        .enclosing_fn(span.range.start, span.range.end)
+        .map(|fn_ix| source_file.fns[fn_ix].name.clone());
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_vm::state::Breakpoint;
+
+    fn step(ix: u64, gas_used: u64) -> ExecutedInstruction {
+        ExecutedInstruction {
+            breakpoint: Breakpoint::script(ix),
+            gas_used,
+            called_contract: None,
+        }
+    }
+
+    fn contract_step(contract_id: tx::ContractId, ix: u64, gas_used: u64) -> ExecutedInstruction {
+        ExecutedInstruction {
+            breakpoint: Breakpoint::new(contract_id, ix),
+            ..step(ix, gas_used)
+        }
+    }
+
+    #[test]
+    fn profiler_call_stacks() {
+        let fn_offsets = BTreeMap::from([(4, "test_foo".to_string()), (10, "foo".to_string())]);
+        let contract_fn_offsets = BTreeMap::from([(3, "bar".to_string())]);
+        let root_contract_id = tx::ContractId::from([1; 32]);
+        let other_contract_id = tx::ContractId::from([2; 32]);
+        let mut profiler =
+            GasProfiler::new(&fn_offsets, Some((root_contract_id, &contract_fn_offsets)));
+        let steps = [
+            // Jump from the preamble to the test.
+            step(0, 1),
+            step(4, 1),
+            // Call `foo`, which returns to instruction 6.
+            step(5, 2),
+            step(10, 3),
+            step(11, 2),
+            // Call the contract under test, which jumps to `bar`.
+            ExecutedInstruction {
+                called_contract: Some(root_contract_id),
+                ..step(6, 50)
+            },
+            contract_step(root_contract_id, 0, 1),
+            contract_step(root_contract_id, 3, 2),
+            // Call another contract from `bar`.
+            ExecutedInstruction {
+                called_contract: Some(other_contract_id),
+                ..contract_step(root_contract_id, 4, 50)
+            },
+            contract_step(other_contract_id, 0, 7),
+            contract_step(root_contract_id, 5, 1),
+            // Return from the contract under test.
+            step(7, 1),
+        ];
+        for executed in &steps {
+            profiler.record(executed);
+        }
+        let profile = profiler.finish();
+
+        let test_foo = Frame::Fn(4);
+        let foo = Frame::Fn(10);
+        let root_contract = Frame::Contract(root_contract_id);
+        let bar = Frame::ContractFn(3);
+        let other_contract = Frame::Contract(other_contract_id);
+        assert_eq!(
+            profile.stacks,
+            [
+                vec![],
+                vec![test_foo],
+                vec![test_foo, foo],
+                vec![test_foo, root_contract],
+                vec![test_foo, root_contract, bar],
+                vec![test_foo, root_contract, bar, other_contract],
+            ]
+        );
+        assert_eq!(
+            profile.samples,
+            HashMap::from([
+                ((0, 0), 1),
+                ((1, 4), 1),
+                ((1, 5), 2),
+                ((2, 10), 3),
+                ((2, 11), 2),
+                ((1, 6), 50),
+                ((3, 0), 1),
+                ((4, 3), 2),
+                ((4, 4), 50),
+                ((5, 0), 7),
+                ((4, 5), 1),
+                ((1, 7), 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn function_gas_and_folded_output() {
+        let stack = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        let profile = PackageGasProfile {
+            stacks: BTreeMap::from([
+                (stack(&["test_foo"]), 5),
+                (stack(&["test_foo", "foo"]), 10),
+                (stack(&["test_foo", "foo", "foo"]), 4),
+                (stack(&["test_foo", "bar"]), 2),
+            ]),
+        };
+        assert_eq!(profile.total_gas(), 21);
+        assert_eq!(
+            profile.functions(),
+            [
+                FunctionGas {
+                    name: "foo".to_string(),
+                    self_gas: 14,
+                    total_gas: 14,
+                },
+                FunctionGas {
+                    name: "test_foo".to_string(),
+                    self_gas: 5,
+                    total_gas: 21,
+                },
+                FunctionGas {
+                    name: "bar".to_string(),
+                    self_gas: 2,
+                    total_gas: 2,
+                },
+            ]
+        );
+
+        let mut folded = vec![];
+        profile.write_folded(&mut folded).unwrap();
+        let expected = "test_foo 5
+test_foo;bar 2
+test_foo;foo 10
+test_foo;foo;foo 4
+";
+        assert_eq!(String::from_utf8(folded).unwrap(), expected);
+    }
+}
@@ -1,7 +1,16 @@
+pub mod coverage;
+pub mod decode;
 pub mod execute;
+pub mod fuzz;
+pub mod gas_profile;
 pub mod setup;
+mod source;
 
+use crate::coverage::TestCoverage;
+use crate::decode::LogDecoder;
 use crate::execute::TestExecutor;
+use crate::fuzz::FuzzReport;
+use crate::gas_profile::{GasProfiler, TestGasProfile};
 use crate::setup::{
     ContractDeploymentSetup, ContractTestSetup, DeploymentSetup, ScriptTestSetup, TestSetup,
 };
@@ -16,7 +25,7 @@ use pkg::{Built, BuiltPackage};
 use rand::{Rng, SeedableRng};
 use rayon::prelude::*;
 use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
-use sway_core::BuildTarget;
+use sway_core::{asm_generation::ProgramABI, BuildTarget};
 use sway_types::Span;
 use vm::prelude::SecretKey;
 
@@ -72,6 +81,12 @@ pub struct TestResult {
     pub logs: Vec<fuel_tx::Receipt>,
     /// Gas used while executing this test.
     pub gas_used: u64,
+    /// The instructions executed by this test, if coverage was requested.
+    pub coverage: Option<TestCoverage>,
+    /// The gas consumed by each instruction executed by this test, if profiling was requested.
+    pub gas_profile: Option<TestGasProfile>,
+    /// The runs with generated arguments, if the test has parameters.
+    pub fuzz: Option<FuzzReport>,
 }
 
 const TEST_METADATA_SEED: u64 = 0x7E57u64;
@@ -150,17 +165,43 @@ pub struct TestOpts {
     pub experimental: ExperimentalFlags,
 }
 
+/// The set of options provided for controlling how tests are executed.
+#[derive(Default, Clone, Debug)]
+pub struct TestRunOpts {
+    /// Record the instructions executed by each test for code coverage reporting.
+    pub coverage: bool,
+    /// Record the gas consumed by each instruction executed by each test for gas profiling.
+    pub gas_profile: bool,
+    /// The number of runs for tests with parameters that don't specify their own number of runs.
+    ///
+    /// Defaults to [fuzz::DEFAULT_RUNS].
+    pub fuzz_runs: Option<u64>,
+    /// The seed from which arguments are generated for tests with parameters.
+    ///
+    /// Defaults to [fuzz::DEFAULT_SEED].
+    pub fuzz_seed: Option<u64>,
+}
+
 /// The set of options provided for controlling logs printed for each test.
 #[derive(Default, Clone)]
 pub struct TestPrintOpts {
     pub pretty_print: bool,
     pub print_logs: bool,
+    pub raw_logs: bool,
 }
 
 impl TestedPackage {
     pub fn tests_passed(&self) -> bool {
         self.tests.iter().all(|test| test.passed())
     }
+
+    /// A decoder for the values logged by this package's tests.
+    pub fn log_decoder(&self) -> LogDecoder {
+        match &self.built.program_abi {
+            ProgramABI::Fuel(program_abi) => LogDecoder::new(program_abi),
+            _ => LogDecoder::default(),
+        }
+    }
 }
 
 impl PackageWithDeploymentToTest {
@@ -298,7 +339,7 @@ impl<'a> PackageTests {
     ///
     /// If this `PackageTests` is `PackageTests::Contract`, built package with tests included is
     /// returned.
-    pub(crate) fn built_pkg_with_tests(&'a self) -> &'a BuiltPackage {
+    pub fn built_pkg_with_tests(&'a self) -> &'a BuiltPackage {
         match self {
             PackageTests::Contract(contract) => contract.pkg(),
             PackageTests::Script(script) => script.pkg(),
@@ -307,6 +348,33 @@ impl<'a> PackageTests {
         }
     }
 
+    /// Returns an iterator over the contract dependencies deployed before the tests are run.
+    pub fn contract_dependencies(&self) -> impl Iterator<Item = &Arc<BuiltPackage>> + '_ {
+        let pkg_with_deployment = match self {
+            PackageTests::Contract(pkg) | PackageTests::Script(pkg) => Some(pkg),
+            PackageTests::Predicate(_) | PackageTests::Library(_) => None,
+        };
+        pkg_with_deployment
+            .into_iter()
+            .flat_map(|pkg| pkg.contract_dependencies())
+    }
+
+    /// Retain only the tests for which `f` returns `true`, so that the others aren't run.
+    pub fn retain_tests(&mut self, mut f: impl FnMut(&pkg::PkgEntry) -> bool) {
+        let built_pkg = match self {
+            PackageTests::Contract(pkg) | PackageTests::Script(pkg) => match pkg {
+                PackageWithDeploymentToTest::Script(script) => &mut script.pkg,
+                PackageWithDeploymentToTest::Contract(contract) => &mut contract.pkg,
+            },
+            PackageTests::Predicate(predicate) => predicate,
+            PackageTests::Library(library) => library,
+        };
+        Arc::make_mut(built_pkg)
+            .bytecode
+            .entries
+            .retain(|entry| entry.kind.test().is_none() || f(entry));
+    }
+
     /// Construct a `PackageTests` from `BuiltPackage`.
     fn from_built_pkg(
         built_pkg: Arc<BuiltPackage>,
@@ -348,6 +416,7 @@ impl<'a> PackageTests {
         &self,
         test_runners: &rayon::ThreadPool,
         test_filter: Option<&TestFilter>,
+        run_opts: &TestRunOpts,
     ) -> anyhow::Result<TestedPackage> {
         let pkg_with_tests = self.built_pkg_with_tests();
         let tests = test_runners.install(|| {
@@ -375,14 +444,57 @@ impl<'a> PackageTests {
                         .expect("test instruction offset out of range");
                     let name = entry.finalized.fn_name.clone();
                     let test_setup = self.setup()?;
-                    TestExecutor::new(
-                        &pkg_with_tests.bytecode.bytes,
-                        offset,
-                        test_setup,
-                        test_entry,
-                        name,
-                    )
-                    .execute()
+                    let run_case = |script_data: Vec<u8>| {
+                        let mut executor = TestExecutor::new(
+                            &pkg_with_tests.bytecode.bytes,
+                            offset,
+                            script_data,
+                            test_setup.clone(),
+                            test_entry,
+                            name.clone(),
+                        );
+                        if !run_opts.coverage && !run_opts.gas_profile {
+                            return executor.execute();
+                        }
+
+                        // Single-step the test, recording each executed instruction.
+                        let root_contract_id = executor.root_contract_id;
+                        let mut coverage = run_opts.coverage.then(TestCoverage::default);
+                        let mut profiler = run_opts.gas_profile.then(|| {
+                            let contract_fn_offsets = pkg_with_tests
+                                .bytecode_without_tests
+                                .as_ref()
+                                .map(|bytecode| &bytecode.fn_offsets);
+                            GasProfiler::new(
+                                &pkg_with_tests.bytecode.fn_offsets,
+                                root_contract_id.zip(contract_fn_offsets),
+                            )
+                        });
+                        let mut result = executor.execute_single_stepping(|executed| {
+                            if let Some(coverage) = &mut coverage {
+                                coverage.record(&executed.breakpoint, root_contract_id);
+                            }
+                            if let Some(profiler) = &mut profiler {
+                                profiler.record(executed);
+                            }
+                        })?;
+                        result.coverage = coverage;
+                        result.gas_profile = profiler.map(GasProfiler::finish);
+                        Ok(result)
+                    };
+                    if test_entry.params.is_empty() {
+                        return run_case(vec![]);
+                    }
+
+                    // Run tests with parameters repeatedly with generated arguments.
+                    let runs = test_entry
+                        .runs
+                        .or(run_opts.fuzz_runs)
+                        .unwrap_or(fuzz::DEFAULT_RUNS);
+                    let seed = run_opts.fuzz_seed.unwrap_or(fuzz::DEFAULT_SEED);
+                    fuzz::run(&test_entry.params, runs, seed, |args| {
+                        run_case(fuzz::encode_args(args))
+                    })
                 })
                 .collect::<anyhow::Result<_>>()
         })?;
@@ -427,6 +539,7 @@ impl From<TestOpts> for pkg::BuildOpts {
             release: val.release,
             error_on_warnings: val.error_on_warnings,
             time_phases: val.time_phases,
+            ir_passes: None,
             metrics_outfile: val.metrics_outfile,
             tests: true,
             member_filter: Default::default(),
@@ -449,6 +562,7 @@ impl TestOpts {
             release: self.release,
             error_on_warnings: self.error_on_warnings,
             time_phases: self.time_phases,
+            ir_passes: None,
             metrics_outfile: self.metrics_outfile,
             tests: true,
             member_filter: Default::default(),
@@ -504,6 +618,7 @@ impl TestResult {
 
 /// Used to control test runner count for forc-test. Number of runners to use can be specified using
 /// `Manual` or can be left forc-test to decide by using `Auto`.
+#[derive(Clone, Copy, Debug)]
 pub enum TestRunnerCount {
     Manual(usize),
     Auto,
@@ -563,6 +678,7 @@ impl BuiltTests {
         self,
         test_runner_count: TestRunnerCount,
         test_filter: Option<TestFilter>,
+        run_opts: TestRunOpts,
     ) -> anyhow::Result<Tested> {
         let test_runners = match test_runner_count {
             TestRunnerCount::Manual(runner_count) => rayon::ThreadPoolBuilder::new()
@@ -570,7 +686,7 @@ impl BuiltTests {
                 .build(),
             TestRunnerCount::Auto => rayon::ThreadPoolBuilder::new().build(),
         }?;
-        run_tests(self, &test_runners, test_filter)
+        run_tests(self, &test_runners, test_filter, &run_opts)
     }
 }
 
@@ -578,10 +694,17 @@ impl BuiltTests {
 pub fn build(opts: TestOpts) -> anyhow::Result<BuiltTests> {
     let build_opts = opts.into();
     let build_plan = pkg::BuildPlan::from_build_opts(&build_opts)?;
-    let built = pkg::build_with_options(build_opts)?;
+    let built = pkg::build_with_plan(&build_opts, &build_plan)?;
     BuiltTests::from_built(built, &build_plan)
 }
 
+/// Builds the package or workspace with the given build plan, ready for execution.
+pub fn build_with_plan(opts: TestOpts, build_plan: &pkg::BuildPlan) -> anyhow::Result<BuiltTests> {
+    let build_opts = opts.into();
+    let built = pkg::build_with_plan(&build_opts, build_plan)?;
+    BuiltTests::from_built(built, build_plan)
+}
+
 /// Deploys the provided contract and returns an interpreter instance ready to be used in test
 /// executions with deployed contract.
 fn deployment_transaction(
@@ -627,16 +750,17 @@ fn run_tests(
     built: BuiltTests,
     test_runners: &rayon::ThreadPool,
     test_filter: Option<TestFilter>,
+    run_opts: &TestRunOpts,
 ) -> anyhow::Result<Tested> {
     match built {
         BuiltTests::Package(pkg) => {
-            let tested_pkg = pkg.run_tests(test_runners, test_filter.as_ref())?;
+            let tested_pkg = pkg.run_tests(test_runners, test_filter.as_ref(), run_opts)?;
             Ok(Tested::Package(Box::new(tested_pkg)))
         }
         BuiltTests::Workspace(workspace) => {
             let tested_pkgs = workspace
                 .into_iter()
-                .map(|pkg| pkg.run_tests(test_runners, test_filter.as_ref()))
+                .map(|pkg| pkg.run_tests(test_runners, test_filter.as_ref(), run_opts))
                 .collect::<anyhow::Result<Vec<TestedPackage>>>()?;
             Ok(Tested::Workspace(tested_pkgs))
         }
@@ -647,7 +771,10 @@ fn run_tests(
 mod tests {
     use std::path::PathBuf;
 
-    use crate::{build, BuiltTests, TestFilter, TestOpts, TestResult};
+    use crate::{
+        build, coverage::PackageCoverage, gas_profile::PackageGasProfile, BuiltTests, TestFilter,
+        TestOpts, TestResult, TestRunOpts, TestedPackage,
+    };
 
     /// Name of the folder containing required data for tests to run, such as an example forc
     /// project.
@@ -660,6 +787,9 @@ mod tests {
     const TEST_PREDICATE_PACKAGE_NAME: &str = "test_predicate";
     /// Name of the script package in the "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME".
     const TEST_SCRIPT_PACKAGE_NAME: &str = "test_script";
+    /// Name of the contract package whose tests call the contract in the
+    /// "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME".
+    const TEST_CONTRACT_CALLS_PACKAGE_NAME: &str = "test_contract_calls";
 
     /// Build the tests in the test package with the given name located at
     /// "CARGO_MANIFEST_DIR/TEST_DATA_FOLDER_NAME/TEST_LIBRARY_PACKAGE_NAME".
@@ -683,11 +813,20 @@ mod tests {
         package_name: &str,
         test_filter: Option<TestFilter>,
     ) -> anyhow::Result<Vec<TestResult>> {
+        let tested_pkg = test_package_tested(package_name, test_filter, TestRunOpts::default())?;
+        Ok(tested_pkg.tests)
+    }
+
+    fn test_package_tested(
+        package_name: &str,
+        test_filter: Option<TestFilter>,
+        run_opts: TestRunOpts,
+    ) -> anyhow::Result<TestedPackage> {
         let built_tests = test_package_built_tests(package_name)?;
         let test_runner_count = crate::TestRunnerCount::Auto;
-        let tested = built_tests.run(test_runner_count, test_filter)?;
+        let tested = built_tests.run(test_runner_count, test_filter, run_opts)?;
         match tested {
-            crate::Tested::Package(tested_pkg) => Ok(tested_pkg.tests),
+            crate::Tested::Package(tested_pkg) => Ok(*tested_pkg),
             crate::Tested::Workspace(_) => {
                 unreachable!("test_library is a package, not a workspace.")
             }
@@ -884,4 +1023,83 @@ mod tests {
         assert_eq!(tested_predicate_test_count, 2);
         assert_eq!(tested_script_test_count, 2);
     }
+
+    #[test]
+    fn test_single_stepping_matches_execution() {
+        let executed = test_package_test_results(TEST_CONTRACT_CALLS_PACKAGE_NAME, None).unwrap();
+        let run_opts = TestRunOpts {
+            coverage: true,
+            ..Default::default()
+        };
+        let stepped = test_package_tested(TEST_CONTRACT_CALLS_PACKAGE_NAME, None, run_opts)
+            .unwrap()
+            .tests;
+        assert_eq!(executed.len(), 2);
+        for (executed, stepped) in executed.iter().zip(&stepped) {
+            assert_eq!(executed.name, stepped.name);
+            assert!(
+                stepped.passed(),
+                "{} failed when single-stepped",
+                stepped.name
+            );
+            assert_eq!(executed.state, stepped.state);
+            assert_eq!(executed.gas_used, stepped.gas_used);
+            assert_eq!(executed.logs, stepped.logs);
+        }
+        // `test_increment_overflow` panics within the called contract after logging.
+        let overflow = &stepped[1];
+        assert!(matches!(
+            overflow.state,
+            fuel_vm::state::ProgramState::Revert(_)
+        ));
+        assert_eq!(overflow.logs.len(), 1);
+    }
+
+    #[test]
+    fn test_coverage_of_called_contract() {
+        let run_opts = TestRunOpts {
+            coverage: true,
+            ..Default::default()
+        };
+        let tested = test_package_tested(TEST_CONTRACT_CALLS_PACKAGE_NAME, None, run_opts).unwrap();
+        let coverage = PackageCoverage::from_tested_pkg(&tested).unwrap();
+        assert_eq!(coverage.files.len(), 1);
+        let file = &coverage.files[0];
+        assert!(file.path.ends_with("src/main.sw"));
+        // `increment` is only reached through calls to the deployed contract.
+        assert_eq!(file.lines[&10], 2);
+        assert_eq!(file.lines[&14], 0);
+        let hits = |name: &str| {
+            file.functions
+                .iter()
+                .find(|function| function.name == name)
+                .map(|function| function.hits)
+        };
+        assert_eq!(hits("Contract::increment"), Some(2));
+        assert_eq!(hits("Contract::decrement"), Some(0));
+    }
+
+    #[test]
+    fn test_gas_profile_of_called_contract() {
+        let run_opts = TestRunOpts {
+            gas_profile: true,
+            ..Default::default()
+        };
+        let tested = test_package_tested(TEST_CONTRACT_CALLS_PACKAGE_NAME, None, run_opts).unwrap();
+        let profile = PackageGasProfile::from_tested_pkg(&tested).unwrap();
+        let gas_used: u64 = tested.tests.iter().map(|test| test.gas_used).sum();
+        assert_eq!(profile.total_gas(), gas_used);
+        // Each test calls `increment` within the deployed contract.
+        for test in &tested.tests {
+            let within_increment = profile.stacks.keys().any(|stack| {
+                matches!(
+                    &stack[..],
+                    [test_name, contract, increment] if *test_name == test.name
+                        && contract.starts_with("<contract 0x")
+                        && increment == "increment"
+                )
+            });
+            assert!(within_increment, "{} did not call `increment`", test.name);
+        }
+    }
 }
@@ -0,0 +1,164 @@
+//! Utilities for mapping source map spans back to the lines and functions of their source files.
+
+use std::{
+    collections::{hash_map, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use sway_ast::{ItemImplItem, ItemKind};
+use sway_error::handler::Handler;
+use sway_types::Spanned;
+
+/// A function declaration and the byte range it spans within its source file.
+pub(crate) struct FnSpan {
+    pub(crate) name: String,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+/// A parsed source file, used to map byte offsets to lines and functions.
+pub(crate) struct SourceFile {
+    line_starts: Vec<usize>,
+    pub(crate) fns: Vec<FnSpan>,
+}
+
+/// A lazily populated cache of parsed source files.
+#[derive(Default)]
+pub(crate) struct SourceFiles {
+    files: HashMap<PathBuf, SourceFile>,
+}
+
+impl SourceFile {
+    pub(crate) fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let src = fs::read_to_string(path)?;
+        Ok(Self::from_src(&src))
+    }
+
+    pub(crate) fn from_src(src: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(src.match_indices('\n').map(|(ix, _)| ix + 1))
+            .collect();
+        let fns = fn_spans(src);
+        Self { line_starts, fns }
+    }
+
+    /// The 1-based line number containing the given byte offset.
+    pub(crate) fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= offset)
+    }
+
+    /// The index of the innermost function containing the given byte range.
+    pub(crate) fn enclosing_fn(&self, start: usize, end: usize) -> Option<usize> {
+        self.fns
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.start <= start && end <= f.end)
+            .min_by_key(|(_, f)| f.end - f.start)
+            .map(|(ix, _)| ix)
+    }
+}
+
+impl SourceFiles {
+    /// Returns the parsed source file at the given path, reading and parsing it on first use.
+    pub(crate) fn get(&mut self, path: &Path) -> anyhow::Result<&SourceFile> {
+        let source_file = match self.files.entry(path.to_path_buf()) {
+            hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            hash_map::Entry::Vacant(entry) => entry.insert(SourceFile::from_path(path)?),
+        };
+        Ok(source_file)
+    }
+}
+
+/// Collect the function declarations within the given source, including methods declared within
+/// `impl`, `trait` and `abi` blocks.
+///
+/// Returns an empty list if the source fails to parse.
+fn fn_spans(src: &str) -> Vec<FnSpan> {
+    let handler = Handler::default();
+    let Ok(module) = sway_parse::parse_file(&handler, Arc::from(src), None) else {
+        return vec![];
+    };
+    let fn_span = |prefix: Option<&str>, item_fn: &sway_ast::ItemFn| {
+        let name = item_fn.fn_signature.name.as_str();
+        let span = item_fn.span();
+        FnSpan {
+            name: match prefix {
+                Some(prefix) => format!("{prefix}::{name}"),
+                None => name.to_string(),
+            },
+            start: span.start(),
+            end: span.end(),
+        }
+    };
+    let mut fns = vec![];
+    for item in &module.value.items {
+        match &item.value {
+            ItemKind::Fn(item_fn) => fns.push(fn_span(None, item_fn)),
+            ItemKind::Impl(item_impl) => {
+                let ty = item_impl.ty.span();
+                for item in item_impl.contents.get() {
+                    if let ItemImplItem::Fn(item_fn) = &item.value {
+                        fns.push(fn_span(Some(ty.as_str()), item_fn));
+                    }
+                }
+            }
+            ItemKind::Trait(item_trait) => {
+                let name = item_trait.name.as_str();
+                for item in item_trait.trait_defs_opt.iter().flat_map(|defs| defs.get()) {
+                    fns.push(fn_span(Some(name), &item.value));
+                }
+            }
+            ItemKind::Abi(item_abi) => {
+                let name = item_abi.name.as_str();
+                for item in item_abi.abi_defs_opt.iter().flat_map(|defs| defs.get()) {
+                    fns.push(fn_span(Some(name), &item.value));
+                }
+            }
+            _ => (),
+        }
+    }
+    fns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &str = r#"library;
+
+struct Foo {}
+
+impl Foo {
+    fn bar() -> u64 {
+        1
+    }
+}
+
+fn baz() -> u64 {
+    let x = 1;
+    x + 1
+}
+"#;
+
+    #[test]
+    fn source_file_lines() {
+        let source_file = SourceFile::from_src(SRC);
+        assert_eq!(source_file.line(0), 1);
+        assert_eq!(source_file.line(SRC.find("struct").unwrap()), 3);
+        assert_eq!(source_file.line(SRC.find("x + 1").unwrap()), 13);
+    }
+
+    #[test]
+    fn source_file_fns() {
+        let source_file = SourceFile::from_src(SRC);
+        let names: Vec<_> = source_file.fns.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["Foo::bar", "baz"]);
+
+        let start = SRC.find("x + 1").unwrap();
+        let fn_ix = source_file.enclosing_fn(start, start + 5).unwrap();
+        assert_eq!(source_file.fns[fn_ix].name, "baz");
+        assert_eq!(source_file.line(source_file.fns[fn_ix].start), 11);
+        assert!(source_file.enclosing_fn(0, 8).is_none());
+    }
+}
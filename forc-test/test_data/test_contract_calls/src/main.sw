@@ -0,0 +1,29 @@
+contract;
+
+abi Counter {
+    fn increment(value: u64) -> u64;
+    fn decrement(value: u64) -> u64;
+}
+
+impl Counter for Contract {
+    fn increment(value: u64) -> u64 {
+        value + 1
+    }
+
+    fn decrement(value: u64) -> u64 {
+        value - 1
+    }
+}
+
+#[test]
+fn test_increment() {
+    let counter = abi(Counter, CONTRACT_ID);
+    assert(counter.increment(1) == 2);
+}
+
+#[test(should_revert)]
+fn test_increment_overflow() {
+    log(u64::max());
+    let counter = abi(Counter, CONTRACT_ID);
+    let _ = counter.increment(u64::max());
+}
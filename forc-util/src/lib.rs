@@ -7,6 +7,7 @@ use ansi_term::Colour;
 use anyhow::{bail, Context, Result};
 use forc_tracing::{println_red_err, println_yellow_err};
 use std::{
+    cell::RefCell,
     collections::{hash_map, HashSet},
     fmt::Display,
     fs::File,
@@ -167,6 +168,16 @@ pub mod tx_utils {
         receipts: &[fuel_tx::Receipt],
         pretty_print: bool,
     ) -> Result<String> {
+        let receipt_to_json_array = log_receipts_to_json(receipts)?;
+        if pretty_print {
+            Ok(serde_json::to_string_pretty(&receipt_to_json_array)?)
+        } else {
+            Ok(serde_json::to_string(&receipt_to_json_array)?)
+        }
+    }
+
+    /// Convert `Log` and `LogData` receipts to a JSON array, with any data encoded as hex.
+    pub fn log_receipts_to_json(receipts: &[fuel_tx::Receipt]) -> Result<serde_json::Value> {
         let mut receipt_to_json_array = serde_json::to_value(receipts)?;
         for (rec_index, receipt) in receipts.iter().enumerate() {
             let rec_value = receipt_to_json_array.get_mut(rec_index).ok_or_else(|| {
@@ -193,11 +204,7 @@ pub mod tx_utils {
                 _ => {}
             }
         }
-        if pretty_print {
-            Ok(serde_json::to_string_pretty(&receipt_to_json_array)?)
-        } else {
-            Ok(serde_json::to_string(&receipt_to_json_array)?)
-        }
+        Ok(receipt_to_json_array)
     }
 }
 
@@ -268,11 +275,30 @@ pub fn default_output_directory(manifest_dir: &Path) -> PathBuf {
     manifest_dir.join(DEFAULT_OUTPUT_DIRECTORY)
 }
 
+thread_local! {
+    /// The `.forc` directory set by [with_user_forc_directory] for the current thread.
+    static USER_FORC_DIRECTORY: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
 /// Returns the user's `.forc` directory, `$HOME/.forc` by default.
 pub fn user_forc_directory() -> PathBuf {
-    dirs::home_dir()
-        .expect("unable to find the user home directory")
-        .join(constants::USER_FORC_DIRECTORY)
+    USER_FORC_DIRECTORY
+        .with(|dir| dir.borrow().clone())
+        .unwrap_or_else(|| {
+            dirs::home_dir()
+                .expect("unable to find the user home directory")
+                .join(constants::USER_FORC_DIRECTORY)
+        })
+}
+
+/// Run `f` with `dir` as the user's `.forc` directory on the current thread.
+///
+/// This lets tests fetch and cache packages without touching the real `.forc` directory.
+pub fn with_user_forc_directory<T>(dir: &Path, f: impl FnOnce() -> T) -> T {
+    let prev = USER_FORC_DIRECTORY.with(|cell| cell.replace(Some(dir.to_path_buf())));
+    let result = f();
+    USER_FORC_DIRECTORY.with(|cell| *cell.borrow_mut() = prev);
+    result
 }
 
 /// The location at which `forc` will checkout git repositories.
@@ -280,6 +306,11 @@ pub fn git_checkouts_directory() -> PathBuf {
     user_forc_directory().join("git").join("checkouts")
 }
 
+/// The location at which `forc` caches the compiler state after compiling dependencies.
+pub fn dependency_cache_directory() -> PathBuf {
+    user_forc_directory().join("cache").join("deps")
+}
+
 /// Given a path to a directory we wish to lock, produce a path for an associated lock file.
 ///
 /// Note that the lock file itself is simply a placeholder for co-ordinating access. As a result,
@@ -7,6 +7,7 @@ forc_util::cli_examples! {
         [ Compile the current projectx => "forc build" ]
         [ Compile the current project from a different path => "forc build --path <PATH>" ]
         [ Compile the current project without updating dependencies => "forc build --path <PATH> --locked" ]
+        [ Compile the current project whenever its sources change => "forc build --watch" ]
     }
 }
 
@@ -32,6 +33,18 @@ pub struct Command {
     /// Also build all tests within the project.
     #[clap(long)]
     pub tests: bool,
+    /// Rebuild the project whenever a Sway source file or manifest of the project's packages
+    /// changes.
+    #[clap(long)]
+    pub watch: bool,
+    /// Run this IR pass pipeline instead of the one of the build profile's optimization level.
+    ///
+    /// The pipeline is a comma separated list of IR passes, where a `repeat(...)` group is run
+    /// until none of its passes change the IR, e.g.
+    /// `inline_module,repeat(constcombine,sccp,simplifycfg),dce`.  This is intended for
+    /// experimenting with the optimizer.
+    #[clap(long)]
+    pub ir_passes: Option<String>,
 
     #[clap(long)]
     /// Experimental flag for the "new encoding" feature
@@ -39,6 +52,10 @@ pub struct Command {
 }
 
 pub(crate) fn exec(command: Command) -> ForcResult<()> {
-    forc_build::build(command)?;
+    if command.watch {
+        forc_build::watch(command)?;
+    } else {
+        forc_build::build(command)?;
+    }
     Ok(())
 }
@@ -1,6 +1,7 @@
-use crate::ops::forc_check;
+use crate::{ops::forc_check, utils::watch};
 use clap::Parser;
-use forc_pkg::source::IPFSNode;
+use forc_pkg::{source::IPFSNode, BuildPlan};
+use forc_tracing::println_green;
 use forc_util::{forc_result_bail, ForcResult};
 use sway_core::{BuildTarget, Engines};
 
@@ -9,6 +10,8 @@ forc_util::cli_examples! {
         [ Check the current project => "forc check" ]
         [ Check the current project with a different path => "forc check --path <PATH>" ]
         [ Check the current project without updating dependencies => "forc check --locked" ]
+        [ Check the current project whenever its sources change => "forc check --watch" ]
+        [ Check the current project after applying the fixes suggested by the compiler => "forc check --fix" ]
     }
 }
 
@@ -39,6 +42,14 @@ pub struct Command {
     /// Disable checking unit tests.
     #[clap(long = "disable-tests")]
     pub disable_tests: bool,
+    /// Check the project again whenever a Sway source file or manifest of the project's packages
+    /// changes.
+    #[clap(long)]
+    pub watch: bool,
+    /// Apply the fixes suggested by the compiler which are certain to be correct, like removing
+    /// an unneeded `mut`, to the sources of the project's packages, and then check them again.
+    #[clap(long, conflicts_with = "watch")]
+    pub fix: bool,
     /// The IPFS Node to use for fetching IPFS sources.
     ///
     /// Possible values: PUBLIC, LOCAL, <GATEWAY_URL>
@@ -51,9 +62,33 @@ pub struct Command {
 }
 
 pub(crate) fn exec(command: Command) -> ForcResult<()> {
+    if command.watch {
+        watch::watch(
+            command.path.as_deref(),
+            || forc_check::build_plan(&command),
+            |plan| check_with_plan(&command, plan),
+        )?;
+        return Ok(());
+    }
+    if command.fix {
+        for (path, applied) in forc_check::fix(&command, &Engines::default())? {
+            let fixes = if applied == 1 { "fix" } else { "fixes" };
+            println_green(&format!("  Fixed {} ({applied} {fixes})", path.display()));
+        }
+    }
     let engines = Engines::default();
     let res = forc_check::check(command, &engines)?;
-    if res.0.is_none() {
+    // Denied lints are reported as errors without preventing type checking.
+    if res.0.is_none() || res.1.has_errors() {
+        forc_result_bail!("unable to type check");
+    }
+    Ok(())
+}
+
+fn check_with_plan(command: &Command, plan: &BuildPlan) -> ForcResult<()> {
+    let engines = Engines::default();
+    let res = forc_check::check_with_plan(command, plan, &engines)?;
+    if res.0.is_none() || res.1.has_errors() {
         forc_result_bail!("unable to type check");
     }
     Ok(())
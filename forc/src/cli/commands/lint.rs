@@ -0,0 +1,59 @@
+use crate::ops::forc_lint;
+use clap::Parser;
+use forc_pkg::source::IPFSNode;
+use forc_util::ForcResult;
+use sway_core::{BuildTarget, Engines};
+
+forc_util::cli_examples! {
+    crate::cli::Opt {
+        [ Lint the current project => "forc lint" ]
+        [ Lint the current project with a different path => "forc lint --path <PATH>" ]
+        [ Lint the current project without updating dependencies => "forc lint --locked" ]
+    }
+}
+
+/// Check the current or target project for errors, and report the warnings of all the lints,
+/// including the opt-in ones like the arithmetic safety analysis.
+///
+/// Lint levels are taken from the `[lints]` table of the package manifest, and from the
+/// `#[allow(..)]`, `#[warn(..)]` and `#[deny(..)]` attributes in the code. Only the warnings of
+/// the workspace members are reported, not those of their dependencies.
+#[derive(Debug, Default, Parser)]
+#[clap(bin_name = "forc lint", version, after_help = help())]
+pub struct Command {
+    /// Build target to use for code generation.
+    #[clap(value_enum, default_value_t=BuildTarget::default(), alias="target")]
+    pub build_target: BuildTarget,
+    /// Path to the project, if not specified, current working directory will be used.
+    #[clap(short, long)]
+    pub path: Option<String>,
+    /// Offline mode, prevents Forc from using the network when managing dependencies.
+    /// Meaning it will only try to use previously downloaded dependencies.
+    #[clap(long = "offline")]
+    pub offline_mode: bool,
+    /// Requires that the Forc.lock file is up-to-date. If the lock file is missing, or it
+    /// needs to be updated, Forc will exit with an error
+    #[clap(long)]
+    pub locked: bool,
+    /// Terse mode. Limited warning and error output.
+    #[clap(long = "terse", short = 't')]
+    pub terse_mode: bool,
+    /// Disable linting unit tests.
+    #[clap(long = "disable-tests")]
+    pub disable_tests: bool,
+    /// The IPFS Node to use for fetching IPFS sources.
+    ///
+    /// Possible values: PUBLIC, LOCAL, <GATEWAY_URL>
+    #[clap(long)]
+    pub ipfs_node: Option<IPFSNode>,
+
+    /// Set of experimental flags
+    #[clap(long)]
+    pub experimental_new_encoding: bool,
+}
+
+pub(crate) fn exec(command: Command) -> ForcResult<()> {
+    let engines = Engines::default();
+    forc_lint::lint(command, &engines)?;
+    Ok(())
+}
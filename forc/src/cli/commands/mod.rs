@@ -5,6 +5,7 @@ pub mod clean;
 pub mod completions;
 pub mod contract_id;
 pub mod init;
+pub mod lint;
 pub mod new;
 pub mod parse_bytecode;
 pub mod plugins;
@@ -1,10 +1,25 @@
-use crate::cli;
+use crate::{cli, ops::forc_test_report, utils::watch};
 use ansi_term::Colour;
 use clap::Parser;
 use forc_pkg as pkg;
-use forc_test::{TestFilter, TestRunnerCount, TestedPackage};
-use forc_util::{tx_utils::format_log_receipts, ForcError, ForcResult};
-use pkg::manifest::build_profile::ExperimentalFlags;
+use forc_test::{
+    coverage::{CoverageSummary, PackageCoverage},
+    decode::LogDecoder,
+    gas_profile::PackageGasProfile,
+    BuiltTests, PackageTests, TestFilter, TestResult, TestRunOpts, TestRunnerCount, TestedPackage,
+};
+use forc_util::{default_output_directory, tx_utils::format_log_receipts, ForcError, ForcResult};
+use pkg::manifest::{build_profile::ExperimentalFlags, GenericManifestFile};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use sway_core::fuel_prelude::fuel_tx::StorageSlot;
+use term_table::{
+    row::Row,
+    table_cell::{Alignment, TableCell},
+};
 use tracing::info;
 
 forc_util::cli_examples! {
@@ -13,6 +28,11 @@ forc_util::cli_examples! {
         [ Run test with a filter => "forc test $filter" ]
         [ Run test without any output => "forc test --silent" ]
         [ Run test without creating or update the lock file  => "forc test --locked" ]
+        [ Run test and report code coverage => "forc test --coverage" ]
+        [ Run test and print a JUnit report => "forc test --format junit" ]
+        [ Run test and profile the gas used by each function => "forc test --gas-profile" ]
+        [ Run tests with parameters more times with another seed => "forc test --fuzz-runs 1000 --fuzz-seed 42" ]
+        [ Run test whenever the sources change => "forc test --watch" ]
     }
 }
 
@@ -49,12 +69,53 @@ pub struct Command {
     /// Number of threads to utilize when running the tests. By default, this is the number of
     /// threads available in your system.
     pub test_threads: Option<usize>,
+    #[clap(long)]
+    /// Record the instructions executed by each test and report code coverage.
+    ///
+    /// A summary is printed for each package and an lcov tracefile is written to
+    /// `<output-directory>/coverage/<package-name>.lcov`.
+    pub coverage: bool,
+    #[clap(long)]
+    /// Record the gas consumed by each instruction executed by each test and report the gas used
+    /// by each function.
+    ///
+    /// A table of the functions consuming the most gas is printed for each package and the call
+    /// stacks are written in the folded stacks format used by flamegraph tools to
+    /// `<output-directory>/gas-profile/<package-name>.folded`.
+    pub gas_profile: bool,
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+    /// The number of runs for tests with parameters, unless specified by the test with
+    /// `#[test(runs = N)]`. Defaults to 256.
+    pub fuzz_runs: Option<u64>,
+    #[clap(long)]
+    /// The seed from which arguments are generated for tests with parameters. Defaults to 0, so
+    /// that the same arguments are generated on every invocation.
+    pub fuzz_seed: Option<u64>,
+
+    #[clap(long)]
+    /// Rebuild the project and run its tests again whenever a Sway source file or manifest of the
+    /// project's packages changes.
+    ///
+    /// Only the tests affected by the changes are run again: those whose code, or code of the
+    /// functions they call, has changed since they last ran. All the tests of a package are run
+    /// again if the contracts deployed for them have changed, or if code coverage or gas
+    /// profiling is requested, as those reports cover every test of the package.
+    pub watch: bool,
 
     #[clap(long)]
     /// Experimental flag for the "new encoding" feature
     pub experimental_new_encoding: bool,
 }
 
+/// What the tests of a package last ran, used to tell which of them are affected by a change.
+#[derive(Default)]
+struct LastRun {
+    /// The bytecode and storage slots of each contract deployed for the tests to call.
+    deployed: Vec<(Vec<u8>, Vec<StorageSlot>)>,
+    /// The fingerprint of each test's code, along with its attributes, by test name.
+    tests: HashMap<String, (u64, String)>,
+}
+
 /// The set of options provided for controlling output of a test.
 #[derive(Parser, Debug, Clone)]
 #[clap(after_help = help())]
@@ -62,9 +123,29 @@ pub struct TestPrintOpts {
     #[clap(long = "pretty-print", short = 'r')]
     /// Pretty-print the logs emiited from tests.
     pub pretty_print: bool,
-    /// Print `Log` and `LogData` receipts for tests.
+    /// Print the values logged by tests, decoded using the program ABI.
     #[clap(long = "logs", short = 'l')]
     pub print_logs: bool,
+    /// Print the `Log` and `LogData` receipts of tests as JSON, rather than the decoded values.
+    #[clap(long)]
+    pub raw_logs: bool,
+    /// The format in which test results are printed.
+    ///
+    /// The `json` and `junit` formats write a machine-readable report of all test results to
+    /// stdout once the tests have finished, and all other output to stderr.
+    #[clap(long, value_enum, default_value_t = TestOutputFormat::Human)]
+    pub format: TestOutputFormat,
+}
+
+/// The formats in which test results can be printed.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestOutputFormat {
+    /// Human-readable results.
+    Human,
+    /// A JSON object for each test followed by a summary of its package, one per line.
+    Json,
+    /// A JUnit XML report with a test suite for each package.
+    Junit,
 }
 
 pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
@@ -74,13 +155,62 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
     };
 
     let test_print_opts = cmd.test_print.clone();
+    let test_run_opts = TestRunOpts {
+        coverage: cmd.coverage,
+        gas_profile: cmd.gas_profile,
+        fuzz_runs: cmd.fuzz_runs,
+        fuzz_seed: cmd.fuzz_seed,
+    };
+    let output_directory = cmd.build.pkg.output_directory.clone();
     let test_filter_phrase = cmd.filter.clone();
     let test_filter = test_filter_phrase.as_ref().map(|filter_phrase| TestFilter {
         filter_phrase,
         exact_match: cmd.filter_exact,
     });
+    let watch = cmd.watch;
     let opts = opts_from_cmd(cmd);
+    let run = |built_tests| {
+        run_built_tests(
+            built_tests,
+            test_runner_count,
+            test_filter.clone(),
+            &test_run_opts,
+            &test_print_opts,
+            output_directory.as_deref(),
+        )
+    };
+
+    if watch {
+        let build_opts: pkg::BuildOpts = opts.clone().into();
+        let whole_pkgs = test_run_opts.coverage || test_run_opts.gas_profile;
+        let mut last_runs = HashMap::new();
+        watch::watch(
+            build_opts.pkg.path.as_deref(),
+            || pkg::BuildPlan::from_build_opts(&build_opts),
+            |plan| {
+                let built_tests = forc_test::build_with_plan(opts.clone(), plan)?;
+                match changed_tests(built_tests, &mut last_runs, whole_pkgs) {
+                    Some(built_tests) => run(built_tests),
+                    None => Ok(()),
+                }
+            },
+        )?;
+        return Ok(());
+    }
+
     let built_tests = forc_test::build(opts)?;
+    run(built_tests)
+}
+
+/// Run the built tests and report their results.
+fn run_built_tests(
+    built_tests: BuiltTests,
+    test_runner_count: TestRunnerCount,
+    test_filter: Option<TestFilter>,
+    test_run_opts: &TestRunOpts,
+    test_print_opts: &TestPrintOpts,
+    output_directory: Option<&str>,
+) -> ForcResult<()> {
     let start = std::time::Instant::now();
     let test_count = built_tests.test_count(test_filter.as_ref());
     let num_tests_running = test_count.total - test_count.ignored;
@@ -92,24 +222,42 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
         num_tests_ignored,
         formatted_test_count_string(&num_tests_ignored)
     );
-    let tested = built_tests.run(test_runner_count, test_filter)?;
+    let tested = built_tests.run(test_runner_count, test_filter, test_run_opts.clone())?;
     let duration = start.elapsed();
 
     // Eventually we'll print this in a fancy manner, but this will do for testing.
-    let all_tests_passed = match tested {
-        forc_test::Tested::Workspace(pkgs) => {
+    let all_tests_passed = match (test_print_opts.format, tested) {
+        (TestOutputFormat::Human, forc_test::Tested::Workspace(pkgs)) => {
             for pkg in &pkgs {
                 let built = &pkg.built.descriptor.name;
                 info!("\n   tested -- {built}\n");
-                print_tested_pkg(pkg, &test_print_opts)?;
+                print_tested_pkg(pkg, test_print_opts)?;
+                report_tested_pkg(pkg, test_run_opts, output_directory)?;
             }
             info!("\n   Finished in {:?}", duration);
             pkgs.iter().all(|pkg| pkg.tests_passed())
         }
-        forc_test::Tested::Package(pkg) => {
-            print_tested_pkg(&pkg, &test_print_opts)?;
+        (TestOutputFormat::Human, forc_test::Tested::Package(pkg)) => {
+            print_tested_pkg(&pkg, test_print_opts)?;
+            report_tested_pkg(&pkg, test_run_opts, output_directory)?;
             pkg.tests_passed()
         }
+        (format, tested) => {
+            let pkgs = match tested {
+                forc_test::Tested::Workspace(pkgs) => pkgs,
+                forc_test::Tested::Package(pkg) => vec![*pkg],
+            };
+            let mut stdout = std::io::stdout().lock();
+            match format {
+                TestOutputFormat::Json => forc_test_report::write_json(&pkgs, &mut stdout)?,
+                TestOutputFormat::Junit => forc_test_report::write_junit(&pkgs, &mut stdout)?,
+                TestOutputFormat::Human => unreachable!("human-readable results are printed above"),
+            }
+            for pkg in &pkgs {
+                report_tested_pkg(pkg, test_run_opts, output_directory)?;
+            }
+            pkgs.iter().all(|pkg| pkg.tests_passed())
+        }
     };
 
     if all_tests_passed {
@@ -121,7 +269,86 @@ pub(crate) fn exec(cmd: Command) -> ForcResult<()> {
     }
 }
 
+/// Exclude the tests which already ran with the same code and deployed contracts, as their
+/// results can't have changed, and record what the remaining tests run.
+///
+/// If `whole_pkgs` is set, all the tests of a package are kept if any of them has changed.
+/// Returns `None` if every test already ran.
+fn changed_tests(
+    built_tests: BuiltTests,
+    last_runs: &mut HashMap<String, LastRun>,
+    whole_pkgs: bool,
+) -> Option<BuiltTests> {
+    let mut changed = |mut pkg_tests: PackageTests| {
+        let built = pkg_tests.built_pkg_with_tests();
+        let deployed = built
+            .bytecode_without_tests
+            .iter()
+            .map(|bytecode| (bytecode.bytes.clone(), built.storage_slots.clone()))
+            .chain(pkg_tests.contract_dependencies().map(|dep| {
+                let bytecode = &dep.bytecode.bytes;
+                (bytecode.clone(), dep.storage_slots.clone())
+            }))
+            .collect::<Vec<_>>();
+        let tests = built
+            .bytecode
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let test = entry.kind.test()?;
+                let attributes = format!(
+                    "{:?} {:?} {:?}",
+                    test.pass_condition, test.params, test.runs
+                );
+                Some((
+                    entry.finalized.fn_name.clone(),
+                    (entry.finalized.fingerprint, attributes),
+                ))
+            })
+            .collect::<HashMap<_, _>>();
+        let name = built.descriptor.name.clone();
+        let last_run = last_runs.insert(name.clone(), LastRun { deployed, tests });
+        let Some(last_run) =
+            last_run.filter(|last_run| last_run.deployed == last_runs[&name].deployed)
+        else {
+            return Some(pkg_tests);
+        };
+        let tests = &last_runs[&name].tests;
+        let is_changed = |test_name: &String| tests.get(test_name) != last_run.tests.get(test_name);
+        let num_changed = tests
+            .keys()
+            .filter(|test_name| is_changed(test_name))
+            .count();
+        if num_changed == 0 {
+            info!("   Skipping the tests of {name}, which are unchanged since they last ran");
+            return None;
+        }
+        if !whole_pkgs {
+            pkg_tests.retain_tests(|entry| is_changed(&entry.finalized.fn_name));
+            let num_unchanged = tests.len() - num_changed;
+            if num_unchanged != 0 {
+                info!(
+                    "   Skipping {num_unchanged} {} of {name}, unchanged since they last ran",
+                    formatted_test_count_string(&num_unchanged)
+                );
+            }
+        }
+        Some(pkg_tests)
+    };
+    match built_tests {
+        BuiltTests::Package(pkg_tests) => changed(pkg_tests).map(BuiltTests::Package),
+        BuiltTests::Workspace(pkgs_tests) => {
+            let pkgs_tests = pkgs_tests
+                .into_iter()
+                .filter_map(&mut changed)
+                .collect::<Vec<_>>();
+            (!pkgs_tests.is_empty()).then_some(BuiltTests::Workspace(pkgs_tests))
+        }
+    }
+}
+
 fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> ForcResult<()> {
+    let log_decoder = pkg.log_decoder();
     let succeeded = pkg.tests.iter().filter(|t| t.passed()).count();
     let failed = pkg.tests.len() - succeeded;
     let mut failed_tests = Vec::new();
@@ -131,19 +358,39 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
             true => ("ok", Colour::Green),
             false => ("FAILED", Colour::Red),
         };
-        info!(
-            "      test {} ... {} ({:?}, {} gas)",
-            test.name,
-            color.paint(state),
-            test.duration,
-            test.gas_used
-        );
+        match &test.fuzz {
+            Some(fuzz) => info!(
+                "      test {} ... {} ({:?}, {} {}, {} gas{})",
+                test.name,
+                color.paint(state),
+                test.duration,
+                fuzz.runs,
+                formatted_run_count_string(fuzz.runs),
+                test.gas_used,
+                if fuzz.counterexample.is_none() {
+                    " on average"
+                } else {
+                    ""
+                }
+            ),
+            None => info!(
+                "      test {} ... {} ({:?}, {} gas)",
+                test.name,
+                color.paint(state),
+                test.duration,
+                test.gas_used
+            ),
+        }
 
         // If logs are enabled, print them.
-        if test_print_opts.print_logs {
+        if test_print_opts.raw_logs {
             let logs = &test.logs;
             let formatted_logs = format_log_receipts(logs, test_print_opts.pretty_print)?;
             info!("{}", formatted_logs);
+        } else if test_print_opts.print_logs {
+            for log in format_decoded_logs(test, &log_decoder, test_print_opts, 8) {
+                info!("{log}");
+            }
         }
 
         // If the test is failing, save the test result for printing the details later on.
@@ -162,8 +409,6 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
             let failed_test_details = failed_test.details()?;
             let path = &*failed_test_details.file_path;
             let line_number = failed_test_details.line_number;
-            let logs = &failed_test.logs;
-            let formatted_logs = format_log_receipts(logs, test_print_opts.pretty_print)?;
             info!(
                 "      - test {}, {:?}:{} ",
                 failed_test_name, path, line_number
@@ -178,7 +423,32 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
                 }
                 info!("{failed_info_str}");
             }
-            info!("        Logs: {}", formatted_logs);
+            if let Some(fuzz) = &failed_test.fuzz {
+                if let Some(counterexample) = &fuzz.counterexample {
+                    let args = counterexample
+                        .iter()
+                        .map(|(name, value)| format!("{name} = {value}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    info!("        counterexample: {args}");
+                }
+                info!(
+                    "        failed after {} {} with seed {}",
+                    fuzz.runs,
+                    formatted_run_count_string(fuzz.runs),
+                    fuzz.seed
+                );
+            }
+            if test_print_opts.raw_logs {
+                let logs = &failed_test.logs;
+                let formatted_logs = format_log_receipts(logs, test_print_opts.pretty_print)?;
+                info!("        Logs: {}", formatted_logs);
+            } else if !failed_test.logs.is_empty() {
+                info!("        Logs:");
+                for log in format_decoded_logs(failed_test, &log_decoder, test_print_opts, 10) {
+                    info!("{log}");
+                }
+            }
         }
         info!("\n");
     }
@@ -199,6 +469,110 @@ fn print_tested_pkg(pkg: &TestedPackage, test_print_opts: &TestPrintOpts) -> For
     Ok(())
 }
 
+/// Print the coverage and gas profile reports requested for the tested package.
+fn report_tested_pkg(
+    pkg: &TestedPackage,
+    test_run_opts: &TestRunOpts,
+    output_directory: Option<&str>,
+) -> ForcResult<()> {
+    let pkg_dir = pkg.built.descriptor.manifest_file.dir();
+    let output_directory = output_directory
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_output_directory(pkg_dir));
+    if test_run_opts.coverage {
+        report_coverage(pkg, &output_directory)?;
+    }
+    if test_run_opts.gas_profile {
+        report_gas_profile(pkg, &output_directory)?;
+    }
+    Ok(())
+}
+
+/// Print a coverage summary for the tested package and write its lcov tracefile.
+fn report_coverage(pkg: &TestedPackage, output_directory: &Path) -> ForcResult<()> {
+    let coverage = PackageCoverage::from_tested_pkg(pkg)?;
+    let pkg_name = &pkg.built.descriptor.name;
+    let pkg_dir = pkg.built.descriptor.manifest_file.dir();
+
+    info!("\n   Coverage:");
+    for file in &coverage.files {
+        let path = file.path.strip_prefix(pkg_dir).unwrap_or(&file.path);
+        info!(
+            "      {} -- {}",
+            path.display(),
+            formatted_coverage_summary(&file.summary())
+        );
+    }
+    info!(
+        "   Total -- {}",
+        formatted_coverage_summary(&coverage.summary())
+    );
+
+    let coverage_dir = output_directory.join("coverage");
+    fs::create_dir_all(&coverage_dir)?;
+    let lcov_path = coverage_dir.join(format!("{pkg_name}.lcov"));
+    let mut lcov = vec![];
+    coverage.write_lcov(pkg_name, &mut lcov)?;
+    fs::write(&lcov_path, lcov)?;
+    info!("   Wrote lcov tracefile to {}", lcov_path.display());
+    Ok(())
+}
+
+/// Print the gas used by each function of the tested package and write its folded call stacks.
+fn report_gas_profile(pkg: &TestedPackage, output_directory: &Path) -> ForcResult<()> {
+    let profile = PackageGasProfile::from_tested_pkg(pkg)?;
+    let pkg_name = &pkg.built.descriptor.name;
+    let total_gas = profile.total_gas();
+
+    let mut table = term_table::Table::new();
+    table.separate_rows = false;
+    table.style = term_table::TableStyle::empty();
+    table.add_row(Row::new(vec![
+        TableCell::new("function"),
+        TableCell::new("self gas"),
+        TableCell::new("self %"),
+        TableCell::new("total gas"),
+    ]));
+    for function in profile.functions() {
+        let self_percent = match total_gas {
+            0 => 0.0,
+            _ => function.self_gas as f64 / total_gas as f64 * 100.0,
+        };
+        table.add_row(Row::new(vec![
+            TableCell::new(function.name),
+            TableCell::new_with_alignment(function.self_gas, 1, Alignment::Right),
+            TableCell::new_with_alignment(format!("{self_percent:.2}%"), 1, Alignment::Right),
+            TableCell::new_with_alignment(function.total_gas, 1, Alignment::Right),
+        ]));
+    }
+    info!("\n   Gas profile ({total_gas} gas):");
+    info!("{}", table.render());
+
+    let profile_dir = output_directory.join("gas-profile");
+    fs::create_dir_all(&profile_dir)?;
+    let folded_path = profile_dir.join(format!("{pkg_name}.folded"));
+    let mut folded = vec![];
+    profile.write_folded(&mut folded)?;
+    fs::write(&folded_path, folded)?;
+    info!("   Wrote folded call stacks to {}", folded_path.display());
+    Ok(())
+}
+
+fn formatted_coverage_summary(summary: &CoverageSummary) -> String {
+    fn formatted_percent(percent: Option<f64>) -> String {
+        percent.map_or_else(|| "-".to_string(), |percent| format!("{percent:.2}%"))
+    }
+    format!(
+        "lines: {}/{} ({}), functions: {}/{} ({})",
+        summary.lines_hit,
+        summary.lines_found,
+        formatted_percent(summary.lines_percent()),
+        summary.functions_hit,
+        summary.functions_found,
+        formatted_percent(summary.functions_percent()),
+    )
+}
+
 fn opts_from_cmd(cmd: Command) -> forc_test::TestOpts {
     forc_test::TestOpts {
         pkg: pkg::PkgOpts {
@@ -244,3 +618,110 @@ fn formatted_test_count_string(count: &usize) -> &str {
         "tests"
     }
 }
+
+/// Decode the values logged by a test, formatting each with the given indentation.
+fn format_decoded_logs(
+    test: &TestResult,
+    log_decoder: &LogDecoder,
+    test_print_opts: &TestPrintOpts,
+    indent: usize,
+) -> Vec<String> {
+    let indent = " ".repeat(indent);
+    log_decoder
+        .decode_logs(&test.logs)
+        .iter()
+        .map(|log| {
+            let log = match test_print_opts.pretty_print {
+                true => format!("{log:#}"),
+                false => log.to_string(),
+            };
+            format!("{indent}{}", log.replace('\n', &format!("\n{indent}")))
+        })
+        .collect()
+}
+
+fn formatted_run_count_string(count: u64) -> &'static str {
+    if count == 1 {
+        "run"
+    } else {
+        "runs"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_library_package;
+
+    fn build_tests(dir: &Path) -> BuiltTests {
+        forc_test::build(forc_test::TestOpts {
+            pkg: pkg::PkgOpts {
+                path: Some(dir.display().to_string()),
+                offline: true,
+                ..Default::default()
+            },
+            build_profile: pkg::BuildProfile::DEBUG.into(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    // The names of the tests that would run.
+    fn test_names(built_tests: Option<BuiltTests>) -> Vec<String> {
+        let Some(BuiltTests::Package(pkg_tests)) = built_tests else {
+            return vec![];
+        };
+        let mut names = pkg_tests
+            .built_pkg_with_tests()
+            .bytecode
+            .entries
+            .iter()
+            .filter(|entry| entry.kind.test().is_some())
+            .map(|entry| entry.finalized.fn_name.clone())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn only_affected_tests_are_run_again() {
+        let dir = tempfile::tempdir().unwrap();
+        // A library whose `helper` returns `value`, with a test calling it and the given others.
+        let write_tests = |value: u64, other_tests: &[&str]| {
+            let src = other_tests.iter().fold(
+                format!(
+                    "library;\n\nfn helper() -> u64 {{ {value} }}\n\n\
+                     #[test]\nfn calls_helper() {{ if helper() != 1 {{ __revert(0) }} }}\n"
+                ),
+                |src, name| src + &format!("\n#[test]\nfn {name}() {{ __revert(1) }}\n"),
+            );
+            write_library_package(dir.path(), &src);
+        };
+        let mut last_runs = HashMap::new();
+        let mut changed = |whole_pkgs| {
+            test_names(changed_tests(
+                build_tests(dir.path()),
+                &mut last_runs,
+                whole_pkgs,
+            ))
+        };
+
+        write_tests(1, &["reverts"]);
+        assert_eq!(changed(false), ["calls_helper", "reverts"]);
+        assert!(changed(false).is_empty());
+
+        // Adding a test runs only the new one.
+        write_tests(1, &["reverts", "added"]);
+        assert_eq!(changed(false), ["added"]);
+
+        // Changing a function runs only the tests calling it.
+        write_tests(2, &["reverts", "added"]);
+        assert_eq!(changed(false), ["calls_helper"]);
+        assert!(changed(false).is_empty());
+
+        // Unless every test of the package is to be run again.
+        write_tests(1, &["reverts", "added"]);
+        assert_eq!(changed(true), ["added", "calls_helper", "reverts"]);
+        assert!(changed(true).is_empty());
+    }
+}
@@ -1,8 +1,8 @@
 use std::str::FromStr;
 
 use self::commands::{
-    addr2line, build, check, clean, completions, contract_id, init, new, parse_bytecode, plugins,
-    predicate_root, template, test, update,
+    addr2line, build, check, clean, completions, contract_id, init, lint, new, parse_bytecode,
+    plugins, predicate_root, template, test, update,
 };
 use addr2line::Command as Addr2LineCommand;
 use anyhow::anyhow;
@@ -12,9 +12,10 @@ use clap::{Parser, Subcommand};
 pub use clean::Command as CleanCommand;
 pub use completions::Command as CompletionsCommand;
 pub(crate) use contract_id::Command as ContractIdCommand;
-use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions};
+use forc_tracing::{init_tracing_subscriber, TracingSubscriberOptions, TracingWriterMode};
 use forc_util::ForcResult;
 pub use init::Command as InitCommand;
+pub use lint::Command as LintCommand;
 pub use new::Command as NewCommand;
 use parse_bytecode::Command as ParseBytecodeCommand;
 pub use plugins::Command as PluginsCommand;
@@ -31,11 +32,12 @@ pub mod shared;
 fn help() -> &'static str {
     Box::leak(
         format!(
-            "Examples:\n{}{}{}{}",
+            "Examples:\n{}{}{}{}{}",
             plugins::examples(),
             test::examples(),
             build::examples(),
             check::examples(),
+            lint::examples(),
         )
         .trim_end()
         .to_string()
@@ -74,6 +76,7 @@ enum Forc {
     Completions(CompletionsCommand),
     New(NewCommand),
     Init(InitCommand),
+    Lint(LintCommand),
     ParseBytecode(ParseBytecodeCommand),
     #[clap(visible_alias = "t")]
     Test(TestCommand),
@@ -96,11 +99,18 @@ enum Forc {
 
 pub async fn run_cli() -> ForcResult<()> {
     let opt = Opt::parse();
+    // Machine-readable test reports are written to stdout, so everything else goes to stderr.
+    let writer_mode = match &opt.command {
+        Forc::Test(command) if command.test_print.format != test::TestOutputFormat::Human => {
+            Some(TracingWriterMode::Stderr)
+        }
+        _ => None,
+    };
     let tracing_options = TracingSubscriberOptions {
         verbosity: Some(opt.verbose),
         silent: Some(opt.silent),
         log_level: opt.log_level,
-        ..Default::default()
+        writer_mode,
     };
 
     init_tracing_subscriber(tracing_options);
@@ -112,6 +122,7 @@ pub async fn run_cli() -> ForcResult<()> {
         Forc::Clean(command) => clean::exec(command),
         Forc::Completions(command) => completions::exec(command),
         Forc::Init(command) => init::exec(command),
+        Forc::Lint(command) => lint::exec(command),
         Forc::New(command) => new::exec(command),
         Forc::ParseBytecode(command) => parse_bytecode::exec(command),
         Forc::Plugins(command) => plugins::exec(command),
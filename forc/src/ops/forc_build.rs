@@ -1,4 +1,4 @@
-use crate::cli::BuildCommand;
+use crate::{cli::BuildCommand, utils::watch};
 use forc_pkg as pkg;
 use forc_util::ForcResult;
 use pkg::manifest::build_profile::ExperimentalFlags;
@@ -9,6 +9,20 @@ pub fn build(cmd: BuildCommand) -> ForcResult<pkg::Built> {
     Ok(built)
 }
 
+/// Build the project, then rebuild it whenever its sources change.
+pub fn watch(cmd: BuildCommand) -> ForcResult<()> {
+    let opts = opts_from_cmd(cmd);
+    watch::watch(
+        opts.pkg.path.as_deref(),
+        || pkg::BuildPlan::from_build_opts(&opts),
+        |plan| {
+            pkg::build_with_plan(&opts, plan)?;
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
 fn opts_from_cmd(cmd: BuildCommand) -> pkg::BuildOpts {
     pkg::BuildOpts {
         pkg: pkg::PkgOpts {
@@ -30,6 +44,7 @@ fn opts_from_cmd(cmd: BuildCommand) -> pkg::BuildOpts {
             reverse_order: cmd.build.print.reverse_order,
         },
         time_phases: cmd.build.print.time_phases,
+        ir_passes: cmd.ir_passes,
         metrics_outfile: cmd.build.print.metrics_outfile,
         minify: pkg::MinifyOpts {
             json_abi: cmd.build.minify.json_abi,
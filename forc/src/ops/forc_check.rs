@@ -3,20 +3,30 @@ use anyhow::Result;
 use forc_pkg as pkg;
 use forc_pkg::manifest::GenericManifestFile;
 use pkg::manifest::ManifestFile;
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashSet},
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
 use sway_core::{language::ty, Engines, ExperimentalFlags};
-use sway_error::handler::Handler;
+use sway_error::{
+    diagnostic::{Applicability, Suggestion, ToDiagnostic},
+    handler::Handler,
+};
 
 pub fn check(command: CheckCommand, engines: &Engines) -> Result<(Option<ty::TyProgram>, Handler)> {
+    let plan = build_plan(&command)?;
+    check_with_plan(&command, &plan, engines)
+}
+
+/// Create the build plan for the project to check.
+pub fn build_plan(command: &CheckCommand) -> Result<pkg::BuildPlan> {
     let CheckCommand {
-        build_target,
         path,
         offline_mode: offline,
-        terse_mode,
         locked,
-        disable_tests,
         ipfs_node,
-        experimental_new_encoding,
+        ..
     } = command;
 
     let this_dir = if let Some(ref path) = path {
@@ -27,25 +37,40 @@ pub fn check(command: CheckCommand, engines: &Engines) -> Result<(Option<ty::TyP
     let manifest_file = ManifestFile::from_dir(this_dir)?;
     let member_manifests = manifest_file.member_manifests()?;
     let lock_path = manifest_file.lock_path()?;
-    let plan = pkg::BuildPlan::from_lock_and_manifests(
+    pkg::BuildPlan::from_lock_and_manifests(
         &lock_path,
         &member_manifests,
-        locked,
-        offline,
-        ipfs_node.unwrap_or_default(),
-    )?;
-    let tests_enabled = !disable_tests;
+        *locked,
+        *offline,
+        ipfs_node.clone().unwrap_or_default(),
+    )
+}
 
-    let mut v = pkg::check(
-        &plan,
+/// Check the project with the given build plan, rather than creating a new one.
+pub fn check_with_plan(
+    command: &CheckCommand,
+    plan: &pkg::BuildPlan,
+    engines: &Engines,
+) -> Result<(Option<ty::TyProgram>, Handler)> {
+    let CheckCommand {
         build_target,
         terse_mode,
+        disable_tests,
+        experimental_new_encoding,
+        ..
+    } = command;
+    let tests_enabled = !disable_tests;
+
+    let mut v = pkg::check(
+        plan,
+        *build_target,
+        *terse_mode,
         None,
         tests_enabled,
         engines,
         None,
         ExperimentalFlags {
-            new_encoding: experimental_new_encoding,
+            new_encoding: *experimental_new_encoding,
         },
     )?;
     let (res, handler) = v
@@ -54,3 +79,236 @@ pub fn check(command: CheckCommand, engines: &Engines) -> Result<(Option<ty::TyP
     let res = res.and_then(|programs| programs.typed.ok());
     Ok((res, handler))
 }
+
+/// Check the project and apply the machine applicable suggestions of the diagnostics of its
+/// members to their source files.
+///
+/// Returns the changed files, together with the number of suggestions applied to each of them.
+pub fn fix(command: &CheckCommand, engines: &Engines) -> Result<Vec<(PathBuf, usize)>> {
+    let plan = build_plan(command)?;
+    let results = pkg::check(
+        &plan,
+        command.build_target,
+        true,
+        None,
+        !command.disable_tests,
+        engines,
+        None,
+        ExperimentalFlags {
+            new_encoding: command.experimental_new_encoding,
+        },
+    )?;
+
+    let members = plan.member_nodes().collect::<HashSet<_>>();
+    let mut suggestions_by_file = BTreeMap::<PathBuf, Vec<Suggestion>>::new();
+    for (node, (_, handler)) in plan.compilation_order().iter().zip(results) {
+        if !members.contains(node) {
+            continue;
+        }
+        let (errors, warnings) = handler.consume();
+        let diagnostics = errors
+            .iter()
+            .map(|error| error.to_diagnostic(engines.se()))
+            .chain(
+                warnings
+                    .iter()
+                    .map(|warning| warning.to_diagnostic(engines.se())),
+            );
+        for diagnostic in diagnostics {
+            for suggestion in diagnostic.suggestions() {
+                if suggestion.applicability != Applicability::MachineApplicable {
+                    continue;
+                }
+                if let Some(source_id) = suggestion.span.source_id() {
+                    suggestions_by_file
+                        .entry(engines.se().get_path(source_id))
+                        .or_default()
+                        .push(suggestion.clone());
+                }
+            }
+        }
+    }
+
+    let mut fixed = vec![];
+    for (path, suggestions) in suggestions_by_file {
+        let src = std::fs::read_to_string(&path)?;
+        let (fixed_src, applied) = apply_suggestions(&src, suggestions);
+        if applied > 0 {
+            write_atomically(&path, &fixed_src)?;
+            fixed.push((path, applied));
+        }
+    }
+    Ok(fixed)
+}
+
+/// Replaces the contents of the file at `path` by writing them to a temporary file next to it
+/// first, so that a failed write cannot leave a partially written source file behind.
+fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    let mut tmp_file_name = OsString::from(".");
+    tmp_file_name.push(path.file_name().unwrap_or_default());
+    tmp_file_name.push(".fix");
+    let tmp_path = path.with_file_name(tmp_file_name);
+
+    let result = std::fs::write(&tmp_path, contents)
+        .and_then(|_| std::fs::set_permissions(&tmp_path, std::fs::metadata(path)?.permissions()))
+        .and_then(|_| std::fs::rename(&tmp_path, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    Ok(result?)
+}
+
+/// Applies the `suggestions` to the `src` they were made for and returns the result, together
+/// with the number of suggestions applied.
+///
+/// Duplicated suggestions are applied once. Of the suggestions that overlap, or insert at the
+/// same position, only the first one is applied, the others are likely to be fixed by it or
+/// can be fixed by running the fix again.
+fn apply_suggestions(src: &str, mut suggestions: Vec<Suggestion>) -> (String, usize) {
+    suggestions.sort_by_key(|suggestion| (suggestion.span.start(), suggestion.span.end()));
+    suggestions.dedup();
+
+    let mut fixed_src = String::with_capacity(src.len());
+    let mut applied = 0;
+    let mut last_start = None;
+    let mut copied_up_to = 0;
+    for suggestion in suggestions {
+        let (start, end) = (suggestion.span.start(), suggestion.span.end());
+        if start < copied_up_to || last_start == Some(start) || end > src.len() {
+            continue;
+        }
+        fixed_src.push_str(&src[copied_up_to..start]);
+        fixed_src.push_str(&suggestion.replacement);
+        copied_up_to = end;
+        last_start = Some(start);
+        applied += 1;
+    }
+    fixed_src.push_str(&src[copied_up_to..]);
+    (fixed_src, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_library_package;
+    use sway_error::error::CompileError;
+    use sway_types::Span;
+
+    fn suggestion(src: &str, start: usize, end: usize, replacement: &str) -> Suggestion {
+        Suggestion::replace(
+            Span::new(src.into(), start, end, None).unwrap(),
+            replacement.to_string(),
+            Applicability::MachineApplicable,
+        )
+    }
+
+    #[test]
+    fn apply_suggestions_in_any_order() {
+        let src = "let mut a = 1;\nlet mut b = 2;\n";
+        let suggestions = vec![
+            suggestion(src, 19, 23, ""),
+            suggestion(src, 4, 8, ""),
+            suggestion(src, 19, 23, ""),
+        ];
+        let (fixed_src, applied) = apply_suggestions(src, suggestions);
+        assert_eq!(fixed_src, "let a = 1;\nlet b = 2;\n");
+        assert_eq!(applied, 2);
+    }
+
+    #[test]
+    fn apply_suggestions_skips_conflicts() {
+        let src = "fn f() {}\n";
+        let suggestions = vec![
+            suggestion(src, 0, 0, "#[storage(read)]\n"),
+            suggestion(src, 0, 0, "#[storage(write)]\n"),
+            suggestion(src, 3, 4, "g"),
+            suggestion(src, 3, 6, "h()"),
+        ];
+        let (fixed_src, applied) = apply_suggestions(src, suggestions);
+        assert_eq!(fixed_src, "#[storage(read)]\nfn g() {}\n");
+        assert_eq!(applied, 2);
+    }
+
+    #[test]
+    fn fix_applies_machine_applicable_suggestions() {
+        let dir = tempfile::tempdir().unwrap();
+        write_library_package(
+            dir.path(),
+            "library;\n\nfn f() -> u64 {\n    let mut a = 1;\n    let mut b = 2;\n    a + b\n}\n",
+        );
+        let command = CheckCommand {
+            path: Some(dir.path().to_string_lossy().to_string()),
+            offline_mode: true,
+            fix: true,
+            ..Default::default()
+        };
+
+        let lib_path = dir.path().join("src").join("lib.sw");
+        let fixed = fix(&command, &Engines::default()).unwrap();
+        assert_eq!(fixed, vec![(lib_path.clone(), 2)]);
+        assert_eq!(
+            std::fs::read_to_string(&lib_path).unwrap(),
+            "library;\n\nfn f() -> u64 {\n    let a = 1;\n    let b = 2;\n    a + b\n}\n"
+        );
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("src")).unwrap().count(),
+            1,
+            "the temporary file must be renamed to the source file"
+        );
+
+        // Everything is fixed already.
+        assert!(fix(&command, &Engines::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn fix_does_not_apply_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = "library;\n\nmod inner;\n\nfn f() -> u64 {\n    let a = ANSWER;\n    g(a)\n}\n";
+        write_library_package(dir.path(), src);
+        std::fs::write(
+            dir.path().join("src").join("inner.sw"),
+            "library;\n\npub const ANSWER: u64 = 42;\n\npub fn g(a: u64) -> u64 {\n    a\n}\n",
+        )
+        .unwrap();
+        let command = CheckCommand {
+            path: Some(dir.path().to_string_lossy().to_string()),
+            offline_mode: true,
+            ..Default::default()
+        };
+
+        let (_, handler) = check(command, &Engines::default()).unwrap();
+        let (errors, _) = handler.consume();
+        let import_candidates = errors
+            .iter()
+            .map(|error| match error {
+                CompileError::SymbolNotFound {
+                    import_candidates, ..
+                }
+                | CompileError::UnknownVariable {
+                    import_candidates, ..
+                } => import_candidates.clone(),
+                error => panic!("unexpected error: {error}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            import_candidates,
+            vec![
+                vec!["inner::ANSWER".to_string()],
+                vec!["inner::g".to_string()]
+            ]
+        );
+
+        // The name alone does not make an import certain to be correct.
+        let command = CheckCommand {
+            path: Some(dir.path().to_string_lossy().to_string()),
+            offline_mode: true,
+            fix: true,
+            ..Default::default()
+        };
+        assert!(fix(&command, &Engines::default()).unwrap().is_empty());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("src").join("lib.sw")).unwrap(),
+            src
+        );
+    }
+}
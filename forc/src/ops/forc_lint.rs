@@ -0,0 +1,136 @@
+use crate::cli::LintCommand;
+use anyhow::{bail, Result};
+use forc_pkg as pkg;
+use forc_pkg::manifest::GenericManifestFile;
+use forc_util::{print_on_failure, print_warnings};
+use pkg::manifest::ManifestFile;
+use std::{collections::HashSet, path::PathBuf};
+use sway_core::{Engines, ExperimentalFlags};
+
+/// Check the project with all the lints enabled, printing the diagnostics of its members.
+///
+/// Fails if any of the members has errors, including the warnings of denied lints.
+pub fn lint(command: LintCommand, engines: &Engines) -> Result<()> {
+    let LintCommand {
+        build_target,
+        path,
+        offline_mode: offline,
+        locked,
+        terse_mode,
+        disable_tests,
+        ipfs_node,
+        experimental_new_encoding,
+    } = command;
+
+    let this_dir = if let Some(ref path) = path {
+        PathBuf::from(path)
+    } else {
+        std::env::current_dir()?
+    };
+    let manifest_file = ManifestFile::from_dir(this_dir)?;
+    let member_manifests = manifest_file.member_manifests()?;
+    let lock_path = manifest_file.lock_path()?;
+    let plan = pkg::BuildPlan::from_lock_and_manifests(
+        &lock_path,
+        &member_manifests,
+        locked,
+        offline,
+        ipfs_node.unwrap_or_default(),
+    )?;
+
+    let results = pkg::lint(
+        &plan,
+        build_target,
+        terse_mode,
+        !disable_tests,
+        engines,
+        ExperimentalFlags {
+            new_encoding: experimental_new_encoding,
+        },
+    )?;
+
+    let members = plan.member_nodes().collect::<HashSet<_>>();
+    let mut failed = vec![];
+    for (node, (programs, handler)) in plan.compilation_order().iter().zip(results) {
+        let pkg = &plan.graph()[*node];
+        if !members.contains(node) {
+            // Dependencies are only reported when they fail to compile.
+            if handler.has_errors() {
+                let (errors, warnings) = handler.consume();
+                print_on_failure(engines.se(), terse_mode, &warnings, &errors, false);
+                failed.push(pkg.name.clone());
+            }
+            continue;
+        }
+        let (errors, warnings) = handler.consume();
+        match programs {
+            Some(programs) if errors.is_empty() => {
+                print_warnings(
+                    engines.se(),
+                    terse_mode,
+                    &pkg.name,
+                    &warnings,
+                    &programs.lexed.kind,
+                );
+            }
+            _ => {
+                print_on_failure(engines.se(), terse_mode, &warnings, &errors, false);
+                failed.push(pkg.name.clone());
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        bail!("Failed to lint {}", failed.join(", "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_library_package;
+
+    const DIVIDE: &str = "pub fn divide(a: u64, b: u64) -> u64 {\n    a / b\n}\n";
+
+    /// Lint a library with the `src`, whose manifest has the `lints` as its `[lints]` table.
+    fn lint_library(lints: &str, src: &str) -> Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        write_library_package(dir.path(), src);
+        let manifest_path = dir.path().join("Forc.toml");
+        let manifest = std::fs::read_to_string(&manifest_path).unwrap();
+        std::fs::write(manifest_path, format!("{manifest}\n[lints]\n{lints}\n")).unwrap();
+        let command = LintCommand {
+            path: Some(dir.path().display().to_string()),
+            offline_mode: true,
+            ..Default::default()
+        };
+        lint(command, &Engines::default())
+    }
+
+    #[test]
+    fn warnings_do_not_fail() {
+        assert!(lint_library("", &format!("library;\n\n{DIVIDE}")).is_ok());
+    }
+
+    #[test]
+    fn opt_in_lints_are_checked() {
+        // A level set in the module does not enable the analysis when checking, but linting
+        // always runs it.
+        let src = format!("#![deny(division_by_zero)]\nlibrary;\n\n{DIVIDE}");
+        assert!(lint_library("", &src).is_err());
+    }
+
+    #[test]
+    fn package_levels_apply() {
+        let src = format!("library;\n\n{DIVIDE}");
+        assert!(lint_library("division_by_zero = \"deny\"", &src).is_err());
+        assert!(lint_library("division_by_zero = \"allow\"", &src).is_ok());
+    }
+
+    #[test]
+    fn item_levels_come_before_package_levels() {
+        let src = format!("library;\n\n#[allow(division_by_zero)]\n{DIVIDE}");
+        assert!(lint_library("division_by_zero = \"deny\"", &src).is_ok());
+    }
+}
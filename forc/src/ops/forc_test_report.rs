@@ -0,0 +1,440 @@
+//! Machine-readable reports of the results of `forc test`.
+
+use anyhow::Result;
+use forc_test::{
+    decode::{DecodedLog, LogDecoder},
+    TestResult, TestedPackage,
+};
+use forc_util::tx_utils::log_receipts_to_json;
+use serde::Serialize;
+use std::{io::Write, path::Path, time::Duration};
+
+/// A single line of the JSON report.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JsonRecord<'a> {
+    Test(TestRecord<'a>),
+    Package(PackageRecord<'a>),
+}
+
+/// The result of a single test.
+#[derive(Debug, Serialize)]
+struct TestRecord<'a> {
+    package: &'a str,
+    name: &'a str,
+    file: &'a Path,
+    line: usize,
+    passed: bool,
+    /// The duration of the test in seconds.
+    duration: f64,
+    gas_used: u64,
+    revert_code: Option<u64>,
+    error_signal: Option<String>,
+    logs: serde_json::Value,
+    decoded_logs: Vec<LogRecord>,
+    fuzz: Option<FuzzRecord>,
+}
+
+/// A value logged by a test.
+#[derive(Debug, Serialize)]
+struct LogRecord {
+    log_id: u64,
+    /// The type of the value, or `None` if the value could not be decoded.
+    #[serde(rename = "type")]
+    ty: Option<String>,
+    /// The decoded value, or the hex encoded data of a value that could not be decoded.
+    value: String,
+}
+
+/// The runs of a test with parameters.
+#[derive(Debug, Serialize)]
+struct FuzzRecord {
+    runs: u64,
+    seed: u64,
+    counterexample: Option<Vec<ArgRecord>>,
+}
+
+/// An argument of a counterexample.
+#[derive(Debug, Serialize)]
+struct ArgRecord {
+    name: String,
+    value: String,
+}
+
+/// A summary of the results of a package's tests.
+#[derive(Debug, Serialize)]
+struct PackageRecord<'a> {
+    name: &'a str,
+    passed: usize,
+    failed: usize,
+    /// The total duration of the package's tests in seconds.
+    duration: f64,
+}
+
+/// The details of a single test that are common to all report formats.
+struct TestReport<'a> {
+    test: &'a TestResult,
+    file: &'a Path,
+    /// The 1-based line number of the test declaration.
+    line: usize,
+    error_signal: Option<String>,
+    logs: serde_json::Value,
+    decoded_logs: Vec<DecodedLog>,
+}
+
+/// Write the results of the tested packages as JSON lines.
+///
+/// A line is written for each test, followed by a summary line for its package.
+pub fn write_json<W: Write>(pkgs: &[TestedPackage], writer: &mut W) -> Result<()> {
+    for pkg in pkgs {
+        let pkg_name = &pkg.built.descriptor.name;
+        let log_decoder = pkg.log_decoder();
+        for test in &pkg.tests {
+            let details = test.details()?;
+            let report =
+                TestReport::new(test, &log_decoder, &details.file_path, details.line_number)?;
+            let record = JsonRecord::Test(TestRecord {
+                package: pkg_name,
+                name: &test.name,
+                file: report.file,
+                line: report.line,
+                passed: test.passed(),
+                duration: test.duration.as_secs_f64(),
+                gas_used: test.gas_used,
+                revert_code: test.revert_code(),
+                error_signal: report.error_signal,
+                logs: report.logs,
+                decoded_logs: report
+                    .decoded_logs
+                    .iter()
+                    .map(|log| LogRecord {
+                        log_id: log.log_id,
+                        ty: log.ty.as_ref().map(|ty| ty.to_string()),
+                        value: log.value.to_string(),
+                    })
+                    .collect(),
+                fuzz: test.fuzz.as_ref().map(|fuzz| FuzzRecord {
+                    runs: fuzz.runs,
+                    seed: fuzz.seed,
+                    counterexample: fuzz.counterexample.as_ref().map(|args| {
+                        args.iter()
+                            .map(|(name, value)| ArgRecord {
+                                name: name.clone(),
+                                value: value.to_string(),
+                            })
+                            .collect()
+                    }),
+                }),
+            });
+            writeln!(writer, "{}", serde_json::to_string(&record)?)?;
+        }
+        let passed = pkg.tests.iter().filter(|test| test.passed()).count();
+        let record = JsonRecord::Package(PackageRecord {
+            name: pkg_name,
+            passed,
+            failed: pkg.tests.len() - passed,
+            duration: pkg_duration(pkg).as_secs_f64(),
+        });
+        writeln!(writer, "{}", serde_json::to_string(&record)?)?;
+    }
+    Ok(())
+}
+
+/// Write the results of the tested packages as a JUnit XML report, with a test suite for each
+/// package.
+pub fn write_junit<W: Write>(pkgs: &[TestedPackage], writer: &mut W) -> Result<()> {
+    let total_tests: usize = pkgs.iter().map(|pkg| pkg.tests.len()).sum();
+    let total_failures: usize = pkgs.iter().map(pkg_failures).sum();
+    let total_duration: Duration = pkgs.iter().map(pkg_duration).sum();
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="forc test" tests="{total_tests}" failures="{total_failures}" time="{:.6}">"#,
+        total_duration.as_secs_f64()
+    )?;
+    for pkg in pkgs {
+        let pkg_name = escape_xml(&pkg.built.descriptor.name);
+        let log_decoder = pkg.log_decoder();
+        writeln!(
+            writer,
+            r#"  <testsuite name="{pkg_name}" tests="{}" failures="{}" time="{:.6}">"#,
+            pkg.tests.len(),
+            pkg_failures(pkg),
+            pkg_duration(pkg).as_secs_f64()
+        )?;
+        for test in &pkg.tests {
+            let details = test.details()?;
+            let report =
+                TestReport::new(test, &log_decoder, &details.file_path, details.line_number)?;
+            writeln!(
+                writer,
+                r#"    <testcase name="{}" classname="{pkg_name}" file="{}" line="{}" time="{:.6}">"#,
+                escape_xml(&test.name),
+                escape_xml(&report.file.display().to_string()),
+                report.line,
+                test.duration.as_secs_f64()
+            )?;
+            writeln!(writer, "      <properties>")?;
+            writeln!(
+                writer,
+                r#"        <property name="gas_used" value="{}"/>"#,
+                test.gas_used
+            )?;
+            if let Some(fuzz) = &test.fuzz {
+                writeln!(
+                    writer,
+                    r#"        <property name="runs" value="{}"/>"#,
+                    fuzz.runs
+                )?;
+                writeln!(
+                    writer,
+                    r#"        <property name="seed" value="{}"/>"#,
+                    fuzz.seed
+                )?;
+            }
+            writeln!(writer, "      </properties>")?;
+            if !test.passed() {
+                let message = report.failure_message();
+                writeln!(
+                    writer,
+                    r#"      <failure message="{}" type="revert">{}</failure>"#,
+                    escape_xml(&message),
+                    escape_xml(&report.failure_details(&message))
+                )?;
+            }
+            if !report.decoded_logs.is_empty() {
+                let logs = report
+                    .decoded_logs
+                    .iter()
+                    .map(|log| log.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                writeln!(
+                    writer,
+                    "      <system-out>{}</system-out>",
+                    escape_xml(&logs)
+                )?;
+            }
+            writeln!(writer, "    </testcase>")?;
+        }
+        writeln!(writer, "  </testsuite>")?;
+    }
+    writeln!(writer, "</testsuites>")?;
+    Ok(())
+}
+
+impl<'a> TestReport<'a> {
+    fn new(
+        test: &'a TestResult,
+        log_decoder: &LogDecoder,
+        file: &'a Path,
+        line_index: usize,
+    ) -> Result<Self> {
+        Ok(Self {
+            test,
+            file,
+            line: line_index + 1,
+            error_signal: test.error_signal().ok().map(|signal| signal.to_string()),
+            logs: log_receipts_to_json(&test.logs)?,
+            decoded_logs: log_decoder.decode_logs(&test.logs),
+        })
+    }
+
+    /// A short description of why the test failed.
+    fn failure_message(&self) -> String {
+        match self.test.revert_code() {
+            Some(revert_code) => {
+                let mut message = format!("revert code: {revert_code:x}");
+                if let Some(error_signal) = &self.error_signal {
+                    message.push_str(&format!(" -- {error_signal}"));
+                }
+                message
+            }
+            None => "expected the test to revert".to_string(),
+        }
+    }
+
+    /// The failure message followed by the test's location and any counterexample.
+    fn failure_details(&self, message: &str) -> String {
+        let mut details = format!("{message}\nat {}:{}", self.file.display(), self.line);
+        if let Some(fuzz) = &self.test.fuzz {
+            if let Some(counterexample) = &fuzz.counterexample {
+                let args = counterexample
+                    .iter()
+                    .map(|(name, value)| format!("{name} = {value}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                details.push_str(&format!("\ncounterexample: {args}"));
+            }
+            let runs = if fuzz.runs == 1 { "run" } else { "runs" };
+            details.push_str(&format!(
+                "\nfailed after {} {runs} with seed {}",
+                fuzz.runs, fuzz.seed
+            ));
+        }
+        details
+    }
+}
+
+fn pkg_failures(pkg: &TestedPackage) -> usize {
+    pkg.tests.iter().filter(|test| !test.passed()).count()
+}
+
+fn pkg_duration(pkg: &TestedPackage) -> Duration {
+    pkg.tests.iter().map(|test| test.duration).sum()
+}
+
+/// Escape the characters with special meaning in XML text and attribute values.
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::write_library_package;
+    use forc_pkg::{BuildProfile, PkgOpts};
+    use forc_test::{TestFilter, TestOpts, TestRunOpts, TestRunnerCount, Tested};
+
+    const TESTS: &str = r#"library;
+
+#[test]
+fn test_passes() {}
+
+#[test]
+fn test_fails() {
+    __revert(42)
+}
+
+#[test]
+fn ignored() {}
+"#;
+
+    /// Runs the tests of a library in a directory whose name has special characters, filtering
+    /// out the `ignored` test.
+    fn tested_packages(dir: &tempfile::TempDir) -> Vec<TestedPackage> {
+        write_library_package(dir.path(), TESTS);
+        let opts = TestOpts {
+            pkg: PkgOpts {
+                path: Some(dir.path().display().to_string()),
+                offline: true,
+                ..Default::default()
+            },
+            build_profile: BuildProfile::DEBUG.into(),
+            ..Default::default()
+        };
+        let filter = TestFilter {
+            filter_phrase: "test_",
+            exact_match: false,
+        };
+        let tested = forc_test::build(opts)
+            .unwrap()
+            .run(
+                TestRunnerCount::Manual(1),
+                Some(filter),
+                TestRunOpts::default(),
+            )
+            .unwrap();
+        match tested {
+            Tested::Package(pkg) => vec![*pkg],
+            Tested::Workspace(pkgs) => pkgs,
+        }
+    }
+
+    fn special_dir() -> tempfile::TempDir {
+        tempfile::Builder::new()
+            .prefix(r#"a<b&c"d'e"#)
+            .tempdir()
+            .unwrap()
+    }
+
+    #[test]
+    fn json_report() {
+        let dir = special_dir();
+        let mut report = Vec::new();
+        write_json(&tested_packages(&dir), &mut report).unwrap();
+
+        let records = String::from_utf8(report)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(records.len(), 3);
+        let test = |name: &str| {
+            records
+                .iter()
+                .find(|record| record["type"] == "test" && record["name"] == name)
+                .unwrap_or_else(|| panic!("no record of {name}"))
+        };
+
+        let passed = test("test_passes");
+        assert_eq!(passed["package"], "test_lib");
+        assert_eq!(passed["passed"], true);
+        assert_eq!(passed["revert_code"], serde_json::Value::Null);
+        assert_eq!(
+            passed["file"],
+            dir.path().join("src").join("lib.sw").display().to_string()
+        );
+        assert_eq!(passed["line"], 4);
+
+        let failed = test("test_fails");
+        assert_eq!(failed["passed"], false);
+        assert_eq!(failed["revert_code"], 42);
+        assert_eq!(failed["line"], 7);
+
+        // Tests which are filtered out aren't reported.
+        assert_eq!(
+            records[2],
+            serde_json::json!({
+                "type": "package",
+                "name": "test_lib",
+                "passed": 1,
+                "failed": 1,
+                "duration": records[2]["duration"],
+            })
+        );
+    }
+
+    #[test]
+    fn junit_report() {
+        let dir = special_dir();
+        let mut report = Vec::new();
+        write_junit(&tested_packages(&dir), &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+
+        assert!(report.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert!(report.contains(r#"<testsuites name="forc test" tests="2" failures="1""#));
+        assert!(report.contains(r#"<testsuite name="test_lib" tests="2" failures="1""#));
+        assert!(report.contains(r#"<testcase name="test_passes" classname="test_lib""#));
+        assert!(report.contains(r#"<testcase name="test_fails" classname="test_lib""#));
+        assert!(report.contains(r#"<failure message="revert code: 2a" type="revert">"#));
+        assert_eq!(report.matches("<failure ").count(), 1);
+        assert!(!report.contains("ignored"));
+
+        // The path of the test's file is escaped.
+        let file = dir.path().join("src").join("lib.sw").display().to_string();
+        assert!(report.contains(&format!(r#"file="{}" line="4""#, escape_xml(&file))));
+        assert!(!report.contains(&file));
+        assert!(report.trim_end().ends_with("</testsuites>"));
+    }
+
+    #[test]
+    fn xml_special_characters_are_escaped() {
+        assert_eq!(
+            escape_xml(r#"<a & "b" 'c'>"#),
+            "&lt;a &amp; &quot;b&quot; &apos;c&apos;&gt;"
+        );
+        assert_eq!(escape_xml("test_passes"), "test_passes");
+    }
+}
@@ -41,7 +41,7 @@ pub async fn update(command: UpdateCommand) -> Result<()> {
     let member_manifests = manifest.member_manifests()?;
     let ipfs_node = command.ipfs_node.unwrap_or_default();
     let new_plan = pkg::BuildPlan::from_manifests(&member_manifests, offline, ipfs_node)?;
-    let new_lock = Lock::from_graph(new_plan.graph());
+    let new_lock = Lock::from_graph(new_plan.graph(), manifest.dir());
     let diff = new_lock.diff(&old_lock);
     let member_names = member_manifests
         .values()
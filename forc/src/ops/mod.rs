@@ -3,6 +3,8 @@ pub mod forc_check;
 pub mod forc_clean;
 pub mod forc_contract_id;
 pub mod forc_init;
+pub mod forc_lint;
 pub mod forc_predicate_root;
 pub mod forc_template;
+pub mod forc_test_report;
 pub mod forc_update;
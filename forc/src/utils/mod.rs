@@ -1,2 +1,5 @@
 pub mod defaults;
 pub mod program_type;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub mod watch;
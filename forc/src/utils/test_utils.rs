@@ -0,0 +1,28 @@
+use std::path::Path;
+
+/// Writes a library package to `dir` whose only dependency is the core library of this
+/// repository, so that it can be compiled without fetching anything.
+///
+/// The `src` is written to the entry module `src/lib.sw`.
+pub(crate) fn write_library_package(dir: &Path, src: &str) {
+    let core_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join("sway-lib-core");
+    let manifest = format!(
+        r#"[project]
+authors = ["Fuel Labs <contact@fuel.sh>"]
+entry = "lib.sw"
+license = "Apache-2.0"
+name = "test_lib"
+implicit-std = false
+
+[dependencies]
+core = {{ path = "{}" }}
+"#,
+        core_path.display()
+    );
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("Forc.toml"), manifest).unwrap();
+    std::fs::write(dir.join("src").join("lib.sw"), src).unwrap();
+}
@@ -0,0 +1,233 @@
+//! Re-running a command whenever the sources of a package or workspace change.
+
+use anyhow::Result;
+use forc_pkg::{self as pkg, manifest::GenericManifestFile, source};
+use forc_util::ForcResult;
+use notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+use sway_utils::constants::{MANIFEST_FILE_NAME, SWAY_EXTENSION};
+use tracing::{error, info};
+
+/// The time to wait for further changes before re-running the command, so that saving several
+/// files at once results in a single run.
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Run a command with the build plan of the project at `path`, then run it again whenever a Sway
+/// source file or manifest of one of the project's local packages changes.
+///
+/// The build plan is created once, fetching any missing dependencies, and is reused until a
+/// manifest changes. Errors returned by the command are printed rather than ending the watch.
+pub fn watch(
+    path: Option<&str>,
+    mut create_plan: impl FnMut() -> Result<pkg::BuildPlan>,
+    mut run: impl FnMut(&pkg::BuildPlan) -> ForcResult<()>,
+) -> Result<()> {
+    let project_dir = match path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()?,
+    }
+    .canonicalize()?;
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, None, tx)?;
+    let mut watched_dirs = BTreeSet::new();
+    let mut plan = create_plan()?;
+    loop {
+        let dirs = local_package_dirs(&project_dir, &plan);
+        for dir in watched_dirs.difference(&dirs) {
+            // The directory may have been removed along with its package.
+            let _ = debouncer.watcher().unwatch(dir);
+        }
+        for dir in dirs.difference(&watched_dirs) {
+            debouncer.watcher().watch(dir, RecursiveMode::Recursive)?;
+        }
+        watched_dirs = dirs;
+
+        if let Err(err) = run(&plan) {
+            error!("Error: {err}");
+        }
+        info!("\n   Watching for changes...");
+
+        // Wait for a change, creating a new build plan if a manifest changed.
+        loop {
+            let changed = next_changes(&rx)?;
+            if !changed.iter().any(|path| is_manifest(path)) {
+                break;
+            }
+            match create_plan() {
+                Ok(new_plan) => {
+                    plan = new_plan;
+                    break;
+                }
+                Err(err) => error!("Error: {err}"),
+            }
+        }
+    }
+}
+
+/// Block until a Sway source file or manifest changes, and return the paths of the changed files.
+fn next_changes(rx: &mpsc::Receiver<DebounceEventResult>) -> Result<Vec<PathBuf>> {
+    loop {
+        let events = match rx.recv()? {
+            Ok(events) => events,
+            Err(errs) => {
+                for err in errs {
+                    error!("Error: {err}");
+                }
+                continue;
+            }
+        };
+        let changed = events
+            .into_iter()
+            .map(|event| event.path)
+            .filter(|path| is_watched(path))
+            .collect::<Vec<_>>();
+        if !changed.is_empty() {
+            return Ok(changed);
+        }
+    }
+}
+
+/// The directories to watch for the project at `project_dir`.
+///
+/// These are the project's directory and the directories of all workspace members and path
+/// dependencies, excluding those within another watched directory. Dependencies fetched from git,
+/// IPFS or a registry are not expected to change.
+fn local_package_dirs(project_dir: &Path, plan: &pkg::BuildPlan) -> BTreeSet<PathBuf> {
+    let manifest_map = plan.manifest_map();
+    let dirs = plan
+        .graph()
+        .node_weights()
+        .filter(|pinned| {
+            matches!(
+                pinned.source,
+                source::Pinned::Member(_) | source::Pinned::Path(_)
+            )
+        })
+        .filter_map(|pinned| manifest_map.get(&pinned.id()))
+        .map(|manifest| manifest.dir().to_path_buf())
+        .chain(std::iter::once(project_dir.to_path_buf()))
+        .collect::<BTreeSet<_>>();
+    dirs.iter()
+        .filter(|dir| {
+            !dirs
+                .iter()
+                .any(|other| other != *dir && dir.starts_with(other))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Whether a change to the file at `path` may change the project, i.e. whether it is a Sway source
+/// file or a manifest. Build artifacts and lock files written by the command itself are ignored.
+fn is_watched(path: &Path) -> bool {
+    is_manifest(path) || is_sway_file(path)
+}
+
+fn is_manifest(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|file_name| file_name == MANIFEST_FILE_NAME)
+}
+
+fn is_sway_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == SWAY_EXTENSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a library package named `name` to `dir` with the given path dependencies.
+    fn write_package(dir: &Path, name: &str, deps: &[(&str, &str)]) {
+        let deps = deps
+            .iter()
+            .map(|(dep, path)| format!("{dep} = {{ path = \"{path}\" }}\n"))
+            .collect::<String>();
+        let manifest = format!(
+            "[project]\nauthors = [\"Fuel Labs <contact@fuel.sh>\"]\nentry = \"lib.sw\"\n\
+             license = \"Apache-2.0\"\nname = \"{name}\"\nimplicit-std = false\n\n\
+             [dependencies]\n{deps}"
+        );
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join(MANIFEST_FILE_NAME), manifest).unwrap();
+        std::fs::write(dir.join("src").join("lib.sw"), "library;\n").unwrap();
+    }
+
+    /// Writes a workspace to `root/ws` with the members `a`, which depends on the package at
+    /// `root/c`, and `b`, which depends on the package at `ws/libs/d`.
+    fn write_workspace(root: &Path) {
+        let ws = root.join("ws");
+        std::fs::create_dir_all(&ws).unwrap();
+        std::fs::write(
+            ws.join(MANIFEST_FILE_NAME),
+            "[workspace]\nmembers = [\"a\", \"b\"]\n",
+        )
+        .unwrap();
+        write_package(&ws.join("a"), "a", &[("c", "../../c")]);
+        write_package(&ws.join("b"), "b", &[("d", "../libs/d")]);
+        write_package(&ws.join("libs").join("d"), "d", &[]);
+        write_package(&root.join("c"), "c", &[]);
+    }
+
+    fn watched_dirs(project_dir: &Path) -> BTreeSet<PathBuf> {
+        let build_opts = pkg::BuildOpts {
+            pkg: pkg::PkgOpts {
+                path: Some(project_dir.display().to_string()),
+                offline: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let plan = pkg::BuildPlan::from_build_opts(&build_opts).unwrap();
+        local_package_dirs(project_dir, &plan)
+    }
+
+    #[test]
+    fn workspace_members_and_path_dependencies_are_watched() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        write_workspace(&root);
+
+        // The members and `d` are within the workspace directory, so only it is watched.
+        let ws = root.join("ws");
+        assert_eq!(
+            watched_dirs(&ws),
+            BTreeSet::from([root.join("c"), ws.clone()])
+        );
+    }
+
+    #[test]
+    fn path_dependencies_of_packages_are_watched() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path().canonicalize().unwrap();
+        write_package(&root.join("p"), "p", &[("c", "../c")]);
+        write_package(&root.join("c"), "c", &[]);
+
+        assert_eq!(
+            watched_dirs(&root.join("p")),
+            BTreeSet::from([root.join("c"), root.join("p")])
+        );
+        assert_eq!(
+            watched_dirs(&root.join("c")),
+            BTreeSet::from([root.join("c")])
+        );
+    }
+
+    #[test]
+    fn only_sources_and_manifests_are_watched() {
+        assert!(is_watched(Path::new("/project/src/main.sw")));
+        assert!(is_watched(Path::new("/project/Forc.toml")));
+        assert!(!is_watched(Path::new("/project/Forc.lock")));
+        assert!(!is_watched(Path::new("/project/out/debug/project.bin")));
+        assert!(!is_watched(Path::new(
+            "/project/out/debug/project-abi.json"
+        )));
+        assert!(!is_watched(Path::new("/project/src")));
+    }
+}
@@ -1,11 +1,11 @@
 use crate::priv_prelude::*;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GenericParams {
     pub parameters: AngleBrackets<Punctuated<Ident, CommaToken>>,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GenericArgs {
     pub parameters: AngleBrackets<Punctuated<Ty, CommaToken>>,
 }
@@ -1,13 +1,13 @@
 use crate::priv_prelude::*;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ItemImplItem {
     Fn(ItemFn),
     Const(ItemConst),
     Type(TraitType),
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemImpl {
     pub impl_token: ImplToken,
     pub generic_params_opt: Option<GenericParams>,
@@ -1,6 +1,6 @@
 use crate::priv_prelude::*;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Module {
     pub kind: ModuleKind,
     pub semicolon_token: SemicolonToken,
@@ -31,7 +31,7 @@ impl Spanned for Module {
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ModuleKind {
     Script { script_token: ScriptToken },
     Contract { contract_token: ContractToken },
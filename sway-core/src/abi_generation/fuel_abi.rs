@@ -67,14 +67,19 @@ pub fn generate_program_abi(
                 configurables: Some(configurables),
             }
         }
-        _ => program_abi::ProgramABI {
-            encoding,
-            types: vec![],
-            functions: vec![],
-            logged_types: None,
-            messages_types: None,
-            configurables: None,
-        },
+        // Libraries have no entry points, but the values logged by their unit tests are still
+        // described so that they can be decoded.
+        TyProgramKind::Library { .. } => {
+            let logged_types = generate_logged_types(ctx, type_engine, decl_engine, types);
+            program_abi::ProgramABI {
+                encoding,
+                types: types.to_vec(),
+                functions: vec![],
+                logged_types: Some(logged_types),
+                messages_types: None,
+                configurables: None,
+            }
+        }
     }
 }
 
@@ -25,6 +25,12 @@ pub struct FinalizedAsm {
     pub program_section: InstructionSet,
     pub program_kind: ProgramKind,
     pub entries: Vec<FinalizedEntry>,
+    /// The instruction offset at which each function in the program begins, mapped to the
+    /// function's name.
+    pub fn_offsets: BTreeMap<u64, String>,
+    /// The number of registers spilled to the stack by the register allocator in each function,
+    /// mapped from the function's name.
+    pub fn_spills: BTreeMap<String, usize>,
     pub abi: Option<ProgramABI>,
 }
 
@@ -39,6 +45,10 @@ pub struct FinalizedEntry {
     /// If this entry is constructed from a test function contains the declaration id for that
     /// function, otherwise contains `None`.
     pub test_decl_ref: Option<DeclRefFunction>,
+    /// A fingerprint of the code run by the entry function, including the functions it calls.
+    ///
+    /// Unlike the bytecode, it only changes between compilations when that code changes.
+    pub fingerprint: u64,
 }
 
 /// The bytecode for a sway program as well as the byte offsets of configuration-time constants in
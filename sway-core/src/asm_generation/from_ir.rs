@@ -11,10 +11,10 @@ use super::{
     MidenVMAsmBuilder,
 };
 
-use crate::{BuildConfig, BuildTarget, ExperimentalFlags};
+use crate::{BuildConfig, BuildTarget, ExperimentalFlags, OptLevel};
 
 use sway_error::handler::{ErrorEmitted, Handler};
-use sway_ir::{Context, Kind, Module};
+use sway_ir::{fingerprint_fns, Context, Function, Kind, Module};
 
 pub fn compile_ir_to_asm(
     handler: &Handler,
@@ -65,22 +65,37 @@ fn compile_module_to_asm(
         None => BuildTarget::default(),
     };
 
+    let optimization_level = match build_config {
+        Some(cfg) => cfg.optimization_level,
+        None => OptLevel::default(),
+    };
+
     let mut builder: Box<dyn AsmBuilder> = match build_target {
         BuildTarget::Fuel => Box::new(FuelAsmBuilder::new(
             kind,
-            DataSection::default(),
+            DataSection {
+                merge_same_bytes: optimization_level == OptLevel::OptSize,
+                overlap_and_pack: optimization_level != OptLevel::Opt0,
+                ..Default::default()
+            },
             reg_seqr,
             context,
+            optimization_level,
         )),
         BuildTarget::EVM => Box::new(EvmAsmBuilder::new(kind, context)),
         BuildTarget::MidenVM => Box::new(MidenVMAsmBuilder::new(kind, context)),
     };
 
     // Pre-create labels for all functions before we generate other code, so we can call them
-    // before compiling them if needed.
-    for func in module.function_iter(context) {
-        builder.func_to_labels(&func);
-    }
+    // before compiling them if needed.  Their start labels are kept so that the offset of each
+    // function may be reported once the labels are realized.
+    let fn_labels = module
+        .function_iter(context)
+        .map(|func| {
+            let (start_label, _) = builder.func_to_labels(&func);
+            (start_label, source_fn_name(context, &func).to_string())
+        })
+        .collect::<Vec<_>>();
 
     for function in module.function_iter(context) {
         builder.compile_function(handler, function)?;
@@ -91,6 +106,7 @@ fn compile_module_to_asm(
     let final_program = match result {
         AsmBuilderResult::Fuel(result) => {
             let (data_section, reg_seqr, entries, non_entries) = result;
+            let fingerprints = fingerprint_fns(context, module);
             let entries = entries
                 .into_iter()
                 .map(|(func, label, ops, test_decl_ref)| {
@@ -102,6 +118,7 @@ fn compile_module_to_asm(
                         label,
                         ops,
                         name,
+                        fingerprint: fingerprints[&func],
                     }
                 })
                 .collect();
@@ -111,10 +128,12 @@ fn compile_module_to_asm(
                 data_section,
                 entries,
                 non_entries,
+                fn_labels,
                 reg_seqr,
                 ExperimentalFlags {
                     new_encoding: context.experimental.new_encoding,
                 },
+                optimization_level,
             );
 
             if build_config
@@ -151,6 +170,20 @@ fn compile_module_to_asm(
     Ok(final_program)
 }
 
+/// The name of the source function from which the given IR function was compiled.
+///
+/// Non-entry functions are given a unique numeric suffix during IR generation, which is removed.
+fn source_fn_name<'a>(context: &'a Context, func: &Function) -> &'a str {
+    let name = func.get_name(context);
+    if func.is_entry(context) {
+        return name;
+    }
+    match name.rsplit_once('_') {
+        Some((source_name, suffix)) if suffix.chars().all(|c| c.is_ascii_digit()) => source_name,
+        _ => name,
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 // NOTE: For stack storage we need to be aware:
@@ -1,6 +1,7 @@
 use crate::{
     asm_generation::fuel::{
-        allocated_abstract_instruction_set::AllocatedAbstractInstructionSet, register_allocator,
+        register_allocator::{self, AllocatedFunction},
+        register_sequencer::RegisterSequencer,
     },
     asm_lang::{
         allocated_ops::{AllocatedOp, AllocatedOpcode},
@@ -166,8 +167,9 @@ impl AbstractInstructionSet {
     /// Allocate registers.
     pub(crate) fn allocate_registers(
         self,
-    ) -> Result<AllocatedAbstractInstructionSet, CompileError> {
-        register_allocator::allocate_registers(&self.ops)
+        reg_seqr: &mut RegisterSequencer,
+    ) -> Result<AllocatedFunction, CompileError> {
+        register_allocator::allocate_registers(&self.ops, reg_seqr)
     }
 }
 
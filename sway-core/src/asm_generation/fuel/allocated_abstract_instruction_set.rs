@@ -8,6 +8,7 @@ use super::{
     abstract_instruction_set::RealizedAbstractInstructionSet,
     compiler_constants as consts,
     data_section::{DataSection, Entry},
+    register_allocator::AllocatedFunction,
 };
 
 use indexmap::{IndexMap, IndexSet};
@@ -34,13 +35,72 @@ pub struct AllocatedAbstractInstructionSet {
     pub(crate) ops: Vec<AllocatedAbstractOp>,
 }
 
+/// Find the registers each function must preserve for its callers, keyed by its PUSHA label.
+///
+/// A function must preserve the registers which are live across any call to it, as well as those
+/// which its callers must preserve in turn, since it may clobber them on their behalf.  A tail
+/// call returns directly to the caller's caller, so the target of a tail call must preserve the
+/// registers of its caller only.  Entries have no callers and preserve nothing.
+pub(crate) fn callee_saved_registers(
+    functions: &[AllocatedFunction],
+) -> HashMap<Label, BTreeSet<AllocatedRegister>> {
+    let push_labels = functions
+        .iter()
+        .map(|function| {
+            function.ops.ops.iter().find_map(|op| match op.opcode {
+                Either::Right(ControlFlowOp::PushAll(label)) => Some(label),
+                _ => None,
+            })
+        })
+        .collect::<Vec<_>>();
+    let fn_labels = push_labels.iter().flatten().collect::<HashSet<_>>();
+
+    // Iterate to a fixed point, as the registers to save flow from callers to callees and calls
+    // may be recursive.
+    let mut saved: HashMap<Label, BTreeSet<AllocatedRegister>> = HashMap::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (function, push_label) in functions.iter().zip(&push_labels) {
+            let caller_saved = push_label
+                .and_then(|label| saved.get(&label).cloned())
+                .unwrap_or_default();
+
+            let tail_calls = function.ops.ops.iter().filter_map(|op| match op.opcode {
+                Either::Right(ControlFlowOp::Jump(label)) if fn_labels.contains(&label) => {
+                    Some((label, None))
+                }
+                _ => None,
+            });
+            let calls = function
+                .calls
+                .iter()
+                .map(|(label, live)| (*label, Some(live)));
+            for (callee, live) in calls.chain(tail_calls) {
+                let callee_saved = saved.entry(callee).or_default();
+                for reg in live.into_iter().flatten().chain(&caller_saved) {
+                    changed |= callee_saved.insert(reg.clone());
+                }
+            }
+        }
+    }
+
+    saved
+}
+
 impl AllocatedAbstractInstructionSet {
-    /// Replace each PUSHA instruction with stores of all used registers to the stack, and each
-    /// POPA with respective loads from the stack.
+    /// Replace each PUSHA instruction with stores of the registers in `saved` for its label which
+    /// are defined within the section, and each POPA with respective loads from the stack.  If
+    /// `saved` is `None` every register defined within the section is saved.  The locals base
+    /// register is always saved.
     ///
-    /// Typically there will be only one of each but the code here allows for nested sections or
-    /// even overlapping sections.
-    pub(crate) fn emit_pusha_popa(mut self) -> Self {
+    /// A section extends from its PUSHA to the end of the function, as a function will POPA both
+    /// before it returns and before each of its tail calls.  There may be more than one section
+    /// but each is expected to have a distinct label.
+    pub(crate) fn emit_pusha_popa(
+        mut self,
+        saved: Option<&HashMap<Label, BTreeSet<AllocatedRegister>>>,
+    ) -> Self {
         // Gather the sets of used registers per section.  Using a fold here because it's actually
         // simpler to manage.  We use a HashSet to keep track of the active section labels and then
         // build a HashMap of Label to HashSet of registers.
@@ -50,30 +110,30 @@ impl AllocatedAbstractInstructionSet {
             .fold(
                 (IndexMap::new(), IndexSet::new()),
                 |(mut reg_sets, mut active_sets), op| {
-                    let reg = match &op.opcode {
+                    let regs = match &op.opcode {
                         Either::Right(ControlFlowOp::PushAll(label)) => {
                             active_sets.insert(*label);
-                            None
-                        }
-                        Either::Right(ControlFlowOp::PopAll(label)) => {
-                            active_sets.swap_remove(label);
-                            None
+                            reg_sets.entry(*label).or_insert_with(BTreeSet::new);
+                            BTreeSet::new()
                         }
-
-                        Either::Left(alloc_op) => alloc_op.def_registers().into_iter().next(),
-                        Either::Right(ctrl_op) => ctrl_op.def_registers().into_iter().next(),
+                        Either::Left(alloc_op) => alloc_op.def_registers(),
+                        Either::Right(ctrl_op) => ctrl_op.def_registers(),
                     };
 
-                    if let Some(reg) = reg {
-                        for active_label in active_sets.clone() {
-                            reg_sets
-                                .entry(active_label)
-                                .and_modify(|regs: &mut BTreeSet<AllocatedRegister>| {
-                                    regs.insert(reg.clone());
-                                })
-                                .or_insert_with(|| {
-                                    BTreeSet::from_iter(std::iter::once(reg).cloned())
-                                });
+                    for reg in regs {
+                        for active_label in &active_sets {
+                            let is_saved = match saved {
+                                Some(saved) => saved
+                                    .get(active_label)
+                                    .is_some_and(|saved| saved.contains(reg)),
+                                None => true,
+                            };
+                            if is_saved {
+                                reg_sets
+                                    .entry(*active_label)
+                                    .or_insert_with(BTreeSet::new)
+                                    .insert(reg.clone());
+                            }
                         }
                     }
 
@@ -401,7 +461,7 @@ impl AllocatedAbstractInstructionSet {
     }
 
     // Instruction size in units of 32b.
-    fn instruction_size(op: &AllocatedAbstractOp, data_section: &DataSection) -> u64 {
+    pub(super) fn instruction_size(op: &AllocatedAbstractOp, data_section: &DataSection) -> u64 {
         use ControlFlowOp::*;
         match op.opcode {
             Either::Right(Label(_)) => 0,
@@ -656,3 +716,151 @@ impl std::fmt::Display for AllocatedAbstractInstructionSet {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reg(idx: u8) -> AllocatedRegister {
+        AllocatedRegister::Allocated(idx)
+    }
+
+    fn cf(opcode: ControlFlowOp<AllocatedRegister>) -> AllocatedAbstractOp {
+        AllocatedAbstractOp {
+            opcode: Either::Right(opcode),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    /// A function labelled `push_label`, or an entry if there is none, which calls the labels in
+    /// `calls` with the given registers live across the calls and tail calls `tail_calls`.
+    fn function(
+        push_label: Option<usize>,
+        calls: &[(usize, &[u8])],
+        tail_calls: &[usize],
+    ) -> AllocatedFunction {
+        let ops = push_label
+            .map(|label| cf(ControlFlowOp::PushAll(Label(label))))
+            .into_iter()
+            .chain(
+                tail_calls
+                    .iter()
+                    .map(|label| cf(ControlFlowOp::Jump(Label(*label)))),
+            )
+            .collect();
+        AllocatedFunction {
+            ops: AllocatedAbstractInstructionSet { ops },
+            spills: 0,
+            calls: calls
+                .iter()
+                .map(|(label, live)| (Label(*label), live.iter().copied().map(reg).collect()))
+                .collect(),
+        }
+    }
+
+    fn saved(functions: &[AllocatedFunction], label: usize) -> Vec<AllocatedRegister> {
+        callee_saved_registers(functions)
+            .get(&Label(label))
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn callees_save_the_registers_live_across_calls() {
+        let functions = [
+            function(None, &[(1, &[0, 1])], &[]),
+            function(Some(1), &[], &[]),
+        ];
+        assert_eq!(saved(&functions, 1), [reg(0), reg(1)]);
+    }
+
+    #[test]
+    fn callees_save_the_registers_of_their_callers() {
+        let functions = [
+            function(None, &[(1, &[0])], &[]),
+            function(Some(1), &[(2, &[1])], &[]),
+            function(Some(2), &[], &[]),
+        ];
+        assert_eq!(saved(&functions, 1), [reg(0)]);
+        assert_eq!(saved(&functions, 2), [reg(0), reg(1)]);
+    }
+
+    #[test]
+    fn tail_callees_save_the_registers_of_their_callers_only() {
+        // The jump to label 3 is within the function and not a tail call.
+        let functions = [
+            function(None, &[(1, &[0])], &[]),
+            function(Some(1), &[], &[2, 3]),
+            function(Some(2), &[], &[]),
+        ];
+        assert_eq!(saved(&functions, 2), [reg(0)]);
+        assert!(saved(&functions, 3).is_empty());
+    }
+
+    #[test]
+    fn recursive_callees_save_the_registers_of_every_call() {
+        let functions = [
+            function(None, &[(1, &[0])], &[]),
+            function(Some(1), &[(2, &[1])], &[]),
+            function(Some(2), &[(1, &[2])], &[2]),
+        ];
+        assert_eq!(saved(&functions, 1), [reg(0), reg(1), reg(2)]);
+        assert_eq!(saved(&functions, 2), [reg(0), reg(1), reg(2)]);
+    }
+
+    #[test]
+    fn only_saved_registers_are_pushed() {
+        let movi = |idx| AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::MOVI(
+                reg(idx),
+                VirtualImmediate18 { value: 1 },
+            )),
+            comment: String::new(),
+            owning_span: None,
+        };
+        let ops = AllocatedAbstractInstructionSet {
+            ops: vec![
+                cf(ControlFlowOp::PushAll(Label(1))),
+                movi(0),
+                movi(1),
+                cf(ControlFlowOp::PopAll(Label(1))),
+            ],
+        };
+        let emit = |saved| {
+            ops.clone()
+                .emit_pusha_popa(saved)
+                .ops
+                .iter()
+                .map(|op| op.opcode.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // The high mask always saves the locals base register.
+        let saved = HashMap::from([(Label(1), BTreeSet::from([reg(1), reg(2)]))]);
+        assert_eq!(
+            emit(Some(&saved)),
+            [
+                "pshl i2",
+                "pshh i524288",
+                "movi $r0 i1",
+                "movi $r1 i1",
+                "poph i524288",
+                "popl i2"
+            ]
+        );
+        assert_eq!(
+            emit(None),
+            [
+                "pshl i3",
+                "pshh i524288",
+                "movi $r0 i1",
+                "movi $r1 i1",
+                "poph i524288",
+                "popl i3"
+            ]
+        );
+    }
+}
@@ -4,8 +4,11 @@ use std::{
     collections::BTreeMap,
     fmt::{self, Write},
     iter::repeat,
+    ops::Range,
 };
 
+use super::compiler_constants::TWELVE_BITS;
+
 // An entry in the data section.  It's important for the size to be correct, especially for unions
 // where the size could be larger than the represented value.
 #[derive(Clone, Debug)]
@@ -183,6 +186,17 @@ impl Entry {
         // allowed to merge the two entries (if their values are equivalent of course).
         equiv_data(&self.value, &entry.value) && self.name == entry.name
     }
+
+    /// Whether the entries are both referenced by address and hold the same bytes, regardless of
+    /// the types of their values.  Configuration-time constants are never considered the same,
+    /// as they're patched individually.
+    pub(crate) fn same_bytes(&self, entry: &Entry) -> bool {
+        self.name.is_none()
+            && entry.name.is_none()
+            && !self.has_copy_type()
+            && !entry.has_copy_type()
+            && self.to_bytes() == entry.to_bytes()
+    }
 }
 
 /// An address which refers to a value in the data section of the asm.
@@ -200,6 +214,22 @@ pub struct DataSection {
     /// the data to be put in the data section of the asm
     pub value_pairs: Vec<Entry>,
     pub config_map: BTreeMap<String, u32>,
+    /// Whether to also share an entry between values of different types which have the same
+    /// bytes.  See [Entry::same_bytes].
+    pub(crate) merge_same_bytes: bool,
+    /// Whether to let entries share bytes with those already in the data section, and to pack
+    /// byte entries together into words.  See [DataSection::lay_out].
+    pub(crate) overlap_and_pack: bool,
+    /// The offset in bytes of each entry in `value_pairs`.
+    pub(crate) offsets: Vec<usize>,
+    /// The bytes of the data section as laid out so far.  Always word aligned.
+    pub(crate) bytes: Vec<u8>,
+    /// The ranges of `bytes` which may not be shared with new entries, as they may yet change,
+    /// either by being patched in the case of configuration-time constants or by packing more
+    /// byte entries into them.
+    pub(crate) unshareable: Vec<Range<usize>>,
+    /// The offset of the last word byte entries were packed into and how many bytes it has left.
+    pub(crate) packed_word: Option<(usize, usize)>,
 }
 
 impl DataSection {
@@ -212,26 +242,11 @@ impl DataSection {
     /// Given a [DataId], calculate the offset _from the beginning of the data section_ to the data
     /// in bytes.
     pub(crate) fn raw_data_id_to_offset(&self, id: u32) -> usize {
-        self.value_pairs
-            .iter()
-            .take(id as usize)
-            .fold(0, |offset, entry| {
-                //entries must be word aligned
-                size_bytes_round_up_to_word_alignment!(offset + entry.to_bytes().len())
-            })
+        self.offsets[id as usize]
     }
 
     pub(crate) fn serialize_to_bytes(&self) -> Vec<u8> {
-        // not the exact right capacity but serves as a lower bound
-        let mut buf = Vec::with_capacity(self.value_pairs.len());
-        for entry in &self.value_pairs {
-            buf.append(&mut entry.to_bytes());
-
-            //entries must be word aligned
-            let aligned_len = size_bytes_round_up_to_word_alignment!(buf.len());
-            buf.extend(vec![0u8; aligned_len - buf.len()]);
-        }
-        buf
+        self.bytes.clone()
     }
 
     /// Returns whether a specific [DataId] value has a copy type (fits in a register).
@@ -264,13 +279,13 @@ impl DataSection {
     /// [DataId].
     pub(crate) fn insert_data_value(&mut self, new_entry: Entry) -> DataId {
         // if there is an identical data value, use the same id
-        match self
-            .value_pairs
-            .iter()
-            .position(|entry| entry.equiv(&new_entry))
-        {
+        match self.value_pairs.iter().position(|entry| {
+            entry.equiv(&new_entry) || (self.merge_same_bytes && entry.same_bytes(&new_entry))
+        }) {
             Some(num) => DataId(num as u32),
             None => {
+                let offset = self.lay_out(&new_entry);
+                self.offsets.push(offset);
                 self.value_pairs.push(new_entry);
                 // the index of the data section where the value is stored
                 DataId((self.value_pairs.len() - 1) as u32)
@@ -278,6 +293,85 @@ impl DataSection {
         }
     }
 
+    /// Place the bytes of a new entry in the data section and return their offset.
+    ///
+    /// Entries are laid out in the order they're inserted, and never move once placed, as
+    /// pointers are appended to the data section while the offsets of the existing entries are
+    /// being realized.  Each entry is word aligned and appended to the end of the data section
+    /// unless `overlap_and_pack` is set, in which case:
+    ///
+    /// - An entry may reuse a word aligned run of bytes already in the data section, or a run at
+    ///   its end which is a prefix of its own bytes, so that e.g. an array which is a prefix of
+    ///   another one takes up no space at all.
+    /// - A byte entry, which is loaded with LB and so needs no alignment, may reuse any byte
+    ///   within the reach of LB, or is otherwise packed into a word along with other bytes.
+    ///
+    /// Configuration-time constants are never shared as they're patched after compilation.
+    fn lay_out(&mut self, entry: &Entry) -> usize {
+        let bytes = entry.to_bytes();
+        if !self.overlap_and_pack || entry.name.is_some() {
+            return self.append_bytes(&bytes, entry.name.is_some());
+        }
+
+        if entry.is_byte() && bytes.len() == 1 {
+            let max_offset = self.bytes.len().min(TWELVE_BITS as usize + 1);
+            if let Some(offset) = (0..max_offset).find(|&offset| {
+                self.bytes[offset] == bytes[0] && self.is_shareable(offset..offset + 1)
+            }) {
+                return offset;
+            }
+
+            let offset = match self.packed_word {
+                Some((word, remaining)) if remaining > 0 => {
+                    if remaining == 1 {
+                        // The word is now full and won't change again.
+                        self.unshareable.retain(|range| range.start != word);
+                    }
+                    self.packed_word = Some((word, remaining - 1));
+                    word + 8 - remaining
+                }
+                _ => {
+                    let word = self.append_bytes(&[0; 8], true);
+                    self.packed_word = Some((word, 7));
+                    word
+                }
+            };
+            self.bytes[offset] = bytes[0];
+            return offset;
+        }
+
+        let end = self.bytes.len();
+        let offset = (0..end)
+            .step_by(8)
+            .find(|&offset| {
+                let overlap = (end - offset).min(bytes.len());
+                self.bytes[offset..offset + overlap] == bytes[..overlap]
+                    && self.is_shareable(offset..offset + overlap)
+            })
+            .unwrap_or(end);
+        let overlap = (end - offset).min(bytes.len());
+        self.append_bytes(&bytes[overlap..], false);
+        offset
+    }
+
+    /// Append word aligned bytes to the end of the data section and return their offset.
+    fn append_bytes(&mut self, bytes: &[u8], unshareable: bool) -> usize {
+        let offset = self.bytes.len();
+        self.bytes.extend_from_slice(bytes);
+        self.bytes
+            .resize(size_bytes_round_up_to_word_alignment!(self.bytes.len()), 0);
+        if unshareable {
+            self.unshareable.push(offset..self.bytes.len());
+        }
+        offset
+    }
+
+    fn is_shareable(&self, range: Range<usize>) -> bool {
+        self.unshareable
+            .iter()
+            .all(|unshareable| range.end <= unshareable.start || unshareable.end <= range.start)
+    }
+
     // If the stored data is Datum::Word, return the inner value.
     pub(crate) fn get_data_word(&self, data_id: &DataId) -> Option<u64> {
         self.value_pairs.get(data_id.0 as usize).and_then(|entry| {
@@ -319,16 +413,125 @@ impl fmt::Display for DataSection {
             }
         }
 
+        // Each entry is followed by where it is placed and whether it shares its bytes with an
+        // earlier entry, then the total size is compared with that of laying out every entry on
+        // its own.
+        let ranges = self
+            .value_pairs
+            .iter()
+            .zip(&self.offsets)
+            .map(|(entry, offset)| *offset..offset + entry.to_bytes().len())
+            .collect::<Vec<_>>();
         let mut data_buf = String::new();
         for (ix, entry) in self.value_pairs.iter().enumerate() {
+            let range = &ranges[ix];
+            let mut placement = format!("offset {}, size {}", range.start, range.len());
+            if let Some(shared_ix) = ranges[..ix]
+                .iter()
+                .position(|prev| prev.start < range.end && range.start < prev.end)
+            {
+                write!(placement, ", shared with {}", DataId(shared_ix as u32))?;
+            }
             writeln!(
                 data_buf,
-                "{} {}",
-                DataId(ix as u32),
-                display_entry(&entry.value)
+                "{:<29} ; {placement}",
+                format!("{} {}", DataId(ix as u32), display_entry(&entry.value))
             )?;
         }
 
+        let unshared_size: usize = ranges
+            .iter()
+            .map(|range| size_bytes_round_up_to_word_alignment!(range.len()))
+            .sum();
+        writeln!(
+            data_buf,
+            ";; {} bytes, {} bytes saved by sharing and packing",
+            self.bytes.len(),
+            unshared_size.saturating_sub(self.bytes.len())
+        )?;
+
         write!(f, ".data:\n{data_buf}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_section() -> DataSection {
+        DataSection {
+            overlap_and_pack: true,
+            ..Default::default()
+        }
+    }
+
+    fn words(words: &[u64]) -> Entry {
+        Entry::new_collection(
+            words
+                .iter()
+                .map(|word| Entry::new_word(*word, None, None))
+                .collect(),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn overlapping_entries() {
+        let mut ds = data_section();
+        let a = ds.insert_data_value(words(&[1, 2, 3]));
+        let b = ds.insert_data_value(words(&[2, 3]));
+        let c = ds.insert_data_value(words(&[3, 4, 5]));
+        let d = ds.insert_data_value(Entry::new_word(5, None, None));
+
+        assert_eq!(ds.data_id_to_offset(&a), 0);
+        assert_eq!(ds.data_id_to_offset(&b), 8);
+        assert_eq!(ds.data_id_to_offset(&c), 16);
+        assert_eq!(ds.data_id_to_offset(&d), 32);
+        assert_eq!(ds.serialize_to_bytes(), words(&[1, 2, 3, 4, 5]).to_bytes());
+    }
+
+    #[test]
+    fn packed_bytes() {
+        let mut ds = data_section();
+        let word = ds.insert_data_value(Entry::new_word(0x0107, None, None));
+        let one = ds.insert_data_value(Entry::new_byte(1, None, None));
+        let two = ds.insert_data_value(Entry::new_byte(2, None, None));
+        let three = ds.insert_data_value(Entry::new_byte(3, None, None));
+
+        assert_eq!(ds.data_id_to_offset(&word), 0);
+        assert_eq!(ds.data_id_to_offset(&one), 6);
+        assert_eq!(ds.data_id_to_offset(&two), 8);
+        assert_eq!(ds.data_id_to_offset(&three), 9);
+        assert_eq!(ds.serialize_to_bytes().len(), 16);
+
+        // An unpacked word may not share the bytes still being packed.
+        let zero = ds.insert_data_value(Entry::new_word(0x0203_0000_0000_0000, None, None));
+        assert_eq!(ds.data_id_to_offset(&zero), 16);
+    }
+
+    #[test]
+    fn configurables_are_not_shared() {
+        let mut ds = data_section();
+        let config = ds.insert_data_value(Entry::new_word(1, Some("C".into()), None));
+        let word = ds.insert_data_value(Entry::new_word(1, None, None));
+        let byte = ds.insert_data_value(Entry::new_byte(1, None, None));
+
+        assert_eq!(ds.data_id_to_offset(&config), 0);
+        assert_eq!(ds.data_id_to_offset(&word), 8);
+        assert_eq!(ds.data_id_to_offset(&byte), 15);
+    }
+
+    #[test]
+    fn unoptimized_layout() {
+        let mut ds = DataSection::default();
+        let a = ds.insert_data_value(words(&[1, 2]));
+        let b = ds.insert_data_value(Entry::new_byte(2, None, None));
+        let c = ds.insert_data_value(Entry::new_word(2, None, None));
+
+        assert_eq!(ds.data_id_to_offset(&a), 0);
+        assert_eq!(ds.data_id_to_offset(&b), 16);
+        assert_eq!(ds.data_id_to_offset(&c), 24);
+        assert_eq!(ds.serialize_to_bytes().len(), 32);
+    }
+}
@@ -16,6 +16,7 @@ use crate::{
     },
     decl_engine::DeclRefFunction,
     metadata::MetadataManager,
+    OptLevel,
 };
 
 use sway_error::{
@@ -28,7 +29,7 @@ use sway_ir::*;
 use sway_types::{span::Span, Spanned};
 
 use either::Either;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub struct FuelAsmBuilder<'ir, 'eng> {
     pub(super) program_kind: ProgramKind,
@@ -58,6 +59,17 @@ pub struct FuelAsmBuilder<'ir, 'eng> {
     // will usually have 0 or 1 entry.
     pub(super) return_ctxs: Vec<(Label, VirtualRegister)>,
 
+    // The start label of the currently compiled function, which labels its saved registers, and
+    // the copy of $reta if the function makes calls.  Used to tear down the call frame both on
+    // return and before a tail call.
+    pub(super) call_frame_ctxs: Vec<(Label, Option<VirtualRegister>)>,
+
+    // The calls in the currently compiled function which are compiled as tail calls.
+    pub(super) tail_calls: HashSet<Value>,
+
+    // Tail calls are only compiled, and $reta only saved when needed, when optimizing.
+    pub(super) optimization_level: OptLevel,
+
     // Stack size and base register for locals and num_extra_args in any call in the function.
     pub(super) locals_ctxs: Vec<(u64, VirtualRegister, u64)>,
 
@@ -112,6 +124,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         data_section: DataSection,
         reg_seqr: RegisterSequencer,
         context: &'ir Context<'eng>,
+        optimization_level: OptLevel,
     ) -> Self {
         FuelAsmBuilder {
             program_kind,
@@ -123,6 +136,9 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             ptr_map: HashMap::new(),
             phi_reg_map: HashMap::new(),
             return_ctxs: Vec::new(),
+            call_frame_ctxs: Vec::new(),
+            tail_calls: HashSet::new(),
+            optimization_level,
             locals_ctxs: Vec::new(),
             context,
             md_mgr: MetadataManager::default(),
@@ -212,7 +228,19 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 InstOp::BinaryOp { op, arg1, arg2 } => {
                     self.compile_binary_op(instr_val, op, arg1, arg2)
                 }
+                InstOp::Branch(to_block)
+                    if to_block
+                        .args
+                        .iter()
+                        .any(|arg| self.tail_calls.contains(arg)) =>
+                {
+                    // The tail call has already returned to our caller.
+                    Ok(())
+                }
                 InstOp::Branch(to_block) => self.compile_branch(to_block),
+                InstOp::Call(func, args) if self.tail_calls.contains(instr_val) => {
+                    self.compile_tail_call(instr_val, func, args)
+                }
                 InstOp::Call(func, args) => self.compile_call(instr_val, func, args),
                 InstOp::CastPtr(val, _ty) => self.compile_no_op_move(instr_val, val),
                 InstOp::Cmp(pred, lhs_value, rhs_value) => {
@@ -328,7 +356,10 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
                 InstOp::Nop => Ok(()),
                 InstOp::PtrToInt(ptr_val, _int_ty) => self.compile_no_op_move(instr_val, ptr_val),
                 InstOp::Ret(ret_val, ty) => {
-                    if func_is_entry {
+                    if self.tail_calls.contains(ret_val) {
+                        // The tail call has already returned to our caller.
+                        Ok(())
+                    } else if func_is_entry {
                         self.compile_ret_from_entry(instr_val, ret_val, ty)
                     } else {
                         self.compile_ret_from_call(instr_val, ret_val)
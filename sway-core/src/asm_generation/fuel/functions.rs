@@ -11,6 +11,7 @@ use crate::{
     },
     decl_engine::DeclRef,
     fuel_prelude::fuel_asm::GTFArgs,
+    OptLevel,
 };
 
 use sway_ir::*;
@@ -24,6 +25,8 @@ use sway_types::{Ident, Span};
 
 use super::{compiler_constants::NUM_ARG_REGISTERS, data_section::DataId};
 
+use std::collections::HashSet;
+
 /// A summary of the adopted calling convention:
 ///
 /// - Function arguments are passed left to right in the reserved registers.  Extra args are passed
@@ -61,6 +64,18 @@ use super::{compiler_constants::NUM_ARG_REGISTERS, data_section::DataId};
 /// is used to point to the stack location of the remaining arguments.
 /// Stack space for the extra arguments is allocated in the caller when
 /// locals of the caller are allocated.
+///
+/// A callee only saves the general purpose registers it defines which are live across some call
+/// to it, or which its callers must preserve in turn.  A function which makes no calls leaves
+/// $reta untouched and does not save it.
+///
+/// A call whose result is immediately returned is compiled as a tail call when the caller has
+/// no stack allocated locals and all args fit in registers.  The caller tears down its call
+/// frame, restoring $reta and its saved registers, and then jumps to the callee, which returns
+/// directly to the caller's caller.
+///
+/// Both of these only apply when optimizing.  Unoptimized functions save $reta and every register
+/// they define, and make no tail calls.
 impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
     pub(super) fn compile_call(
         &mut self,
@@ -171,6 +186,92 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         Ok(())
     }
 
+    /// Compile a call in tail position as a jump to the callee, after tearing down our own call
+    /// frame, so that the callee returns directly to our caller.
+    pub(super) fn compile_tail_call(
+        &mut self,
+        instr_val: &Value,
+        function: &Function,
+        args: &[Value],
+    ) -> Result<(), CompileError> {
+        // Put the args into the args registers.  Tail calls never pass args on the stack.
+        for (idx, arg_val) in args.iter().enumerate() {
+            let arg_reg = self.value_to_register(arg_val)?;
+            self.cur_bytecode.push(Op::register_move(
+                VirtualRegister::Constant(ConstantRegister::ARG_REGS[idx]),
+                arg_reg,
+                format!("pass arg {idx}"),
+                self.md_mgr.val_to_span(self.context, *arg_val),
+            ));
+        }
+
+        self.drop_call_frame();
+
+        let (fn_label, _) = self.func_to_labels(function);
+        self.cur_bytecode.push(Op {
+            opcode: Either::Right(OrganizationalOp::Jump(fn_label)),
+            comment: format!("tail call {}", function.get_name(self.context)),
+            owning_span: self.md_mgr.val_to_span(self.context, *instr_val),
+        });
+
+        Ok(())
+    }
+
+    /// Find the calls in `function` which can be compiled as tail calls.  These are the calls
+    /// whose result is immediately returned, possibly via a return block, and whose args are all
+    /// passed in registers.
+    ///
+    /// Our call frame is freed before a tail call, so nothing may be allocated on the stack by
+    /// the function, as the args could point to it.
+    fn find_tail_calls(&self, function: Function, locals_size_bytes: u64) -> HashSet<Value> {
+        if locals_size_bytes != 0 {
+            return HashSet::new();
+        }
+
+        let mut tail_calls = HashSet::new();
+        for block in function.block_iter(self.context) {
+            let mut insts = block.instruction_iter(self.context).peekable();
+            while let Some(inst) = insts.next() {
+                match &inst.get_instruction(self.context).unwrap().op {
+                    InstOp::AsmBlock(asm, _)
+                        if asm.body.iter().any(|asm_inst| {
+                            matches!(asm_inst.op_name.as_str(), "cfe" | "cfei")
+                        }) =>
+                    {
+                        return HashSet::new();
+                    }
+                    InstOp::Call(_, args)
+                        if args.len() <= compiler_constants::NUM_ARG_REGISTERS as usize
+                            && insts.peek().is_some_and(|next| self.returns(*next, inst)) =>
+                    {
+                        tail_calls.insert(inst);
+                    }
+                    _otherwise => (),
+                }
+            }
+        }
+        tail_calls
+    }
+
+    /// Whether `inst` immediately returns `val`, either directly or by branching to a block which
+    /// immediately returns its only arg.
+    fn returns(&self, inst: Value, val: Value) -> bool {
+        match &inst.get_instruction(self.context).unwrap().op {
+            InstOp::Ret(ret_val, _) => *ret_val == val,
+            InstOp::Branch(BranchToWithArgs { block, args }) => {
+                args.as_slice() == [val]
+                    && block.num_args(self.context) == 1
+                    && block
+                        .instruction_iter(self.context)
+                        .next()
+                        .is_some_and(|first| {
+                            self.returns(first, block.get_arg(self.context, 0).unwrap())
+                        })
+            }
+            _otherwise => false,
+        }
+    }
+
     pub(super) fn compile_ret_from_call(
         &mut self,
         instr_val: &Value,
@@ -257,22 +358,43 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         let locals_alloc_result = self.alloc_locals(function);
 
         if func_is_entry {
-            self.compile_external_args(function)
+            self.compile_external_args(function, test_decl_ref.is_some())
                 .map_err(|e| handler.emit_err(e))?
         } else {
             // Make copies of the arg registers.
             self.compile_fn_call_args(function)
         }
 
-        let reta = self.reg_seqr.next(); // XXX only do this if this function makes calls
         if !func_is_entry {
-            // Save $reta and $retv
-            self.cur_bytecode.push(Op::register_move(
-                reta.clone(),
-                VirtualRegister::Constant(ConstantRegister::CallReturnAddress),
-                "save reta",
-                None,
-            ));
+            // Save $reta, unless this is a leaf function (or only makes tail calls) which leaves
+            // it untouched, and $retv.  Unoptimized functions make no tail calls and always save
+            // $reta.
+            let optimize_calls = self.optimization_level != OptLevel::Opt0;
+            if optimize_calls {
+                self.tail_calls = self.find_tail_calls(function, locals_alloc_result.0);
+            }
+            let makes_calls = !optimize_calls
+                || function.instruction_iter(self.context).any(|(_, inst)| {
+                    matches!(
+                        inst.get_instruction(self.context),
+                        Some(Instruction {
+                            op: InstOp::Call(..),
+                            ..
+                        })
+                    ) && !self.tail_calls.contains(&inst)
+                });
+            let reta = makes_calls.then(|| {
+                let reta = self.reg_seqr.next();
+                self.cur_bytecode.push(Op::register_move(
+                    reta.clone(),
+                    VirtualRegister::Constant(ConstantRegister::CallReturnAddress),
+                    "save reta",
+                    None,
+                ));
+                reta
+            });
+            self.call_frame_ctxs.push((start_label, reta));
+
             let retv = self.reg_seqr.next();
             self.cur_bytecode.push(Op::register_move(
                 retv.clone(),
@@ -296,31 +418,25 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             self.compile_block(handler, block, func_is_entry)?;
         }
 
-        if !func_is_entry {
+        // A function which only ever returns via tail calls needs no epilogue.
+        let only_tail_calls_return = !self.tail_calls.is_empty()
+            && !function.instruction_iter(self.context).any(|(_, inst)| {
+                matches!(
+                    inst.get_instruction(self.context),
+                    Some(Instruction {
+                        op: InstOp::Ret(ret_val, _),
+                        ..
+                    }) if !self.tail_calls.contains(ret_val)
+                )
+            });
+
+        if !func_is_entry && !only_tail_calls_return {
             // Insert the end of function label.
             self.cur_bytecode.push(Op::unowned_jump_label(end_label));
 
-            // Pop the call frame entry.
-            self.return_ctxs.pop();
-
-            // Free our stack allocated locals.  This is unneeded for entries since they will have
-            // actually returned to the calling context via a VM RET.
-            self.drop_locals();
-
-            // Restore $reta.
-            self.cur_bytecode.push(Op::register_move(
-                VirtualRegister::Constant(ConstantRegister::CallReturnAddress),
-                reta,
-                "restore reta",
-                None,
-            ));
-
-            // Restore GP regs.
-            self.cur_bytecode.push(Op {
-                opcode: Either::Right(OrganizationalOp::PopAll(start_label)),
-                comment: "restore all regs".to_owned(),
-                owning_span: None,
-            });
+            // Free our stack allocated locals and restore the registers.  This is unneeded for
+            // entries since they will have actually returned to the calling context via a VM RET.
+            self.drop_call_frame();
 
             // Jump to the return address.
             self.cur_bytecode.push(Op::jump_to_register(
@@ -330,6 +446,13 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             ));
         }
 
+        if !func_is_entry {
+            // Pop the call frame entry.
+            self.return_ctxs.pop();
+            self.call_frame_ctxs.pop();
+            self.tail_calls.clear();
+        }
+
         // Save this function.
         let mut ops = Vec::new();
         ops.append(&mut self.cur_bytecode);
@@ -343,6 +466,32 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
         Ok(())
     }
 
+    /// Free the stack allocated locals of the current function, restore $reta if it was saved and
+    /// restore the general purpose registers.
+    fn drop_call_frame(&mut self) {
+        self.drop_locals();
+
+        let (start_label, reta) = self
+            .call_frame_ctxs
+            .last()
+            .cloned()
+            .expect("Calls guaranteed to save call frame context.");
+        if let Some(reta) = reta {
+            self.cur_bytecode.push(Op::register_move(
+                VirtualRegister::Constant(ConstantRegister::CallReturnAddress),
+                reta,
+                "restore reta",
+                None,
+            ));
+        }
+
+        self.cur_bytecode.push(Op {
+            opcode: Either::Right(OrganizationalOp::PopAll(start_label)),
+            comment: "restore all regs".to_owned(),
+            owning_span: None,
+        });
+    }
+
     fn compile_fn_call_args(&mut self, function: Function) {
         if function.num_args(self.context) <= compiler_constants::NUM_ARG_REGISTERS as usize {
             // All arguments are passed through registers.
@@ -406,7 +555,17 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
     }
 
     // Handle loading the arguments of a contract call
-    fn compile_external_args(&mut self, function: Function) -> Result<(), CompileError> {
+    fn compile_external_args(
+        &mut self,
+        function: Function,
+        is_test: bool,
+    ) -> Result<(), CompileError> {
+        // Tests are always executed as scripts, so their arguments are read from the script data.
+        let program_kind = if is_test {
+            ProgramKind::Script
+        } else {
+            self.program_kind
+        };
         match function.args_iter(self.context).count() {
             // Nothing to do if there are no arguments
             0 => Ok(()),
@@ -416,13 +575,13 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             1 => {
                 let (_, val) = function.args_iter(self.context).next().unwrap();
                 let single_arg_reg = self.reg_seqr.next();
-                match self.program_kind {
+                match program_kind {
                     ProgramKind::Contract => {
                         self.read_args_base_from_frame(&single_arg_reg);
                     }
                     ProgramKind::Library => {} // Nothing to do here
                     ProgramKind::Script | ProgramKind::Predicate => {
-                        if let ProgramKind::Predicate = self.program_kind {
+                        if let ProgramKind::Predicate = program_kind {
                             self.read_args_base_from_predicate_data(&single_arg_reg);
                         } else {
                             self.read_args_base_from_script_data(&single_arg_reg);
@@ -453,7 +612,7 @@ impl<'ir, 'eng> FuelAsmBuilder<'ir, 'eng> {
             // Otherwise, the args are bundled together and pointed to by the base register.
             _ => {
                 let args_base_reg = self.reg_seqr.next();
-                match self.program_kind {
+                match program_kind {
                     ProgramKind::Contract => self.read_args_base_from_frame(&args_base_reg),
                     ProgramKind::Library => return Ok(()), // Nothing to do here
                     ProgramKind::Predicate => {
@@ -979,3 +1138,257 @@ struct InitMutVars {
     var_size: TypeSize,
     data_id: DataId,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        asm_generation::fuel::{data_section::DataSection, register_sequencer::RegisterSequencer},
+        source_map::SourceMap,
+        BuildConfig, BuildTarget,
+    };
+    use fuel_vm::{
+        checked_transaction::builder::TransactionBuilderExt,
+        interpreter::NotSupportedEcal,
+        prelude::{Interpreter, Receipt, TransactionBuilder},
+    };
+    use sway_types::SourceEngine;
+
+    const FUNCTIONS: &str = r#"
+script {
+    fn leaf(a: u64) -> u64 {
+        entry(a: u64):
+        ret u64 a
+    }
+
+    fn direct(a: u64) -> u64 {
+        entry(a: u64):
+        v0 = call leaf(a)
+        ret u64 v0
+    }
+
+    fn via_return_block(a: u64, b: bool) -> u64 {
+        entry(a: u64, b: bool):
+        cbr b, block0(), block1(a)
+
+        block0():
+        v0 = call leaf(a)
+        br block1(v0)
+
+        block1(v1: u64):
+        ret u64 v1
+    }
+
+    fn not_in_tail_position(a: u64) -> u64 {
+        entry(a: u64):
+        v0 = call leaf(a)
+        v1 = add v0, a
+        ret u64 v1
+    }
+
+    fn seven_args(a: u64, b: u64, c: u64, d: u64, e: u64, f: u64, g: u64) -> u64 {
+        entry(a: u64, b: u64, c: u64, d: u64, e: u64, f: u64, g: u64):
+        ret u64 g
+    }
+
+    fn stack_args(a: u64) -> u64 {
+        entry(a: u64):
+        v0 = call seven_args(a, a, a, a, a, a, a)
+        ret u64 v0
+    }
+
+    fn frame_asm(a: u64) -> u64 {
+        entry(a: u64):
+        v0 = asm() {
+            cfei   i8
+        }
+        v1 = call leaf(a)
+        ret u64 v1
+    }
+}
+"#;
+
+    /// The callees of the tail calls found in the function named `name`.
+    fn tail_callees(name: &str, locals_size_bytes: u64) -> Vec<String> {
+        let source_engine = SourceEngine::default();
+        let context =
+            sway_ir::parser::parse(FUNCTIONS, &source_engine, ExperimentalFlags::default())
+                .unwrap();
+        let builder = FuelAsmBuilder::new(
+            ProgramKind::Script,
+            DataSection::default(),
+            RegisterSequencer::new(),
+            &context,
+            OptLevel::Opt1,
+        );
+        let function = context
+            .module_iter()
+            .flat_map(|module| module.function_iter(&context))
+            .find(|function| function.get_name(&context) == name)
+            .unwrap();
+
+        let mut callees = builder
+            .find_tail_calls(function, locals_size_bytes)
+            .into_iter()
+            .map(|call| match &call.get_instruction(&context).unwrap().op {
+                InstOp::Call(callee, _) => callee.get_name(&context).to_string(),
+                _ => unreachable!("only calls are tail calls"),
+            })
+            .collect::<Vec<_>>();
+        callees.sort();
+        callees
+    }
+
+    #[test]
+    fn returned_calls_are_tail_calls() {
+        assert_eq!(tail_callees("direct", 0), ["leaf"]);
+        assert_eq!(tail_callees("via_return_block", 0), ["leaf"]);
+    }
+
+    #[test]
+    fn calls_whose_result_is_used_are_not_tail_calls() {
+        assert!(tail_callees("not_in_tail_position", 0).is_empty());
+    }
+
+    #[test]
+    fn calls_passing_args_on_the_stack_are_not_tail_calls() {
+        assert!(tail_callees("stack_args", 0).is_empty());
+    }
+
+    #[test]
+    fn functions_with_locals_make_no_tail_calls() {
+        // The args of the call could point to the locals, which are freed before a tail call.
+        assert!(tail_callees("direct", 8).is_empty());
+        assert!(tail_callees("frame_asm", 0).is_empty());
+    }
+
+    const RECURSION: &str = r#"
+script {
+    entry fn main() -> u64 {
+        entry():
+        v0 = const u64 100000
+        v1 = const u64 0
+        v2 = call count(v0, v1)
+        v3 = const u64 10001
+        v4 = call is_odd(v3)
+        v5 = const u64 1000
+        v6 = call sum(v5)
+        v7 = const u64 7
+        v8 = const u64 11
+        v9 = call sum(v7)
+        v10 = add v2, v4
+        v11 = add v10, v6
+        v12 = mul v9, v8
+        v13 = add v11, v12
+        ret u64 v13
+    }
+
+    fn count(n: u64, acc: u64) -> u64 {
+        entry(n: u64, acc: u64):
+        v0 = const u64 0
+        v1 = cmp eq n v0
+        cbr v1, block0(), block1()
+
+        block0():
+        ret u64 acc
+
+        block1():
+        v2 = const u64 1
+        v3 = sub n, v2
+        v4 = add acc, v2
+        v5 = call count(v3, v4)
+        ret u64 v5
+    }
+
+    fn is_even(n: u64) -> u64 {
+        entry(n: u64):
+        v0 = const u64 0
+        v1 = cmp eq n v0
+        cbr v1, block0(), block1()
+
+        block0():
+        v2 = const u64 1
+        ret u64 v2
+
+        block1():
+        v3 = const u64 1
+        v4 = sub n, v3
+        v5 = call is_odd(v4)
+        ret u64 v5
+    }
+
+    fn is_odd(n: u64) -> u64 {
+        entry(n: u64):
+        v0 = const u64 0
+        v1 = cmp eq n v0
+        cbr v1, block0(), block1()
+
+        block0():
+        ret u64 v0
+
+        block1():
+        v2 = const u64 1
+        v3 = sub n, v2
+        v4 = call is_even(v3)
+        ret u64 v4
+    }
+
+    fn sum(n: u64) -> u64 {
+        entry(n: u64):
+        v0 = const u64 0
+        v1 = cmp eq n v0
+        cbr v1, block0(), block1()
+
+        block0():
+        ret u64 v0
+
+        block1():
+        v2 = const u64 1
+        v3 = sub n, v2
+        v4 = call sum(v3)
+        v5 = add v4, n
+        ret u64 v5
+    }
+}
+"#;
+
+    /// Compiles the IR script `ir` at `optimization_level`, runs it and returns its result.
+    fn run(ir: &str, optimization_level: OptLevel) -> u64 {
+        let source_engine = SourceEngine::default();
+        let context =
+            sway_ir::parser::parse(ir, &source_engine, ExperimentalFlags::default()).unwrap();
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            "/script/src/main.sw".into(),
+            "/script".into(),
+            BuildTarget::Fuel,
+        )
+        .with_optimization_level(optimization_level);
+
+        let handler = Handler::default();
+        let mut asm = compile_ir_to_asm(&handler, &context, Some(&build_config)).unwrap();
+        let bytecode = asm
+            .to_bytecode_mut(&handler, &mut SourceMap::new(), &source_engine)
+            .unwrap()
+            .bytecode;
+
+        let mut builder = TransactionBuilder::script(bytecode, vec![]);
+        builder.script_gas_limit(10_000_000).add_random_fee_input();
+        let tx = builder.finalize_checked(0.into());
+        let mut interpreter: Interpreter<_, _, NotSupportedEcal> =
+            Interpreter::with_memory_storage();
+        let transition = interpreter.transact(tx).unwrap();
+        match transition.receipts() {
+            [Receipt::Return { val, .. }, ..] => *val,
+            receipts => panic!("the script did not return: {receipts:?}"),
+        }
+    }
+
+    #[test]
+    fn recursive_calls_run_at_every_optimization_level() {
+        // 100000 tail calls to `count`, `is_odd(10001)`, `sum(1000)` and `sum(7) * 11`, the last
+        // relying on the registers of `main` being preserved across the calls.
+        let expected = 100_000 + 1 + 500_500 + 28 * 11;
+        assert_eq!(run(RECURSION, OptLevel::Opt0), expected);
+        assert_eq!(run(RECURSION, OptLevel::Opt1), expected);
+    }
+}
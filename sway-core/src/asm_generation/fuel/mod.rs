@@ -6,6 +6,8 @@ pub(super) mod abstract_instruction_set;
 pub(super) mod allocated_abstract_instruction_set;
 pub(super) mod checks;
 pub(super) mod fuel_asm_builder;
+pub(super) mod outlining;
+pub(super) mod peephole;
 pub(super) mod register_sequencer;
 
 mod analyses;
@@ -66,7 +66,8 @@ impl AbstractInstructionSet {
             ) {
                 match reg_contents.get(opd1) {
                     Some(RegContents::Constant(c1)) => {
-                        reg_contents.insert(dest.clone(), RegContents::Constant(c1 + c2));
+                        reg_contents
+                            .insert(dest.clone(), RegContents::Constant(c1.wrapping_add(c2)));
                         record_new_def(latest_version, dest);
                     }
                     Some(RegContents::BaseOffset(base_reg, offset))
@@ -74,7 +75,7 @@ impl AbstractInstructionSet {
                     {
                         reg_contents.insert(
                             dest.clone(),
-                            RegContents::BaseOffset(base_reg.clone(), offset + c2),
+                            RegContents::BaseOffset(base_reg.clone(), offset.wrapping_add(c2)),
                         );
                         record_new_def(latest_version, dest);
                     }
@@ -107,7 +108,12 @@ impl AbstractInstructionSet {
                     VirtualOp::MUL(dest, opd1, opd2) => {
                         match (reg_contents.get(opd1), reg_contents.get(opd2)) {
                             (Some(RegContents::Constant(c1)), Some(RegContents::Constant(c2))) => {
-                                reg_contents.insert(dest.clone(), RegContents::Constant(c1 * c2));
+                                // The VM either wraps or panics on an overflow, in which case
+                                // the result is never used.
+                                reg_contents.insert(
+                                    dest.clone(),
+                                    RegContents::Constant(c1.wrapping_mul(*c2)),
+                                );
                                 record_new_def(&mut latest_version, dest);
                             }
                             _ => {
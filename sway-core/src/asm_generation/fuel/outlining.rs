@@ -0,0 +1,503 @@
+//! Outlining of repeated sequences of instructions, to reduce the size of the bytecode.
+//!
+//! A sequence of instructions which is repeated often enough is moved into a subroutine of its
+//! own and each of its occurrences is replaced with a call to it.  The subroutines don't follow
+//! the calling convention of functions: their return address is passed in the scratch register,
+//! which is otherwise only used within short straight sequences of instructions, and nothing is
+//! saved or restored.
+
+use either::Either;
+use rustc_hash::FxHashMap;
+
+use crate::asm_lang::{
+    allocated_ops::{AllocatedOpcode, AllocatedRegister},
+    AllocatedAbstractOp, ConstantRegister, ControlFlowOp,
+};
+
+use super::{
+    allocated_abstract_instruction_set::AllocatedAbstractInstructionSet, compiler_constants,
+    data_section::DataSection, register_sequencer::RegisterSequencer,
+};
+
+/// The number of instructions of the shortest and longest sequences which may be outlined.
+const MIN_SEQUENCE_LEN: usize = 2;
+const MAX_SEQUENCE_LEN: usize = 32;
+
+/// The size of a call to an outlined sequence: setting the return address takes three
+/// instructions, followed by the jump.
+const CALL_SIZE: u64 = 4;
+
+/// The size of the jump back from an outlined sequence.
+const RETURN_SIZE: u64 = 1;
+
+const SCRATCH: AllocatedRegister = AllocatedRegister::Constant(ConstantRegister::Scratch);
+
+/// The occurrences of a sequence of instructions which are worth outlining.
+struct Candidate {
+    len: usize,
+    // The index of the function and of the first instruction of each occurrence, in order.
+    occurrences: Vec<(usize, usize)>,
+}
+
+/// Outline the sequences of instructions which are repeated within or across functions, for as
+/// long as it reduces the size of the program.  The outlined sequences are appended to the
+/// functions.
+pub(crate) fn outline_repeated_sequences(
+    functions: &mut Vec<AllocatedAbstractInstructionSet>,
+    reg_seqr: &mut RegisterSequencer,
+    data_section: &DataSection,
+) {
+    // A far call is made by loading its destination into the scratch register, which would
+    // overwrite the return address.  So only programs where every call is near are outlined.
+    let program_size: u64 = functions
+        .iter()
+        .flat_map(|function| function.ops.iter())
+        .map(|op| AllocatedAbstractInstructionSet::instruction_size(op, data_section))
+        .sum();
+    if program_size > compiler_constants::EIGHTEEN_BITS {
+        return;
+    }
+
+    while let Some(candidate) = best_candidate(functions, data_section) {
+        outline(functions, reg_seqr, candidate);
+    }
+}
+
+/// Find the sequence of instructions whose outlining saves the most space, if any.
+fn best_candidate(
+    functions: &[AllocatedAbstractInstructionSet],
+    data_section: &DataSection,
+) -> Option<Candidate> {
+    // Identify the instructions which may be outlined, using the same ID for equal instructions.
+    let mut op_ids = FxHashMap::<String, u32>::default();
+    let op_keys = functions
+        .iter()
+        .map(|function| {
+            function
+                .ops
+                .iter()
+                .zip(scratch_liveness(&function.ops))
+                .map(|(op, scratch_live)| match &op.opcode {
+                    Either::Left(opcode) if !scratch_live && may_outline(opcode) => {
+                        let next_id = op_ids.len() as u32;
+                        Some(*op_ids.entry(opcode.to_string()).or_insert(next_id))
+                    }
+                    _otherwise => None,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Identify the windows of instructions which may be outlined, using the same ID for equal
+    // windows.  A window is identified by the ID of the window one instruction shorter and its
+    // last instruction, so that each window is indexed once rather than hashed in full.
+    let mut window_ids = FxHashMap::<(Option<usize>, u32), usize>::default();
+    let mut windows: Vec<(usize, Vec<(usize, usize)>)> = Vec::new();
+    for (fn_idx, keys) in op_keys.iter().enumerate() {
+        for start in 0..keys.len() {
+            let mut prefix = None;
+            for (len, key) in (1..=MAX_SEQUENCE_LEN).zip(&keys[start..]) {
+                let Some(key) = key else {
+                    break;
+                };
+                let next_id = windows.len();
+                let id = *window_ids.entry((prefix, *key)).or_insert(next_id);
+                if id == next_id {
+                    windows.push((len, Vec::new()));
+                }
+                windows[id].1.push((fn_idx, start));
+                prefix = Some(id);
+            }
+        }
+    }
+
+    let mut best: Option<(u64, Candidate)> = None;
+    for (len, all_occurrences) in windows {
+        if len < MIN_SEQUENCE_LEN {
+            continue;
+        }
+
+        // Repeated instructions may make the occurrences overlap, keep the first of those.
+        let mut occurrences: Vec<(usize, usize)> = Vec::new();
+        for (fn_idx, start) in all_occurrences {
+            let overlaps = occurrences
+                .last()
+                .is_some_and(|&(last_fn_idx, last_start)| {
+                    last_fn_idx == fn_idx && start < last_start + len
+                });
+            if !overlaps {
+                occurrences.push((fn_idx, start));
+            }
+        }
+        if occurrences.len() < 2 {
+            continue;
+        }
+
+        let (fn_idx, start) = occurrences[0];
+        let size: u64 = functions[fn_idx].ops[start..start + len]
+            .iter()
+            .map(|op| AllocatedAbstractInstructionSet::instruction_size(op, data_section))
+            .sum();
+        let count = occurrences.len() as u64;
+        let inlined_size = count * size;
+        let outlined_size = count * CALL_SIZE + size + RETURN_SIZE;
+        if inlined_size <= outlined_size {
+            continue;
+        }
+        let saved = inlined_size - outlined_size;
+        if best
+            .as_ref()
+            .is_some_and(|(best_saved, _)| *best_saved >= saved)
+        {
+            continue;
+        }
+        best = Some((saved, Candidate { len, occurrences }));
+    }
+
+    best.map(|(_, candidate)| candidate)
+}
+
+/// Move the sequence into a new subroutine and replace its occurrences with calls to it.
+fn outline(
+    functions: &mut Vec<AllocatedAbstractInstructionSet>,
+    reg_seqr: &mut RegisterSequencer,
+    Candidate { len, occurrences }: Candidate,
+) {
+    let label = reg_seqr.get_label();
+    let (fn_idx, start) = occurrences[0];
+    let mut ops = vec![AllocatedAbstractOp {
+        opcode: Either::Right(ControlFlowOp::Label(label)),
+        comment: "outlined instructions".into(),
+        owning_span: None,
+    }];
+    ops.extend_from_slice(&functions[fn_idx].ops[start..start + len]);
+    ops.push(AllocatedAbstractOp {
+        opcode: Either::Left(AllocatedOpcode::JMP(SCRATCH)),
+        comment: "return from outlined instructions".into(),
+        owning_span: None,
+    });
+
+    // Replace the last occurrences first, so that the indices of the others are unaffected.
+    for &(fn_idx, start) in occurrences.iter().rev() {
+        let ret_label = reg_seqr.get_label();
+        let owning_span = functions[fn_idx].ops[start].owning_span.clone();
+        functions[fn_idx].ops.splice(
+            start..start + len,
+            [
+                AllocatedAbstractOp {
+                    opcode: Either::Right(ControlFlowOp::SaveRetAddr(SCRATCH, ret_label)),
+                    comment: "set return addr of outlined instructions".into(),
+                    owning_span: None,
+                },
+                AllocatedAbstractOp {
+                    opcode: Either::Right(ControlFlowOp::Call(label)),
+                    comment: "call outlined instructions".into(),
+                    owning_span,
+                },
+                AllocatedAbstractOp {
+                    opcode: Either::Right(ControlFlowOp::Label(ret_label)),
+                    comment: String::new(),
+                    owning_span: None,
+                },
+            ],
+        );
+    }
+
+    functions.push(AllocatedAbstractInstructionSet { ops });
+}
+
+/// Whether an instruction behaves the same wherever it is, and so may be outlined.
+fn may_outline(opcode: &AllocatedOpcode) -> bool {
+    use AllocatedOpcode::*;
+    let is_position_dependent = matches!(
+        opcode,
+        // Control flow.
+        JMP(..)
+            | JI(..)
+            | JNE(..)
+            | JNEI(..)
+            | JNZI(..)
+            | JMPB(..)
+            | JMPF(..)
+            | JNZB(..)
+            | JNZF(..)
+            | RET(..)
+            | RETD(..)
+            | RVRT(..)
+            // Calling a contract or loading code changes the context of the execution.
+            | CALL(..)
+            | LDC(..)
+            // Pseudo instructions.
+            | BLOB(..)
+            | DataSectionOffsetPlaceholder
+            | DataSectionRegisterLoadPlaceholder
+            | Undefined
+    );
+    !is_position_dependent
+        && !opcode.registers().iter().any(|reg| {
+            matches!(
+                reg,
+                AllocatedRegister::Constant(
+                    ConstantRegister::Scratch | ConstantRegister::ProgramCounter
+                )
+            )
+        })
+}
+
+/// Whether the scratch register is live before each of the instructions.
+///
+/// The scratch register is never live across control flow, so this is a conservative analysis of
+/// the straight sequences of instructions.
+fn scratch_liveness(ops: &[AllocatedAbstractOp]) -> Vec<bool> {
+    let mut live = false;
+    let mut live_in = vec![false; ops.len()];
+    for (idx, op) in ops.iter().enumerate().rev() {
+        live =
+            match &op.opcode {
+                Either::Left(
+                    AllocatedOpcode::LoadDataId(reg, _) | AllocatedOpcode::MOVI(reg, _),
+                ) if *reg == SCRATCH => false,
+                Either::Left(opcode) => live || opcode.registers().contains(&SCRATCH),
+                Either::Right(ControlFlowOp::SaveRetAddr(reg, _)) if *reg == SCRATCH => false,
+                Either::Right(
+                    ControlFlowOp::Label(_)
+                    | ControlFlowOp::Jump(_)
+                    | ControlFlowOp::JumpIfNotZero(..)
+                    | ControlFlowOp::Call(_),
+                ) => false,
+                Either::Right(_) => live,
+            };
+        live_in[idx] = live;
+    }
+    live_in
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm_lang::{
+        virtual_ops::Label, VirtualImmediate12, VirtualImmediate18, VirtualImmediate24,
+    };
+    use AllocatedOpcode::*;
+
+    fn reg(idx: u8) -> AllocatedRegister {
+        AllocatedRegister::Allocated(idx)
+    }
+
+    fn op(opcode: AllocatedOpcode) -> AllocatedAbstractOp {
+        AllocatedAbstractOp {
+            opcode: Either::Left(opcode),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    fn label(idx: usize) -> AllocatedAbstractOp {
+        AllocatedAbstractOp {
+            opcode: Either::Right(ControlFlowOp::Label(Label(idx))),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    /// An instruction which may be outlined, different for every `idx`.
+    fn add(idx: u8) -> AllocatedAbstractOp {
+        op(ADD(reg(idx), reg(idx), reg(idx)))
+    }
+
+    /// A sequence of `len` instructions which may be outlined, different for every `first`.
+    fn sequence(first: u8, len: u8) -> Vec<AllocatedAbstractOp> {
+        (first..first + len).map(add).collect()
+    }
+
+    fn function(
+        ops: impl IntoIterator<Item = AllocatedAbstractOp>,
+    ) -> AllocatedAbstractInstructionSet {
+        AllocatedAbstractInstructionSet {
+            ops: ops.into_iter().collect(),
+        }
+    }
+
+    fn opcodes(function: &AllocatedAbstractInstructionSet) -> Vec<String> {
+        function
+            .ops
+            .iter()
+            .map(|op| op.opcode.to_string())
+            .collect()
+    }
+
+    fn best(functions: &[AllocatedAbstractInstructionSet]) -> Option<(usize, Vec<(usize, usize)>)> {
+        best_candidate(functions, &DataSection::default())
+            .map(|Candidate { len, occurrences }| (len, occurrences))
+    }
+
+    #[test]
+    fn repeated_sequences_are_outlined_into_a_subroutine() {
+        let mut functions = vec![
+            function([add(100)].into_iter().chain(sequence(0, 10))),
+            function(sequence(0, 10).into_iter().chain([add(101)])),
+        ];
+        outline_repeated_sequences(
+            &mut functions,
+            &mut RegisterSequencer::new(),
+            &DataSection::default(),
+        );
+
+        assert_eq!(functions.len(), 3);
+        let Either::Right(ControlFlowOp::Label(subroutine)) = functions[2].ops[0].opcode else {
+            panic!("the outlined sequence is not labelled");
+        };
+        assert_eq!(
+            opcodes(&functions[2])[1..11],
+            opcodes(&function(sequence(0, 10)))
+        );
+        assert!(matches!(
+            &functions[2].ops[11].opcode,
+            Either::Left(JMP(reg)) if *reg == SCRATCH
+        ));
+
+        assert_eq!(functions[0].ops.len(), 4);
+        assert_eq!(opcodes(&functions[0])[0], add(100).opcode.to_string());
+        assert_calls(&functions[0].ops[1..], subroutine);
+        assert_eq!(functions[1].ops.len(), 4);
+        assert_calls(&functions[1].ops[..3], subroutine);
+        assert_eq!(opcodes(&functions[1])[3], add(101).opcode.to_string());
+    }
+
+    /// Assert that `ops` call the subroutine labelled `subroutine` and return right after.
+    fn assert_calls(ops: &[AllocatedAbstractOp], subroutine: Label) {
+        match ops.iter().map(|op| &op.opcode).collect::<Vec<_>>()[..] {
+            [Either::Right(ControlFlowOp::SaveRetAddr(reg, ret_label)), Either::Right(ControlFlowOp::Call(callee)), Either::Right(ControlFlowOp::Label(after))]
+                if *reg == SCRATCH && *callee == subroutine && after == ret_label => {}
+            _ => panic!(
+                "not a call to {subroutine}: {:?}",
+                opcodes(&function(ops.to_vec()))
+            ),
+        }
+    }
+
+    #[test]
+    fn sequences_are_only_outlined_if_it_saves_space() {
+        // Once, or too short for the calls to be smaller than the sequence.
+        assert!(best(&[function(sequence(0, 10))]).is_none());
+        assert!(best(&[function(sequence(0, 3)), function(sequence(0, 3))]).is_none());
+        // Twice ten instructions take 20, the calls and the subroutine 19.
+        assert_eq!(
+            best(&[function(sequence(0, 10)), function(sequence(0, 10))]),
+            Some((10, vec![(0, 0), (1, 0)]))
+        );
+    }
+
+    #[test]
+    fn the_candidate_saving_the_most_is_outlined_first() {
+        let functions = [
+            function(
+                sequence(0, 10)
+                    .into_iter()
+                    .chain([add(90)])
+                    .chain(sequence(20, 12)),
+            ),
+            function(
+                sequence(20, 12)
+                    .into_iter()
+                    .chain([add(91)])
+                    .chain(sequence(0, 10)),
+            ),
+        ];
+        assert_eq!(best(&functions), Some((12, vec![(0, 11), (1, 0)])));
+    }
+
+    #[test]
+    fn overlapping_occurrences_are_not_outlined() {
+        let ops = std::iter::repeat_with(|| add(0))
+            .take(40)
+            .collect::<Vec<_>>();
+        let (len, occurrences) = best(&[function(ops)]).unwrap();
+        for pair in occurrences.windows(2) {
+            assert!(pair[0].1 + len <= pair[1].1);
+        }
+    }
+
+    #[test]
+    fn windows_with_control_flow_are_not_outlined() {
+        let with = |middle: AllocatedAbstractOp| {
+            let ops = sequence(0, 5)
+                .into_iter()
+                .chain([middle])
+                .chain(sequence(5, 5))
+                .collect::<Vec<_>>();
+            best(&[function(ops.clone()), function(ops)])
+        };
+        assert!(with(label(0)).is_none());
+        assert!(with(op(JMP(reg(0)))).is_none());
+        assert!(with(op(JNEI(reg(0), reg(1), VirtualImmediate12 { value: 0 }))).is_none());
+        assert!(with(add(100)).is_some());
+    }
+
+    #[test]
+    fn windows_where_the_scratch_register_is_live_are_not_outlined() {
+        let with = |last: AllocatedAbstractOp| {
+            let ops = [op(MOVI(SCRATCH, VirtualImmediate18 { value: 1 }))]
+                .into_iter()
+                .chain(sequence(0, 10))
+                .chain([last])
+                .collect::<Vec<_>>();
+            best(&[function(ops.clone()), function(ops)])
+        };
+        assert!(with(op(ADD(reg(0), SCRATCH, reg(0)))).is_none());
+        assert!(with(add(100)).is_some());
+    }
+
+    #[test]
+    fn programs_out_of_range_of_near_calls_are_not_outlined() {
+        let blob = op(BLOB(VirtualImmediate24 {
+            value: compiler_constants::EIGHTEEN_BITS as u32,
+        }));
+        let mut functions = vec![
+            function(sequence(0, 10)),
+            function(sequence(0, 10).into_iter().chain([blob])),
+        ];
+        outline_repeated_sequences(
+            &mut functions,
+            &mut RegisterSequencer::new(),
+            &DataSection::default(),
+        );
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].ops.len(), 10);
+    }
+
+    #[test]
+    fn scratch_register_liveness() {
+        let ops = [
+            op(MOVI(SCRATCH, VirtualImmediate18 { value: 1 })),
+            add(0),
+            op(ADD(reg(0), SCRATCH, reg(0))),
+            add(1),
+        ];
+        assert_eq!(scratch_liveness(&ops), [false, true, true, false]);
+
+        // The scratch register is never live across control flow.
+        let ops = [
+            op(MOVI(SCRATCH, VirtualImmediate18 { value: 1 })),
+            label(0),
+            op(ADD(reg(0), SCRATCH, reg(0))),
+        ];
+        assert_eq!(scratch_liveness(&ops), [false, false, true]);
+    }
+
+    #[test]
+    fn position_dependent_instructions_may_not_be_outlined() {
+        assert!(may_outline(&ADD(reg(0), reg(1), reg(2))));
+        assert!(!may_outline(&JMP(reg(0))));
+        assert!(!may_outline(&RET(reg(0))));
+        assert!(!may_outline(&MOVI(
+            SCRATCH,
+            VirtualImmediate18 { value: 1 }
+        )));
+        assert!(!may_outline(&ADD(
+            reg(0),
+            AllocatedRegister::Constant(ConstantRegister::ProgramCounter),
+            reg(1)
+        )));
+    }
+}
@@ -0,0 +1,406 @@
+//! Peephole optimization of the instructions after register allocation.
+//!
+//! A table of rules is matched against the instructions at each position of a function.  A rule
+//! which matches replaces a short window of instructions with cheaper, equivalent ones, after
+//! which the matching resumes just before the rewritten window so that rewrites may cascade.
+
+use either::Either;
+
+use crate::asm_lang::{
+    allocated_ops::{AllocatedOpcode, AllocatedRegister},
+    AllocatedAbstractOp, ConstantRegister, ControlFlowOp, VirtualImmediate12,
+};
+
+use super::{
+    allocated_abstract_instruction_set::AllocatedAbstractInstructionSet, compiler_constants,
+};
+
+/// The replacement of the first `len` instructions of a window with `ops`.
+struct Rewrite {
+    len: usize,
+    ops: Vec<AllocatedAbstractOp>,
+}
+
+/// A rule is given the instructions from the current position to the end of the function.
+type Rule = fn(&[AllocatedAbstractOp]) -> Option<Rewrite>;
+
+const RULES: &[Rule] = &[
+    redundant_move,
+    fold_addi_chain,
+    use_zero_register,
+    jump_to_next,
+    load_of_stored_value,
+];
+
+const ZERO: AllocatedRegister = AllocatedRegister::Constant(ConstantRegister::Zero);
+
+/// Apply the peephole rules to the instructions of a function until none of them match.
+pub(crate) fn peephole_optimize(
+    mut ops: AllocatedAbstractInstructionSet,
+) -> AllocatedAbstractInstructionSet {
+    let mut idx = 0;
+    while idx < ops.ops.len() {
+        match RULES.iter().find_map(|rule| rule(&ops.ops[idx..])) {
+            Some(Rewrite { len, ops: new_ops }) => {
+                ops.ops.splice(idx..idx + len, new_ops);
+                idx = idx.saturating_sub(1);
+            }
+            None => idx += 1,
+        }
+    }
+    ops
+}
+
+/// `MOVE a a` does nothing, and neither does `MOVE b a` right after `MOVE a b`.
+fn redundant_move(ops: &[AllocatedAbstractOp]) -> Option<Rewrite> {
+    match ops {
+        [AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::MOVE(dst, src)),
+            ..
+        }, ..]
+            if dst == src =>
+        {
+            Some(Rewrite {
+                len: 1,
+                ops: Vec::new(),
+            })
+        }
+        [first @ AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::MOVE(dst0, src0)),
+            ..
+        }, AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::MOVE(dst1, src1)),
+            ..
+        }, ..]
+            if dst0 == src1 && src0 == dst1 =>
+        {
+            Some(Rewrite {
+                len: 2,
+                ops: vec![first.clone()],
+            })
+        }
+        _otherwise => None,
+    }
+}
+
+/// `ADDI a b i; ADDI a a j` is `ADDI a b (i + j)`, as long as the sum fits in the immediate.
+fn fold_addi_chain(ops: &[AllocatedAbstractOp]) -> Option<Rewrite> {
+    match ops {
+        [first @ AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::ADDI(dst0, src0, imm0)),
+            ..
+        }, AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::ADDI(dst1, src1, imm1)),
+            ..
+        }, ..]
+            if dst0 == src1 && dst1 == src1 =>
+        {
+            let sum = imm0.value as u64 + imm1.value as u64;
+            (sum <= compiler_constants::TWELVE_BITS).then(|| Rewrite {
+                len: 2,
+                ops: vec![AllocatedAbstractOp {
+                    opcode: Either::Left(AllocatedOpcode::ADDI(
+                        dst0.clone(),
+                        src0.clone(),
+                        VirtualImmediate12 { value: sum as u16 },
+                    )),
+                    ..first.clone()
+                }],
+            })
+        }
+        _otherwise => None,
+    }
+}
+
+/// A register set by `MOVI r 0` can be replaced with `$zero` wherever it is read, up to where it
+/// is set again.  The register must be set again before any control flow, as it could otherwise
+/// still be read afterwards.
+fn use_zero_register(ops: &[AllocatedAbstractOp]) -> Option<Rewrite> {
+    let [AllocatedAbstractOp {
+        opcode: Either::Left(AllocatedOpcode::MOVI(reg, imm)),
+        ..
+    }, rest @ ..] = ops
+    else {
+        return None;
+    };
+    if imm.value != 0 || matches!(reg, AllocatedRegister::Constant(_)) {
+        return None;
+    }
+
+    let mut new_ops = Vec::new();
+    for op in rest {
+        match &op.opcode {
+            Either::Left(opcode) if is_control_flow(opcode) => return None,
+            Either::Left(opcode) if !opcode.registers().contains(reg) => new_ops.push(op.clone()),
+            Either::Left(opcode) => {
+                new_ops.push(AllocatedAbstractOp {
+                    opcode: Either::Left(replace_use(opcode, reg)?),
+                    ..op.clone()
+                });
+                if opcode.def_registers().contains(reg) {
+                    return Some(Rewrite {
+                        len: new_ops.len() + 1,
+                        ops: new_ops,
+                    });
+                }
+            }
+            Either::Right(ControlFlowOp::Comment) => new_ops.push(op.clone()),
+            Either::Right(_) => return None,
+        }
+    }
+    None
+}
+
+/// A jump to a label which immediately follows it is not needed.
+fn jump_to_next(ops: &[AllocatedAbstractOp]) -> Option<Rewrite> {
+    let [AllocatedAbstractOp {
+        opcode: Either::Right(ControlFlowOp::Jump(label) | ControlFlowOp::JumpIfNotZero(_, label)),
+        ..
+    }, rest @ ..] = ops
+    else {
+        return None;
+    };
+    rest.iter()
+        .map_while(|op| match &op.opcode {
+            Either::Right(ControlFlowOp::Label(next_label)) => Some(Some(next_label)),
+            Either::Right(ControlFlowOp::Comment) => Some(None),
+            _otherwise => None,
+        })
+        .flatten()
+        .any(|next_label| next_label == label)
+        .then(|| Rewrite {
+            len: 1,
+            ops: Vec::new(),
+        })
+}
+
+/// `LW d a i` right after `SW a v i` loads `v`, which can be moved instead.
+fn load_of_stored_value(ops: &[AllocatedAbstractOp]) -> Option<Rewrite> {
+    match ops {
+        [store @ AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::SW(store_addr, value, store_offs)),
+            ..
+        }, load @ AllocatedAbstractOp {
+            opcode: Either::Left(AllocatedOpcode::LW(dst, load_addr, load_offs)),
+            ..
+        }, ..]
+            if store_addr == load_addr && store_offs.value == load_offs.value =>
+        {
+            let mut new_ops = vec![store.clone()];
+            if dst != value {
+                new_ops.push(AllocatedAbstractOp {
+                    opcode: Either::Left(AllocatedOpcode::MOVE(dst.clone(), value.clone())),
+                    ..load.clone()
+                });
+            }
+            Some(Rewrite {
+                len: 2,
+                ops: new_ops,
+            })
+        }
+        _otherwise => None,
+    }
+}
+
+fn is_control_flow(opcode: &AllocatedOpcode) -> bool {
+    use AllocatedOpcode::*;
+    matches!(
+        opcode,
+        JMP(..)
+            | JI(..)
+            | JNE(..)
+            | JNEI(..)
+            | JNZI(..)
+            | JMPB(..)
+            | JMPF(..)
+            | JNZB(..)
+            | JNZF(..)
+            | RET(..)
+            | RETD(..)
+            | RVRT(..)
+            | CALL(..)
+            | LDC(..)
+    )
+}
+
+/// Replace the reads of `reg` by an instruction with reads of `$zero`, leaving its writes as they
+/// are.
+///
+/// Only the common instructions are handled, `None` is returned for the others.
+fn replace_use(opcode: &AllocatedOpcode, reg: &AllocatedRegister) -> Option<AllocatedOpcode> {
+    use AllocatedOpcode::*;
+    let zero = |r: &AllocatedRegister| if r == reg { ZERO } else { r.clone() };
+    Some(match opcode {
+        ADD(d, a, b) => ADD(d.clone(), zero(a), zero(b)),
+        AND(d, a, b) => AND(d.clone(), zero(a), zero(b)),
+        DIV(d, a, b) => DIV(d.clone(), zero(a), zero(b)),
+        EQ(d, a, b) => EQ(d.clone(), zero(a), zero(b)),
+        GT(d, a, b) => GT(d.clone(), zero(a), zero(b)),
+        LT(d, a, b) => LT(d.clone(), zero(a), zero(b)),
+        MOD(d, a, b) => MOD(d.clone(), zero(a), zero(b)),
+        MUL(d, a, b) => MUL(d.clone(), zero(a), zero(b)),
+        OR(d, a, b) => OR(d.clone(), zero(a), zero(b)),
+        SLL(d, a, b) => SLL(d.clone(), zero(a), zero(b)),
+        SRL(d, a, b) => SRL(d.clone(), zero(a), zero(b)),
+        SUB(d, a, b) => SUB(d.clone(), zero(a), zero(b)),
+        XOR(d, a, b) => XOR(d.clone(), zero(a), zero(b)),
+        ADDI(d, a, i) => ADDI(d.clone(), zero(a), i.clone()),
+        MOVE(d, a) => MOVE(d.clone(), zero(a)),
+        NOT(d, a) => NOT(d.clone(), zero(a)),
+        SW(a, v, i) => SW(zero(a), zero(v), i.clone()),
+        SB(a, v, i) => SB(zero(a), zero(v), i.clone()),
+        LW(d, a, i) => LW(d.clone(), zero(a), i.clone()),
+        LB(d, a, i) => LB(d.clone(), zero(a), i.clone()),
+        MOVI(d, i) => MOVI(d.clone(), i.clone()),
+        LoadDataId(d, id) => LoadDataId(d.clone(), id.clone()),
+        _otherwise => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm_lang::{Label, VirtualImmediate18};
+
+    fn reg(idx: u8) -> AllocatedRegister {
+        AllocatedRegister::Allocated(idx)
+    }
+
+    fn imm12(value: u16) -> VirtualImmediate12 {
+        VirtualImmediate12 { value }
+    }
+
+    fn op(opcode: AllocatedOpcode) -> AllocatedAbstractOp {
+        AllocatedAbstractOp {
+            opcode: Either::Left(opcode),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    fn cf(opcode: ControlFlowOp<AllocatedRegister>) -> AllocatedAbstractOp {
+        AllocatedAbstractOp {
+            opcode: Either::Right(opcode),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    fn optimize(ops: Vec<AllocatedAbstractOp>) -> Vec<String> {
+        peephole_optimize(AllocatedAbstractInstructionSet { ops })
+            .ops
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn redundant_moves() {
+        use AllocatedOpcode::*;
+        assert_eq!(
+            optimize(vec![
+                op(MOVE(reg(0), reg(0))),
+                op(MOVE(reg(1), reg(2))),
+                op(MOVE(reg(2), reg(1))),
+                op(MOVE(reg(3), reg(1))),
+            ]),
+            ["move $r1 $r2", "move $r3 $r1"]
+        );
+    }
+
+    #[test]
+    fn addi_chains() {
+        use AllocatedOpcode::*;
+        assert_eq!(
+            optimize(vec![
+                op(ADDI(reg(0), reg(1), imm12(8))),
+                op(ADDI(reg(0), reg(0), imm12(16))),
+                op(ADDI(reg(0), reg(0), imm12(32))),
+                op(ADDI(reg(2), reg(0), imm12(8))),
+                op(ADDI(reg(3), reg(3), imm12(4000))),
+                op(ADDI(reg(3), reg(3), imm12(100))),
+            ]),
+            [
+                "addi $r0 $r1 i56",
+                "addi $r2 $r0 i8",
+                "addi $r3 $r3 i4000",
+                "addi $r3 $r3 i100",
+            ]
+        );
+    }
+
+    #[test]
+    fn movi_zero() {
+        use AllocatedOpcode::*;
+        let movi_zero = || op(MOVI(reg(0), VirtualImmediate18 { value: 0 }));
+        assert_eq!(
+            optimize(vec![
+                movi_zero(),
+                op(SW(reg(1), reg(0), imm12(0))),
+                op(ADD(reg(2), reg(0), reg(1))),
+                op(MOVI(reg(0), VirtualImmediate18 { value: 1 })),
+            ]),
+            ["sw   $r1 $zero i0", "add  $r2 $zero $r1", "movi $r0 i1"]
+        );
+
+        // The register may be read after the jump.
+        assert_eq!(
+            optimize(vec![
+                movi_zero(),
+                op(SW(reg(1), reg(0), imm12(0))),
+                cf(ControlFlowOp::Jump(Label(0))),
+            ])
+            .len(),
+            3
+        );
+
+        // The register may be read when it is set again.
+        assert_eq!(
+            optimize(vec![movi_zero(), op(ADD(reg(0), reg(0), reg(1)))]),
+            ["add  $r0 $zero $r1"]
+        );
+
+        // Not all instructions are handled.
+        assert_eq!(
+            optimize(vec![movi_zero(), op(MCP(reg(1), reg(2), reg(0)))]).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn jumps_to_next() {
+        assert_eq!(
+            optimize(vec![
+                cf(ControlFlowOp::Jump(Label(1))),
+                cf(ControlFlowOp::Comment),
+                cf(ControlFlowOp::Label(Label(0))),
+                cf(ControlFlowOp::Label(Label(1))),
+                cf(ControlFlowOp::JumpIfNotZero(reg(0), Label(0))),
+                op(AllocatedOpcode::NOOP),
+                cf(ControlFlowOp::Label(Label(0))),
+            ])
+            .len(),
+            6
+        );
+    }
+
+    #[test]
+    fn loads_of_stored_values() {
+        use AllocatedOpcode::*;
+        assert_eq!(
+            optimize(vec![
+                op(SW(reg(0), reg(1), imm12(2))),
+                op(LW(reg(2), reg(0), imm12(2))),
+                op(SW(reg(0), reg(1), imm12(3))),
+                op(LW(reg(1), reg(0), imm12(3))),
+                op(LW(reg(3), reg(0), imm12(2))),
+            ]),
+            [
+                "sw   $r0 $r1 i2",
+                "move $r2 $r1",
+                "sw   $r0 $r1 i3",
+                "lw   $r3 $r0 i2",
+            ]
+        );
+    }
+}
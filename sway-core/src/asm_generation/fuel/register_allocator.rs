@@ -1,8 +1,8 @@
 use crate::{
     asm_generation::fuel::{analyses::liveness_analysis, compiler_constants},
     asm_lang::{
-        allocated_ops::AllocatedRegister, virtual_register::*, AllocatedAbstractOp, Op,
-        VirtualImmediate12, VirtualImmediate18, VirtualImmediate24, VirtualOp,
+        allocated_ops::AllocatedRegister, virtual_register::*, AllocatedAbstractOp, ControlFlowOp,
+        Label, Op, VirtualImmediate12, VirtualImmediate18, VirtualImmediate24, VirtualOp,
     },
 };
 
@@ -19,7 +19,10 @@ use sway_error::error::CompileError;
 use sway_ir::size_bytes_round_up_to_word_alignment;
 use sway_types::{FxIndexSet, Span};
 
-use super::allocated_abstract_instruction_set::AllocatedAbstractInstructionSet;
+use super::{
+    allocated_abstract_instruction_set::AllocatedAbstractInstructionSet,
+    register_sequencer::RegisterSequencer,
+};
 
 // Each node in the interference graph represents a VirtualRegister.
 // An edge from V1 -> V2 means that V2 was an open live range at the
@@ -497,13 +500,14 @@ pub(crate) fn color_interference_graph(
             }
         }
 
-        if let Some(&spill_reg_index) = pending.iter().max_by(|&&node1, &&node2| {
-            // At the moment, our spill priority function is just this,
-            // i.e., spill the register with more incoming interferences.
-            // (roughly indicating how long the interval is).
-            get_connected_incoming_neighbors(interference_graph, node1)
-                .count()
-                .cmp(&get_connected_incoming_neighbors(interference_graph, node2).count())
+        if let Some(&spill_reg_index) = pending.iter().max_by_key(|&&node| {
+            // Spill the register with more incoming interferences (roughly indicating how
+            // long the interval is), preferring constants among equals as they are
+            // rematerialised rather than stored.
+            (
+                get_connected_incoming_neighbors(interference_graph, node).count(),
+                rematerializable_def(ops, &def_use_points, &interference_graph[node]).is_some(),
+            )
         }) {
             let spill_reg = interference_graph[spill_reg_index].clone();
             spills.insert(spill_reg.clone());
@@ -550,14 +554,23 @@ pub(crate) fn color_interference_graph(
     }
 }
 
+/// A function after register allocation.
+pub(crate) struct AllocatedFunction {
+    pub(crate) ops: AllocatedAbstractInstructionSet,
+    /// The number of virtual registers which were spilled to the stack.
+    pub(crate) spills: usize,
+    /// The label called by each call in the function, with the registers live across the call.
+    pub(crate) calls: Vec<(Label, BTreeSet<AllocatedRegister>)>,
+}
+
 /// Assigns an allocatable register to each virtual register used by some instruction in the
 /// list `self.ops`. The algorithm used is Chaitin's graph-coloring register allocation
 /// algorithm (https://en.wikipedia.org/wiki/Chaitin%27s_algorithm). The individual steps of
 /// the algorithm are thoroughly explained in register_allocator.rs.
-
 pub(crate) fn allocate_registers(
     ops: &[Op],
-) -> Result<AllocatedAbstractInstructionSet, CompileError> {
+    reg_seqr: &mut RegisterSequencer,
+) -> Result<AllocatedFunction, CompileError> {
     enum ColouringResult {
         Success {
             updated_ops: Vec<Op>,
@@ -603,6 +616,8 @@ pub(crate) fn allocate_registers(
     let mut updated_ops;
     // How many times to try spilling before we give up.
     let mut try_count = 0;
+    // How many registers have been spilled to the stack.
+    let mut spill_count = 0;
     // Try and assign registers. If we fail, spill. Repeat few times.
     let (updated_ops, interference_graph, mut stack) = loop {
         match try_color(updated_ops_ref) {
@@ -621,9 +636,7 @@ pub(crate) fn allocate_registers(
                     let comment = updated_ops_before_spill
                         .iter()
                         .find_map(|op| {
-                            if let Either::Right(crate::asm_lang::ControlFlowOp::Label(_)) =
-                                op.opcode
-                            {
+                            if let Either::Right(ControlFlowOp::Label(_)) = op.opcode {
                                 Some(op.comment.clone())
                             } else {
                                 None
@@ -641,7 +654,9 @@ pub(crate) fn allocate_registers(
                     ));
                 }
                 try_count += 1;
-                updated_ops = spill(&updated_ops_before_spill, &spills);
+                let spilled;
+                (updated_ops, spilled) = spill(&updated_ops_before_spill, &spills, reg_seqr);
+                spill_count += spilled;
                 updated_ops_ref = &updated_ops;
             }
         }
@@ -659,7 +674,27 @@ pub(crate) fn allocate_registers(
         })
     }
 
-    Ok(AllocatedAbstractInstructionSet { ops: buf })
+    // Step 7: Find the registers live across each call, which the callee must preserve.
+    let live_out = liveness_analysis(&updated_ops, true);
+    let calls = updated_ops
+        .iter()
+        .zip(&live_out)
+        .filter_map(|(op, live)| match op.opcode {
+            Either::Right(ControlFlowOp::Call(label)) => Some((
+                label,
+                live.iter()
+                    .filter_map(|reg| pool.get_register(reg))
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect();
+
+    Ok(AllocatedFunction {
+        ops: AllocatedAbstractInstructionSet { ops: buf },
+        spills: spill_count,
+        calls,
+    })
 }
 
 /// Use the stack generated by the coloring algorithm to figure out a register assignment for each
@@ -703,15 +738,47 @@ fn assign_registers(
     Ok(pool)
 }
 
+/// If `reg` has a single definition which loads a constant, return that definition.  Such a
+/// register can be rematerialised, i.e., defined again right before each of its uses rather than
+/// being spilled to the stack.
+fn rematerializable_def<'a>(
+    ops: &'a [Op],
+    def_use_points: &FxHashMap<VirtualRegister, (Vec<usize>, Vec<usize>)>,
+    reg: &VirtualRegister,
+) -> Option<&'a Op> {
+    match def_use_points.get(reg) {
+        Some((defs, _)) if defs.len() == 1 => {
+            let def = &ops[defs[0]];
+            matches!(
+                def.opcode,
+                Either::Left(VirtualOp::MOVI(..) | VirtualOp::LoadDataId(..))
+            )
+            .then_some(def)
+        }
+        _ => None,
+    }
+}
+
 /// Given a function, its locals info (stack frame usage details)
 /// and a set of virtual registers to be spilled, insert the actual spills
-/// and return the updated function and the updated stack info.
-fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
+/// and return the updated function along with the number of registers
+/// which were spilled to the stack.
+///
+/// Registers which hold a constant are rematerialised at their uses instead of being spilled.
+/// The live range of every other spilled register is split: each spill and each refill uses a new
+/// virtual register which is live only around the instruction that needs it.  A MOVE to or from
+/// a spilled register becomes the store or the load itself.
+fn spill(
+    ops: &[Op],
+    spills: &FxHashSet<VirtualRegister>,
+    reg_seqr: &mut RegisterSequencer,
+) -> (Vec<Op>, usize) {
     let mut spilled: Vec<Op> = vec![];
 
-    // Attempt to discover the current stack size and base register.
+    // Attempt to discover the current stack size and base register.  The stack is shrunk on
+    // return and before each tail call.
     let mut cfe_idx_opt = None;
-    let mut cfs_idx_opt = None;
+    let mut cfs_idxs = FxHashSet::default();
     for (op_idx, op) in ops.iter().enumerate() {
         match &op.opcode {
             Either::Left(VirtualOp::CFEI(_)) => {
@@ -719,8 +786,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                 cfe_idx_opt = Some(op_idx);
             }
             Either::Left(VirtualOp::CFSI(_)) => {
-                assert!(cfs_idx_opt.is_none(), "Found more than one stack shrink");
-                cfs_idx_opt = Some(op_idx);
+                cfs_idxs.insert(op_idx);
             }
             _ => (),
         }
@@ -738,19 +804,40 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
     // pad up the locals size in bytes to a word.
     let locals_size_bytes = size_bytes_round_up_to_word_alignment!(locals_size_bytes);
 
+    // Separate the constants which can be rematerialised from the actual spills.
+    let def_use_points = compute_def_use_points(ops);
+    let remats: FxHashMap<&VirtualRegister, &Op> = spills
+        .iter()
+        .filter_map(|reg| rematerializable_def(ops, &def_use_points, reg).map(|def| (reg, def)))
+        .collect();
+    let mut stack_spills: Vec<&VirtualRegister> = spills
+        .iter()
+        .filter(|reg| !remats.contains_key(reg))
+        .collect();
+    stack_spills.sort();
+
     // Determine the stack slots for each spilled register.
-    let spill_offsets_bytes: FxHashMap<VirtualRegister, u32> = spills
+    let spill_offsets_bytes: FxHashMap<&VirtualRegister, u32> = stack_spills
         .iter()
         .enumerate()
-        .map(|(i, reg)| (reg.clone(), (i * 8) as u32 + locals_size_bytes))
+        .map(|(i, &reg)| (reg, (i * 8) as u32 + locals_size_bytes))
         .collect();
 
-    let spills_size = (8 * spills.len()) as u32;
+    let spills_size = (8 * stack_spills.len()) as u32;
     let new_locals_byte_size = locals_size_bytes + spills_size;
     if new_locals_byte_size > compiler_constants::TWENTY_FOUR_BITS as u32 {
         panic!("Enormous stack usage for locals.");
     }
 
+    // Rematerialise `reg` into `dst` by repeating its definition.
+    let remat = |inst_list: &mut Vec<Op>, reg: &VirtualRegister, dst: &VirtualRegister| {
+        let def = remats[reg];
+        inst_list.push(Op {
+            comment: "Rematerialising spill".to_string(),
+            ..def.update_register(&IndexMap::from([(reg, dst)]))
+        });
+    };
+
     for (op_idx, op) in ops.iter().enumerate() {
         if op_idx == cfe_idx {
             // This is the CFE instruction, use the new stack size.
@@ -761,7 +848,7 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                 comment: op.comment.clone() + &format!(" and {spills_size} bytes for spills"),
                 owning_span: op.owning_span.clone(),
             });
-        } else if matches!(cfs_idx_opt, Some(cfs_idx) if cfs_idx == op_idx) {
+        } else if cfs_idxs.contains(&op_idx) {
             // This is the CFS instruction, use the new stack size.
             spilled.push(Op {
                 opcode: Either::Left(VirtualOp::CFSI(VirtualImmediate24 {
@@ -770,171 +857,287 @@ fn spill(ops: &[Op], spills: &FxHashSet<VirtualRegister>) -> Vec<Op> {
                 comment: op.comment.clone() + &format!(" and {spills_size} bytes for spills"),
                 owning_span: op.owning_span.clone(),
             });
+        } else if remats.values().any(|def| std::ptr::eq(*def, op)) {
+            // The definition of a rematerialised constant, which is repeated at each use instead.
         } else {
+            // A MOVE from a spilled register is a refill straight into its destination, and a
+            // MOVE from a rematerialised constant is its definition.
+            if let Either::Left(VirtualOp::MOVE(dst, src)) = &op.opcode {
+                if !spills.contains(dst) {
+                    if let Some(&offset_bytes) = spill_offsets_bytes.get(src) {
+                        refill(&mut spilled, dst, offset_bytes);
+                        continue;
+                    }
+                    if remats.contains_key(src) {
+                        remat(&mut spilled, src, dst);
+                        continue;
+                    }
+                }
+            }
+
             // For every other instruction:
-            //   If it defines a spilled register, store that register to its stack slot.
-            //   If it uses a spilled register, load that register from its stack slot.
-            let use_registers = op.use_registers();
-            let def_registers = op.def_registers();
+            //   If it uses a spilled register, load that register from its stack slot into a new
+            //   register, or rematerialise it, and use the new register instead.
+            //   If it defines a spilled register, define a new register instead and store that to
+            //   the stack slot.
+            let mut split_regs = IndexMap::<&VirtualRegister, VirtualRegister>::new();
+            for &spilled_use in op.use_registers().iter().filter(|r| spills.contains(*r)) {
+                let split_reg = reg_seqr.next();
+                match spill_offsets_bytes.get(spilled_use) {
+                    Some(&offset_bytes) => refill(&mut spilled, &split_reg, offset_bytes),
+                    None => remat(&mut spilled, spilled_use, &split_reg),
+                }
+                split_regs.insert(spilled_use, split_reg);
+            }
 
-            // Calculate the address off a local in a register + imm word offset.
-            fn calculate_offset_reg_wordimm(
-                inst_list: &mut Vec<Op>,
-                offset_bytes: u32,
-            ) -> (VirtualRegister, VirtualImmediate12) {
-                assert!(offset_bytes % 8 == 0);
-                if offset_bytes <= compiler_constants::EIGHTEEN_BITS as u32 {
-                    let offset_mov_instr = Op {
-                        opcode: Either::Left(VirtualOp::MOVI(
-                            VirtualRegister::Constant(ConstantRegister::Scratch),
-                            VirtualImmediate18 {
-                                value: offset_bytes,
-                            },
-                        )),
-                        comment: "Spill/Refill: Set offset".to_string(),
-                        owning_span: None,
-                    };
-                    inst_list.push(offset_mov_instr);
-                    let offset_add_instr = Op {
-                        opcode: Either::Left(VirtualOp::ADD(
-                            VirtualRegister::Constant(ConstantRegister::Scratch),
-                            VirtualRegister::Constant(ConstantRegister::Scratch),
-                            VirtualRegister::Constant(ConstantRegister::LocalsBase),
-                        )),
-                        comment: "Spill/Refill: Add offset to stack base".to_string(),
-                        owning_span: None,
-                    };
-                    inst_list.push(offset_add_instr);
-                    (
-                        VirtualRegister::Constant(ConstantRegister::Scratch),
-                        VirtualImmediate12 { value: 0 },
-                    )
-                } else {
-                    assert!(offset_bytes <= compiler_constants::TWENTY_FOUR_BITS as u32);
-                    // To have a 24b immediate value, we split it into 12-12 bits
-                    // The upper 12 bits are shifted down, then put in a register using
-                    // MOVi and then shifted back up via SLLI. Adding back the lower 12 bits
-                    // gives us back the original value. We first add the locals_base register
-                    // though and then just return the lower 12 bits (but in words) to be used
-                    // as an imm value and added in the consumer LW/SW.
-                    let offset_upper_12 = offset_bytes >> 12;
-                    let offset_lower_12 = offset_bytes & 0b111111111111;
-                    assert!((offset_upper_12 << 12) + offset_lower_12 == offset_bytes);
-                    let offset_upper_mov_instr = Op {
-                        opcode: Either::Left(VirtualOp::MOVI(
-                            VirtualRegister::Constant(ConstantRegister::Scratch),
-                            VirtualImmediate18 {
-                                value: offset_upper_12,
-                            },
-                        )),
-                        comment: "Spill/Refill: Offset computation".to_string(),
-                        owning_span: None,
-                    };
-                    inst_list.push(offset_upper_mov_instr);
-                    let offset_upper_shift_instr = Op {
-                        opcode: Either::Left(VirtualOp::SLLI(
-                            VirtualRegister::Constant(ConstantRegister::Scratch),
-                            VirtualRegister::Constant(ConstantRegister::Scratch),
-                            VirtualImmediate12 { value: 12 },
-                        )),
-                        comment: "Spill/Refill: Offset computation".to_string(),
-                        owning_span: None,
-                    };
-                    inst_list.push(offset_upper_shift_instr);
-                    let offset_add_instr = Op {
-                        opcode: Either::Left(VirtualOp::ADD(
-                            VirtualRegister::Constant(ConstantRegister::Scratch),
-                            VirtualRegister::Constant(ConstantRegister::Scratch),
-                            VirtualRegister::Constant(ConstantRegister::LocalsBase),
-                        )),
-                        comment: "Spill/Refill: Offset computation".to_string(),
-                        owning_span: None,
-                    };
-                    inst_list.push(offset_add_instr);
-                    (
-                        VirtualRegister::Constant(ConstantRegister::Scratch),
-                        VirtualImmediate12 {
-                            // This will be multiplied by 8 by the VM
-                            value: (offset_lower_12 / 8) as u16,
-                        },
-                    )
+            // A MOVE to a spilled register is a store of its source.
+            if let Either::Left(VirtualOp::MOVE(dst, src)) = &op.opcode {
+                if let Some(&offset_bytes) = spill_offsets_bytes.get(dst) {
+                    let src = split_regs.get(src).unwrap_or(src);
+                    store(&mut spilled, src, offset_bytes);
+                    continue;
                 }
             }
 
-            // Take care of any refills on the uses.
-            for &spilled_use in use_registers.iter().filter(|r#use| spills.contains(r#use)) {
-                // Load the spilled register from its stack slot.
-                let offset_bytes = spill_offsets_bytes[spilled_use];
-                assert!(offset_bytes % 8 == 0);
-                if offset_bytes / 8 <= compiler_constants::TWELVE_BITS as u32 {
-                    spilled.push(Op {
-                        opcode: Either::Left(VirtualOp::LW(
-                            spilled_use.clone(),
-                            VirtualRegister::Constant(ConstantRegister::LocalsBase),
-                            VirtualImmediate12 {
-                                // This will be multiplied by 8 by the VM
-                                value: (offset_bytes / 8) as u16,
-                            },
-                        )),
-                        comment: "Refilling from spill".to_string(),
-                        owning_span: None,
-                    });
-                } else {
-                    let (offset_reg, offset_imm_word) =
-                        calculate_offset_reg_wordimm(&mut spilled, offset_bytes);
-                    let lw = Op {
-                        opcode: Either::Left(VirtualOp::LW(
-                            spilled_use.clone(),
-                            offset_reg,
-                            // This will be multiplied by 8 by the VM
-                            offset_imm_word,
-                        )),
-                        comment: "Refilling from spill".to_string(),
-                        owning_span: None,
-                    };
-                    spilled.push(lw);
+            let def_registers = op.def_registers();
+            for &spilled_def in def_registers.iter().filter(|d| spills.contains(*d)) {
+                if !split_regs.contains_key(spilled_def) {
+                    split_regs.insert(spilled_def, reg_seqr.next());
                 }
             }
 
             // The op itself.
-            spilled.push(op.clone());
+            spilled.push(op.update_register(&split_regs.iter().map(|(&k, v)| (k, v)).collect()));
 
             // Take care of spills from the def registers.
-            for &spilled_def in def_registers.iter().filter(|def| spills.contains(def)) {
-                // Store the def register to its stack slot.
-                let offset_bytes = spill_offsets_bytes[spilled_def];
-                assert!(offset_bytes % 8 == 0);
-                if offset_bytes / 8 <= compiler_constants::TWELVE_BITS as u32 {
-                    spilled.push(Op {
-                        opcode: Either::Left(VirtualOp::SW(
-                            VirtualRegister::Constant(ConstantRegister::LocalsBase),
-                            spilled_def.clone(),
-                            VirtualImmediate12 {
-                                // This will be multiplied by 8 by the VM
-                                value: (offset_bytes / 8) as u16,
-                            },
-                        )),
-                        comment: "Spill".to_string(),
-                        owning_span: None,
-                    });
-                } else {
-                    let (offset_reg, offset_imm_word) =
-                        calculate_offset_reg_wordimm(&mut spilled, offset_bytes);
-                    let sw = Op {
-                        opcode: Either::Left(VirtualOp::SW(
-                            offset_reg,
-                            spilled_def.clone(),
-                            // This will be multiplied by 8 by the VM
-                            offset_imm_word,
-                        )),
-                        comment: "Spill".to_string(),
-                        owning_span: None,
-                    };
-                    spilled.push(sw);
-                }
+            for &spilled_def in def_registers.iter().filter(|d| spills.contains(*d)) {
+                store(
+                    &mut spilled,
+                    &split_regs[spilled_def],
+                    spill_offsets_bytes[spilled_def],
+                );
             }
         }
     }
 
-    spilled
+    (spilled, stack_spills.len())
+}
+
+/// Load `reg` from the spill slot at `offset_bytes` from the locals base.
+fn refill(inst_list: &mut Vec<Op>, reg: &VirtualRegister, offset_bytes: u32) {
+    let (base_reg, offset_imm_word) = calculate_offset_reg_wordimm(inst_list, offset_bytes);
+    inst_list.push(Op {
+        opcode: Either::Left(VirtualOp::LW(reg.clone(), base_reg, offset_imm_word)),
+        comment: "Refilling from spill".to_string(),
+        owning_span: None,
+    });
+}
+
+/// Store `reg` to the spill slot at `offset_bytes` from the locals base.
+fn store(inst_list: &mut Vec<Op>, reg: &VirtualRegister, offset_bytes: u32) {
+    let (base_reg, offset_imm_word) = calculate_offset_reg_wordimm(inst_list, offset_bytes);
+    inst_list.push(Op {
+        opcode: Either::Left(VirtualOp::SW(base_reg, reg.clone(), offset_imm_word)),
+        comment: "Spill".to_string(),
+        owning_span: None,
+    });
+}
+
+// Calculate the address off a local in a register + imm word offset.
+fn calculate_offset_reg_wordimm(
+    inst_list: &mut Vec<Op>,
+    offset_bytes: u32,
+) -> (VirtualRegister, VirtualImmediate12) {
+    assert!(offset_bytes % 8 == 0);
+    if offset_bytes / 8 <= compiler_constants::TWELVE_BITS as u32 {
+        (
+            VirtualRegister::Constant(ConstantRegister::LocalsBase),
+            VirtualImmediate12 {
+                // This will be multiplied by 8 by the VM
+                value: (offset_bytes / 8) as u16,
+            },
+        )
+    } else if offset_bytes <= compiler_constants::EIGHTEEN_BITS as u32 {
+        let offset_mov_instr = Op {
+            opcode: Either::Left(VirtualOp::MOVI(
+                VirtualRegister::Constant(ConstantRegister::Scratch),
+                VirtualImmediate18 {
+                    value: offset_bytes,
+                },
+            )),
+            comment: "Spill/Refill: Set offset".to_string(),
+            owning_span: None,
+        };
+        inst_list.push(offset_mov_instr);
+        let offset_add_instr = Op {
+            opcode: Either::Left(VirtualOp::ADD(
+                VirtualRegister::Constant(ConstantRegister::Scratch),
+                VirtualRegister::Constant(ConstantRegister::Scratch),
+                VirtualRegister::Constant(ConstantRegister::LocalsBase),
+            )),
+            comment: "Spill/Refill: Add offset to stack base".to_string(),
+            owning_span: None,
+        };
+        inst_list.push(offset_add_instr);
+        (
+            VirtualRegister::Constant(ConstantRegister::Scratch),
+            VirtualImmediate12 { value: 0 },
+        )
+    } else {
+        assert!(offset_bytes <= compiler_constants::TWENTY_FOUR_BITS as u32);
+        // To have a 24b immediate value, we split it into 12-12 bits
+        // The upper 12 bits are shifted down, then put in a register using
+        // MOVi and then shifted back up via SLLI. Adding back the lower 12 bits
+        // gives us back the original value. We first add the locals_base register
+        // though and then just return the lower 12 bits (but in words) to be used
+        // as an imm value and added in the consumer LW/SW.
+        let offset_upper_12 = offset_bytes >> 12;
+        let offset_lower_12 = offset_bytes & 0b111111111111;
+        assert!((offset_upper_12 << 12) + offset_lower_12 == offset_bytes);
+        let offset_upper_mov_instr = Op {
+            opcode: Either::Left(VirtualOp::MOVI(
+                VirtualRegister::Constant(ConstantRegister::Scratch),
+                VirtualImmediate18 {
+                    value: offset_upper_12,
+                },
+            )),
+            comment: "Spill/Refill: Offset computation".to_string(),
+            owning_span: None,
+        };
+        inst_list.push(offset_upper_mov_instr);
+        let offset_upper_shift_instr = Op {
+            opcode: Either::Left(VirtualOp::SLLI(
+                VirtualRegister::Constant(ConstantRegister::Scratch),
+                VirtualRegister::Constant(ConstantRegister::Scratch),
+                VirtualImmediate12 { value: 12 },
+            )),
+            comment: "Spill/Refill: Offset computation".to_string(),
+            owning_span: None,
+        };
+        inst_list.push(offset_upper_shift_instr);
+        let offset_add_instr = Op {
+            opcode: Either::Left(VirtualOp::ADD(
+                VirtualRegister::Constant(ConstantRegister::Scratch),
+                VirtualRegister::Constant(ConstantRegister::Scratch),
+                VirtualRegister::Constant(ConstantRegister::LocalsBase),
+            )),
+            comment: "Spill/Refill: Offset computation".to_string(),
+            owning_span: None,
+        };
+        inst_list.push(offset_add_instr);
+        (
+            VirtualRegister::Constant(ConstantRegister::Scratch),
+            VirtualImmediate12 {
+                // This will be multiplied by 8 by the VM
+                value: (offset_lower_12 / 8) as u16,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vreg(name: &str) -> VirtualRegister {
+        VirtualRegister::Virtual(name.to_string())
+    }
+
+    fn op(opcode: VirtualOp) -> Op {
+        Op {
+            opcode: Either::Left(opcode),
+            comment: String::new(),
+            owning_span: None,
+        }
+    }
+
+    fn spill_regs(ops: Vec<Op>, spills: &[&str]) -> (Vec<String>, usize) {
+        // Start the new registers well clear of the ones used by the tests.
+        let mut reg_seqr = RegisterSequencer::new();
+        for _ in 0..100 {
+            reg_seqr.next();
+        }
+        let spills = spills.iter().map(|name| vreg(name)).collect();
+        let (ops, count) = spill(&ops, &spills, &mut reg_seqr);
+        (ops.iter().map(|op| op.opcode.to_string()).collect(), count)
+    }
+
+    #[test]
+    fn spill_splits_live_ranges() {
+        use VirtualOp::*;
+        assert_eq!(
+            spill_regs(
+                vec![
+                    op(CFEI(VirtualImmediate24 { value: 8 })),
+                    op(ADD(vreg("0"), vreg("1"), vreg("2"))),
+                    op(ADD(vreg("3"), vreg("0"), vreg("0"))),
+                    op(ADD(vreg("0"), vreg("0"), vreg("3"))),
+                    op(CFSI(VirtualImmediate24 { value: 8 })),
+                ],
+                &["0"],
+            ),
+            (
+                vec![
+                    "cfei i16".to_string(),
+                    "add $r100 $r1 $r2".to_string(),
+                    "sw $$locbase $r100 i1".to_string(),
+                    "lw $r101 $$locbase i1".to_string(),
+                    "add $r3 $r101 $r101".to_string(),
+                    "lw $r102 $$locbase i1".to_string(),
+                    "add $r102 $r102 $r3".to_string(),
+                    "sw $$locbase $r102 i1".to_string(),
+                    "cfsi i16".to_string(),
+                ],
+                1
+            )
+        );
+    }
+
+    #[test]
+    fn spill_rematerializes_constants() {
+        use VirtualOp::*;
+        assert_eq!(
+            spill_regs(
+                vec![
+                    op(CFEI(VirtualImmediate24 { value: 0 })),
+                    op(MOVI(vreg("0"), VirtualImmediate18 { value: 42 })),
+                    op(ADD(vreg("1"), vreg("0"), vreg("2"))),
+                    op(MOVE(vreg("3"), vreg("0"))),
+                ],
+                &["0"],
+            ),
+            (
+                vec![
+                    "cfei i0".to_string(),
+                    "movi $r100 i42".to_string(),
+                    "add $r1 $r100 $r2".to_string(),
+                    "movi $r3 i42".to_string(),
+                ],
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn spill_coalesces_moves() {
+        use VirtualOp::*;
+        assert_eq!(
+            spill_regs(
+                vec![
+                    op(CFEI(VirtualImmediate24 { value: 0 })),
+                    op(MOVE(vreg("0"), vreg("1"))),
+                    op(MOVE(vreg("2"), vreg("0"))),
+                ],
+                &["0"],
+            ),
+            (
+                vec![
+                    "cfei i8".to_string(),
+                    "sw $$locbase $r1 i0".to_string(),
+                    "lw $r2 $$locbase i0".to_string(),
+                ],
+                1
+            )
+        );
+    }
 }
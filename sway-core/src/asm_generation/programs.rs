@@ -8,10 +8,12 @@ use super::fuel::{
     register_sequencer::RegisterSequencer,
 };
 
+use std::collections::BTreeMap;
+
 use crate::{
     asm_lang::{allocated_ops::AllocatedOp, Label},
     decl_engine::DeclRefFunction,
-    ExperimentalFlags,
+    ExperimentalFlags, OptLevel,
 };
 
 type SelectorOpt = Option<[u8; 4]>;
@@ -36,8 +38,10 @@ pub(super) struct AbstractProgram {
     data_section: DataSection,
     entries: Vec<AbstractEntry>,
     non_entries: Vec<AbstractInstructionSet>,
+    fn_labels: Vec<(Label, FnName)>,
     reg_seqr: RegisterSequencer,
     experimental: ExperimentalFlags,
+    optimization_level: OptLevel,
 }
 
 /// The entry point of an abstract program.
@@ -47,6 +51,7 @@ pub(super) struct AbstractEntry {
     pub(super) ops: AbstractInstructionSet,
     pub(super) name: FnName,
     pub(super) test_decl_ref: Option<DeclRefFunction>,
+    pub(super) fingerprint: u64,
 }
 
 /// An AllocatedProgram represents code which has allocated registers but still has abstract
@@ -56,7 +61,9 @@ pub(super) struct AllocatedProgram {
     data_section: DataSection,
     prologue: AllocatedAbstractInstructionSet,
     functions: Vec<AllocatedAbstractInstructionSet>,
-    entries: Vec<(SelectorOpt, Label, FnName, Option<DeclRefFunction>)>,
+    entries: Vec<(SelectorOpt, Label, FnName, Option<DeclRefFunction>, u64)>,
+    fn_labels: Vec<(Label, FnName)>,
+    fn_spills: BTreeMap<FnName, usize>,
 }
 
 /// A FinalProgram represents code which may be serialized to VM bytecode.
@@ -65,7 +72,9 @@ pub(super) enum FinalProgram {
         kind: ProgramKind,
         data_section: DataSection,
         ops: Vec<AllocatedOp>,
-        entries: Vec<(SelectorOpt, ImmOffset, FnName, Option<DeclRefFunction>)>,
+        entries: Vec<(SelectorOpt, ImmOffset, FnName, Option<DeclRefFunction>, u64)>,
+        fn_offsets: BTreeMap<ImmOffset, FnName>,
+        fn_spills: BTreeMap<FnName, usize>,
     },
     Evm {
         ops: Vec<etk_asm::ops::AbstractOp>,
@@ -1,41 +1,51 @@
-use super::{AbstractEntry, AbstractProgram, AllocatedProgram, ProgramKind};
+use super::{AbstractEntry, AbstractProgram, AllocatedProgram, FnName, ProgramKind};
 
 use crate::{
     asm_generation::fuel::{
         abstract_instruction_set::AbstractInstructionSet,
-        allocated_abstract_instruction_set::AllocatedAbstractInstructionSet,
+        allocated_abstract_instruction_set::{
+            callee_saved_registers, AllocatedAbstractInstructionSet,
+        },
         compiler_constants,
         data_section::{DataSection, Entry},
+        outlining::outline_repeated_sequences,
+        peephole::peephole_optimize,
         register_sequencer::RegisterSequencer,
     },
     asm_lang::{
         allocated_ops::{AllocatedOpcode, AllocatedRegister},
-        AllocatedAbstractOp, ConstantRegister, ControlFlowOp, VirtualImmediate12,
+        AllocatedAbstractOp, ConstantRegister, ControlFlowOp, Label, VirtualImmediate12,
         VirtualImmediate18,
     },
-    ExperimentalFlags,
+    ExperimentalFlags, OptLevel,
 };
 
 use sway_error::error::CompileError;
 
 use either::Either;
+use std::collections::{BTreeMap, HashMap};
 
 impl AbstractProgram {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         kind: ProgramKind,
         data_section: DataSection,
         entries: Vec<AbstractEntry>,
         non_entries: Vec<AbstractInstructionSet>,
+        fn_labels: Vec<(Label, FnName)>,
         reg_seqr: RegisterSequencer,
         experimental: ExperimentalFlags,
+        optimization_level: OptLevel,
     ) -> Self {
         AbstractProgram {
             kind,
             data_section,
             entries,
             non_entries,
+            fn_labels,
             reg_seqr,
             experimental,
+            optimization_level,
         }
     }
 
@@ -64,6 +74,7 @@ impl AbstractProgram {
                     entry.label,
                     entry.name.clone(),
                     entry.test_decl_ref.clone(),
+                    entry.fingerprint,
                 )
             })
             .collect();
@@ -78,15 +89,41 @@ impl AbstractProgram {
             .map(AbstractInstructionSet::verify)
             .collect::<Result<Vec<_>, _>>()?;
 
-        // Allocate the registers for each function.
-        let functions = abstract_functions
+        // Allocate the registers for each function, counting the spills of each by the name of
+        // its start label.
+        let fn_names: HashMap<_, _> = self.fn_labels.iter().cloned().collect();
+        let mut fn_spills = BTreeMap::new();
+        let allocated_functions = abstract_functions
             .into_iter()
             .map(|fn_ops| {
-                fn_ops
-                    .allocate_registers()
-                    .map(AllocatedAbstractInstructionSet::emit_pusha_popa)
+                let name = fn_ops.ops.iter().find_map(|op| match op.opcode {
+                    Either::Right(ControlFlowOp::Label(label)) => fn_names.get(&label).cloned(),
+                    _ => None,
+                });
+                let allocated = fn_ops.allocate_registers(&mut self.reg_seqr)?;
+                if let Some(name) = name {
+                    *fn_spills.entry(name).or_default() += allocated.spills;
+                }
+                Ok(allocated)
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>, CompileError>>()?;
+
+        // Each function need only save the registers which its callers rely on.  Unoptimized
+        // functions save every register they define.
+        let saved = (self.optimization_level != OptLevel::Opt0)
+            .then(|| callee_saved_registers(&allocated_functions));
+        let mut functions = allocated_functions
+            .into_iter()
+            .map(|allocated| allocated.ops.emit_pusha_popa(saved.as_ref()))
+            .collect::<Vec<_>>();
+
+        if self.optimization_level != OptLevel::Opt0 {
+            functions = functions.into_iter().map(peephole_optimize).collect();
+        }
+
+        if self.optimization_level == OptLevel::OptSize {
+            outline_repeated_sequences(&mut functions, &mut self.reg_seqr, &self.data_section);
+        }
 
         // XXX need to verify that the stack use for each function is balanced.
 
@@ -96,6 +133,8 @@ impl AbstractProgram {
             prologue,
             functions,
             entries,
+            fn_labels: self.fn_labels,
+            fn_spills,
         })
     }
 
@@ -16,16 +16,27 @@ impl AllocatedProgram {
             abstract_ops.realize_labels(&mut self.data_section)?;
         let ops = realized_ops.pad_to_even();
 
+        // Collect the function offsets, skipping any whose labels were optimised away.
+        let fn_offsets = self
+            .fn_labels
+            .into_iter()
+            .filter_map(|(label, name)| {
+                label_offsets
+                    .get(&label)
+                    .map(|basic_block| (basic_block.offs, name))
+            })
+            .collect();
+
         // Collect the entry point offsets.
         let entries = self
             .entries
             .into_iter()
-            .map(|(selector, label, name, test_decl_ref)| {
+            .map(|(selector, label, name, test_decl_ref, fingerprint)| {
                 let offset = label_offsets
                     .remove(&label)
                     .expect("no offset for entry")
                     .offs;
-                (selector, offset, name, test_decl_ref)
+                (selector, offset, name, test_decl_ref, fingerprint)
             })
             .collect();
 
@@ -34,6 +45,8 @@ impl AllocatedProgram {
             data_section: self.data_section,
             ops,
             entries,
+            fn_offsets,
+            fn_spills: self.fn_spills,
         })
     }
 }
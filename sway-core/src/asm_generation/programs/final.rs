@@ -15,19 +15,26 @@ impl FinalProgram {
                 data_section,
                 ops,
                 entries,
+                fn_offsets,
+                fn_spills,
             } => FinalizedAsm {
                 data_section,
                 program_section: InstructionSet::Fuel { ops },
                 program_kind: kind,
                 entries: entries
                     .into_iter()
-                    .map(|(selector, imm, fn_name, test_decl_ref)| FinalizedEntry {
-                        imm,
-                        fn_name,
-                        selector,
-                        test_decl_ref,
-                    })
+                    .map(
+                        |(selector, imm, fn_name, test_decl_ref, fingerprint)| FinalizedEntry {
+                            imm,
+                            fn_name,
+                            selector,
+                            test_decl_ref,
+                            fingerprint,
+                        },
+                    )
                     .collect(),
+                fn_offsets,
+                fn_spills,
                 abi: None,
             },
             FinalProgram::Evm { ops, abi } => FinalizedAsm {
@@ -37,6 +44,8 @@ impl FinalProgram {
                 program_section: InstructionSet::Evm { ops },
                 program_kind: super::ProgramKind::Script,
                 entries: vec![],
+                fn_offsets: Default::default(),
+                fn_spills: Default::default(),
                 abi: Some(ProgramABI::Evm(abi)),
             },
             FinalProgram::MidenVM { ops } => FinalizedAsm {
@@ -47,6 +56,8 @@ impl FinalProgram {
                 // should this be a script? :think:
                 program_kind: super::ProgramKind::Script,
                 entries: vec![],
+                fn_offsets: Default::default(),
+                fn_spills: Default::default(),
                 abi: None, /* TODO? */
             },
         }
@@ -246,6 +246,132 @@ pub(crate) enum AllocatedOpcode {
 }
 
 impl AllocatedOpcode {
+    /// Returns a list of all registers *read or written* by instruction `self`, except those in
+    /// the mask of a register PUSH/POP instruction.
+    pub(crate) fn registers(&self) -> BTreeSet<&AllocatedRegister> {
+        use AllocatedOpcode::*;
+        (match self {
+            /* Arithmetic/Logic (ALU) Instructions */
+            ADD(r1, r2, r3) => vec![r1, r2, r3],
+            ADDI(r1, r2, _i) => vec![r1, r2],
+            AND(r1, r2, r3) => vec![r1, r2, r3],
+            ANDI(r1, r2, _i) => vec![r1, r2],
+            DIV(r1, r2, r3) => vec![r1, r2, r3],
+            DIVI(r1, r2, _i) => vec![r1, r2],
+            EQ(r1, r2, r3) => vec![r1, r2, r3],
+            EXP(r1, r2, r3) => vec![r1, r2, r3],
+            EXPI(r1, r2, _i) => vec![r1, r2],
+            GT(r1, r2, r3) => vec![r1, r2, r3],
+            LT(r1, r2, r3) => vec![r1, r2, r3],
+            MLOG(r1, r2, r3) => vec![r1, r2, r3],
+            MOD(r1, r2, r3) => vec![r1, r2, r3],
+            MODI(r1, r2, _i) => vec![r1, r2],
+            MOVE(r1, r2) => vec![r1, r2],
+            MOVI(r1, _i) => vec![r1],
+            MROO(r1, r2, r3) => vec![r1, r2, r3],
+            MUL(r1, r2, r3) => vec![r1, r2, r3],
+            MULI(r1, r2, _i) => vec![r1, r2],
+            NOOP => vec![],
+            NOT(r1, r2) => vec![r1, r2],
+            OR(r1, r2, r3) => vec![r1, r2, r3],
+            ORI(r1, r2, _i) => vec![r1, r2],
+            SLL(r1, r2, r3) => vec![r1, r2, r3],
+            SLLI(r1, r2, _i) => vec![r1, r2],
+            SRL(r1, r2, r3) => vec![r1, r2, r3],
+            SRLI(r1, r2, _i) => vec![r1, r2],
+            SUB(r1, r2, r3) => vec![r1, r2, r3],
+            SUBI(r1, r2, _i) => vec![r1, r2],
+            XOR(r1, r2, r3) => vec![r1, r2, r3],
+            XORI(r1, r2, _i) => vec![r1, r2],
+            WQOP(r1, r2, r3, _) => vec![r1, r2, r3],
+            WQML(r1, r2, r3, _) => vec![r1, r2, r3],
+            WQDV(r1, r2, r3, _) => vec![r1, r2, r3],
+            WQCM(r1, r2, r3, _) => vec![r1, r2, r3],
+            WQAM(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+
+            /* Control Flow Instructions */
+            JMP(r1) => vec![r1],
+            JI(_im) => vec![],
+            JNE(r1, r2, r3) => vec![r1, r2, r3],
+            JNEI(r1, r2, _i) => vec![r1, r2],
+            JNZI(r1, _i) => vec![r1],
+            JMPB(r1, _i) => vec![r1],
+            JMPF(r1, _i) => vec![r1],
+            JNZB(r1, r2, _i) => vec![r1, r2],
+            JNZF(r1, r2, _i) => vec![r1, r2],
+            RET(r1) => vec![r1],
+
+            /* Memory Instructions */
+            ALOC(r1) => vec![r1],
+            CFEI(_imm) => vec![],
+            CFSI(_imm) => vec![],
+            CFE(r1) => vec![r1],
+            CFS(r1) => vec![r1],
+            LB(r1, r2, _i) => vec![r1, r2],
+            LW(r1, r2, _i) => vec![r1, r2],
+            MCL(r1, r2) => vec![r1, r2],
+            MCLI(r1, _imm) => vec![r1],
+            MCP(r1, r2, r3) => vec![r1, r2, r3],
+            MCPI(r1, r2, _imm) => vec![r1, r2],
+            MEQ(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            // The registers of a PUSH/POP are in its mask.  They're always allocated registers or
+            // the locals base register.
+            PSHH(_mask) | PSHL(_mask) | POPH(_mask) | POPL(_mask) => vec![],
+            SB(r1, r2, _i) => vec![r1, r2],
+            SW(r1, r2, _i) => vec![r1, r2],
+
+            /* Contract Instructions */
+            BAL(r1, r2, r3) => vec![r1, r2, r3],
+            BHEI(r1) => vec![r1],
+            BHSH(r1, r2) => vec![r1, r2],
+            BURN(r1, r2) => vec![r1, r2],
+            CALL(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            CB(r1) => vec![r1],
+            CCP(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            CROO(r1, r2) => vec![r1, r2],
+            CSIZ(r1, r2) => vec![r1, r2],
+            LDC(r1, r2, r3) => vec![r1, r2, r3],
+            LOG(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            LOGD(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            MINT(r1, r2) => vec![r1, r2],
+            RETD(r1, r2) => vec![r1, r2],
+            RVRT(r1) => vec![r1],
+            SMO(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            SCWQ(r1, r2, r3) => vec![r1, r2, r3],
+            SRW(r1, r2, r3) => vec![r1, r2, r3],
+            SRWQ(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            SWW(r1, r2, r3) => vec![r1, r2, r3],
+            SWWQ(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+            TIME(r1, r2) => vec![r1, r2],
+            TR(r1, r2, r3) => vec![r1, r2, r3],
+            TRO(r1, r2, r3, r4) => vec![r1, r2, r3, r4],
+
+            /* Cryptographic Instructions */
+            ECK1(r1, r2, r3) => vec![r1, r2, r3],
+            ECR1(r1, r2, r3) => vec![r1, r2, r3],
+            ED19(r1, r2, r3) => vec![r1, r2, r3],
+            K256(r1, r2, r3) => vec![r1, r2, r3],
+            S256(r1, r2, r3) => vec![r1, r2, r3],
+
+            /* Other Instructions */
+            FLAG(r1) => vec![r1],
+            GM(r1, _imm) => vec![r1],
+            GTF(r1, r2, _i) => vec![r1, r2],
+
+            /* Non-VM Instructions */
+            BLOB(_imm) => vec![],
+            DataSectionOffsetPlaceholder => vec![],
+            DataSectionRegisterLoadPlaceholder => vec![
+                &AllocatedRegister::Constant(ConstantRegister::DataSectionStart),
+                &AllocatedRegister::Constant(ConstantRegister::InstructionStart),
+            ],
+            LoadDataId(r1, _i) => vec![r1],
+            Undefined => vec![],
+        })
+        .into_iter()
+        .collect()
+    }
+
     /// Returns a list of all registers *written* by instruction `self`.
     pub(crate) fn def_registers(&self) -> BTreeSet<&AllocatedRegister> {
         use AllocatedOpcode::*;
@@ -716,10 +842,10 @@ fn realize_load(
         "Internal miscalculation in data section -- data id did not match up to any actual data",
     );
 
-    // all data is word-aligned right now, and `offset_to_id` returns the offset in bytes
+    // all data but bytes is word-aligned, and `offset_to_id` returns the offset in bytes
     let offset_bytes = data_section.data_id_to_offset(data_id) as u64;
     assert!(
-        offset_bytes % 8 == 0,
+        is_byte || offset_bytes % 8 == 0,
         "Internal miscalculation in data section -- data offset is not aligned to a word",
     );
     let offset_words = offset_bytes / 8;
@@ -1282,8 +1282,9 @@ impl<Reg: Clone + Eq + Ord + Hash> ControlFlowOp<Reg> {
             | PushAll(_)
             | PopAll(_) => (),
 
+            // A jump to a label outside of `ops` is a tail call, which doesn't return.
             Jump(jump_label) | JumpIfNotZero(_, jump_label) => {
-                next_ops.push(label_to_index[jump_label]);
+                next_ops.extend(label_to_index.get(jump_label));
             }
         };
 
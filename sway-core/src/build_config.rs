@@ -1,6 +1,7 @@
 use serde::{Deserialize, Deserializer, Serialize};
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 use strum::{Display, EnumString};
+use sway_error::lint::{Lint, LintLevel};
 
 #[derive(
     Clone,
@@ -37,15 +38,27 @@ pub enum OptLevel {
     #[default]
     Opt0 = 0,
     Opt1 = 1,
+    /// Optimize for bytecode size rather than for speed.
+    OptSize = 2,
 }
 
 impl<'de> serde::Deserialize<'de> for OptLevel {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        let num = u8::deserialize(d)?;
-        match num {
-            0 => Ok(OptLevel::Opt0),
-            1 => Ok(OptLevel::Opt1),
-            _ => Err(serde::de::Error::custom(format!("invalid opt level {num}"))),
+        // The level is either a number or, like Cargo's `opt-level = "z"`, a letter.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Level {
+            Num(u8),
+            Str(String),
+        }
+        match Level::deserialize(d)? {
+            Level::Num(0) => Ok(OptLevel::Opt0),
+            Level::Num(1) => Ok(OptLevel::Opt1),
+            Level::Str(s) if s == "z" => Ok(OptLevel::OptSize),
+            Level::Num(num) => Err(serde::de::Error::custom(format!("invalid opt level {num}"))),
+            Level::Str(s) => Err(serde::de::Error::custom(format!(
+                "invalid opt level \"{s}\", expected 0, 1 or \"z\""
+            ))),
         }
     }
 }
@@ -65,6 +78,9 @@ pub struct BuildConfig {
     pub(crate) print_ir: bool,
     pub(crate) include_tests: bool,
     pub(crate) optimization_level: OptLevel,
+    pub(crate) ir_passes: Option<String>,
+    pub(crate) arithmetic_safety_analysis: bool,
+    pub(crate) lint_levels: BTreeMap<Lint, LintLevel>,
     pub time_phases: bool,
     pub metrics_outfile: Option<String>,
     pub experimental: ExperimentalFlags,
@@ -114,6 +130,9 @@ impl BuildConfig {
             time_phases: false,
             metrics_outfile: None,
             optimization_level: OptLevel::Opt0,
+            ir_passes: None,
+            arithmetic_safety_analysis: false,
+            lint_levels: BTreeMap::new(),
             experimental: ExperimentalFlags::default(),
             lsp_mode: None,
         }
@@ -175,6 +194,34 @@ impl BuildConfig {
         }
     }
 
+    /// Run this textual IR pass pipeline, e.g. `inline_module,repeat(constcombine,simplifycfg)`,
+    /// instead of the one of the optimization level.
+    pub fn with_ir_passes(self, ir_passes: Option<String>) -> Self {
+        Self { ir_passes, ..self }
+    }
+
+    /// Whether or not to warn about arithmetic on unchecked ABI method arguments, narrowing
+    /// integer conversions and divisions by values which could be zero.
+    ///
+    /// Default: `false`
+    pub fn with_arithmetic_safety_analysis(self, arithmetic_safety_analysis: bool) -> Self {
+        Self {
+            arithmetic_safety_analysis,
+            ..self
+        }
+    }
+
+    /// The levels of the lints for the whole package, e.g. from the `[lints]` table of the
+    /// manifest. Setting an opt-in lint to `warn` or `deny` enables the analysis behind it.
+    ///
+    /// Default: empty, i.e. all the lints are at their default level.
+    pub fn with_lint_levels(self, lint_levels: BTreeMap<Lint, LintLevel>) -> Self {
+        Self {
+            lint_levels,
+            ..self
+        }
+    }
+
     /// Whether or not to include test functions in parsing, type-checking and codegen.
     ///
     /// This should be set to `true` by invocations like `forc test` or `forc check --tests`.
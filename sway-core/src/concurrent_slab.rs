@@ -5,8 +5,9 @@ use std::{
 };
 
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub(crate) struct ConcurrentSlab<T> {
     inner: RwLock<HashMap<usize, Arc<T>>>,
     last_id: Arc<RwLock<usize>>,
@@ -100,4 +101,17 @@ where
         inner.clear();
         inner.shrink_to(0);
     }
+
+    pub fn is_empty(&self) -> bool {
+        let inner = self.inner.read().unwrap();
+        inner.is_empty()
+    }
+
+    /// Replaces the contents of the slab with the contents of `other`.
+    pub fn restore(&self, other: Self) {
+        let mut inner = self.inner.write().unwrap();
+        let mut last_id = self.last_id.write().unwrap();
+        *inner = other.inner.into_inner().unwrap();
+        *last_id = *other.last_id.read().unwrap();
+    }
 }
@@ -2248,7 +2248,7 @@ fn connect_call_path_decl<'eng: 'cfg, 'cfg>(
 
 /// Checks [AttributesMap] for `#[allow(dead_code)]` usage, if so returns true
 /// otherwise returns false.
-fn allow_dead_code(attributes: AttributesMap) -> bool {
+pub(crate) fn allow_dead_code(attributes: AttributesMap) -> bool {
     fn allow_dead_code_helper(attributes: AttributesMap) -> Option<bool> {
         Some(
             attributes
@@ -40,4 +40,5 @@ mod analyze_return_paths;
 mod dead_code_analysis;
 mod flow_graph;
 
+pub(crate) use dead_code_analysis::allow_dead_code;
 pub use flow_graph::*;
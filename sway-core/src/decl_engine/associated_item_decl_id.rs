@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use sway_error::error::CompileError;
 use sway_types::{Named, Span, Spanned};
 
@@ -8,7 +9,7 @@ use crate::{
     Engines,
 };
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
 pub enum AssociatedItemDeclId {
     TraitFn(DeclId<ty::TyTraitFn>),
     Function(DeclId<ty::TyFunctionDecl>),
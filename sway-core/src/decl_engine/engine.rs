@@ -4,6 +4,7 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+use serde::{Deserialize, Serialize};
 use sway_types::{ModuleId, Named, Spanned};
 
 use crate::{
@@ -17,7 +18,7 @@ use crate::{
 };
 
 /// Used inside of type inference to store declarations.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct DeclEngine {
     function_slab: ConcurrentSlab<TyFunctionDecl>,
     trait_slab: ConcurrentSlab<TyTraitDecl>,
@@ -230,6 +231,22 @@ decl_engine_clear_module!(
 );
 
 impl DeclEngine {
+    /// Replaces the contents of the [DeclEngine] with the contents of `other`.
+    pub(crate) fn restore(&self, other: DeclEngine) {
+        self.function_slab.restore(other.function_slab);
+        self.trait_slab.restore(other.trait_slab);
+        self.trait_fn_slab.restore(other.trait_fn_slab);
+        self.trait_type_slab.restore(other.trait_type_slab);
+        self.impl_trait_slab.restore(other.impl_trait_slab);
+        self.struct_slab.restore(other.struct_slab);
+        self.storage_slab.restore(other.storage_slab);
+        self.abi_slab.restore(other.abi_slab);
+        self.constant_slab.restore(other.constant_slab);
+        self.enum_slab.restore(other.enum_slab);
+        self.type_alias_slab.restore(other.type_alias_slab);
+        *self.parents.write().unwrap() = other.parents.into_inner().unwrap();
+    }
+
     /// Given a [DeclRef] `index`, finds all the parents of `index` and all the
     /// recursive parents of those parents, and so on. Does not perform
     /// duplicated computation---if the parents of a [DeclRef] have already been
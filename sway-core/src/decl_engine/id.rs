@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::Hasher;
 use std::marker::PhantomData;
@@ -74,6 +75,18 @@ impl<T> Ord for DeclId<T> {
     }
 }
 
+impl<T> Serialize for DeclId<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for DeclId<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        DeclIdIndexType::deserialize(deserializer).map(DeclId::new)
+    }
+}
+
 impl<T> DeclId<T> {
     pub(crate) fn new(id: usize) -> Self {
         DeclId(id, PhantomData)
@@ -1,6 +1,7 @@
 use crate::{decl_engine::*, language::ty};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
 pub enum InterfaceDeclId {
     Abi(DeclId<ty::TyAbiDecl>),
     Trait(DeclId<ty::TyTraitDecl>),
@@ -8,13 +8,14 @@ use crate::{
     },
 };
 
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use sway_types::{ModuleId, Spanned};
 
 use super::parsed_id::ParsedDeclId;
 
 /// Used inside of type inference to store declarations.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ParsedDeclEngine {
     variable_slab: ConcurrentSlab<VariableDeclaration>,
     function_slab: ConcurrentSlab<FunctionDeclaration>,
@@ -172,6 +173,23 @@ decl_engine_clear_module!(
 );
 
 impl ParsedDeclEngine {
+    /// Replaces the contents of the [ParsedDeclEngine] with the contents of `other`.
+    pub(crate) fn restore(&self, other: ParsedDeclEngine) {
+        self.variable_slab.restore(other.variable_slab);
+        self.function_slab.restore(other.function_slab);
+        self.trait_slab.restore(other.trait_slab);
+        self.trait_fn_slab.restore(other.trait_fn_slab);
+        self.trait_type_slab.restore(other.trait_type_slab);
+        self.impl_trait_slab.restore(other.impl_trait_slab);
+        self.impl_self_slab.restore(other.impl_self_slab);
+        self.struct_slab.restore(other.struct_slab);
+        self.storage_slab.restore(other.storage_slab);
+        self.abi_slab.restore(other.abi_slab);
+        self.constant_slab.restore(other.constant_slab);
+        self.enum_slab.restore(other.enum_slab);
+        self.type_alias_slab.restore(other.type_alias_slab);
+    }
+
     /// Friendly helper method for calling the `get` method from the
     /// implementation of [ParsedDeclEngineGet] for [ParsedDeclEngine]
     ///
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 use std::{fmt, hash::Hash};
 
@@ -47,6 +48,18 @@ impl<T> Ord for ParsedDeclId<T> {
     }
 }
 
+impl<T> Serialize for ParsedDeclId<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ParsedDeclId<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ParsedDeclIdIndexType::deserialize(deserializer).map(ParsedDeclId::new)
+    }
+}
+
 impl<T> ParsedDeclId<T> {
     pub(crate) fn new(id: usize) -> Self {
         ParsedDeclId(id, PhantomData)
@@ -7,7 +7,9 @@
 /// [SubstList](crate::type_system::SubstList) contained in this field is simply
 /// a template for usages of the declaration declared in that particular
 /// [TyDecl](crate::language::ty::TyDecl) node.
-#[derive(Clone, Debug, Default)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Template<T>(T)
 where
     T: Clone;
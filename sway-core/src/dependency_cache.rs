@@ -0,0 +1,345 @@
+//! A persistent cache of the [Engines] state after compiling the dependencies of a package.
+//!
+//! Dependency packages like `core` and `std` rarely change between builds, yet type checking
+//! them dominates the build times of small packages. After compiling a sequence of dependency
+//! packages the whole [Engines] state, including the [QueryEngine] caches of the compiled
+//! programs, is written to disk. A later build compiling the same sequence of dependencies with
+//! the same compiler restores that state into its empty [Engines], so that `compile_to_ast` reuses
+//! the cached programs of the dependencies instead of compiling them again.
+
+use crate::{
+    decl_engine::{parsed_engine::ParsedDeclEngine, DeclEngine},
+    query_engine::QueryEngine,
+    type_system::{TypeEngine, TypeId, TypeSourceInfo},
+    BuildConfig, Engines,
+};
+use bincode::Options;
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use sway_types::{
+    span::{collect_span_sources, with_span_sources},
+    SourceEngine,
+};
+
+/// The version of the cache file format, bumped whenever the format of the cache files changes in
+/// a way that is not covered by the compiler fingerprint.
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// Identifies the [Engines] state after compiling a sequence of dependency packages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DependencyCacheKey {
+    /// Identifies the packages and their build configuration. Names the cache file, so that a
+    /// cache file is replaced rather than accumulated whenever the sources of a package change.
+    id: [u8; 32],
+    /// Identifies the compiler and the sources of the packages. A cache file is only restored if
+    /// its fingerprint matches.
+    fingerprint: [u8; 32],
+}
+
+impl Default for DependencyCacheKey {
+    /// The key of the empty sequence of packages.
+    ///
+    /// Besides the compiler version, the fingerprint includes the size and modification time of
+    /// the running executable, so that development builds of the compiler don't restore cache
+    /// files written by a compiler with a different serialization format.
+    fn default() -> Self {
+        let exe_stamp = std::env::current_exe()
+            .and_then(fs::metadata)
+            .map(|metadata| format!("{}:{:?}", metadata.len(), metadata.modified().ok()))
+            .unwrap_or_default();
+        DependencyCacheKey {
+            id: sha256(&[&CACHE_FORMAT_VERSION.to_le_bytes()]),
+            fingerprint: sha256(&[env!("CARGO_PKG_VERSION").as_bytes(), exe_stamp.as_bytes()]),
+        }
+    }
+}
+
+impl DependencyCacheKey {
+    /// Returns the key of the packages identified by `self` followed by the package named
+    /// `package_name`, compiled using `build_config`.
+    pub fn with_package(&self, package_name: &str, build_config: &BuildConfig) -> Self {
+        let root_module = build_config.canonical_root_module();
+        let config = format!(
+            "{:?}",
+            (
+                root_module.as_path(),
+                build_config.build_target,
+                build_config.include_tests,
+                build_config.experimental.new_encoding,
+                build_config.arithmetic_safety_analysis,
+                &build_config.lint_levels,
+                build_config
+                    .lsp_mode
+                    .as_ref()
+                    .map(|lsp_mode| lsp_mode.optimized_build),
+            )
+        );
+        let id = sha256(&[&self.id, package_name.as_bytes(), config.as_bytes()]);
+
+        // All the modules of a package live next to its root module.
+        let mut sources = root_module
+            .parent()
+            .map(|dir| sway_utils::get_sway_files(dir.to_path_buf()))
+            .unwrap_or_default();
+        sources.sort();
+        let mut hasher = Sha256::new();
+        hasher.update(self.fingerprint);
+        for path in sources {
+            hasher.update(path.to_string_lossy().as_bytes());
+            hasher.update(fs::read(&path).unwrap_or_default());
+        }
+
+        DependencyCacheKey {
+            id,
+            fingerprint: hasher.finalize().into(),
+        }
+    }
+}
+
+/// A directory of cached [Engines] states, see [DependencyCacheKey].
+#[derive(Clone, Debug)]
+pub struct DependencyCache {
+    dir: PathBuf,
+}
+
+impl DependencyCache {
+    pub fn new(dir: PathBuf) -> Self {
+        DependencyCache { dir }
+    }
+
+    /// Restores the [Engines] state cached for `key` into `engines`.
+    ///
+    /// Returns `false`, leaving `engines` untouched, if nothing is cached for `key`, if the cache
+    /// file cannot be read, or if `engines` were already used to compile something.
+    pub fn load(&self, key: &DependencyCacheKey, engines: &Engines) -> bool {
+        if !engines.is_empty() {
+            return false;
+        }
+        let Ok(Some(cached)) = self.read(key) else {
+            return false;
+        };
+
+        // The type engine hashes its types using the other engines, so it goes last.
+        engines.de().restore(cached.decl_engine);
+        engines.pe().restore(cached.parsed_decl_engine);
+        engines.qe().restore(cached.query_engine);
+        engines.se().restore(cached.source_engine);
+        engines
+            .te()
+            .restore(engines, cached.type_engine, cached.type_id_map);
+        true
+    }
+
+    /// Returns `true` if an [Engines] state is cached for `key`.
+    pub fn contains(&self, key: &DependencyCacheKey) -> bool {
+        let mut fingerprint = [0; 32];
+        fs::File::open(self.path(key))
+            .and_then(|mut file| file.read_exact(&mut fingerprint))
+            .is_ok_and(|()| fingerprint == key.fingerprint)
+    }
+
+    /// Caches the state of `engines` for `key`.
+    ///
+    /// Fails if any of the cached programs has compilation errors.
+    pub fn store(&self, key: &DependencyCacheKey, engines: &Engines) -> io::Result<()> {
+        let cached = CachedEnginesRef {
+            type_engine: engines.te(),
+            type_id_map: engines.te().id_map_entries(),
+            decl_engine: engines.de(),
+            parsed_decl_engine: engines.pe(),
+            query_engine: engines.qe(),
+            source_engine: engines.se(),
+        };
+        // The table of span sources is only complete after serializing the engines, but is
+        // written first so that it is available when deserializing them.
+        let (engines_bytes, sources) =
+            collect_span_sources(|| bincode_options().serialize(&cached));
+        let engines_bytes = engines_bytes.map_err(into_io_error)?;
+
+        // Write to a temporary file first so that concurrent builds never read a partial file.
+        static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+        fs::create_dir_all(&self.dir)?;
+        let temp_path = self.dir.join(format!(
+            ".{}.{}.{}",
+            hex(&key.id),
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed),
+        ));
+        let result = (|| {
+            let mut file = io::BufWriter::new(fs::File::create(&temp_path)?);
+            file.write_all(&key.fingerprint)?;
+            let mut payload = DeflateEncoder::new(file, Compression::fast());
+            bincode_options()
+                .serialize_into(&mut payload, &sources)
+                .map_err(into_io_error)?;
+            payload.write_all(&engines_bytes)?;
+            payload.finish()?.flush()?;
+            fs::rename(&temp_path, self.path(key))
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result
+    }
+
+    fn path(&self, key: &DependencyCacheKey) -> PathBuf {
+        self.dir.join(hex(&key.id))
+    }
+
+    fn read(&self, key: &DependencyCacheKey) -> io::Result<Option<CachedEngines>> {
+        let mut file = match fs::File::open(self.path(key)) {
+            Ok(file) => io::BufReader::new(file),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let mut fingerprint = [0; 32];
+        file.read_exact(&mut fingerprint)?;
+        if fingerprint != key.fingerprint {
+            return Ok(None);
+        }
+
+        let mut payload = io::BufReader::new(DeflateDecoder::new(file));
+        let sources: Vec<Arc<str>> = bincode_options()
+            .deserialize_from(&mut payload)
+            .map_err(into_io_error)?;
+        with_span_sources(sources, || bincode_options().deserialize_from(&mut payload))
+            .map(Some)
+            .map_err(into_io_error)
+    }
+}
+
+/// The cached [Engines] state, must be kept in sync with [CachedEngines].
+#[derive(Serialize)]
+struct CachedEnginesRef<'a> {
+    type_engine: &'a TypeEngine,
+    type_id_map: Vec<(TypeSourceInfo, TypeId)>,
+    decl_engine: &'a DeclEngine,
+    parsed_decl_engine: &'a ParsedDeclEngine,
+    query_engine: &'a QueryEngine,
+    source_engine: &'a SourceEngine,
+}
+
+#[derive(Deserialize)]
+struct CachedEngines {
+    type_engine: TypeEngine,
+    type_id_map: Vec<(TypeSourceInfo, TypeId)>,
+    decl_engine: DeclEngine,
+    parsed_decl_engine: ParsedDeclEngine,
+    query_engine: QueryEngine,
+    source_engine: SourceEngine,
+}
+
+/// Spans and ids make up most of the cached state, so integers are encoded with variable length.
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+fn sha256(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn into_io_error(err: bincode::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        language::{parsed::TreeType, ty},
+        namespace, Programs,
+    };
+    use std::path::Path;
+    use sway_error::handler::Handler;
+    use sway_types::Ident;
+
+    fn compile_library(engines: &Engines, dir: &Path) -> Programs {
+        let src_dir = dir.join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(dir.join("Forc.toml"), "").unwrap();
+        let src = "library;\n\npub struct Point {\n    pub x: u64,\n}\n\npub fn origin() -> Point {\n    Point { x: 0 }\n}\n";
+        fs::write(src_dir.join("lib.sw"), src).unwrap();
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            src_dir.join("lib.sw"),
+            dir.to_path_buf(),
+            Default::default(),
+        );
+        let namespace = namespace::Module {
+            name: Some(Ident::new_no_span("lib".to_string())),
+            ..Default::default()
+        };
+        crate::compile_to_ast(
+            &Handler::default(),
+            engines,
+            Arc::from(src),
+            namespace,
+            Some(&build_config),
+            "lib",
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn restores_compiled_programs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let cache = DependencyCache::new(dir.join("cache"));
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            dir.join("lib").join("src").join("lib.sw"),
+            dir.join("lib"),
+            Default::default(),
+        );
+
+        let engines = Engines::default();
+        let programs = compile_library(&engines, &dir.join("lib"));
+        assert_eq!(programs.metrics.reused_modules, 0);
+        let key = DependencyCacheKey::default().with_package("lib", &build_config);
+        assert!(!cache.contains(&key));
+        cache.store(&key, &engines).unwrap();
+        assert!(cache.contains(&key));
+
+        // Restoring requires unused engines and the same sources.
+        assert!(!cache.load(&key, &engines));
+        let restored = Engines::default();
+        let other_key = DependencyCacheKey::default().with_package("other", &build_config);
+        assert!(!cache.load(&other_key, &restored));
+        assert!(cache.load(&key, &restored));
+
+        let programs = compile_library(&restored, &dir.join("lib"));
+        assert_eq!(programs.metrics.reused_modules, 1);
+        let typed = programs.typed.unwrap();
+        assert!(matches!(typed.kind.tree_type(), TreeType::Library));
+        let return_types = typed
+            .root
+            .all_nodes
+            .iter()
+            .filter_map(|node| match &node.content {
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::FunctionDecl(decl)) => {
+                    let decl = restored.de().get_function(&decl.decl_id);
+                    Some(restored.help_out(decl.return_type.type_id).to_string())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(return_types, ["Point"]);
+    }
+}
@@ -56,6 +56,11 @@ impl Engines {
         &self.source_engine
     }
 
+    /// Returns `true` if nothing has been compiled using these engines yet.
+    pub fn is_empty(&self) -> bool {
+        self.type_engine.is_empty() && self.source_engine.is_empty()
+    }
+
     /// Removes all data associated with `module_id` from the declaration and type engines.
     /// It is intended to be used during garbage collection to remove any data that is no longer needed.
     pub fn clear_module(&mut self, module_id: &sway_types::ModuleId) {
@@ -87,10 +87,11 @@ pub(crate) fn check_function_purity(
         .unwrap_or_else(Span::dummy);
 
     // Simple closures for each of the error types.
-    let error = |span, storage_op, existing, needed| {
+    let error = |span: Span, storage_op, existing, needed| {
         handler.emit_err(CompileError::ImpureInPureContext {
             storage_op,
             attrs: promote_purity(existing, needed).to_attribute_syntax(),
+            fn_decl_span: (existing == Pure).then(|| span.clone()),
             span,
         });
     };
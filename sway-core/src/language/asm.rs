@@ -1,8 +1,9 @@
+use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
 use sway_types::{BaseIdent, Ident, Span};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AsmOp {
     pub(crate) op_name: Ident,
     pub(crate) op_args: Vec<Ident>,
@@ -43,7 +44,7 @@ impl PartialEq for AsmOp {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct AsmRegister {
     pub(crate) name: String,
 }
@@ -2,12 +2,13 @@ mod program;
 
 use crate::language::ModName;
 pub use program::LexedProgram;
+use serde::{Deserialize, Serialize};
 use sway_ast::Module;
 
 use super::{HasModule, HasSubmodules};
 
 /// A module and its submodules in the form of a tree.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LexedModule {
     /// The content of this module in the form of a [Module].
     pub tree: Module,
@@ -18,7 +19,7 @@ pub struct LexedModule {
 /// A library module that was declared as a `mod` of another module.
 ///
 /// Only submodules are guaranteed to be a `library`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LexedSubmodule {
     pub module: LexedModule,
 }
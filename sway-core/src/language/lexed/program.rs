@@ -1,10 +1,11 @@
 use super::LexedModule;
 use crate::language::parsed::TreeType;
+use serde::{Deserialize, Serialize};
 
 /// A lexed, but not yet parsed or type-checked, Sway program.
 ///
 /// Includes all modules in the form of a [LexedModule] tree accessed via the `root`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LexedProgram {
     pub kind: TreeType,
     pub root: LexedModule,
@@ -1,4 +1,5 @@
 use crate::{type_system::*, Engines};
+use serde::{Deserialize, Serialize};
 
 use sway_error::error::CompileError;
 use sway_types::{integer_bits::IntegerBits, span, u256::U256};
@@ -9,7 +10,7 @@ use std::{
     num::{IntErrorKind, ParseIntError},
 };
 
-#[derive(Debug, Clone, Eq)]
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub enum Literal {
     U8(u8),
     U16(u16),
@@ -1,8 +1,9 @@
 use crate::language::parsed::AstNode;
+use serde::{Deserialize, Serialize};
 
 use sway_types::{span::Span, Spanned};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeBlock {
     pub contents: Vec<AstNode>,
     pub(crate) whole_block_span: Span,
@@ -21,8 +21,9 @@ pub use type_alias::*;
 pub use variable::*;
 
 use crate::{decl_engine::parsed_id::ParsedDeclId, Engines};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Declaration {
     VariableDeclaration(ParsedDeclId<VariableDeclaration>),
     FunctionDeclaration(ParsedDeclId<FunctionDeclaration>),
@@ -1,4 +1,5 @@
 use crate::{decl_engine::parsed_id::ParsedDeclId, transform};
+use serde::{Deserialize, Serialize};
 
 use super::{FunctionDeclaration, Supertrait, TraitItem};
 
@@ -6,7 +7,7 @@ use sway_types::{ident::Ident, span::Span};
 
 /// An `abi` declaration, which declares an interface for a contract
 /// to implement or for a caller to use to call a contract.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AbiDeclaration {
     /// The name of the abi trait (also known as a "contract trait")
     pub name: Ident,
@@ -2,9 +2,10 @@ use crate::{
     language::{parsed::Expression, Visibility},
     transform, TypeArgument,
 };
+use serde::{Deserialize, Serialize};
 use sway_types::{Ident, Span};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConstantDeclaration {
     pub name: Ident,
     pub attributes: transform::AttributesMap,
@@ -1,7 +1,8 @@
 use crate::{language::Visibility, transform, type_system::*};
+use serde::{Deserialize, Serialize};
 use sway_types::{ident::Ident, span::Span};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumDeclaration {
     pub name: Ident,
     pub attributes: transform::AttributesMap,
@@ -11,7 +12,7 @@ pub struct EnumDeclaration {
     pub visibility: Visibility,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumVariant {
     pub name: Ident,
     pub attributes: transform::AttributesMap,
@@ -4,16 +4,17 @@ use crate::{
     transform::{self, AttributeKind},
     type_system::*,
 };
+use serde::{Deserialize, Serialize};
 use sway_types::{ident::Ident, span::Span};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FunctionDeclarationKind {
     Default,
     Entry,
     Test,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionDeclaration {
     pub purity: Purity,
     pub attributes: transform::AttributesMap,
@@ -28,7 +29,7 @@ pub struct FunctionDeclaration {
     pub kind: FunctionDeclarationKind,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionParameter {
     pub name: Ident,
     pub is_reference: bool,
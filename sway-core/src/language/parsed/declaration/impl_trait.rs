@@ -3,17 +3,18 @@ use crate::{
     decl_engine::parsed_id::ParsedDeclId, language::CallPath, type_system::TypeArgument,
     TypeParameter,
 };
+use serde::{Deserialize, Serialize};
 
 use sway_types::span::Span;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ImplItem {
     Fn(ParsedDeclId<FunctionDeclaration>),
     Constant(ParsedDeclId<ConstantDeclaration>),
     Type(ParsedDeclId<TraitTypeDeclaration>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImplTrait {
     pub impl_type_parameters: Vec<TypeParameter>,
     pub trait_name: CallPath,
@@ -26,7 +27,7 @@ pub struct ImplTrait {
 
 /// An impl of methods without a trait
 /// like `impl MyType { fn foo { .. } }`
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImplSelf {
     pub impl_type_parameters: Vec<TypeParameter>,
     pub implementing_for: TypeArgument,
@@ -1,7 +1,8 @@
 use crate::{language::Visibility, transform, type_system::TypeParameter, TypeArgument};
+use serde::{Deserialize, Serialize};
 use sway_types::{ident::Ident, span::Span};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructDeclaration {
     pub name: Ident,
     pub attributes: transform::AttributesMap,
@@ -11,7 +12,7 @@ pub struct StructDeclaration {
     pub(crate) span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructField {
     pub visibility: Visibility,
     pub name: Ident,
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
 use super::{ConstantDeclaration, FunctionDeclaration, FunctionParameter};
@@ -12,7 +13,7 @@ use crate::{
 use sway_error::handler::ErrorEmitted;
 use sway_types::{ident::Ident, span::Span, Spanned};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TraitItem {
     TraitFn(TraitFn),
     Constant(ParsedDeclId<ConstantDeclaration>),
@@ -21,7 +22,7 @@ pub enum TraitItem {
     Error(Box<[Span]>, ErrorEmitted),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitDeclaration {
     pub name: Ident,
     pub(crate) type_parameters: Vec<TypeParameter>,
@@ -33,7 +34,7 @@ pub struct TraitDeclaration {
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Supertrait {
     pub name: CallPath,
     pub decl_ref: Option<DeclRefTrait>,
@@ -68,7 +69,7 @@ impl HashWithEngines for Supertrait {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitFn {
     pub name: Ident,
     pub span: Span,
@@ -78,7 +79,7 @@ pub struct TraitFn {
     pub return_type: TypeArgument,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitTypeDeclaration {
     pub name: Ident,
     pub attributes: transform::AttributesMap,
@@ -1,8 +1,9 @@
 use crate::{language::Visibility, transform, type_system::*};
+use serde::{Deserialize, Serialize};
 
 use sway_types::{ident::Ident, span::Span};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeAliasDeclaration {
     pub name: Ident,
     pub attributes: transform::AttributesMap,
@@ -1,6 +1,7 @@
 use crate::{language::parsed::Expression, Ident, TypeArgument};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariableDeclaration {
     pub name: Ident,
     pub type_ascription: TypeArgument,
@@ -3,9 +3,10 @@ use crate::{
     language::{AsmOp, AsmRegister},
     TypeInfo,
 };
+use serde::{Deserialize, Serialize};
 use sway_types::{ident::Ident, span::Span};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AsmExpression {
     pub registers: Vec<AsmRegisterDeclaration>,
     pub(crate) body: Vec<AsmOp>,
@@ -14,7 +15,7 @@ pub struct AsmExpression {
     pub(crate) whole_block_span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AsmRegisterDeclaration {
     pub(crate) name: Ident,
     pub initializer: Option<Expression>,
@@ -1,7 +1,8 @@
 use super::{Expression, Scrutinee};
+use serde::{Deserialize, Serialize};
 use sway_types::span;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchBranch {
     pub scrutinee: Scrutinee,
     pub result: Expression,
@@ -1,9 +1,10 @@
 use crate::language::CallPath;
 use crate::type_system::TypeBinding;
 use crate::{Ident, TypeArgument, TypeId, TypeInfo};
+use serde::{Deserialize, Serialize};
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MethodName {
     /// Represents a method lookup with a type somewhere in the path
     /// like `a::b::C::d()` with `C` being the type.
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{cmp::Ordering, fmt, hash::Hasher};
 
 use crate::{
@@ -23,71 +24,71 @@ pub use scrutinee::*;
 use sway_ast::intrinsics::Intrinsic;
 
 /// Represents a parsed, but not yet type checked, [Expression](https://en.wikipedia.org/wiki/Expression_(computer_science)).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Expression {
     pub kind: ExpressionKind,
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FunctionApplicationExpression {
     pub call_path_binding: TypeBinding<CallPath>,
     pub arguments: Vec<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LazyOperatorExpression {
     pub op: LazyOp,
     pub lhs: Box<Expression>,
     pub rhs: Box<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TupleIndexExpression {
     pub prefix: Box<Expression>,
     pub index: usize,
     pub index_span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArrayExpression {
     pub contents: Vec<Expression>,
     pub length_span: Option<Span>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructExpression {
     pub call_path_binding: TypeBinding<CallPath>,
     pub fields: Vec<StructExpressionField>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IfExpression {
     pub condition: Box<Expression>,
     pub then: Box<Expression>,
     pub r#else: Option<Box<Expression>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchExpression {
     pub value: Box<Expression>,
     pub branches: Vec<MatchBranch>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MethodApplicationExpression {
     pub method_name_binding: TypeBinding<MethodName>,
     pub contract_call_params: Vec<StructExpressionField>,
     pub arguments: Vec<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubfieldExpression {
     pub prefix: Box<Expression>,
     pub field_to_access: Ident,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AmbiguousSuffix {
     /// If the suffix is a pair, the ambiguous part of the suffix.
     ///
@@ -111,7 +112,7 @@ impl Spanned for AmbiguousSuffix {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QualifiedPathRootTypes {
     pub ty: TypeArgument,
     pub as_trait: TypeId,
@@ -188,14 +189,14 @@ impl DebugWithEngines for QualifiedPathRootTypes {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AmbiguousPathExpression {
     pub qualified_path_root: Option<QualifiedPathRootTypes>,
     pub call_path_binding: TypeBinding<CallPath<AmbiguousSuffix>>,
     pub args: Vec<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DelineatedPathExpression {
     pub call_path_binding: TypeBinding<QualifiedCallPath>,
     /// When args is equal to Option::None then it means that the
@@ -204,49 +205,49 @@ pub struct DelineatedPathExpression {
     pub args: Option<Vec<Expression>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AbiCastExpression {
     pub abi_name: CallPath,
     pub address: Box<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArrayIndexExpression {
     pub prefix: Box<Expression>,
     pub index: Box<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageAccessExpression {
     pub field_names: Vec<Ident>,
     pub storage_keyword_span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntrinsicFunctionExpression {
     pub name: Ident,
     pub kind_binding: TypeBinding<Intrinsic>,
     pub arguments: Vec<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WhileLoopExpression {
     pub condition: Box<Expression>,
     pub body: CodeBlock,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForLoopExpression {
     pub desugared: Box<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReassignmentExpression {
     pub lhs: ReassignmentTarget,
     pub rhs: Box<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ExpressionKind {
     /// A malformed expression.
     ///
@@ -325,19 +326,19 @@ pub enum ExpressionKind {
     Deref(Box<Expression>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RefExpression {
     /// True if the reference is a reference to a mutable `value`.
     pub to_mutable_value: bool,
     pub value: Box<Expression>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ReassignmentTarget {
     VariableExpression(Box<Expression>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructExpressionField {
     pub name: Ident,
     pub value: Expression,
@@ -2,6 +2,7 @@ use crate::{
     language::{CallPath, Literal},
     TypeInfo,
 };
+use serde::{Deserialize, Serialize};
 
 use sway_error::handler::ErrorEmitted;
 use sway_types::{ident::Ident, span::Span, Spanned};
@@ -10,7 +11,7 @@ use sway_types::{ident::Ident, span::Span, Spanned};
 /// not a pattern will succeed at pattern matching and what, if any, elements will
 /// need to be implemented in a desugared if expression.
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Scrutinee {
     Or {
         elems: Vec<Scrutinee>,
@@ -49,7 +50,7 @@ pub enum Scrutinee {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum StructScrutineeField {
     Rest {
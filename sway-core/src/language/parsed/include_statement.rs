@@ -1,8 +1,9 @@
+use serde::{Deserialize, Serialize};
 use sway_types::{span::Span, Ident};
 
 use crate::language::Visibility;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IncludeStatement {
     // this span may be used for errors in the future, although it is not right now.
     pub span: Span,
@@ -13,6 +13,7 @@ pub use expression::*;
 pub use include_statement::IncludeStatement;
 pub use module::{ParseModule, ParseSubmodule};
 pub use program::{ParseProgram, TreeType};
+use serde::{Deserialize, Serialize};
 use sway_error::handler::ErrorEmitted;
 use sway_types::span::Span;
 pub use use_statement::{ImportType, UseStatement};
@@ -21,7 +22,7 @@ use crate::Engines;
 
 /// Represents some exportable information that results from compiling some
 /// Sway source code.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParseTree {
     /// The untyped AST nodes that constitute this tree's root nodes.
     pub root_nodes: Vec<AstNode>,
@@ -31,7 +32,7 @@ pub struct ParseTree {
 
 /// A single [AstNode] represents a node in the parse tree. Note that [AstNode]
 /// is a recursive type and can contain other [AstNode], thus populating the tree.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AstNode {
     /// The content of this ast node, which could be any control flow structure or other
     /// basic organizational component.
@@ -42,7 +43,7 @@ pub struct AstNode {
 
 /// Represents the various structures that constitute a Sway program.
 #[allow(clippy::large_enum_variant)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AstNodeContent {
     /// A statement of the form `use foo::bar;` or `use ::foo::bar;`
     UseStatement(UseStatement),
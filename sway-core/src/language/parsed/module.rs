@@ -2,6 +2,7 @@ use crate::{
     language::{HasModule, HasSubmodules, ModName, Visibility},
     transform,
 };
+use serde::{Deserialize, Serialize};
 
 use super::ParseTree;
 use sway_types::Span;
@@ -9,7 +10,7 @@ use sway_types::Span;
 pub type ModuleHash = u64;
 
 /// A module and its submodules in the form of a tree.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParseModule {
     /// The content of this module in the form of a `ParseTree`.
     pub tree: ParseTree,
@@ -27,7 +28,7 @@ pub struct ParseModule {
 /// A library module that was declared as a `mod` of another module.
 ///
 /// Only submodules are guaranteed to be a `library`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParseSubmodule {
     pub module: ParseModule,
     pub mod_name_span: Span,
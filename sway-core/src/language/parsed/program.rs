@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use strum::EnumString;
 
 use crate::Engines;
@@ -7,7 +8,7 @@ use super::ParseModule;
 /// A parsed, but not yet type-checked, Sway program.
 ///
 /// Includes all modules in the form of a `ParseModule` tree accessed via the `root`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParseProgram {
     pub kind: TreeType,
     pub root: ParseModule,
@@ -16,7 +17,7 @@ pub struct ParseProgram {
 /// A Sway program can be either a contract, script, predicate, or a library.
 ///
 /// All submodules declared with `dep` should be `Library`s.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString, Serialize, Deserialize)]
 pub enum TreeType {
     #[strum(serialize = "predicate")]
     Predicate,
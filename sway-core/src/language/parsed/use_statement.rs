@@ -1,7 +1,8 @@
 use crate::parsed::Span;
+use serde::{Deserialize, Serialize};
 use sway_types::ident::Ident;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ImportType {
     Star,
     SelfImport(Span),
@@ -9,7 +10,7 @@ pub enum ImportType {
 }
 
 /// A [UseStatement] is a statement that imports something from a module into the local namespace.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct UseStatement {
     pub call_path: Vec<Ident>,
     pub span: Span,
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use sway_error::handler::ErrorEmitted;
 use sway_utils::PerformanceData;
 
@@ -5,7 +6,7 @@ use super::{lexed::LexedProgram, parsed::ParseProgram, ty::TyProgram};
 
 /// Contains the lexed, parsed, typed compilation stages of a program, as well
 /// as compilation metrics.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Programs {
     pub lexed: LexedProgram,
     pub parsed: ParseProgram,
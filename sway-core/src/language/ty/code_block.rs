@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::hash::Hasher;
 
 use sway_error::handler::{ErrorEmitted, Handler};
@@ -8,7 +9,7 @@ use crate::{
     type_system::*,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TyCodeBlock {
     pub contents: Vec<TyAstNode>,
     pub(crate) whole_block_span: Span,
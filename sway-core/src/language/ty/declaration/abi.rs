@@ -1,4 +1,5 @@
 use crate::{engine_threading::*, language::parsed, transform, type_system::*};
+use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
 use sway_types::{Ident, Named, Span, Spanned};
@@ -7,7 +8,7 @@ use super::{TyTraitInterfaceItem, TyTraitItem};
 
 /// A [TyAbiDecl] contains the type-checked version of the parse tree's
 /// `AbiDeclaration`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TyAbiDecl {
     /// The name of the abi trait (also known as a "contract trait")
     pub name: Ident,
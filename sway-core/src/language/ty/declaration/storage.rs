@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
 use sway_error::{
@@ -14,7 +15,7 @@ use crate::{
     Namespace,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TyStorageDecl {
     pub fields: Vec<TyStorageField>,
     pub span: Span,
@@ -244,7 +245,7 @@ impl Spanned for TyStorageField {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TyStorageField {
     pub name: Ident,
     pub type_argument: TypeArgument,
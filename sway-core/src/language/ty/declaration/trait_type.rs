@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{
     fmt,
     hash::{Hash, Hasher},
@@ -7,7 +8,7 @@ use sway_types::{Ident, Named, Span, Spanned};
 
 use crate::{engine_threading::*, transform, type_system::*};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TyTraitType {
     pub name: Ident,
     pub attributes: transform::AttributesMap,
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
 use sway_error::handler::{ErrorEmitted, Handler};
@@ -13,7 +14,7 @@ use crate::{
     type_system::*,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TyVariableDecl {
     pub name: Ident,
     pub body: TyExpression,
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{fmt, hash::Hasher};
 
 use sway_error::{
@@ -19,7 +20,7 @@ use crate::{
     types::*,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TyExpression {
     pub expression: TyExpressionVariant,
     pub return_type: TypeId,
@@ -351,6 +352,7 @@ impl TyExpression {
         fn emit_warning_if_deprecated(
             attributes: &AttributesMap,
             span: &Span,
+            name_span: Option<&Span>,
             handler: &Handler,
             message: &str,
             allow_deprecated: &mut AllowDeprecatedState,
@@ -363,21 +365,32 @@ impl TyExpression {
                 .get(&AttributeKind::Deprecated)
                 .and_then(|x| x.last())
             {
-                let mut message = message.to_string();
-
-                if let Some(sway_ast::Literal::String(s)) = v
+                let string_arg = |name: &str| match v
                     .args
                     .iter()
-                    .find(|x| x.name.as_str() == "note")
+                    .find(|x| x.name.as_str() == name)
                     .and_then(|x| x.value.as_ref())
                 {
+                    Some(sway_ast::Literal::String(s)) => Some(s.parsed.clone()),
+                    _ => None,
+                };
+
+                let mut message = message.to_string();
+
+                if let Some(note) = string_arg("note") {
                     message.push_str(": ");
-                    message.push_str(s.parsed.as_str());
+                    message.push_str(note.as_str());
                 }
 
+                // The name to use instead, if the deprecated item suggests one.
+                let replacement = name_span.cloned().zip(string_arg("suggestion"));
+
                 handler.emit_warn(CompileWarning {
                     span: span.clone(),
-                    warning_content: Warning::UsingDeprecated { message },
+                    warning_content: Warning::UsingDeprecated {
+                        message,
+                        replacement,
+                    },
                 })
             }
         }
@@ -386,12 +399,14 @@ impl TyExpression {
             TyExpressionVariant::StructExpression {
                 struct_ref,
                 instantiation_span,
+                call_path_binding,
                 ..
             } => {
                 let s = engines.de().get(struct_ref.id());
                 emit_warning_if_deprecated(
                     &s.attributes,
                     instantiation_span,
+                    Some(&call_path_binding.inner.suffix.span()),
                     handler,
                     "deprecated struct",
                     allow_deprecated,
@@ -407,6 +422,7 @@ impl TyExpression {
                         emit_warning_if_deprecated(
                             &s.attributes,
                             &call_path.span(),
+                            None,
                             handler,
                             "deprecated struct",
                             allow_deprecated,
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{
     collections::VecDeque,
     fmt::{self, Write},
@@ -20,7 +21,7 @@ use crate::{
     type_system::*,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TyExpressionVariant {
     Literal(Literal),
     FunctionApplication {
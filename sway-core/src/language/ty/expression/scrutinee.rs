@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use sway_types::{Ident, Span};
 
 use crate::{
@@ -6,14 +7,14 @@ use crate::{
     type_system::*,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TyScrutinee {
     pub variant: TyScrutineeVariant,
     pub type_id: TypeId,
     pub span: Span,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TyScrutineeVariant {
     Or(Vec<TyScrutinee>),
     CatchAll,
@@ -36,7 +37,7 @@ pub enum TyScrutineeVariant {
     Tuple(Vec<TyScrutinee>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TyStructScrutineeField {
     pub field: Ident,
     pub scrutinee: Option<TyScrutinee>,
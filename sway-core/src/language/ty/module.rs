@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use sway_error::handler::{ErrorEmitted, Handler};
@@ -12,16 +13,18 @@ use crate::{
     Engines,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TyModule {
     pub span: Span,
     pub submodules: Vec<(ModName, TySubmodule)>,
+    /// Not serialized, see [TyModule::restore_namespaces].
+    #[serde(skip, default = "placeholder_namespace")]
     pub namespace: namespace::Namespace,
     pub all_nodes: Vec<TyAstNode>,
     pub attributes: transform::AttributesMap,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TySubmodule {
     pub module: TyModule,
     pub mod_name_span: Span,
@@ -38,7 +41,26 @@ pub struct SubmodulesRecursive<'module> {
     )>,
 }
 
+fn placeholder_namespace() -> namespace::Namespace {
+    namespace::Namespace::init_root(namespace::Module::default())
+}
+
 impl TyModule {
+    /// Restores the namespaces of this module and its submodules after deserialization.
+    ///
+    /// The namespace of each submodule is a snapshot of the namespace of the root module, which
+    /// ends up containing all of them. Serializing all of the snapshots would repeat most of
+    /// the root namespace for each submodule, so only the root namespace is serialized and the
+    /// submodules get views of it at their module paths.
+    pub(crate) fn restore_namespaces(&mut self, namespace: namespace::Namespace) {
+        for (name, submodule) in self.submodules.iter_mut() {
+            let mut submodule_namespace = namespace.clone();
+            submodule_namespace.mod_path.push(name.clone());
+            submodule.module.restore_namespaces(submodule_namespace);
+        }
+        self.namespace = namespace;
+    }
+
     /// An iterator yielding all submodules recursively, depth-first.
     pub fn submodules_recursive(&self) -> SubmodulesRecursive {
         SubmodulesRecursive {
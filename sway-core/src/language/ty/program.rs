@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::{
@@ -16,9 +17,10 @@ use sway_error::{
 };
 use sway_types::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TyProgram {
     pub kind: TyProgramKind,
+    #[serde(with = "root_module")]
     pub root: TyModule,
     pub declarations: Vec<TyDecl>,
     pub configurables: Vec<TyConstantDecl>,
@@ -27,6 +29,28 @@ pub struct TyProgram {
     pub messages_types: Vec<(MessageId, TypeId)>,
 }
 
+/// Serializes the root module together with its namespace, see [TyModule::restore_namespaces].
+mod root_module {
+    use super::TyModule;
+    use crate::semantic_analysis::namespace::Namespace;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        root: &TyModule,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        (&root.namespace, root).serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TyModule, D::Error> {
+        let (namespace, mut root): (Namespace, TyModule) = Deserialize::deserialize(deserializer)?;
+        root.restore_namespaces(namespace);
+        Ok(root)
+    }
+}
+
 fn get_type_not_allowed_error(
     engines: &Engines,
     type_id: TypeId,
@@ -521,7 +545,7 @@ impl CollectTypesMetadata for TyProgram {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TyProgramKind {
     Contract {
         entry_function: Option<DeclId<TyFunctionDecl>>,
@@ -1,8 +1,9 @@
 use crate::language::Visibility;
+use serde::{Deserialize, Serialize};
 
 use sway_types::{ident::Ident, Span, Spanned};
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TyIncludeStatement {
     pub span: Span,
     pub visibility: Visibility,
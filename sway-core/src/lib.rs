@@ -12,8 +12,10 @@ pub mod compiler_generated;
 mod concurrent_slab;
 mod control_flow_analysis;
 pub mod decl_engine;
+pub mod dependency_cache;
 pub mod ir_generation;
 pub mod language;
+mod lint_levels;
 mod metadata;
 pub mod query_engine;
 pub mod semantic_analysis;
@@ -39,13 +41,16 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use sway_ast::AttributeDecl;
 use sway_error::handler::{ErrorEmitted, Handler};
+use sway_error::lint::LintLevel;
 use sway_ir::{
-    create_o1_pass_group, register_known_passes, Context, Kind, Module, PassGroup, PassManager,
-    ARGDEMOTION_NAME, CONSTDEMOTION_NAME, DCE_NAME, INLINE_MODULE_NAME, MEM2REG_NAME,
-    MEMCPYOPT_NAME, MISCDEMOTION_NAME, MODULEPRINTER_NAME, RETDEMOTION_NAME, SIMPLIFYCFG_NAME,
-    SROA_NAME,
+    create_o1_pass_group, create_oz_pass_group, register_known_passes, Context, Kind, Module,
+    PassGroup, PassManager, ARGDEMOTION_NAME, CONSTDEMOTION_NAME, DCE_NAME, INLINE_MODULE_NAME,
+    MEM2REG_NAME, MEMCPYOPT_NAME, MISCDEMOTION_NAME, MODULEPRINTER_NAME, RETDEMOTION_NAME,
+    SIMPLIFYCFG_NAME, SROA_NAME,
+};
+use sway_types::constants::{
+    ALLOW_ATTRIBUTE_NAME, DENY_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME, WARN_ATTRIBUTE_NAME,
 };
-use sway_types::constants::DOC_COMMENT_ATTRIBUTE_NAME;
 use sway_types::SourceEngine;
 use sway_utils::{time_expr, PerformanceData, PerformanceMetric};
 use transform::{Attribute, AttributeArg, AttributeKind, AttributesMap};
@@ -142,8 +147,15 @@ fn module_attrs_to_map(
         let attrs = attr_decl.attribute.get().into_iter();
         for attr in attrs {
             let name = attr.name.as_str();
-            if name != DOC_COMMENT_ATTRIBUTE_NAME {
-                // prevent using anything except doc comment attributes
+            let attr_kind = match name {
+                DOC_COMMENT_ATTRIBUTE_NAME => Some(AttributeKind::DocComment),
+                ALLOW_ATTRIBUTE_NAME => Some(AttributeKind::Allow),
+                WARN_ATTRIBUTE_NAME => Some(AttributeKind::Warn),
+                DENY_ATTRIBUTE_NAME => Some(AttributeKind::Deny),
+                _ => None,
+            };
+            if attr_kind.is_none() {
+                // prevent using anything except doc comment and lint level attributes
                 handler.emit_err(CompileError::ExpectedModuleDocComment {
                     span: attr.name.span(),
                 });
@@ -172,14 +184,12 @@ fn module_attrs_to_map(
                 span: attr_decl.span(),
             };
 
-            if let Some(attr_kind) = match name {
-                DOC_COMMENT_ATTRIBUTE_NAME => Some(AttributeKind::DocComment),
-                _ => None,
-            } {
+            if let Some(attr_kind) = attr_kind {
                 attrs_map.entry(attr_kind).or_default().push(attribute);
             }
         }
     }
+    to_parsed_lang::check_attribute_args(handler, &attrs_map);
     Ok(AttributesMap::new(Arc::new(attrs_map)))
 }
 
@@ -599,6 +609,35 @@ pub fn parsed_to_ast(
         handler.emit_warn(warn);
     }
 
+    // Storage fields that are only ever written or only ever read
+    let storage_access_warnings =
+        semantic_analysis::storage_access_analysis::analyze_program(engines, &typed_program);
+    for warn in storage_access_warnings {
+        handler.emit_warn(warn);
+    }
+
+    // Mutable variables which are never changed
+    let unused_mut_warnings =
+        semantic_analysis::unused_mut_analysis::analyze_program(engines, &typed_program);
+    for warn in unused_mut_warnings {
+        handler.emit_warn(warn);
+    }
+
+    // Arithmetic safety analysis, if asked for either directly or by setting one of its lints
+    if build_config.is_some_and(|cfg| {
+        cfg.arithmetic_safety_analysis
+            || cfg
+                .lint_levels
+                .iter()
+                .any(|(lint, level)| lint.is_opt_in() && *level != LintLevel::Allow)
+    }) {
+        let arithmetic_safety_warnings =
+            semantic_analysis::arithmetic_safety_analysis::analyze_program(engines, &typed_program);
+        for warn in arithmetic_safety_warnings {
+            handler.emit_warn(warn);
+        }
+    }
+
     // Check that all storage initializers can be evaluated at compile time.
     let typed_wiss_res = typed_program.get_typed_program_with_initialized_storage_slots(
         handler,
@@ -714,6 +753,9 @@ pub fn compile_to_ast(
 
     let programs = Programs::new(lexed_program, parsed_program, typed_res, metrics);
 
+    // Denied warnings are reported as errors, but the programs are still returned in full.
+    let _ = lint_levels::apply_lint_levels(handler, &programs, build_config);
+
     if let Some(config) = build_config {
         let path = config.canonical_root_module();
         let cache_entry = ProgramsCacheEntry {
@@ -764,7 +806,12 @@ pub fn ast_to_asm(
         Err(err) => return Err(*err),
     };
 
-    let asm = match compile_ast_to_ir_to_asm(handler, engines, typed_program, build_config) {
+    let asm_res = compile_ast_to_ir_to_asm(handler, engines, typed_program, build_config);
+
+    // Code generation emits warnings of its own, e.g. about unneeded storage attributes.
+    let lints_res = lint_levels::apply_lint_levels(handler, programs, Some(build_config));
+
+    let asm = match asm_res.and_then(|asm| lints_res.map(|_| asm)) {
         Ok(res) => res,
         Err(err) => {
             handler.dedup();
@@ -828,14 +875,29 @@ pub(crate) fn compile_ast_to_ir_to_asm(
     register_known_passes(&mut pass_mgr);
     let mut pass_group = PassGroup::default();
 
-    match build_config.optimization_level {
-        OptLevel::Opt1 => {
-            pass_group.append_group(create_o1_pass_group());
-        }
-        OptLevel::Opt0 => {
-            // Inlining is necessary until #4899 is resolved.
-            pass_group.append_pass(INLINE_MODULE_NAME);
-        }
+    match &build_config.ir_passes {
+        // A custom pipeline replaces the one of the optimization level.
+        Some(pipeline) => match pass_mgr.parse_pipeline(pipeline) {
+            Ok(group) => pass_group.append_group(group),
+            Err(ir_error) => {
+                return Err(handler.emit_err(CompileError::InvalidIrPassPipeline(
+                    ir_error.to_string(),
+                    span::Span::dummy(),
+                )))
+            }
+        },
+        None => match build_config.optimization_level {
+            OptLevel::Opt1 => {
+                pass_group.append_group(create_o1_pass_group());
+            }
+            OptLevel::OptSize => {
+                pass_group.append_group(create_oz_pass_group());
+            }
+            OptLevel::Opt0 => {
+                // Inlining is necessary until #4899 is resolved.
+                pass_group.append_pass(INLINE_MODULE_NAME);
+            }
+        },
     }
 
     // Target specific transforms should be moved into something more configured.
@@ -857,7 +919,7 @@ pub(crate) fn compile_ast_to_ir_to_asm(
         pass_group.append_pass(SIMPLIFYCFG_NAME);
 
         match build_config.optimization_level {
-            OptLevel::Opt1 => {
+            OptLevel::Opt1 | OptLevel::OptSize => {
                 pass_group.append_pass(SROA_NAME);
                 pass_group.append_pass(MEM2REG_NAME);
                 pass_group.append_pass(DCE_NAME);
@@ -881,6 +943,15 @@ pub(crate) fn compile_ast_to_ir_to_asm(
     };
     res?;
 
+    if build_config.time_phases {
+        for (name, stats) in pass_mgr.stats() {
+            println!(
+                "  Time elapsed to run IR pass {name}: {:?} ({} runs, {} modifying)",
+                stats.elapsed, stats.runs, stats.modifying_runs
+            );
+        }
+    }
+
     let final_asm = compile_ir_to_asm(handler, &ir, Some(build_config))?;
 
     Ok(final_asm)
@@ -0,0 +1,391 @@
+//! Applies lint levels to the warnings emitted while compiling a package.
+//!
+//! The level of a warning's lint is taken from the innermost item annotated with `#[allow(..)]`,
+//! `#[warn(..)]` or `#[deny(..)]` for that lint, then from the `#![..]` attributes of the
+//! module the warning is in, and then from the package wide levels in the [BuildConfig].
+//! Warnings of allowed lints are dropped and warnings of denied lints are turned into errors.
+
+use std::collections::{BTreeMap, HashMap};
+
+use sway_ast::{attribute::Annotated, AttributeDecl, ItemKind, Module};
+use sway_error::{
+    error::CompileError,
+    handler::{ErrorEmitted, Handler},
+    lint::{Lint, LintLevel},
+    warning::CompileWarning,
+};
+use sway_types::{
+    constants::{ALLOW_ATTRIBUTE_NAME, DENY_ATTRIBUTE_NAME, WARN_ATTRIBUTE_NAME},
+    SourceId, Span, Spanned,
+};
+
+use crate::{
+    language::{HasModule, HasSubmodules, Programs},
+    BuildConfig,
+};
+
+/// Applies the lint levels set in the `programs` and in the `build_config` to the warnings
+/// collected so far in the `handler`.
+///
+/// Applying the levels more than once is harmless, so this can be called after every
+/// compilation phase which emits warnings. Returns an error if any of the warnings was denied.
+pub(crate) fn apply_lint_levels(
+    handler: &Handler,
+    programs: &Programs,
+    build_config: Option<&BuildConfig>,
+) -> Result<(), ErrorEmitted> {
+    let scopes = LintScopes::new(programs, build_config);
+    let mut denied = vec![];
+    handler.retain_warnings(|warning| match scopes.level_of(warning) {
+        LintLevel::Allow => false,
+        LintLevel::Warn => true,
+        LintLevel::Deny => {
+            denied.push(warning.clone());
+            false
+        }
+    });
+    let mut res = Ok(());
+    for warning in denied {
+        res = Err(handler.emit_err(CompileError::DeniedLint {
+            lint: warning.warning_content.lint(),
+            warning: warning.warning_content,
+            span: warning.span,
+        }));
+    }
+    res
+}
+
+/// Lint levels set on items and modules, and for the whole package.
+struct LintScopes<'a> {
+    /// Levels set on items, together with the spans of the items, attributes included.
+    items: Vec<(Span, Vec<(Lint, LintLevel)>)>,
+    /// Levels set for whole modules by their inner attributes.
+    modules: HashMap<SourceId, Vec<(Lint, LintLevel)>>,
+    /// Levels set for the whole package.
+    package: Option<&'a BTreeMap<Lint, LintLevel>>,
+}
+
+impl<'a> LintScopes<'a> {
+    fn new(programs: &Programs, build_config: Option<&'a BuildConfig>) -> Self {
+        let mut scopes = LintScopes {
+            items: vec![],
+            modules: HashMap::new(),
+            package: build_config.map(|config| &config.lint_levels),
+        };
+
+        let parsed_modules = std::iter::once(&programs.parsed.root).chain(
+            programs
+                .parsed
+                .root
+                .submodules_recursive()
+                .map(|(_, submodule)| submodule.module()),
+        );
+        for module in parsed_modules {
+            let levels = module.attributes.lint_levels();
+            if let (false, Some(source_id)) =
+                (levels.is_empty(), module.module_kind_span.source_id())
+            {
+                scopes.modules.insert(*source_id, levels);
+            }
+        }
+
+        let lexed_modules = std::iter::once(&programs.lexed.root).chain(
+            programs
+                .lexed
+                .root
+                .submodules_recursive()
+                .map(|(_, submodule)| submodule.module()),
+        );
+        for module in lexed_modules {
+            scopes.collect_module(&module.tree);
+        }
+
+        scopes
+    }
+
+    fn collect_module(&mut self, module: &Module) {
+        for item in &module.items {
+            self.collect(item);
+            match &item.value {
+                ItemKind::Struct(item_struct) => {
+                    for field in item_struct.fields.get() {
+                        self.collect(field);
+                    }
+                }
+                ItemKind::Enum(item_enum) => {
+                    for variant in item_enum.fields.get() {
+                        self.collect(variant);
+                    }
+                }
+                ItemKind::Trait(item_trait) => {
+                    for trait_item in item_trait.trait_items.get() {
+                        self.collect(trait_item);
+                    }
+                    for trait_def in item_trait.trait_defs_opt.iter().flat_map(|d| d.get()) {
+                        self.collect(trait_def);
+                    }
+                }
+                ItemKind::Abi(item_abi) => {
+                    for abi_item in item_abi.abi_items.get() {
+                        self.collect(abi_item);
+                    }
+                    for abi_def in item_abi.abi_defs_opt.iter().flat_map(|d| d.get()) {
+                        self.collect(abi_def);
+                    }
+                }
+                ItemKind::Impl(item_impl) => {
+                    for impl_item in item_impl.contents.get() {
+                        self.collect(impl_item);
+                    }
+                }
+                ItemKind::Storage(item_storage) => {
+                    for field in item_storage.fields.get() {
+                        self.collect(field);
+                    }
+                }
+                ItemKind::Configurable(item_configurable) => {
+                    for field in item_configurable.fields.get() {
+                        self.collect(field);
+                    }
+                }
+                ItemKind::Submodule(_)
+                | ItemKind::Use(_)
+                | ItemKind::Fn(_)
+                | ItemKind::Const(_)
+                | ItemKind::TypeAlias(_)
+                | ItemKind::Error(..) => {}
+            }
+        }
+    }
+
+    /// Records the levels set by the attributes of the `annotated` item, if any.
+    fn collect<T: Spanned>(&mut self, annotated: &Annotated<T>) {
+        let levels = lint_levels(&annotated.attribute_list);
+        if levels.is_empty() {
+            return;
+        }
+        let span = match annotated.attribute_list.first() {
+            Some(attr) => Span::join(attr.span(), annotated.value.span()),
+            None => annotated.value.span(),
+        };
+        self.items.push((span, levels));
+    }
+
+    fn level_of(&self, warning: &CompileWarning) -> LintLevel {
+        let lint = warning.warning_content.lint();
+        let last_level_of = |levels: &[(Lint, LintLevel)]| {
+            levels
+                .iter()
+                .rev()
+                .find(|(l, _)| *l == lint)
+                .map(|(_, level)| *level)
+        };
+
+        let innermost_item_level = self
+            .items
+            .iter()
+            .filter(|(span, _)| contains(span, &warning.span))
+            .filter_map(|(span, levels)| Some((span.end() - span.start(), last_level_of(levels)?)))
+            .min_by_key(|(len, _)| *len)
+            .map(|(_, level)| level);
+
+        innermost_item_level
+            .or_else(|| {
+                let source_id = warning.span.source_id()?;
+                last_level_of(self.modules.get(source_id)?)
+            })
+            .or_else(|| self.package?.get(&lint).copied())
+            .unwrap_or_default()
+    }
+}
+
+/// Returns true if `inner` is within `outer`.
+fn contains(outer: &Span, inner: &Span) -> bool {
+    outer.source_id() == inner.source_id()
+        && outer.start() <= inner.start()
+        && inner.end() <= outer.end()
+}
+
+/// Returns the lint levels set by the `allow`, `warn` and `deny` attributes in `attribute_list`,
+/// in the order in which they appear. Unknown lint names are reported elsewhere and skipped.
+fn lint_levels(attribute_list: &[AttributeDecl]) -> Vec<(Lint, LintLevel)> {
+    attribute_list
+        .iter()
+        .flat_map(|attr_decl| attr_decl.attribute.get().into_iter())
+        .filter_map(|attr| {
+            let level = match attr.name.as_str() {
+                ALLOW_ATTRIBUTE_NAME => LintLevel::Allow,
+                WARN_ATTRIBUTE_NAME => LintLevel::Warn,
+                DENY_ATTRIBUTE_NAME => LintLevel::Deny,
+                _ => return None,
+            };
+            Some((attr, level))
+        })
+        .flat_map(|(attr, level)| {
+            attr.args
+                .iter()
+                .flat_map(|args| args.get().into_iter())
+                .filter_map(move |arg| Some((arg.name.as_str().parse().ok()?, level)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{namespace, BuildTarget, Engines};
+    use std::{path::PathBuf, sync::Arc};
+    use sway_error::warning::Warning;
+    use sway_types::Ident;
+
+    /// Compiles the library `src`, which cannot have dependencies, with the lint levels of the
+    /// `package`, and returns the compiled programs, the build config and the handler.
+    fn compile(src: &str, package: &[(Lint, LintLevel)]) -> (Programs, BuildConfig, Handler) {
+        let engines = Engines::default();
+        let handler = Handler::default();
+        let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+            PathBuf::from("/lint_levels_test/src/lib.sw"),
+            PathBuf::from("/lint_levels_test"),
+            BuildTarget::default(),
+        )
+        .with_lint_levels(package.iter().copied().collect());
+        let initial_namespace = namespace::Module {
+            name: Some(Ident::new_no_span("lint_levels_test".to_string())),
+            ..Default::default()
+        };
+        let programs = crate::compile_to_ast(
+            &handler,
+            &engines,
+            Arc::from(src),
+            initial_namespace,
+            Some(&build_config),
+            "lint_levels_test",
+            None,
+        )
+        .expect("the test library compiles");
+        (programs, build_config, handler)
+    }
+
+    /// The names of the variables with unused `mut` reported as warnings and as denied lints.
+    fn unused_muts(handler: Handler) -> (Vec<String>, Vec<String>) {
+        let (errors, warnings) = handler.consume();
+        let warned = warnings
+            .into_iter()
+            .filter_map(|warning| match warning.warning_content {
+                Warning::UnusedMut { name, .. } => Some(name.to_string()),
+                _ => None,
+            })
+            .collect();
+        let denied = errors
+            .into_iter()
+            .map(|error| match error {
+                CompileError::DeniedLint {
+                    warning: Warning::UnusedMut { name, .. },
+                    ..
+                } => name.to_string(),
+                error => panic!("unexpected error: {error}"),
+            })
+            .collect();
+        (warned, denied)
+    }
+
+    const ITEMS: &str = r#"
+struct S {}
+
+#[allow(unused_mut)]
+impl S {
+    #[warn(unused_mut)]
+    fn item() {
+        let mut a = 1;
+        let _ = a;
+    }
+
+    fn enclosing_item() {
+        let mut b = 1;
+        let _ = b;
+    }
+}
+
+fn module() {
+    let mut c = 1;
+    let _ = c;
+}
+"#;
+
+    #[test]
+    fn warn_by_default() {
+        let (_, _, handler) = compile("library;\nfn f() { let mut a = 1; let _ = a; }", &[]);
+        assert_eq!(unused_muts(handler), (vec!["a".to_string()], vec![]));
+    }
+
+    #[test]
+    fn item_level_comes_before_enclosing_item_level() {
+        let (_, _, handler) = compile(&format!("library;\n{ITEMS}"), &[]);
+        assert_eq!(
+            unused_muts(handler),
+            (vec!["a".to_string(), "c".to_string()], vec![])
+        );
+    }
+
+    #[test]
+    fn enclosing_item_level_comes_before_module_level() {
+        let (_, _, handler) = compile(&format!("#![deny(unused_mut)]\nlibrary;\n{ITEMS}"), &[]);
+        assert_eq!(
+            unused_muts(handler),
+            (vec!["a".to_string()], vec!["c".to_string()])
+        );
+    }
+
+    #[test]
+    fn module_level_comes_before_package_level() {
+        let (_, _, handler) = compile(
+            &format!("#![warn(unused_mut)]\nlibrary;\n{ITEMS}"),
+            &[(Lint::UnusedMut, LintLevel::Deny)],
+        );
+        assert_eq!(
+            unused_muts(handler),
+            (vec!["a".to_string(), "c".to_string()], vec![])
+        );
+    }
+
+    #[test]
+    fn package_level_comes_last() {
+        let (_, _, handler) = compile(
+            &format!("library;\n{ITEMS}"),
+            &[(Lint::UnusedMut, LintLevel::Deny)],
+        );
+        assert_eq!(
+            unused_muts(handler),
+            (vec!["a".to_string()], vec!["c".to_string()])
+        );
+
+        let (_, _, handler) = compile(
+            &format!("library;\n{ITEMS}"),
+            &[(Lint::UnusedMut, LintLevel::Allow)],
+        );
+        assert_eq!(unused_muts(handler), (vec!["a".to_string()], vec![]));
+    }
+
+    #[test]
+    fn deny_turns_warnings_into_errors() {
+        let (_, _, handler) = compile(
+            "#![deny(unused_mut)]\nlibrary;\nfn f() { let mut a = 1; let _ = a; }",
+            &[],
+        );
+        assert!(handler.has_errors());
+        assert_eq!(unused_muts(handler), (vec![], vec!["a".to_string()]));
+    }
+
+    #[test]
+    fn applying_lint_levels_twice_is_harmless() {
+        let (programs, build_config, handler) = compile(
+            &format!("library;\n{ITEMS}"),
+            &[(Lint::UnusedMut, LintLevel::Deny)],
+        );
+        assert!(apply_lint_levels(&handler, &programs, Some(&build_config)).is_ok());
+        assert_eq!(
+            unused_muts(handler),
+            (vec!["a".to_string()], vec!["c".to_string()])
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::RwLock;
 use std::time::SystemTime;
@@ -10,7 +11,7 @@ use crate::Programs;
 
 pub type ModulePath = Arc<PathBuf>;
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ModuleCacheKey {
     pub path: Arc<PathBuf>,
     pub include_tests: bool,
@@ -25,7 +26,7 @@ impl ModuleCacheKey {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ModuleCacheEntry {
     pub path: ModulePath,
     pub modified_time: Option<SystemTime>,
@@ -36,16 +37,42 @@ pub struct ModuleCacheEntry {
 
 pub type ModuleCacheMap = HashMap<ModuleCacheKey, ModuleCacheEntry>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProgramsCacheEntry {
     pub path: ModulePath,
     pub programs: Programs,
+    #[serde(with = "handler_data")]
     pub handler_data: (Vec<CompileError>, Vec<CompileWarning>),
 }
 
+/// Only the warnings of a cached compilation are serialized, entries with errors cannot be
+/// serialized, so that [CompileError] does not need to be deserializable.
+mod handler_data {
+    use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+    use sway_error::{error::CompileError, warning::CompileWarning};
+
+    pub fn serialize<S: Serializer>(
+        (errors, warnings): &(Vec<CompileError>, Vec<CompileWarning>),
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if !errors.is_empty() {
+            return Err(ser::Error::custom(
+                "compilation errors cannot be serialized",
+            ));
+        }
+        warnings.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<(Vec<CompileError>, Vec<CompileWarning>), D::Error> {
+        Ok((vec![], Vec::deserialize(deserializer)?))
+    }
+}
+
 pub type ProgramsCacheMap = HashMap<ModulePath, ProgramsCacheEntry>;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct QueryEngine {
     parse_module_cache: RwLock<ModuleCacheMap>,
     programs_cache: RwLock<ProgramsCacheMap>,
@@ -86,4 +113,10 @@ impl QueryEngine {
         let mut cache = self.programs_cache.write().unwrap();
         cache.insert(entry.path.clone(), entry);
     }
+
+    /// Replaces the contents of the [QueryEngine] with the contents of `other`.
+    pub(crate) fn restore(&self, other: QueryEngine) {
+        *self.parse_module_cache.write().unwrap() = other.parse_module_cache.into_inner().unwrap();
+        *self.programs_cache.write().unwrap() = other.programs_cache.into_inner().unwrap();
+    }
 }
@@ -1,14 +1,20 @@
 //! Type checking for Sway.
+pub(crate) mod arithmetic_safety_analysis;
 pub mod ast_node;
 pub(crate) mod cei_pattern_analysis;
 pub(crate) mod coins_analysis;
+pub(crate) mod import_candidates;
 mod module;
 pub mod namespace;
 mod node_dependencies;
 mod program;
+pub mod storage_access_analysis;
+#[cfg(test)]
+mod test_utils;
 mod type_check_analysis;
 pub(crate) mod type_check_context;
 mod type_check_finalization;
+pub(crate) mod unused_mut_analysis;
 pub use ast_node::*;
 pub use namespace::Namespace;
 pub(crate) use type_check_analysis::*;
@@ -0,0 +1,525 @@
+// Arithmetic safety analysis warns about integer arithmetic which is likely to revert at runtime
+// because of values the contract does not control:
+// - arithmetic on ABI method arguments, which are chosen by the caller, before their bounds
+//   are checked,
+// - conversions from wider to narrower unsigned integers through `asm` blocks, which
+//   silently truncate the value, before its bounds are checked,
+// - divisions and remainders by values which could be zero.
+// The analysis is opt-in and intraprocedural: a value counts as checked once it has been
+// compared to something earlier in the same function, on every path leading to the arithmetic,
+// e.g. in a `require` or in the condition of an `if` which reverts, regardless of what the
+// comparison is.
+// Each kind of warning can be disabled for a function with `#[allow(unchecked_arithmetic)]`,
+// `#[allow(unchecked_narrowing)]` and `#[allow(division_by_zero)]` respectively.
+
+use crate::{
+    decl_engine::*,
+    language::{
+        ty::{self, TyFunctionDecl},
+        Literal,
+    },
+    transform::{AttributeKind, AttributesMap},
+    Engines, TypeId, TypeInfo,
+};
+use std::collections::{HashMap, HashSet};
+use sway_error::warning::{CompileWarning, Warning};
+use sway_types::{
+    constants::{
+        ALLOW_DIVISION_BY_ZERO_NAME, ALLOW_UNCHECKED_ARITHMETIC_NAME,
+        ALLOW_UNCHECKED_NARROWING_NAME,
+    },
+    integer_bits::IntegerBits,
+    u256::U256,
+};
+
+// The state of the analysis of a single function.
+struct FunctionAnalysis<'eng, 'w> {
+    engines: &'eng Engines,
+    check_arithmetic: bool,
+    check_narrowing: bool,
+    check_division: bool,
+    // The local variables holding (a copy of) an ABI method argument, with the name of
+    // the argument.
+    abi_arguments: HashMap<String, String>,
+    // The places, i.e. variables possibly followed by struct field and tuple element
+    // accesses, whose value has been compared to something.
+    checked: HashSet<String>,
+    warnings: &'w mut Vec<CompileWarning>,
+}
+
+pub(crate) fn analyze_program(engines: &Engines, prog: &ty::TyProgram) -> Vec<CompileWarning> {
+    let decl_engine = engines.de();
+    let abi_entries: HashSet<DeclId<TyFunctionDecl>> = match &prog.kind {
+        ty::TyProgramKind::Contract { abi_entries, .. } => abi_entries.iter().copied().collect(),
+        ty::TyProgramKind::Library { .. }
+        | ty::TyProgramKind::Script { .. }
+        | ty::TyProgramKind::Predicate { .. } => HashSet::new(),
+    };
+    let mut warnings = vec![];
+    let modules = std::iter::once(&prog.root).chain(
+        prog.root
+            .submodules_recursive()
+            .map(|(_, submodule)| &submodule.module),
+    );
+    for module in modules {
+        for node in &module.all_nodes {
+            match &node.content {
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::FunctionDecl(ty::FunctionDecl {
+                    decl_id,
+                    ..
+                })) => analyze_function(engines, decl_id, &abi_entries, &mut warnings),
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::ImplTrait(ty::ImplTrait {
+                    decl_id,
+                    ..
+                })) => {
+                    for item in &decl_engine.get_impl_trait(decl_id).items {
+                        if let ty::TyImplItem::Fn(fn_ref) = item {
+                            analyze_function(engines, fn_ref.id(), &abi_entries, &mut warnings);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    warnings
+}
+
+fn analyze_function(
+    engines: &Engines,
+    fn_decl_id: &DeclId<TyFunctionDecl>,
+    abi_entries: &HashSet<DeclId<TyFunctionDecl>>,
+    warnings: &mut Vec<CompileWarning>,
+) {
+    let fn_decl = engines.de().get_function(fn_decl_id);
+    if fn_decl.is_test() {
+        return;
+    }
+    let abi_arguments = if abi_entries.contains(fn_decl_id) {
+        fn_decl
+            .parameters
+            .iter()
+            .map(|param| (param.name.to_string(), param.name.to_string()))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let mut analysis = FunctionAnalysis {
+        engines,
+        check_arithmetic: !is_allowed(&fn_decl.attributes, ALLOW_UNCHECKED_ARITHMETIC_NAME),
+        check_narrowing: !is_allowed(&fn_decl.attributes, ALLOW_UNCHECKED_NARROWING_NAME),
+        check_division: !is_allowed(&fn_decl.attributes, ALLOW_DIVISION_BY_ZERO_NAME),
+        abi_arguments,
+        checked: HashSet::new(),
+        warnings,
+    };
+    analysis.analyze_code_block(&fn_decl.body);
+}
+
+fn is_allowed(attributes: &AttributesMap, name: &str) -> bool {
+    attributes
+        .get(&AttributeKind::Allow)
+        .into_iter()
+        .flatten()
+        .flat_map(|attr| attr.args.iter())
+        .any(|arg| arg.name.as_str() == name)
+}
+
+impl FunctionAnalysis<'_, '_> {
+    fn analyze_code_block(&mut self, codeblock: &ty::TyCodeBlock) {
+        for ast_node in &codeblock.contents {
+            match &ast_node.content {
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::VariableDecl(var_decl)) => {
+                    self.analyze_expression(&var_decl.body);
+                    self.bind(var_decl.name.as_str(), &var_decl.body);
+                }
+                // Declarations (except variable declarations) are not allowed in a codeblock
+                ty::TyAstNodeContent::Declaration(_) => {}
+                ty::TyAstNodeContent::Expression(expr) => self.analyze_expression(expr),
+                ty::TyAstNodeContent::SideEffect(_) | ty::TyAstNodeContent::Error(_, _) => {}
+            }
+        }
+    }
+
+    // Update what is known about the variable `name` once it holds the value of `expr`.
+    fn bind(&mut self, name: &str, expr: &ty::TyExpression) {
+        let abi_argument = self.unchecked_abi_argument(expr);
+        let nested_prefix = format!("{name}.");
+        self.checked
+            .retain(|place| place != name && !place.starts_with(&nested_prefix));
+        match abi_argument {
+            Some(argument) => self.abi_arguments.insert(name.to_string(), argument),
+            None => self.abi_arguments.remove(name),
+        };
+    }
+
+    fn analyze_expression(&mut self, expr: &ty::TyExpression) {
+        use crate::ty::TyExpressionVariant::*;
+        match &expr.expression {
+            Literal(_)
+            | ConstantExpression { .. }
+            | VariableExpression { .. }
+            | FunctionParameter
+            | Break
+            | Continue
+            | AbiName(_)
+            | StorageAccess(_) => {}
+            Reassignment(reassgn) => {
+                self.analyze_expression(&reassgn.rhs);
+                if reassgn.lhs_indices.is_empty() {
+                    self.bind(reassgn.lhs_base_name.as_str(), &reassgn.rhs);
+                }
+            }
+            CodeBlock(codeblock) => self.analyze_code_block(codeblock),
+            LazyOperator { lhs, rhs, .. } => {
+                self.analyze_expression(lhs);
+                self.analyze_expression(rhs);
+            }
+            ArrayIndex { prefix, index } => {
+                self.analyze_expression(prefix);
+                self.analyze_expression(index);
+            }
+            FunctionApplication {
+                arguments,
+                call_path,
+                selector,
+                ..
+            } => {
+                for (_, arg) in arguments {
+                    self.analyze_expression(arg);
+                }
+                // operators are desugared into calls to the `core::ops` trait methods
+                let is_operator = selector.is_none()
+                    && call_path.prefixes.len() == 2
+                    && call_path.prefixes[0].as_str() == "core"
+                    && call_path.prefixes[1].as_str() == "ops";
+                if is_operator {
+                    self.analyze_operator(expr, call_path.suffix.as_str(), arguments);
+                }
+            }
+            IntrinsicFunction(intrinsic) => {
+                for arg in &intrinsic.arguments {
+                    self.analyze_expression(arg);
+                }
+                if matches!(
+                    intrinsic.kind,
+                    sway_ast::Intrinsic::Eq | sway_ast::Intrinsic::Gt | sway_ast::Intrinsic::Lt
+                ) {
+                    self.mark_checked(&intrinsic.arguments);
+                }
+            }
+            Tuple { fields: exprs }
+            | Array {
+                elem_type: _,
+                contents: exprs,
+            } => {
+                for expr in exprs {
+                    self.analyze_expression(expr);
+                }
+            }
+            StructExpression { fields, .. } => {
+                for field in fields {
+                    self.analyze_expression(&field.value);
+                }
+            }
+            StructFieldAccess { prefix: expr, .. }
+            | TupleElemAccess { prefix: expr, .. }
+            | ImplicitReturn(expr)
+            | Return(expr)
+            | EnumTag { exp: expr }
+            | UnsafeDowncast { exp: expr, .. }
+            | AbiCast { address: expr, .. }
+            | Ref(expr)
+            | Deref(expr)
+            | MatchExp {
+                desugared: expr, ..
+            }
+            | ForLoop { desugared: expr } => self.analyze_expression(expr),
+            EnumInstantiation { contents, .. } => {
+                if let Some(expr) = contents {
+                    self.analyze_expression(expr);
+                }
+            }
+            IfExp {
+                condition,
+                then,
+                r#else,
+            } => {
+                self.analyze_expression(condition);
+                // a place is only checked after the `if` if it is checked on both branches
+                let checked = self.checked.clone();
+                self.analyze_expression(then);
+                let checked_by_then = std::mem::replace(&mut self.checked, checked);
+                if let Some(else_exp) = r#else {
+                    self.analyze_expression(else_exp);
+                }
+                self.checked.retain(|place| checked_by_then.contains(place));
+            }
+            WhileLoop { condition, body } => {
+                self.analyze_expression(condition);
+                // the body may not run at all
+                let checked = self.checked.clone();
+                self.analyze_code_block(body);
+                self.checked.retain(|place| checked.contains(place));
+            }
+            AsmExpression {
+                registers,
+                body,
+                returns,
+                ..
+            } => {
+                for init in registers.iter().filter_map(|r| r.initializer.as_ref()) {
+                    self.analyze_expression(init);
+                }
+                // an `asm` block which only returns one of its inputs converts it to the
+                // block's type
+                if !self.check_narrowing || !body.is_empty() {
+                    return;
+                }
+                let returned_input = returns.as_ref().and_then(|(returned_reg, _)| {
+                    registers
+                        .iter()
+                        .find(|r| r.name.as_str() == returned_reg.name)
+                        .and_then(|r| r.initializer.as_ref())
+                });
+                if let Some(input) = returned_input {
+                    self.analyze_conversion(expr, input);
+                }
+            }
+        }
+    }
+
+    fn analyze_operator(
+        &mut self,
+        expr: &ty::TyExpression,
+        operator: &str,
+        arguments: &[(sway_types::Ident, ty::TyExpression)],
+    ) {
+        match operator {
+            "eq" | "neq" | "lt" | "gt" | "le" | "ge" => {
+                self.mark_checked(arguments.iter().map(|(_, arg)| arg));
+            }
+            "add" | "subtract" | "multiply" | "pow"
+                if self.check_arithmetic && is_unsigned_integer(self.engines, expr.return_type) =>
+            {
+                let abi_argument = arguments
+                    .iter()
+                    .find_map(|(_, arg)| self.unchecked_abi_argument(arg));
+                if let Some(argument) = abi_argument {
+                    self.warnings.push(CompileWarning {
+                        span: expr.span.clone(),
+                        warning_content: Warning::ArithmeticOnUncheckedAbiArgument { argument },
+                    });
+                }
+            }
+            "divide" | "modulo"
+                if self.check_division
+                    && is_unsigned_integer(self.engines, expr.return_type)
+                    && arguments.len() == 2
+                    && self.is_possibly_zero(&arguments[1].1) =>
+            {
+                self.warnings.push(CompileWarning {
+                    span: expr.span.clone(),
+                    warning_content: Warning::PossibleDivisionByZero,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn analyze_conversion(&mut self, expr: &ty::TyExpression, value: &ty::TyExpression) {
+        let type_engine = self.engines.te();
+        let (TypeInfo::UnsignedInteger(from), TypeInfo::UnsignedInteger(to)) = (
+            &*type_engine.get(value.return_type),
+            &*type_engine.get(expr.return_type),
+        ) else {
+            return;
+        };
+        let is_narrowing = bit_width(from) > bit_width(to);
+        let is_checked = match &value.expression {
+            ty::TyExpressionVariant::Literal(_) => true,
+            _ => place_of(value).is_some_and(|place| self.is_checked(&place)),
+        };
+        if is_narrowing && !is_checked {
+            self.warnings.push(CompileWarning {
+                span: expr.span.clone(),
+                warning_content: Warning::UncheckedNarrowingConversion {
+                    from: self.engines.help_out(value.return_type).to_string(),
+                    to: self.engines.help_out(expr.return_type).to_string(),
+                },
+            });
+        }
+    }
+
+    // The operands of a comparison count as checked from now on.
+    fn mark_checked<'a>(&mut self, operands: impl IntoIterator<Item = &'a ty::TyExpression>) {
+        self.checked
+            .extend(operands.into_iter().filter_map(place_of));
+    }
+
+    // The name of the ABI method argument the expression reads, unless it has been checked.
+    fn unchecked_abi_argument(&self, expr: &ty::TyExpression) -> Option<String> {
+        let place = place_of(expr)?;
+        let variable = place.split('.').next()?;
+        let argument = self.abi_arguments.get(variable)?;
+        (!self.is_checked(&place)).then(|| argument.clone())
+    }
+
+    // A place is checked if it, or a place it is part of, has been compared to something.
+    fn is_checked(&self, place: &str) -> bool {
+        place
+            .match_indices('.')
+            .map(|(idx, _)| &place[..idx])
+            .chain(std::iter::once(place))
+            .any(|prefix| self.checked.contains(prefix))
+    }
+
+    fn is_possibly_zero(&self, expr: &ty::TyExpression) -> bool {
+        use crate::ty::TyExpressionVariant::*;
+        match &expr.expression {
+            Literal(literal) => is_zero(literal),
+            ConstantExpression { .. } => false,
+            // `x + 1` is either non-zero or reverts
+            FunctionApplication {
+                arguments,
+                call_path,
+                ..
+            } if call_path.suffix.as_str() == "add" => arguments
+                .iter()
+                .all(|(_, arg)| !matches!(&arg.expression, Literal(literal) if !is_zero(literal))),
+            _ => match place_of(expr) {
+                Some(place) => !self.is_checked(&place),
+                None => true,
+            },
+        }
+    }
+}
+
+// The place the expression reads, e.g. `a.b.0` for `a.b.0`, if it reads a local variable.
+fn place_of(expr: &ty::TyExpression) -> Option<String> {
+    use crate::ty::TyExpressionVariant::*;
+    match &expr.expression {
+        VariableExpression { name, .. } => Some(name.to_string()),
+        StructFieldAccess {
+            prefix,
+            field_to_access,
+            ..
+        } => Some(format!("{}.{}", place_of(prefix)?, field_to_access.name)),
+        TupleElemAccess {
+            prefix,
+            elem_to_access_num,
+            ..
+        } => Some(format!("{}.{}", place_of(prefix)?, elem_to_access_num)),
+        _ => None,
+    }
+}
+
+fn is_unsigned_integer(engines: &Engines, type_id: TypeId) -> bool {
+    matches!(&*engines.te().get(type_id), TypeInfo::UnsignedInteger(_))
+}
+
+fn bit_width(bits: &IntegerBits) -> u32 {
+    match bits {
+        IntegerBits::Eight => 8,
+        IntegerBits::Sixteen => 16,
+        IntegerBits::ThirtyTwo => 32,
+        IntegerBits::SixtyFour => 64,
+        IntegerBits::V256 => 256,
+    }
+}
+
+fn is_zero(literal: &Literal) -> bool {
+    match literal {
+        Literal::U8(value) => *value == 0,
+        Literal::U16(value) => *value == 0,
+        Literal::U32(value) => *value == 0,
+        Literal::U64(value) | Literal::Numeric(value) => *value == 0,
+        Literal::U256(value) => *value == U256::from(0u64),
+        Literal::String(_) | Literal::Boolean(_) | Literal::B256(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic_analysis::test_utils::compile_with_core;
+    use sway_types::Spanned;
+
+    /// The arithmetic reported as done on unchecked arguments in the body of the ABI method
+    /// `f(x: u64, c: bool, n: u64) -> u64`.
+    fn unchecked_arithmetic(body: &str) -> Vec<String> {
+        let src = format!(
+            "contract;
+            abi A {{ fn f(x: u64, c: bool, n: u64) -> u64; }}
+            impl A for Contract {{ fn f(x: u64, c: bool, n: u64) -> u64 {{ {body} }} }}"
+        );
+        let engines = Engines::default();
+        let program = compile_with_core(&engines, &src);
+        analyze_program(&engines, &program)
+            .into_iter()
+            .filter(|warning| {
+                matches!(
+                    warning.warning_content,
+                    Warning::ArithmeticOnUncheckedAbiArgument { .. }
+                )
+            })
+            .map(|warning| warning.span().as_str().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn arithmetic_after_a_comparison_is_checked() {
+        let warnings = unchecked_arithmetic("if x > 10 { __revert(0); } x + 1");
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn arithmetic_before_a_comparison_is_unchecked() {
+        let warnings = unchecked_arithmetic("let y = x + 1; if x > 10 { __revert(0); } y");
+        assert_eq!(warnings, ["x + 1"]);
+    }
+
+    #[test]
+    fn comparisons_on_another_branch_do_not_check() {
+        let warnings = unchecked_arithmetic("if c { if x > 10 { __revert(0); } 0 } else { x + 1 }");
+        assert_eq!(warnings, ["x + 1"]);
+    }
+
+    #[test]
+    fn comparisons_on_one_branch_do_not_check_after_the_if() {
+        let warnings = unchecked_arithmetic("if c { if x > 10 { __revert(0); } } x + 1");
+        assert_eq!(warnings, ["x + 1"]);
+    }
+
+    #[test]
+    fn comparisons_on_both_branches_check_after_the_if() {
+        let warnings = unchecked_arithmetic(
+            "if c { if x > 10 { __revert(0); } } else { if x > 20 { __revert(0); } } x + 1",
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn comparisons_later_in_a_loop_do_not_check() {
+        let warnings = unchecked_arithmetic(
+            "let mut y = 0; let mut i = 0;
+            while i < n { y = x + 1; if x > 10 { __revert(0); } i = i + 1; }
+            y",
+        );
+        assert_eq!(warnings, ["x + 1"]);
+    }
+
+    #[test]
+    fn comparisons_in_a_loop_do_not_check_after_the_loop() {
+        let warnings = unchecked_arithmetic(
+            "let mut i = 0; while i < n { if x > 10 { __revert(0); } i = i + 1; } x + 1",
+        );
+        assert_eq!(warnings, ["x + 1"]);
+    }
+
+    #[test]
+    fn comparisons_in_a_loop_condition_check_after_the_loop() {
+        let warnings = unchecked_arithmetic("while x > 10 { __revert(0); } x + 1");
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+}
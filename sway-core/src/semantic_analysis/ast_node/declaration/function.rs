@@ -178,6 +178,7 @@ impl ty::TyFunctionDecl {
         // create a namespace for the function
         ctx.by_ref()
             .with_purity(ty_fn_decl.purity)
+            .with_fn_decl_span(ty_fn_decl.span.clone())
             .with_const_shadowing_mode(ConstShadowingMode::Sequential)
             .disallow_functions()
             .scoped(|mut ctx| {
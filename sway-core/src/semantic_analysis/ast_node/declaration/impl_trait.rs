@@ -1513,6 +1513,7 @@ fn handle_supertraits(
                     handler.emit_err(CompileError::TraitNotFound {
                         name: supertrait.name.to_string(),
                         span: supertrait.name.span(),
+                        import_candidates: vec![],
                     });
                 }
             }
@@ -153,6 +153,7 @@ pub(crate) fn insert_supertraits_into_namespace(
                     handler.emit_err(CompileError::TraitNotFound {
                         name: supertrait.name.to_string(),
                         span: supertrait.name.span(),
+                        import_candidates: vec![],
                     });
                 }
             }
@@ -554,6 +554,7 @@ impl ty::TyExpression {
                 let err = handler.emit_err(CompileError::UnknownVariable {
                     var_name: name.clone(),
                     span,
+                    import_candidates: vec![],
                 });
                 ty::TyExpression::error(err, name.span(), engines)
             }
@@ -1523,6 +1524,7 @@ impl ty::TyExpression {
                 return Err(handler.emit_err(CompileError::SymbolNotFound {
                     name: unknown_call_path_binding.inner.call_path.suffix.clone(),
                     span: unknown_call_path_binding.inner.call_path.suffix.span(),
+                    import_candidates: vec![],
                 }));
             }
             _ => {
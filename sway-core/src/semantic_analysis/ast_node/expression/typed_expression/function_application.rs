@@ -40,6 +40,10 @@ pub(crate) fn instantiate_function_application(
         handler.emit_err(CompileError::StorageAccessMismatch {
             attrs: promote_purity(ctx.purity(), function_decl.purity).to_attribute_syntax(),
             span: call_path_binding.span(),
+            fn_decl_span: ctx
+                .fn_decl_span()
+                .filter(|_| ctx.purity() == Purity::Pure)
+                .cloned(),
         });
     }
 
@@ -125,6 +125,10 @@ pub(crate) fn type_check_method_application(
             handler.emit_err(CompileError::StorageAccessMismatch {
                 attrs: promote_purity(ctx.purity(), method.purity).to_attribute_syntax(),
                 span: method_name_binding.inner.easy_name().span(),
+                fn_decl_span: ctx
+                    .fn_decl_span()
+                    .filter(|_| ctx.purity() == Purity::Pure)
+                    .cloned(),
             });
         }
         if !contract_call_params.is_empty() {
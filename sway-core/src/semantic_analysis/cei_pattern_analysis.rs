@@ -7,6 +7,10 @@
 // for more information on storage reads after interaction.
 // We also treat the balance tree reads and writes separately,
 // as well as modifying output messages.
+// The analysis is interprocedural: the effects of each function called from a contract
+// entry point, including trait methods and library functions, are summarised once
+// together with the chain of calls through which they happen, and any violation found
+// inside such a function is reported along with the call chain from the entry point.
 
 use crate::{
     decl_engine::*,
@@ -16,12 +20,12 @@ use crate::{
     },
     Engines,
 };
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
-use std::{collections::HashSet, sync::Arc};
 use sway_error::warning::{CompileWarning, Warning};
 use sway_types::{Ident, Span, Spanned};
 
-#[derive(PartialEq, Eq, Hash, Clone)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 enum Effect {
     Interaction,  // interaction with external contracts
     StorageWrite, // storage modification
@@ -60,6 +64,43 @@ impl Effect {
     }
 }
 
+// The effects of a piece of code, each with the chain of function calls through which
+// it happens, which is empty if the code has the effect directly.
+type Effects = BTreeMap<Effect, Vec<Ident>>;
+
+// The state of the analysis of a contract.
+struct CEIAnalysis<'eng> {
+    engines: &'eng Engines,
+    // The contract's entry points, which are analysed on their own rather than as
+    // part of the call chain of another entry point.
+    entry_points: HashSet<FunctionKey>,
+    // The effects of each function analysed so far.
+    summaries: HashMap<FunctionKey, Effects>,
+    warnings: Vec<CompileWarning>,
+}
+
+// Functions are identified by the span of their declaration, as each call site refers
+// to its own copy of the declaration, except for the ones generated by the compiler,
+// whose spans are not necessarily distinct.
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub(crate) enum FunctionKey {
+    Span(Span),
+    Decl(DeclId<TyFunctionDecl>),
+}
+
+impl FunctionKey {
+    pub(crate) fn new(
+        fn_decl_id: &DeclId<TyFunctionDecl>,
+        fn_decl: &TyFunctionDecl,
+    ) -> FunctionKey {
+        if fn_decl.span.source_id().is_some() {
+            FunctionKey::Span(fn_decl.span.clone())
+        } else {
+            FunctionKey::Decl(*fn_decl_id)
+        }
+    }
+}
+
 // The algorithm that searches for storage operations after interaction
 // is organized as an automaton.
 // After an interaction is found in a code block, we keep looking either for
@@ -82,24 +123,33 @@ pub(crate) fn analyze_program(engines: &Engines, prog: &ty::TyProgram) -> Vec<Co
 
 fn analyze_contract(engines: &Engines, ast_nodes: &[ty::TyAstNode]) -> Vec<CompileWarning> {
     let decl_engine = engines.de();
-    let mut warnings: Vec<CompileWarning> = vec![];
-    for fn_decl in contract_entry_points(decl_engine, ast_nodes) {
-        analyze_code_block(engines, &fn_decl.body, &fn_decl.name, &mut warnings);
+    let entry_points = contract_entry_points(decl_engine, ast_nodes);
+    let mut analysis = CEIAnalysis {
+        engines,
+        entry_points: entry_points
+            .iter()
+            .map(|fn_decl_id| FunctionKey::new(fn_decl_id, &decl_engine.get_function(fn_decl_id)))
+            .collect(),
+        summaries: HashMap::new(),
+        warnings: vec![],
+    };
+    for fn_decl_id in &entry_points {
+        analyze_function(&mut analysis, fn_decl_id, &[]);
     }
-    warnings
+    analysis.warnings
 }
 
 // standalone functions and methods
 fn contract_entry_points(
     decl_engine: &DeclEngine,
     ast_nodes: &[ty::TyAstNode],
-) -> Vec<Arc<ty::TyFunctionDecl>> {
+) -> Vec<DeclId<TyFunctionDecl>> {
     use crate::ty::TyAstNodeContent::Declaration;
     ast_nodes
         .iter()
         .flat_map(|ast_node| match &ast_node.content {
             Declaration(ty::TyDecl::FunctionDecl(ty::FunctionDecl { decl_id, .. })) => {
-                decl_id_to_fn_decls(decl_engine, decl_id)
+                vec![*decl_id]
             }
             Declaration(ty::TyDecl::ImplTrait(ty::ImplTrait { decl_id, .. })) => {
                 impl_trait_methods(decl_engine, decl_id)
@@ -109,116 +159,127 @@ fn contract_entry_points(
         .collect()
 }
 
-fn decl_id_to_fn_decls(
-    decl_engine: &DeclEngine,
-    decl_id: &DeclId<TyFunctionDecl>,
-) -> Vec<Arc<TyFunctionDecl>> {
-    vec![decl_engine.get_function(decl_id)]
-}
-
 fn impl_trait_methods(
     decl_engine: &DeclEngine,
     impl_trait_decl_id: &DeclId<TyImplTrait>,
-) -> Vec<Arc<ty::TyFunctionDecl>> {
+) -> Vec<DeclId<TyFunctionDecl>> {
     let impl_trait = decl_engine.get_impl_trait(impl_trait_decl_id);
     impl_trait
         .items
         .iter()
         .flat_map(|item| match item {
-            ty::TyImplItem::Fn(fn_decl) => Some(fn_decl),
+            ty::TyImplItem::Fn(fn_decl) => Some(*fn_decl.id()),
             ty::TyImplItem::Constant(_) => None,
             ty::TyImplItem::Type(_) => None,
         })
-        .flat_map(|fn_decl| decl_id_to_fn_decls(decl_engine, &fn_decl.id().clone()))
         .collect()
 }
 
+// Analyze the function the first time it is reached, via `caller_chain` unless it is an
+// entry point, and return its effects summary.
+fn analyze_function(
+    analysis: &mut CEIAnalysis,
+    fn_decl_id: &DeclId<TyFunctionDecl>,
+    caller_chain: &[Ident],
+) -> Effects {
+    let fn_decl = analysis.engines.de().get_function(fn_decl_id);
+    let key = FunctionKey::new(fn_decl_id, &fn_decl);
+    if let Some(effects) = analysis.summaries.get(&key) {
+        return effects.clone();
+    }
+    let call_chain = if analysis.entry_points.contains(&key) {
+        vec![fn_decl.name.clone()]
+    } else {
+        let mut call_chain = caller_chain.to_vec();
+        call_chain.push(fn_decl.name.clone());
+        call_chain
+    };
+    // Recursion is not allowed, but make sure an erroneous program can't send us
+    // into an endless loop.
+    analysis.summaries.insert(key.clone(), Effects::new());
+    let effects = analyze_code_block(analysis, &fn_decl.body, &call_chain);
+    analysis.summaries.insert(key, effects.clone());
+    effects
+}
+
 // This is the main part of the analysis algorithm:
 // we are looking for various effects after contract interaction
 fn analyze_code_block(
-    engines: &Engines,
+    analysis: &mut CEIAnalysis,
     codeblock: &ty::TyCodeBlock,
-    block_name: &Ident,
-    warnings: &mut Vec<CompileWarning>,
-) -> HashSet<Effect> {
+    call_chain: &[Ident],
+) -> Effects {
     let mut interaction_span: Span = Span::dummy();
-    let mut codeblock_effects = HashSet::new();
+    let mut codeblock_effects = Effects::new();
     let mut analysis_state: CEIAnalysisState = CEIAnalysisState::LookingForInteraction;
 
     for ast_node in &codeblock.contents {
-        let codeblock_entry_effects =
-            analyze_code_block_entry(engines, ast_node, block_name, warnings);
+        let codeblock_entry_effects = analyze_code_block_entry(analysis, ast_node, call_chain);
         match analysis_state {
             CEIAnalysisState::LookingForInteraction => {
-                if codeblock_entry_effects.contains(&Effect::Interaction) {
+                if codeblock_entry_effects.contains_key(&Effect::Interaction) {
                     analysis_state = CEIAnalysisState::LookingForEffect;
                     interaction_span = ast_node.span.clone();
                 }
             }
             CEIAnalysisState::LookingForEffect => warn_after_interaction(
+                analysis,
                 &codeblock_entry_effects,
                 &interaction_span,
                 &ast_node.span,
-                block_name,
-                warnings,
+                call_chain,
             ),
         };
-        codeblock_effects.extend(codeblock_entry_effects)
+        codeblock_effects = effects_union(codeblock_effects, codeblock_entry_effects)
     }
     codeblock_effects
 }
 
 fn analyze_code_block_entry(
-    engines: &Engines,
+    analysis: &mut CEIAnalysis,
     entry: &ty::TyAstNode,
-    block_name: &Ident,
-    warnings: &mut Vec<CompileWarning>,
-) -> HashSet<Effect> {
+    call_chain: &[Ident],
+) -> Effects {
     match &entry.content {
         ty::TyAstNodeContent::Declaration(decl) => {
-            analyze_codeblock_decl(engines, decl, block_name, warnings)
-        }
-        ty::TyAstNodeContent::Expression(expr) => {
-            analyze_expression(engines, expr, block_name, warnings)
+            analyze_codeblock_decl(analysis, decl, call_chain)
         }
-        ty::TyAstNodeContent::SideEffect(_) | ty::TyAstNodeContent::Error(_, _) => HashSet::new(),
+        ty::TyAstNodeContent::Expression(expr) => analyze_expression(analysis, expr, call_chain),
+        ty::TyAstNodeContent::SideEffect(_) | ty::TyAstNodeContent::Error(_, _) => Effects::new(),
     }
 }
 
 fn analyze_codeblock_decl(
-    engines: &Engines,
+    analysis: &mut CEIAnalysis,
     decl: &ty::TyDecl,
-    block_name: &Ident,
-    warnings: &mut Vec<CompileWarning>,
-) -> HashSet<Effect> {
+    call_chain: &[Ident],
+) -> Effects {
     // Declarations (except variable declarations) are not allowed in a codeblock
     use crate::ty::TyDecl::*;
     match decl {
-        VariableDecl(var_decl) => analyze_expression(engines, &var_decl.body, block_name, warnings),
-        _ => HashSet::new(),
+        VariableDecl(var_decl) => analyze_expression(analysis, &var_decl.body, call_chain),
+        _ => Effects::new(),
     }
 }
 
 fn analyze_expression(
-    engines: &Engines,
+    analysis: &mut CEIAnalysis,
     expr: &ty::TyExpression,
-    block_name: &Ident,
-    warnings: &mut Vec<CompileWarning>,
-) -> HashSet<Effect> {
+    call_chain: &[Ident],
+) -> Effects {
     use crate::ty::TyExpressionVariant::*;
-    let decl_engine = engines.de();
     match &expr.expression {
         // base cases: no warnings can be emitted
         Literal(_)
         | ConstantExpression { .. }
         | VariableExpression { .. }
         | FunctionParameter
-        | StorageAccess(_)
         | Break
         | Continue
-        | AbiName(_) => effects_of_expression(engines, expr),
-        Reassignment(reassgn) => analyze_expression(engines, &reassgn.rhs, block_name, warnings),
-        CodeBlock(codeblock) => analyze_code_block(engines, codeblock, block_name, warnings),
+        | AbiName(_) => Effects::new(),
+        StorageAccess(_) => effects_of_storage_access(analysis.engines, expr),
+        Reassignment(reassgn) => analyze_expression(analysis, &reassgn.rhs, call_chain),
+        CodeBlock(codeblock) => analyze_code_block(analysis, codeblock, call_chain),
         LazyOperator {
             lhs: left,
             rhs: right,
@@ -227,7 +288,7 @@ fn analyze_expression(
         | ArrayIndex {
             prefix: left,
             index: right,
-        } => analyze_two_expressions(engines, left, right, block_name, warnings),
+        } => analyze_two_expressions(analysis, left, right, call_chain),
         FunctionApplication {
             arguments,
             fn_ref,
@@ -235,53 +296,55 @@ fn analyze_expression(
             call_path,
             ..
         } => {
-            let func = decl_engine.get_function(fn_ref);
-            // we don't need to run full analysis on the function body as it will be covered
-            // as a separate step of the whole contract analysis
-            // we just need function's effects at this point
-            let fn_effs = effects_of_codeblock(engines, &func.body);
-
             // assuming left-to-right arguments evaluation
             // we run CEI violation analysis as if the arguments form a code block
             let args_effs = analyze_expressions(
-                engines,
+                analysis,
                 arguments.iter().map(|(_, e)| e).collect(),
-                block_name,
-                warnings,
+                call_chain,
             );
-            if args_effs.contains(&Effect::Interaction) {
+
+            let fn_effs = if selector.is_some() {
+                // external contract call (a.k.a. interaction), whose other effects
+                // happen in the context of the other contract
+                direct_effects([Effect::Interaction])
+            } else {
+                // the function's own violations are reported when it is analysed,
+                // here we just need its effects
+                let callee_name = analysis.engines.de().get_function(fn_ref).name.clone();
+                analyze_function(analysis, fn_ref.id(), call_chain)
+                    .into_iter()
+                    .map(|(eff, mut chain)| {
+                        chain.insert(0, callee_name.clone());
+                        (eff, chain)
+                    })
+                    .collect()
+            };
+
+            if args_effs.contains_key(&Effect::Interaction) {
                 // TODO: interaction span has to be more precise and point to an argument which performs interaction
                 let last_arg_span = &arguments.last().unwrap().1.span;
                 warn_after_interaction(
+                    analysis,
                     &fn_effs,
                     &call_path.span(),
                     last_arg_span,
-                    block_name,
-                    warnings,
+                    call_chain,
                 )
             }
 
-            let mut result_effs = set_union(fn_effs, args_effs);
-            if selector.is_some() {
-                // external contract call (a.k.a. interaction)
-                result_effs.extend(HashSet::from([Effect::Interaction]))
-            };
-            result_effs
+            effects_union(fn_effs, args_effs)
         }
         IntrinsicFunction(intrinsic) => {
             let intr_effs = effects_of_intrinsic(&intrinsic.kind);
             // assuming left-to-right arguments evaluation
-            let args_effs = analyze_expressions(
-                engines,
-                intrinsic.arguments.iter().collect(),
-                block_name,
-                warnings,
-            );
-            if args_effs.contains(&Effect::Interaction) {
+            let args_effs =
+                analyze_expressions(analysis, intrinsic.arguments.iter().collect(), call_chain);
+            if args_effs.contains_key(&Effect::Interaction) {
                 // TODO: interaction span has to be more precise and point to an argument which performs interaction
-                warn_after_interaction(&intr_effs, &expr.span, &expr.span, block_name, warnings)
+                warn_after_interaction(analysis, &intr_effs, &expr.span, &expr.span, call_chain)
             }
-            set_union(intr_effs, args_effs)
+            effects_union(intr_effs, args_effs)
         }
         Tuple { fields: exprs }
         | Array {
@@ -289,15 +352,14 @@ fn analyze_expression(
             contents: exprs,
         } => {
             // assuming left-to-right fields/elements evaluation
-            analyze_expressions(engines, exprs.iter().collect(), block_name, warnings)
+            analyze_expressions(analysis, exprs.iter().collect(), call_chain)
         }
         StructExpression { fields, .. } => {
             // assuming left-to-right fields evaluation
             analyze_expressions(
-                engines,
+                analysis,
                 fields.iter().map(|e| &e.value).collect(),
-                block_name,
-                warnings,
+                call_chain,
             )
         }
         StructFieldAccess { prefix: expr, .. }
@@ -308,42 +370,41 @@ fn analyze_expression(
         | UnsafeDowncast { exp: expr, .. }
         | AbiCast { address: expr, .. }
         | Ref(expr)
-        | Deref(expr) => analyze_expression(engines, expr, block_name, warnings),
+        | Deref(expr) => analyze_expression(analysis, expr, call_chain),
         EnumInstantiation { contents, .. } => match contents {
-            Some(expr) => analyze_expression(engines, expr, block_name, warnings),
-            None => HashSet::new(),
+            Some(expr) => analyze_expression(analysis, expr, call_chain),
+            None => Effects::new(),
         },
-        MatchExp { desugared, .. } => analyze_expression(engines, desugared, block_name, warnings),
+        MatchExp { desugared, .. } => analyze_expression(analysis, desugared, call_chain),
         IfExp {
             condition,
             then,
             r#else,
         } => {
-            let cond_then_effs =
-                analyze_two_expressions(engines, condition, then, block_name, warnings);
+            let cond_then_effs = analyze_two_expressions(analysis, condition, then, call_chain);
             let cond_else_effs = match r#else {
                 Some(else_exp) => {
-                    analyze_two_expressions(engines, condition, else_exp, block_name, warnings)
+                    analyze_two_expressions(analysis, condition, else_exp, call_chain)
                 }
-                None => HashSet::new(),
+                None => Effects::new(),
             };
-            set_union(cond_then_effs, cond_else_effs)
+            effects_union(cond_then_effs, cond_else_effs)
         }
         WhileLoop { condition, body } => {
             // if the loop (condition + body) contains both interaction and state effects
             // in _any_ order, we report CEI pattern violation
-            let cond_effs = analyze_expression(engines, condition, block_name, warnings);
-            let body_effs = analyze_code_block(engines, body, block_name, warnings);
-            let res_effs = set_union(cond_effs, body_effs);
-            if res_effs.contains(&Effect::Interaction) {
+            let cond_effs = analyze_expression(analysis, condition, call_chain);
+            let body_effs = analyze_code_block(analysis, body, call_chain);
+            let res_effs = effects_union(cond_effs, body_effs);
+            if res_effs.contains_key(&Effect::Interaction) {
                 // TODO: the span is not very precise, we can do better here, but this
                 // will need a bit of refactoring of the CEI analysis
                 let span = expr.span.clone();
-                warn_after_interaction(&res_effs, &span, &span, &block_name.clone(), warnings)
+                warn_after_interaction(analysis, &res_effs, &span, &span, call_chain)
             }
             res_effs
         }
-        ForLoop { desugared } => analyze_expression(engines, desugared, block_name, warnings),
+        ForLoop { desugared } => analyze_expression(analysis, desugared, call_chain),
         AsmExpression {
             registers, body, ..
         } => {
@@ -351,329 +412,184 @@ fn analyze_expression(
                 .iter()
                 .filter_map(|rdecl| rdecl.initializer.as_ref())
                 .collect();
-            let init_effs = analyze_expressions(engines, init_exprs, block_name, warnings);
-            let asmblock_effs = analyze_asm_block(body, block_name, warnings);
-            if init_effs.contains(&Effect::Interaction) {
+            let init_effs = analyze_expressions(analysis, init_exprs, call_chain);
+            let asmblock_effs = analyze_asm_block(analysis, body, call_chain);
+            if init_effs.contains_key(&Effect::Interaction) {
                 // TODO: improve locations accuracy
-                warn_after_interaction(&asmblock_effs, &expr.span, &expr.span, block_name, warnings)
+                warn_after_interaction(analysis, &asmblock_effs, &expr.span, &expr.span, call_chain)
             }
-            set_union(init_effs, asmblock_effs)
+            effects_union(init_effs, asmblock_effs)
         }
     }
 }
 
 fn analyze_two_expressions(
-    engines: &Engines,
+    analysis: &mut CEIAnalysis,
     first: &ty::TyExpression,
     second: &ty::TyExpression,
-    block_name: &Ident,
-    warnings: &mut Vec<CompileWarning>,
-) -> HashSet<Effect> {
-    let first_effs = analyze_expression(engines, first, block_name, warnings);
-    let second_effs = analyze_expression(engines, second, block_name, warnings);
-    if first_effs.contains(&Effect::Interaction) {
+    call_chain: &[Ident],
+) -> Effects {
+    let first_effs = analyze_expression(analysis, first, call_chain);
+    let second_effs = analyze_expression(analysis, second, call_chain);
+    if first_effs.contains_key(&Effect::Interaction) {
         warn_after_interaction(
+            analysis,
             &second_effs,
             &first.span,
             &second.span,
-            block_name,
-            warnings,
+            call_chain,
         )
     }
-    set_union(first_effs, second_effs)
+    effects_union(first_effs, second_effs)
 }
 
 // Analyze a sequence of expressions
 // TODO: analyze_expressions, analyze_codeblock and analyze_asm_block (see below) are very similar in structure
 //       looks like the algorithm implementation should be generalized
 fn analyze_expressions(
-    engines: &Engines,
+    analysis: &mut CEIAnalysis,
     expressions: Vec<&ty::TyExpression>,
-    block_name: &Ident,
-    warnings: &mut Vec<CompileWarning>,
-) -> HashSet<Effect> {
+    call_chain: &[Ident],
+) -> Effects {
     let mut interaction_span: Span = Span::dummy();
-    let mut accumulated_effects = HashSet::new();
+    let mut accumulated_effects = Effects::new();
     let mut analysis_state: CEIAnalysisState = CEIAnalysisState::LookingForInteraction;
 
     for expr in expressions {
-        let expr_effs = analyze_expression(engines, expr, block_name, warnings);
+        let expr_effs = analyze_expression(analysis, expr, call_chain);
         match analysis_state {
             CEIAnalysisState::LookingForInteraction => {
-                if expr_effs.contains(&Effect::Interaction) {
+                if expr_effs.contains_key(&Effect::Interaction) {
                     analysis_state = CEIAnalysisState::LookingForEffect;
                     interaction_span = expr.span.clone();
                 }
             }
             CEIAnalysisState::LookingForEffect => warn_after_interaction(
+                analysis,
                 &expr_effs,
                 &interaction_span,
                 &expr.span,
-                block_name,
-                warnings,
+                call_chain,
             ),
         };
-        accumulated_effects.extend(expr_effs)
+        accumulated_effects = effects_union(accumulated_effects, expr_effs)
     }
     accumulated_effects
 }
 
 // No need to worry about jumps because they are not allowed in `asm` blocks.
 fn analyze_asm_block(
+    analysis: &mut CEIAnalysis,
     asm_block: &Vec<AsmOp>,
-    block_name: &Ident,
-    warnings: &mut Vec<CompileWarning>,
-) -> HashSet<Effect> {
+    call_chain: &[Ident],
+) -> Effects {
     let mut interaction_span: Span = Span::dummy();
-    let mut accumulated_effects = HashSet::new();
+    let mut accumulated_effects = Effects::new();
     let mut analysis_state: CEIAnalysisState = CEIAnalysisState::LookingForInteraction;
 
     for asm_op in asm_block {
         let asm_op_effs = effects_of_asm_op(asm_op);
         match analysis_state {
             CEIAnalysisState::LookingForInteraction => {
-                if asm_op_effs.contains(&Effect::Interaction) {
+                if asm_op_effs.contains_key(&Effect::Interaction) {
                     analysis_state = CEIAnalysisState::LookingForEffect;
                     interaction_span = asm_op.span.clone();
                 }
             }
             CEIAnalysisState::LookingForEffect => warn_after_interaction(
+                analysis,
                 &asm_op_effs,
                 &interaction_span,
                 &asm_op.span,
-                block_name,
-                warnings,
+                call_chain,
             ),
         };
-        accumulated_effects.extend(asm_op_effs)
+        accumulated_effects = effects_union(accumulated_effects, asm_op_effs)
     }
     accumulated_effects
 }
 
 fn warn_after_interaction(
-    ast_node_effects: &HashSet<Effect>,
+    analysis: &mut CEIAnalysis,
+    ast_node_effects: &Effects,
     interaction_span: &Span,
     effect_span: &Span,
-    block_name: &Ident,
-    warnings: &mut Vec<CompileWarning>,
+    call_chain: &[Ident],
 ) {
-    let interaction_singleton = HashSet::from([Effect::Interaction]);
-    let state_effects = ast_node_effects.difference(&interaction_singleton);
-    for eff in state_effects {
-        warnings.push(CompileWarning {
+    let state_effects = ast_node_effects
+        .iter()
+        .filter(|(eff, _)| **eff != Effect::Interaction);
+    for (eff, effect_chain) in state_effects {
+        analysis.warnings.push(CompileWarning {
             span: Span::join(interaction_span.clone(), effect_span.clone()),
             warning_content: Warning::EffectAfterInteraction {
                 effect: eff.to_string(),
                 effect_in_suggestion: Effect::to_suggestion(eff),
-                block_name: block_name.clone(),
+                block_name: call_chain.last().unwrap().clone(),
+                call_chain: [call_chain, effect_chain].concat(),
             },
         });
     }
 }
 
-fn effects_of_codeblock_entry(engines: &Engines, ast_node: &ty::TyAstNode) -> HashSet<Effect> {
-    match &ast_node.content {
-        ty::TyAstNodeContent::Declaration(decl) => effects_of_codeblock_decl(engines, decl),
-        ty::TyAstNodeContent::Expression(expr) => effects_of_expression(engines, expr),
-        ty::TyAstNodeContent::SideEffect(_) | ty::TyAstNodeContent::Error(_, _) => HashSet::new(),
-    }
-}
-
-fn effects_of_codeblock_decl(engines: &Engines, decl: &ty::TyDecl) -> HashSet<Effect> {
-    use crate::ty::TyDecl::*;
-    match decl {
-        VariableDecl(var_decl) => effects_of_expression(engines, &var_decl.body),
-        // Declarations (except variable declarations) are not allowed in the body of a function
-        _ => HashSet::new(),
-    }
-}
-
-fn effects_of_expression(engines: &Engines, expr: &ty::TyExpression) -> HashSet<Effect> {
-    use crate::ty::TyExpressionVariant::*;
+fn effects_of_storage_access(engines: &Engines, expr: &ty::TyExpression) -> Effects {
     let type_engine = engines.te();
     let decl_engine = engines.de();
-    match &expr.expression {
-        Literal(_)
-        | ConstantExpression { .. }
-        | VariableExpression { .. }
-        | FunctionParameter
-        | Break
-        | Continue
-        | AbiName(_) => HashSet::new(),
-        // this type of assignment only mutates local variables and not storage
-        Reassignment(reassgn) => effects_of_expression(engines, &reassgn.rhs),
-        StorageAccess(_) => match &*type_engine.get(expr.return_type) {
-            // accessing a storage map's method (or a storage vector's method),
-            // which is represented using a struct with empty fields
-            // does not result in a storage read
-            crate::TypeInfo::Struct(decl_ref)
-                if decl_engine.get_struct(decl_ref).fields.is_empty() =>
-            {
-                HashSet::new()
-            }
-            // if it's an empty enum then it cannot be constructed and hence cannot be read
-            // adding this check here just to be on the safe side
-            crate::TypeInfo::Enum(decl_ref)
-                if decl_engine.get_enum(decl_ref).variants.is_empty() =>
-            {
-                HashSet::new()
-            }
-            _ => HashSet::from([Effect::StorageRead]),
-        },
-        LazyOperator { lhs, rhs, .. }
-        | ArrayIndex {
-            prefix: lhs,
-            index: rhs,
-        } => {
-            let mut effs = effects_of_expression(engines, lhs);
-            let rhs_effs = effects_of_expression(engines, rhs);
-            effs.extend(rhs_effs);
-            effs
+    match &*type_engine.get(expr.return_type) {
+        // accessing a storage map's method (or a storage vector's method),
+        // which is represented using a struct with empty fields
+        // does not result in a storage read
+        crate::TypeInfo::Struct(decl_ref) if decl_engine.get_struct(decl_ref).fields.is_empty() => {
+            Effects::new()
         }
-        Tuple { fields: exprs }
-        | Array {
-            elem_type: _,
-            contents: exprs,
-        } => effects_of_expressions(engines, exprs),
-        StructExpression { fields, .. } => effects_of_struct_expressions(engines, fields),
-        CodeBlock(codeblock) => effects_of_codeblock(engines, codeblock),
-        MatchExp { desugared, .. } => effects_of_expression(engines, desugared),
-        IfExp {
-            condition,
-            then,
-            r#else,
-        } => {
-            let mut effs = effects_of_expression(engines, condition);
-            effs.extend(effects_of_expression(engines, then));
-            let else_effs = match r#else {
-                Some(expr) => effects_of_expression(engines, expr),
-                None => HashSet::new(),
-            };
-            effs.extend(else_effs);
-            effs
+        // if it's an empty enum then it cannot be constructed and hence cannot be read
+        // adding this check here just to be on the safe side
+        crate::TypeInfo::Enum(decl_ref) if decl_engine.get_enum(decl_ref).variants.is_empty() => {
+            Effects::new()
         }
-        StructFieldAccess { prefix: expr, .. }
-        | TupleElemAccess { prefix: expr, .. }
-        | EnumTag { exp: expr }
-        | UnsafeDowncast { exp: expr, .. }
-        | ImplicitReturn(expr)
-        | Return(expr)
-        | Ref(expr)
-        | Deref(expr) => effects_of_expression(engines, expr),
-        EnumInstantiation { contents, .. } => match contents {
-            Some(expr) => effects_of_expression(engines, expr),
-            None => HashSet::new(),
-        },
-        AbiCast { address, .. } => effects_of_expression(engines, address),
-        IntrinsicFunction(intr_fn) => effects_of_expressions(engines, &intr_fn.arguments)
-            .union(&effects_of_intrinsic(&intr_fn.kind))
-            .cloned()
-            .collect(),
-        WhileLoop { condition, body } => effects_of_expression(engines, condition)
-            .union(&effects_of_codeblock(engines, body))
-            .cloned()
-            .collect(),
-        ForLoop { desugared } => effects_of_expression(engines, desugared),
-        FunctionApplication {
-            fn_ref,
-            arguments,
-            selector,
-            ..
-        } => {
-            let fn_body = &decl_engine.get_function(fn_ref).body;
-            let mut effs = effects_of_codeblock(engines, fn_body);
-            let args_effs = map_hashsets_union(arguments, |e| effects_of_expression(engines, &e.1));
-            effs.extend(args_effs);
-            if selector.is_some() {
-                // external contract call (a.k.a. interaction)
-                effs.extend(HashSet::from([Effect::Interaction]))
-            };
-            effs
-        }
-        AsmExpression {
-            registers,
-            body,
-            whole_block_span: _,
-            ..
-        } => effects_of_register_initializers(engines, registers)
-            .union(&effects_of_asm_ops(body))
-            .cloned()
-            .collect(),
+        _ => direct_effects([Effect::StorageRead]),
     }
 }
 
-fn effects_of_intrinsic(intr: &sway_ast::Intrinsic) -> HashSet<Effect> {
+fn effects_of_intrinsic(intr: &sway_ast::Intrinsic) -> Effects {
     use sway_ast::Intrinsic::*;
     match intr {
-        StateClear | StateStoreWord | StateStoreQuad => HashSet::from([Effect::StorageWrite]),
-        StateLoadWord | StateLoadQuad => HashSet::from([Effect::StorageRead]),
-        Smo => HashSet::from([Effect::OutputMessage]),
+        StateClear | StateStoreWord | StateStoreQuad => direct_effects([Effect::StorageWrite]),
+        StateLoadWord | StateLoadQuad => direct_effects([Effect::StorageRead]),
+        Smo => direct_effects([Effect::OutputMessage]),
         Revert | JmpMem | IsReferenceType | IsStrArray | SizeOfType | SizeOfVal | SizeOfStr
         | ContractCall | ContractRet | AssertIsStrArray | ToStrArray | Eq | Gt | Lt | Gtf
         | AddrOf | Log | Add | Sub | Mul | Div | And | Or | Xor | Mod | Rsh | Lsh | PtrAdd
-        | PtrSub | Not => HashSet::new(),
+        | PtrSub | Not => Effects::new(),
     }
 }
 
-fn effects_of_asm_op(op: &AsmOp) -> HashSet<Effect> {
+fn effects_of_asm_op(op: &AsmOp) -> Effects {
     match op.op_name.as_str().to_lowercase().as_str() {
-        "scwq" | "sww" | "swwq" => HashSet::from([Effect::StorageWrite]),
-        "srw" | "srwq" => HashSet::from([Effect::StorageRead]),
-        "tr" | "tro" => HashSet::from([Effect::BalanceTreeReadWrite]),
-        "bal" => HashSet::from([Effect::BalanceTreeRead]),
-        "smo" => HashSet::from([Effect::OutputMessage]),
-        "call" => HashSet::from([Effect::Interaction]),
+        "scwq" | "sww" | "swwq" => direct_effects([Effect::StorageWrite]),
+        "srw" | "srwq" => direct_effects([Effect::StorageRead]),
+        "tr" | "tro" => direct_effects([Effect::BalanceTreeReadWrite]),
+        "bal" => direct_effects([Effect::BalanceTreeRead]),
+        "smo" => direct_effects([Effect::OutputMessage]),
+        "call" => direct_effects([Effect::Interaction]),
         // the rest of the assembly instructions are considered to not have effects
-        _ => HashSet::new(),
+        _ => Effects::new(),
     }
 }
 
-fn set_union<E>(set1: HashSet<E>, set2: HashSet<E>) -> HashSet<E>
-where
-    E: std::hash::Hash + Eq + Clone,
-{
-    set1.union(&set2).cloned().collect()
-}
-
-fn map_hashsets_union<T, F, E>(elems: &[T], to_set: F) -> HashSet<E>
-where
-    F: Fn(&T) -> HashSet<E>,
-    E: std::hash::Hash + Eq + Clone,
-{
-    elems
-        .iter()
-        .fold(HashSet::new(), |set, e| set_union(to_set(e), set))
+fn direct_effects<const N: usize>(effects: [Effect; N]) -> Effects {
+    effects.into_iter().map(|eff| (eff, vec![])).collect()
 }
 
-fn effects_of_codeblock(engines: &Engines, codeblock: &ty::TyCodeBlock) -> HashSet<Effect> {
-    map_hashsets_union(&codeblock.contents, |entry| {
-        effects_of_codeblock_entry(engines, entry)
-    })
-}
-
-fn effects_of_expressions(engines: &Engines, exprs: &[ty::TyExpression]) -> HashSet<Effect> {
-    map_hashsets_union(exprs, |e| effects_of_expression(engines, e))
-}
-
-fn effects_of_struct_expressions(
-    engines: &Engines,
-    struct_exprs: &[ty::TyStructExpressionField],
-) -> HashSet<Effect> {
-    map_hashsets_union(struct_exprs, |se| effects_of_expression(engines, &se.value))
-}
-
-fn effects_of_asm_ops(asm_ops: &[AsmOp]) -> HashSet<Effect> {
-    map_hashsets_union(asm_ops, effects_of_asm_op)
-}
-
-fn effects_of_register_initializers(
-    engines: &Engines,
-    initializers: &[ty::TyAsmRegisterDeclaration],
-) -> HashSet<Effect> {
-    map_hashsets_union(initializers, |asm_reg_decl| {
-        asm_reg_decl
-            .initializer
-            .as_ref()
-            .map_or(HashSet::new(), |e| effects_of_expression(engines, e))
-    })
+// When both sets have the same effect, keep the shortest call chain to it.
+fn effects_union(mut effs1: Effects, effs2: Effects) -> Effects {
+    for (eff, chain) in effs2 {
+        match effs1.get(&eff) {
+            Some(existing_chain) if existing_chain.len() <= chain.len() => {}
+            _ => {
+                effs1.insert(eff, chain);
+            }
+        }
+    }
+    effs1
 }
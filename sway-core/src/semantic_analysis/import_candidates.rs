@@ -0,0 +1,84 @@
+//! Fills in the import candidates of "not found" errors, so that their diagnostics can suggest
+//! the missing `use` statements.
+use std::collections::BTreeSet;
+
+use sway_error::{error::CompileError, handler::Handler};
+
+use crate::{
+    language::{ty::TyDecl, CallPath},
+    namespace::Module,
+    Engines, Namespace,
+};
+
+/// Attaches to every unresolved symbol, variable and trait error emitted so far the paths of the
+/// public items with the same name declared in the submodules of the root module, i.e. in the
+/// dependencies and in the package's own modules.
+pub(crate) fn fill_import_candidates(handler: &Handler, engines: &Engines, namespace: &Namespace) {
+    handler.update_errors(|error| {
+        let (name, import_candidates, filter): (_, _, fn(&TyDecl) -> bool) = match error {
+            CompileError::SymbolNotFound {
+                name,
+                import_candidates,
+                ..
+            } => (name.as_str(), import_candidates, |_| true),
+            CompileError::UnknownVariable {
+                var_name,
+                import_candidates,
+                ..
+            } => (var_name.as_str(), import_candidates, |decl| {
+                matches!(decl, TyDecl::ConstantDecl(_))
+            }),
+            CompileError::TraitNotFound {
+                name,
+                import_candidates,
+                ..
+            } => (name.as_str(), import_candidates, |decl| {
+                matches!(decl, TyDecl::TraitDecl(_))
+            }),
+            _ => return,
+        };
+
+        let mut candidates = BTreeSet::new();
+        for module in namespace.root_module().submodules().values() {
+            collect_candidates(engines, namespace, module, name, filter, &mut candidates);
+        }
+        *import_candidates = candidates.into_iter().collect();
+    });
+}
+
+fn collect_candidates(
+    engines: &Engines,
+    namespace: &Namespace,
+    module: &Module,
+    name: &str,
+    filter: fn(&TyDecl) -> bool,
+    candidates: &mut BTreeSet<String>,
+) {
+    let decl_engine = engines.de();
+    for (ident, decl) in module.current_items().symbols() {
+        if ident.as_str() != name || !filter(decl) || !decl.visibility(decl_engine).is_public() {
+            continue;
+        }
+        if let Some(call_path) = declared_call_path(engines, decl) {
+            candidates.insert(call_path.to_import_path(namespace).to_string());
+        }
+    }
+    for submodule in module.submodules().values() {
+        collect_candidates(engines, namespace, submodule, name, filter, candidates);
+    }
+}
+
+/// The path of the declaration, for the kinds of declarations that can be imported by name.
+fn declared_call_path(engines: &Engines, decl: &TyDecl) -> Option<CallPath> {
+    let decl_engine = engines.de();
+    let call_path = match decl {
+        TyDecl::StructDecl(decl) => decl_engine.get_struct(&decl.decl_id).call_path.clone(),
+        TyDecl::EnumDecl(decl) => decl_engine.get_enum(&decl.decl_id).call_path.clone(),
+        TyDecl::TraitDecl(decl) => decl_engine.get_trait(&decl.decl_id).call_path.clone(),
+        TyDecl::FunctionDecl(decl) => decl_engine.get_function(&decl.decl_id).call_path.clone(),
+        TyDecl::ConstantDecl(decl) => decl_engine.get_constant(&decl.decl_id).call_path.clone(),
+        TyDecl::TypeAliasDecl(decl) => decl_engine.get_type_alias(&decl.decl_id).call_path.clone(),
+        _ => return None,
+    };
+    Some(call_path)
+}
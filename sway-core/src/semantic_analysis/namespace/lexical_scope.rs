@@ -9,6 +9,7 @@ use crate::{
     semantic_analysis::{ast_node::ConstShadowingMode, GenericShadowingMode},
     type_system::*,
 };
+use serde::{Deserialize, Serialize};
 
 use super::TraitMap;
 
@@ -21,7 +22,7 @@ use sway_types::{span::Span, Spanned};
 use std::sync::Arc;
 
 /// Is this a glob (`use foo::*;`) import?
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub(crate) enum GlobImport {
     Yes,
     No,
@@ -42,7 +43,7 @@ pub type LexicalScopePath = Vec<LexicalScopeId>;
 
 /// A `LexicalScope` contains a set of all items that exist within the lexical scope via declaration or
 /// importing, along with all its associated hierarchical scopes.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct LexicalScope {
     /// The set of symbols, implementations, synonyms and aliases present within this scope.
     pub items: Items,
@@ -53,7 +54,7 @@ pub struct LexicalScope {
 }
 
 /// The set of items that exist within some lexical scope via declaration or importing.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Items {
     /// An ordered map from `Ident`s to their associated typed declarations.
     pub(crate) symbols: SymbolMap,
@@ -284,6 +285,7 @@ impl Items {
             .ok_or_else(|| CompileError::SymbolNotFound {
                 name: name.clone(),
                 span: name.span(),
+                import_candidates: vec![],
             })
     }
 
@@ -367,6 +369,7 @@ impl Items {
                 return Err(handler.emit_err(CompileError::UnknownVariable {
                     var_name: base_name.clone(),
                     span: base_name.span(),
+                    import_candidates: vec![],
                 }));
             }
         };
@@ -10,6 +10,7 @@ use crate::{
     transform::to_parsed_lang,
     Ident, Namespace, TypeId, TypeInfo,
 };
+use serde::{Deserialize, Serialize};
 
 use super::{
     lexical_scope::{GlobImport, Items, LexicalScope, SymbolMap},
@@ -33,7 +34,7 @@ use sway_utils::iter_prefixes;
 ///
 /// A `Module` contains a set of all items that exist within the lexical scope via declaration or
 /// importing, along with a map of each of its submodules.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Module {
     /// Submodules of the current module represented as an ordered map from each submodule's name
     /// to the associated `Module`.
@@ -517,6 +518,7 @@ impl Module {
                 return Err(handler.emit_err(CompileError::SymbolNotFound {
                     name: item.clone(),
                     span: item.span(),
+                    import_candidates: vec![],
                 }));
             }
         };
@@ -613,6 +615,7 @@ impl Module {
                         return Err(handler.emit_err(CompileError::SymbolNotFound {
                             name: variant_name.clone(),
                             span: variant_name.span(),
+                            import_candidates: vec![],
                         }));
                     }
                 } else {
@@ -626,6 +629,7 @@ impl Module {
                 return Err(handler.emit_err(CompileError::SymbolNotFound {
                     name: enum_name.clone(),
                     span: enum_name.span(),
+                    import_candidates: vec![],
                 }));
             }
         };
@@ -702,6 +706,7 @@ impl Module {
                 return Err(handler.emit_err(CompileError::SymbolNotFound {
                     name: enum_name.clone(),
                     span: enum_name.span(),
+                    import_candidates: vec![],
                 }));
             }
         };
@@ -968,6 +973,7 @@ impl Module {
                 return Err(handler.emit_err(CompileError::SymbolNotFound {
                     name: symbol.clone(),
                     span: symbol.span(),
+                    import_candidates: vec![],
                 }))
             }
         })
@@ -2,6 +2,7 @@ use crate::{
     language::{ty, ty::TyTraitItem, CallPath, Visibility},
     Engines, Ident, TypeId,
 };
+use serde::{Deserialize, Serialize};
 
 use super::{module::Module, root::Root, submodule_namespace::SubmoduleNamespace, Path, PathBuf};
 
@@ -17,7 +18,7 @@ pub enum TryInsertingTraitImplOnFailure {
 }
 
 /// The set of items that represent the namespace context passed throughout type checking.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Namespace {
     /// An immutable namespace that consists of the names that should always be present, no matter
     /// what module or scope we are currently checking.
@@ -1,4 +1,5 @@
 use super::{module::Module, namespace::Namespace};
+use serde::{Deserialize, Serialize};
 
 /// The root module, from which all other modules can be accessed.
 ///
@@ -8,7 +9,7 @@ use super::{module::Module, namespace::Namespace};
 /// canonical paths, or that use canonical paths internally, are *only* called from the root. This
 /// normally includes methods that first lookup some canonical path via `use_synonyms` before using
 /// that canonical path to look up the symbol declaration.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Root {
     pub(crate) module: Module,
 }
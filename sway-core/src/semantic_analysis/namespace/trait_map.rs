@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     collections::{BTreeSet, HashMap},
@@ -23,7 +24,7 @@ use crate::{
 
 use super::TryInsertingTraitImplOnFailure;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct TraitSuffix {
     name: Ident,
     args: Vec<TypeArgument>,
@@ -84,7 +85,7 @@ impl DebugWithEngines for TraitSuffix {
 
 type TraitName = CallPath<TraitSuffix>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct TraitKey {
     name: TraitName,
     type_id: TypeId,
@@ -102,14 +103,14 @@ impl OrdWithEngines for TraitKey {
 /// Map of name to [TyImplItem](ty::TyImplItem)
 type TraitItems = im::HashMap<String, TyImplItem>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct TraitValue {
     trait_items: TraitItems,
     /// The span of the entire impl block.
     impl_span: Span,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct TraitEntry {
     key: TraitKey,
     value: TraitValue,
@@ -122,7 +123,7 @@ type TraitImpls = Vec<TraitEntry>;
 ///
 /// Note: "impl self" blocks are considered traits and are stored in the
 /// [TraitMap].
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub(crate) struct TraitMap {
     trait_impls: TraitImpls,
 }
@@ -984,6 +985,7 @@ impl TraitMap {
             Ordering::Less => Err(handler.emit_err(CompileError::SymbolNotFound {
                 name: symbol.clone(),
                 span: symbol.span(),
+                import_candidates: vec![],
             })),
             Ordering::Equal => Ok(candidates.values().next().unwrap().clone()),
         }
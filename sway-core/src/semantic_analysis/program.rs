@@ -5,6 +5,7 @@ use crate::{
     },
     metadata::MetadataManager,
     semantic_analysis::{
+        import_candidates::fill_import_candidates,
         namespace::{self, Namespace},
         TypeCheckContext,
     },
@@ -51,7 +52,10 @@ impl TyProgram {
             parsed.kind,
             root,
             module_eval_order,
-        )?;
+        );
+        // Only now are all the items that could be imported known.
+        fill_import_candidates(handler, engines, ctx.namespace());
+        let root = root?;
 
         let (kind, declarations, configurables) = Self::validate_root(
             handler,
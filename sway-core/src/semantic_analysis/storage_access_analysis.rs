@@ -0,0 +1,631 @@
+// Storage access analysis computes, for each contract entry point, the set of storage
+// fields it reads and writes, including the accesses made by the functions it calls.
+// Storage is accessed through `StorageKey`s, so an access is attributed to a storage field
+// when the `StorageKey` it goes through is derived from a `storage.<field>` expression.
+// The reads and writes of a function that are not attributed to any field, like the ones
+// made by the `StorageKey` methods themselves, are attributed to the storage fields of the
+// `StorageKey`s passed to it at each call site.
+// Whatever cannot be attributed to a field, e.g. accesses through keys computed by hand,
+// ends up in the unknown slots of the entry point.
+// The result is exported as a report and used to warn about storage fields that are only
+// ever written or only ever read by the contract.
+
+use crate::{
+    control_flow_analysis::allow_dead_code,
+    decl_engine::*,
+    language::{
+        ty::{self, TyFunctionDecl},
+        AsmOp,
+    },
+    semantic_analysis::cei_pattern_analysis::FunctionKey,
+    Engines, TypeId, TypeInfo,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use sway_error::warning::{CompileWarning, Warning};
+use sway_types::Spanned;
+
+/// The storage fields read and written by each entry point of a contract.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StorageAccessReport {
+    pub entry_points: Vec<EntryPointStorageAccess>,
+}
+
+/// The storage fields read and written by a contract entry point, directly or through the
+/// functions it calls.
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryPointStorageAccess {
+    pub name: String,
+    pub reads: Vec<String>,
+    pub writes: Vec<String>,
+    /// The entry point reads storage slots that could not be attributed to a storage field.
+    pub reads_unknown_slots: bool,
+    /// The entry point writes storage slots that could not be attributed to a storage field.
+    pub writes_unknown_slots: bool,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Access {
+    read: bool,
+    write: bool,
+}
+
+impl Access {
+    fn merge(&mut self, other: Access) {
+        self.read |= other.read;
+        self.write |= other.write;
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.read && !self.write
+    }
+}
+
+const READ: Access = Access {
+    read: true,
+    write: false,
+};
+
+const WRITE: Access = Access {
+    read: false,
+    write: true,
+};
+
+// The storage accesses of a function.
+#[derive(Clone, Default)]
+struct StorageEffects {
+    fields: BTreeMap<String, Access>,
+    unattributed: Access,
+    // Accesses made by the function itself through intrinsics or `asm` blocks, which are
+    // folded into `unattributed` once the whole function has been analysed.
+    direct: Access,
+}
+
+impl StorageEffects {
+    fn merge_field(&mut self, field: &str, access: Access) {
+        self.fields
+            .entry(field.to_string())
+            .or_default()
+            .merge(access);
+    }
+}
+
+// The state of the analysis of a contract.
+struct StorageAccessAnalysis<'eng> {
+    engines: &'eng Engines,
+    // The storage accesses of each function analysed so far.
+    summaries: HashMap<FunctionKey, StorageEffects>,
+}
+
+// The state of the analysis of a single function.
+#[derive(Default)]
+struct FunctionContext {
+    // The storage fields of the `StorageKey`s bound to local variables.
+    locals: HashMap<String, BTreeSet<String>>,
+}
+
+/// Computes the storage fields read and written by each entry point of a contract,
+/// or returns `None` if the program is not a contract.
+pub fn storage_access_report(
+    engines: &Engines,
+    prog: &ty::TyProgram,
+) -> Option<StorageAccessReport> {
+    let ty::TyProgramKind::Contract { abi_entries, .. } = &prog.kind else {
+        return None;
+    };
+    let mut analysis = StorageAccessAnalysis {
+        engines,
+        summaries: HashMap::new(),
+    };
+    let entry_points = abi_entries
+        .iter()
+        .map(|fn_decl_id| {
+            let fn_decl = engines.de().get_function(fn_decl_id);
+            let effects = analyze_function(&mut analysis, fn_decl_id);
+            let fields_with = |pred: fn(&Access) -> bool| {
+                effects
+                    .fields
+                    .iter()
+                    .filter(|(_, access)| pred(access))
+                    .map(|(field, _)| field.clone())
+                    .collect()
+            };
+            EntryPointStorageAccess {
+                name: fn_decl.name.to_string(),
+                reads: fields_with(|access| access.read),
+                writes: fields_with(|access| access.write),
+                reads_unknown_slots: effects.unattributed.read,
+                writes_unknown_slots: effects.unattributed.write,
+            }
+        })
+        .collect();
+    Some(StorageAccessReport { entry_points })
+}
+
+pub(crate) fn analyze_program(engines: &Engines, prog: &ty::TyProgram) -> Vec<CompileWarning> {
+    let Some(report) = storage_access_report(engines, prog) else {
+        return vec![];
+    };
+
+    // If some entry point accesses storage slots we know nothing about, any field
+    // could be accessed through them.
+    let reads_unknown_slots = report.entry_points.iter().any(|e| e.reads_unknown_slots);
+    let writes_unknown_slots = report.entry_points.iter().any(|e| e.writes_unknown_slots);
+    let mut accesses = BTreeMap::<&str, Access>::new();
+    for entry_point in &report.entry_points {
+        for field in &entry_point.reads {
+            accesses.entry(field).or_default().merge(READ);
+        }
+        for field in &entry_point.writes {
+            accesses.entry(field).or_default().merge(WRITE);
+        }
+    }
+
+    let decl_engine = engines.de();
+    let mut warnings = vec![];
+    for decl in &prog.declarations {
+        let ty::TyDecl::StorageDecl(ty::StorageDecl { decl_id, .. }) = decl else {
+            continue;
+        };
+        let storage_decl = decl_engine.get_storage(decl_id);
+        for field in &storage_decl.fields {
+            if allow_dead_code(field.attributes.clone()) {
+                continue;
+            }
+            // Fields that are never accessed are reported by the dead code analysis.
+            let Some(access) = accesses.get(field.name.as_str()) else {
+                continue;
+            };
+            let warning_content = match (access.read, access.write) {
+                (false, true) if !reads_unknown_slots => Warning::StorageFieldWrittenButNeverRead,
+                (true, false) if !writes_unknown_slots => Warning::StorageFieldReadButNeverWritten,
+                _ => continue,
+            };
+            warnings.push(CompileWarning {
+                span: field.name.span(),
+                warning_content,
+            });
+        }
+    }
+    warnings
+}
+
+// Analyze the function the first time it is reached and return its storage accesses.
+fn analyze_function(
+    analysis: &mut StorageAccessAnalysis,
+    fn_decl_id: &DeclId<TyFunctionDecl>,
+) -> StorageEffects {
+    let fn_decl = analysis.engines.de().get_function(fn_decl_id);
+    let key = FunctionKey::new(fn_decl_id, &fn_decl);
+    if let Some(effects) = analysis.summaries.get(&key) {
+        return effects.clone();
+    }
+    // Recursion is not allowed, but make sure an erroneous program can't send us
+    // into an endless loop.
+    analysis
+        .summaries
+        .insert(key.clone(), StorageEffects::default());
+    let mut effects = StorageEffects::default();
+    let mut ctx = FunctionContext::default();
+    analyze_code_block(analysis, &mut ctx, &mut effects, &fn_decl.body);
+    // Writing part of a storage slot means reading it first, so that the rest of it can be
+    // written back unchanged. Such reads are part of the write rather than reads on their own.
+    let direct = std::mem::take(&mut effects.direct);
+    effects.unattributed.merge(Access {
+        read: direct.read && !direct.write,
+        write: direct.write,
+    });
+    analysis.summaries.insert(key, effects.clone());
+    effects
+}
+
+fn analyze_code_block(
+    analysis: &mut StorageAccessAnalysis,
+    ctx: &mut FunctionContext,
+    effects: &mut StorageEffects,
+    codeblock: &ty::TyCodeBlock,
+) {
+    for ast_node in &codeblock.contents {
+        match &ast_node.content {
+            ty::TyAstNodeContent::Declaration(ty::TyDecl::VariableDecl(var_decl)) => {
+                analyze_expression(analysis, ctx, effects, &var_decl.body);
+                let fields = storage_fields_of(analysis.engines, ctx, &var_decl.body);
+                ctx.locals.insert(var_decl.name.to_string(), fields);
+            }
+            // Declarations (except variable declarations) are not allowed in a codeblock
+            ty::TyAstNodeContent::Declaration(_) => {}
+            ty::TyAstNodeContent::Expression(expr) => {
+                analyze_expression(analysis, ctx, effects, expr)
+            }
+            ty::TyAstNodeContent::SideEffect(_) | ty::TyAstNodeContent::Error(_, _) => {}
+        }
+    }
+}
+
+fn analyze_expression(
+    analysis: &mut StorageAccessAnalysis,
+    ctx: &mut FunctionContext,
+    effects: &mut StorageEffects,
+    expr: &ty::TyExpression,
+) {
+    use crate::ty::TyExpressionVariant::*;
+    match &expr.expression {
+        // `storage.<field>` only yields a `StorageKey`, the access itself happens when
+        // the key is used
+        Literal(_)
+        | ConstantExpression { .. }
+        | VariableExpression { .. }
+        | FunctionParameter
+        | Break
+        | Continue
+        | AbiName(_)
+        | StorageAccess(_) => {}
+        Reassignment(reassgn) => analyze_expression(analysis, ctx, effects, &reassgn.rhs),
+        CodeBlock(codeblock) => analyze_code_block(analysis, ctx, effects, codeblock),
+        LazyOperator { lhs, rhs, .. } => {
+            analyze_expression(analysis, ctx, effects, lhs);
+            analyze_expression(analysis, ctx, effects, rhs);
+        }
+        ArrayIndex { prefix, index } => {
+            analyze_expression(analysis, ctx, effects, prefix);
+            analyze_expression(analysis, ctx, effects, index);
+        }
+        FunctionApplication {
+            arguments,
+            fn_ref,
+            selector,
+            ..
+        } => {
+            for (_, arg) in arguments {
+                analyze_expression(analysis, ctx, effects, arg);
+            }
+            // the storage accesses of external contract calls happen in the other contract
+            if selector.is_none() {
+                let callee_effects = analyze_function(analysis, fn_ref.id());
+                for (field, access) in &callee_effects.fields {
+                    effects.merge_field(field, *access);
+                }
+                if !callee_effects.unattributed.is_empty() {
+                    let key_fields = arguments
+                        .iter()
+                        .flat_map(|(_, arg)| storage_fields_of(analysis.engines, ctx, arg))
+                        .collect::<BTreeSet<_>>();
+                    if key_fields.is_empty() {
+                        effects.unattributed.merge(callee_effects.unattributed);
+                    }
+                    for field in &key_fields {
+                        effects.merge_field(field, callee_effects.unattributed);
+                    }
+                }
+            }
+        }
+        IntrinsicFunction(intrinsic) => {
+            for arg in &intrinsic.arguments {
+                analyze_expression(analysis, ctx, effects, arg);
+            }
+            effects.direct.merge(access_of_intrinsic(&intrinsic.kind));
+        }
+        Tuple { fields: exprs }
+        | Array {
+            elem_type: _,
+            contents: exprs,
+        } => {
+            for expr in exprs {
+                analyze_expression(analysis, ctx, effects, expr);
+            }
+        }
+        StructExpression { fields, .. } => {
+            for field in fields {
+                analyze_expression(analysis, ctx, effects, &field.value);
+            }
+        }
+        StructFieldAccess { prefix: expr, .. }
+        | TupleElemAccess { prefix: expr, .. }
+        | ImplicitReturn(expr)
+        | Return(expr)
+        | EnumTag { exp: expr }
+        | UnsafeDowncast { exp: expr, .. }
+        | AbiCast { address: expr, .. }
+        | Ref(expr)
+        | Deref(expr)
+        | MatchExp {
+            desugared: expr, ..
+        }
+        | ForLoop { desugared: expr } => analyze_expression(analysis, ctx, effects, expr),
+        EnumInstantiation { contents, .. } => {
+            if let Some(expr) = contents {
+                analyze_expression(analysis, ctx, effects, expr);
+            }
+        }
+        IfExp {
+            condition,
+            then,
+            r#else,
+        } => {
+            analyze_expression(analysis, ctx, effects, condition);
+            analyze_expression(analysis, ctx, effects, then);
+            if let Some(else_exp) = r#else {
+                analyze_expression(analysis, ctx, effects, else_exp);
+            }
+        }
+        WhileLoop { condition, body } => {
+            analyze_expression(analysis, ctx, effects, condition);
+            analyze_code_block(analysis, ctx, effects, body);
+        }
+        AsmExpression {
+            registers, body, ..
+        } => {
+            for init in registers.iter().filter_map(|r| r.initializer.as_ref()) {
+                analyze_expression(analysis, ctx, effects, init);
+            }
+            for asm_op in body {
+                effects.direct.merge(access_of_asm_op(asm_op));
+            }
+        }
+    }
+}
+
+// The storage fields of the `StorageKey`s the expression evaluates to, if any.
+fn storage_fields_of(
+    engines: &Engines,
+    ctx: &FunctionContext,
+    expr: &ty::TyExpression,
+) -> BTreeSet<String> {
+    if !contains_storage_key(engines, expr.return_type) {
+        return BTreeSet::new();
+    }
+    use crate::ty::TyExpressionVariant::*;
+    match &expr.expression {
+        StorageAccess(storage_access) => {
+            BTreeSet::from([storage_access.storage_field_name().to_string()])
+        }
+        // a `StorageKey` returned by a function, like `StorageMap::get`,
+        // is derived from the ones passed to it
+        FunctionApplication {
+            arguments,
+            selector: None,
+            ..
+        } => arguments
+            .iter()
+            .flat_map(|(_, arg)| storage_fields_of(engines, ctx, arg))
+            .collect(),
+        VariableExpression { name, .. } => {
+            ctx.locals.get(name.as_str()).cloned().unwrap_or_default()
+        }
+        StructFieldAccess { prefix: expr, .. }
+        | TupleElemAccess { prefix: expr, .. }
+        | Ref(expr)
+        | Deref(expr)
+        | UnsafeDowncast { exp: expr, .. }
+        | MatchExp {
+            desugared: expr, ..
+        } => storage_fields_of_unchecked(engines, ctx, expr),
+        EnumInstantiation {
+            contents: Some(expr),
+            ..
+        } => storage_fields_of(engines, ctx, expr),
+        IfExp {
+            then,
+            r#else: Some(else_exp),
+            ..
+        } => {
+            let mut fields = storage_fields_of(engines, ctx, then);
+            fields.extend(storage_fields_of(engines, ctx, else_exp));
+            fields
+        }
+        _ => BTreeSet::new(),
+    }
+}
+
+// Like `storage_fields_of`, for expressions whose type does not have to contain a
+// `StorageKey` on its own, like the prefix of a field access.
+fn storage_fields_of_unchecked(
+    engines: &Engines,
+    ctx: &FunctionContext,
+    expr: &ty::TyExpression,
+) -> BTreeSet<String> {
+    use crate::ty::TyExpressionVariant::*;
+    match &expr.expression {
+        StorageAccess(storage_access) => {
+            BTreeSet::from([storage_access.storage_field_name().to_string()])
+        }
+        VariableExpression { name, .. } => {
+            ctx.locals.get(name.as_str()).cloned().unwrap_or_default()
+        }
+        StructFieldAccess { prefix: expr, .. }
+        | TupleElemAccess { prefix: expr, .. }
+        | Ref(expr)
+        | Deref(expr) => storage_fields_of_unchecked(engines, ctx, expr),
+        _ => storage_fields_of(engines, ctx, expr),
+    }
+}
+
+fn contains_storage_key(engines: &Engines, type_id: TypeId) -> bool {
+    let decl_engine = engines.de();
+    match &*engines.te().get(type_id) {
+        TypeInfo::Struct(decl_ref) => {
+            let struct_decl = decl_engine.get_struct(decl_ref);
+            struct_decl.call_path.suffix.as_str() == "StorageKey"
+                || struct_decl
+                    .type_parameters
+                    .iter()
+                    .any(|param| contains_storage_key(engines, param.type_id))
+        }
+        TypeInfo::Enum(decl_ref) => decl_engine
+            .get_enum(decl_ref)
+            .type_parameters
+            .iter()
+            .any(|param| contains_storage_key(engines, param.type_id)),
+        TypeInfo::Tuple(elems) => elems
+            .iter()
+            .any(|elem| contains_storage_key(engines, elem.type_id)),
+        TypeInfo::Array(elem, _) | TypeInfo::Ref(elem) | TypeInfo::Alias { ty: elem, .. } => {
+            contains_storage_key(engines, elem.type_id)
+        }
+        _ => false,
+    }
+}
+
+fn access_of_intrinsic(intr: &sway_ast::Intrinsic) -> Access {
+    use sway_ast::Intrinsic::*;
+    match intr {
+        StateClear | StateStoreWord | StateStoreQuad => WRITE,
+        StateLoadWord | StateLoadQuad => READ,
+        Revert | JmpMem | IsReferenceType | IsStrArray | SizeOfType | SizeOfVal | SizeOfStr
+        | ContractCall | ContractRet | AssertIsStrArray | ToStrArray | Eq | Gt | Lt | Gtf
+        | AddrOf | Log | Add | Sub | Mul | Div | And | Or | Xor | Mod | Rsh | Lsh | PtrAdd
+        | PtrSub | Not | Smo => Access::default(),
+    }
+}
+
+fn access_of_asm_op(op: &AsmOp) -> Access {
+    match op.op_name.as_str().to_lowercase().as_str() {
+        "scwq" | "sww" | "swwq" => WRITE,
+        "srw" | "srwq" => READ,
+        _ => Access::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic_analysis::test_utils::compile_with_core;
+
+    /// The storage of the test contracts, and helpers which access it through `StorageKey`s.
+    const STORAGE: &str = "
+        storage {
+            a: u64 = 0,
+            b: u64 = 0,
+            c: u64 = 0,
+        }
+
+        #[storage(read)]
+        fn read(key: StorageKey<u64>) -> u64 {
+            __state_load_word(key.slot())
+        }
+
+        #[storage(write)]
+        fn write(key: StorageKey<u64>, value: u64) {
+            let _ = __state_store_word(key.slot(), value);
+        }
+
+        #[storage(read, write)]
+        fn increment(key: StorageKey<u64>) {
+            write(key, read(key) + 1);
+        }
+    ";
+
+    /// Compiles a contract with the test storage, the ABI `abi` and its implementation `imp`.
+    fn compile(engines: &Engines, abi: &str, imp: &str) -> ty::TyProgram {
+        let src = format!(
+            "contract;
+            {STORAGE}
+            abi A {{ {abi} }}
+            impl A for Contract {{ {imp} }}"
+        );
+        compile_with_core(engines, &src)
+    }
+
+    /// The storage fields reported as read and written only, and the names of the fields
+    /// they are reported for.
+    fn warned_fields(abi: &str, imp: &str) -> (Vec<String>, Vec<String>) {
+        let engines = Engines::default();
+        let program = compile(&engines, abi, imp);
+        let (mut read_only, mut written_only) = (vec![], vec![]);
+        for warning in analyze_program(&engines, &program) {
+            let field = warning.span.as_str().to_string();
+            match warning.warning_content {
+                Warning::StorageFieldReadButNeverWritten => read_only.push(field),
+                Warning::StorageFieldWrittenButNeverRead => written_only.push(field),
+                _ => {}
+            }
+        }
+        (read_only, written_only)
+    }
+
+    #[test]
+    fn accesses_are_attributed_to_the_fields_of_the_keys() {
+        let engines = Engines::default();
+        let program = compile(
+            &engines,
+            "#[storage(read)] fn get_a() -> u64;
+            #[storage(write)] fn set_b(value: u64);
+            #[storage(read, write)] fn increment_c();
+            #[storage(read)] fn read_by_hand() -> u64;
+            fn pure() -> u64;",
+            "#[storage(read)] fn get_a() -> u64 { read(storage.a) }
+            #[storage(write)] fn set_b(value: u64) { let key = storage.b; write(key, value); }
+            #[storage(read, write)] fn increment_c() { increment(storage.c); }
+            #[storage(read)] fn read_by_hand() -> u64 {
+                __state_load_word(0x0000000000000000000000000000000000000000000000000000000000000001)
+            }
+            fn pure() -> u64 { 42 }",
+        );
+        let report = storage_access_report(&engines, &program).expect("a contract has a report");
+        let mut summary = report
+            .entry_points
+            .iter()
+            .map(|entry_point| {
+                (
+                    entry_point.name.as_str(),
+                    entry_point.reads.clone(),
+                    entry_point.writes.clone(),
+                    entry_point.reads_unknown_slots,
+                    entry_point.writes_unknown_slots,
+                )
+            })
+            .collect::<Vec<_>>();
+        summary.sort();
+        let fields = |fields: &[&str]| fields.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                ("get_a", fields(&["a"]), fields(&[]), false, false),
+                ("increment_c", fields(&["c"]), fields(&["c"]), false, false),
+                ("pure", fields(&[]), fields(&[]), false, false),
+                ("read_by_hand", fields(&[]), fields(&[]), true, false),
+                ("set_b", fields(&[]), fields(&["b"]), false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn scripts_have_no_report() {
+        let engines = Engines::default();
+        let program = compile_with_core(&engines, "script; fn main() {}");
+        assert!(storage_access_report(&engines, &program).is_none());
+    }
+
+    #[test]
+    fn fields_only_read_or_only_written_are_reported() {
+        let (read_only, written_only) = warned_fields(
+            "#[storage(read)] fn get_a() -> u64;
+            #[storage(write)] fn set_b(value: u64);
+            #[storage(read, write)] fn increment_c();",
+            "#[storage(read)] fn get_a() -> u64 { read(storage.a) }
+            #[storage(write)] fn set_b(value: u64) { write(storage.b, value); }
+            #[storage(read, write)] fn increment_c() { increment(storage.c); }",
+        );
+        assert_eq!(read_only, ["a"]);
+        assert_eq!(written_only, ["b"]);
+    }
+
+    #[test]
+    fn fields_are_not_reported_if_unknown_slots_are_accessed() {
+        let (read_only, written_only) = warned_fields(
+            "#[storage(read)] fn get_a() -> u64;
+            #[storage(write)] fn set_b(value: u64);
+            #[storage(read, write)] fn increment_c();
+            #[storage(read)] fn read_by_hand() -> u64;",
+            "#[storage(read)] fn get_a() -> u64 { read(storage.a) }
+            #[storage(write)] fn set_b(value: u64) { write(storage.b, value); }
+            #[storage(read, write)] fn increment_c() { increment(storage.c); }
+            #[storage(read)] fn read_by_hand() -> u64 {
+                __state_load_word(0x0000000000000000000000000000000000000000000000000000000000000001)
+            }",
+        );
+        // `b` could be read through the unknown slot
+        assert_eq!(read_only, ["a"]);
+        assert!(written_only.is_empty(), "{written_only:?}");
+    }
+}
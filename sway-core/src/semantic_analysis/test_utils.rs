@@ -0,0 +1,79 @@
+//! Helpers for the tests of the analyses which run on whole typed programs.
+use crate::{
+    language::{ty, Visibility},
+    namespace, BuildConfig, BuildTarget, Engines,
+};
+use std::{path::Path, sync::Arc};
+use sway_error::handler::Handler;
+use sway_types::Ident;
+
+/// Compiles the program `src`, which may only depend on `core`, and returns its typed program.
+///
+/// Panics if `core` or the program do not compile.
+pub(crate) fn compile_with_core(engines: &Engines, src: &str) -> ty::TyProgram {
+    let core_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../sway-lib-core");
+    let core_entry = core_dir.join("src/lib.sw");
+    let core_src = std::fs::read_to_string(&core_entry).expect("`core` is readable");
+    let core = compile(engines, &core_src, &core_entry, &core_dir, module("core"));
+    let mut core_module = core.root.namespace.module().clone();
+    core_module.name = Some(Ident::new_no_span("core".to_string()));
+
+    let mut namespace = module("test");
+    namespace.insert_submodule("core".to_string(), core_module);
+    let _ = namespace.star_import_with_reexports(
+        &Handler::default(),
+        engines,
+        &["core", "prelude"].map(|s| Ident::new_no_span(s.to_string())),
+        &[],
+        true,
+    );
+    compile(
+        engines,
+        src,
+        Path::new("/test/src/main.sw"),
+        Path::new("/test"),
+        namespace,
+    )
+}
+
+fn module(name: &str) -> namespace::Module {
+    namespace::Module {
+        name: Some(Ident::new_no_span(name.to_string())),
+        is_external: true,
+        visibility: Visibility::Public,
+        ..Default::default()
+    }
+}
+
+fn compile(
+    engines: &Engines,
+    src: &str,
+    entry: &Path,
+    manifest_dir: &Path,
+    namespace: namespace::Module,
+) -> ty::TyProgram {
+    let handler = Handler::default();
+    let build_config = BuildConfig::root_from_file_name_and_manifest_path(
+        entry.to_path_buf(),
+        manifest_dir.to_path_buf(),
+        BuildTarget::default(),
+    );
+    let name = namespace.name.as_ref().unwrap().to_string();
+    let programs = crate::compile_to_ast(
+        &handler,
+        engines,
+        Arc::from(src),
+        namespace,
+        Some(&build_config),
+        &name,
+        None,
+    );
+    let (errors, _warnings) = handler.consume();
+    if !errors.is_empty() {
+        panic!("`{name}` does not compile: {errors:#?}");
+    }
+    programs
+        .ok()
+        .and_then(|programs| programs.typed.ok())
+        .unwrap_or_else(|| panic!("`{name}` does not compile"))
+}
@@ -80,6 +80,9 @@ pub struct TypeCheckContext<'a> {
     /// Tracks the purity of the context, e.g. whether or not we should be allowed to write to
     /// storage.
     purity: Purity,
+    /// The span of the function declaration whose body is being type checked, if any.
+    /// Used to point at the function when it lacks the storage attributes its body needs.
+    fn_decl_span: Option<Span>,
     /// Provides the kind of the module.
     /// This is useful for example to throw an error when while loops are present in predicates.
     kind: TreeType,
@@ -122,6 +125,7 @@ impl<'a> TypeCheckContext<'a> {
             const_shadowing_mode: ConstShadowingMode::ItemStyle,
             generic_shadowing_mode: GenericShadowingMode::Disallow,
             purity: Purity::default(),
+            fn_decl_span: None,
             kind: TreeType::Contract,
             disallow_functions: false,
             defer_monomorphization: false,
@@ -164,6 +168,7 @@ impl<'a> TypeCheckContext<'a> {
             const_shadowing_mode: ConstShadowingMode::ItemStyle,
             generic_shadowing_mode: GenericShadowingMode::Disallow,
             purity: Purity::default(),
+            fn_decl_span: None,
             kind: TreeType::Contract,
             disallow_functions: false,
             defer_monomorphization: false,
@@ -193,6 +198,7 @@ impl<'a> TypeCheckContext<'a> {
             generic_shadowing_mode: self.generic_shadowing_mode,
             help_text: self.help_text,
             purity: self.purity,
+            fn_decl_span: self.fn_decl_span.clone(),
             kind: self.kind,
             engines: self.engines,
             disallow_functions: self.disallow_functions,
@@ -220,6 +226,7 @@ impl<'a> TypeCheckContext<'a> {
             generic_shadowing_mode: self.generic_shadowing_mode,
             help_text: self.help_text,
             purity: self.purity,
+            fn_decl_span: self.fn_decl_span.clone(),
             kind: self.kind,
             engines: self.engines,
             disallow_functions: self.disallow_functions,
@@ -332,6 +339,15 @@ impl<'a> TypeCheckContext<'a> {
         Self { purity, ..self }
     }
 
+    /// Map this `TypeCheckContext` instance to a new one with the span of the function
+    /// declaration whose body is being type checked.
+    pub(crate) fn with_fn_decl_span(self, fn_decl_span: Span) -> Self {
+        Self {
+            fn_decl_span: Some(fn_decl_span),
+            ..self
+        }
+    }
+
     /// Map this `TypeCheckContext` instance to a new one with the given module kind.
     pub(crate) fn with_kind(self, kind: TreeType) -> Self {
         Self { kind, ..self }
@@ -421,6 +437,10 @@ impl<'a> TypeCheckContext<'a> {
         self.purity
     }
 
+    pub(crate) fn fn_decl_span(&self) -> Option<&Span> {
+        self.fn_decl_span.as_ref()
+    }
+
     #[allow(dead_code)]
     pub(crate) fn kind(&self) -> TreeType {
         self.kind
@@ -0,0 +1,297 @@
+// Unused `mut` analysis warns about local variables declared with `let mut` which are never
+// changed, and suggests removing the `mut`.
+// A variable counts as changed if it, or a part of it, is reassigned, passed to a `ref mut`
+// parameter, including the `ref mut self` of a method, or referenced. Variables which are
+// handed to an `asm` block or to `__addr_of` could be changed through their address and
+// count as changed as well.
+// Variables are matched to their declarations by name, following the block scopes of the
+// function body, which is enough because the body has already been type checked.
+
+use crate::{
+    decl_engine::*,
+    language::ty::{self, TyFunctionDecl, VariableMutability},
+    Engines,
+};
+use sway_error::warning::{CompileWarning, Warning};
+use sway_types::{Ident, Span, Spanned};
+
+// A mutable local variable which is in scope.
+struct MutVariable {
+    name: Ident,
+    mut_span: Span,
+    changed: bool,
+}
+
+struct FunctionAnalysis<'eng, 'w> {
+    engines: &'eng Engines,
+    variables: Vec<MutVariable>,
+    warnings: &'w mut Vec<CompileWarning>,
+}
+
+pub(crate) fn analyze_program(engines: &Engines, prog: &ty::TyProgram) -> Vec<CompileWarning> {
+    let decl_engine = engines.de();
+    let mut warnings = vec![];
+    let modules = std::iter::once(&prog.root).chain(
+        prog.root
+            .submodules_recursive()
+            .map(|(_, submodule)| &submodule.module),
+    );
+    for module in modules {
+        for node in &module.all_nodes {
+            match &node.content {
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::FunctionDecl(ty::FunctionDecl {
+                    decl_id,
+                    ..
+                })) => analyze_function(engines, decl_id, &mut warnings),
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::ImplTrait(ty::ImplTrait {
+                    decl_id,
+                    ..
+                })) => {
+                    for item in &decl_engine.get_impl_trait(decl_id).items {
+                        if let ty::TyImplItem::Fn(fn_ref) = item {
+                            analyze_function(engines, fn_ref.id(), &mut warnings);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    warnings
+}
+
+fn analyze_function(
+    engines: &Engines,
+    fn_decl_id: &DeclId<TyFunctionDecl>,
+    warnings: &mut Vec<CompileWarning>,
+) {
+    let fn_decl = engines.de().get_function(fn_decl_id);
+    let mut analysis = FunctionAnalysis {
+        engines,
+        variables: vec![],
+        warnings,
+    };
+    analysis.analyze_code_block(&fn_decl.body);
+}
+
+impl FunctionAnalysis<'_, '_> {
+    fn analyze_code_block(&mut self, codeblock: &ty::TyCodeBlock) {
+        let scope_start = self.variables.len();
+        for ast_node in &codeblock.contents {
+            match &ast_node.content {
+                ty::TyAstNodeContent::Declaration(ty::TyDecl::VariableDecl(var_decl)) => {
+                    self.analyze_expression(&var_decl.body);
+                    self.declare(var_decl);
+                }
+                // Declarations (except variable declarations) are not allowed in a codeblock
+                ty::TyAstNodeContent::Declaration(_) => {}
+                ty::TyAstNodeContent::Expression(expr) => self.analyze_expression(expr),
+                ty::TyAstNodeContent::SideEffect(_) | ty::TyAstNodeContent::Error(_, _) => {}
+            }
+        }
+        for variable in self.variables.drain(scope_start..) {
+            if !variable.changed {
+                self.warnings.push(CompileWarning {
+                    span: variable.name.span(),
+                    warning_content: Warning::UnusedMut {
+                        name: variable.name,
+                        mut_span: variable.mut_span,
+                    },
+                });
+            }
+        }
+    }
+
+    fn declare(&mut self, var_decl: &ty::TyVariableDecl) {
+        if var_decl.mutability != VariableMutability::Mutable {
+            return;
+        }
+        // Variables introduced by desugaring, e.g., of `for` loops, are not written by the user.
+        if var_decl.name.as_str().starts_with("__") {
+            return;
+        }
+        if let Some(mut_span) = mut_keyword_span(&var_decl.name) {
+            self.variables.push(MutVariable {
+                name: var_decl.name.clone(),
+                mut_span,
+                changed: false,
+            });
+        }
+    }
+
+    // Marks the variable the expression is a place in, if any, as changed.
+    fn change(&mut self, expr: &ty::TyExpression) {
+        if let Some(name) = base_variable(expr) {
+            self.change_variable(name);
+        }
+    }
+
+    fn change_variable(&mut self, name: &Ident) {
+        if let Some(variable) = self
+            .variables
+            .iter_mut()
+            .rev()
+            .find(|variable| variable.name.as_str() == name.as_str())
+        {
+            variable.changed = true;
+        }
+    }
+
+    fn analyze_expression(&mut self, expr: &ty::TyExpression) {
+        use crate::ty::TyExpressionVariant::*;
+        match &expr.expression {
+            Literal(_)
+            | ConstantExpression { .. }
+            | VariableExpression { .. }
+            | FunctionParameter
+            | Break
+            | Continue
+            | AbiName(_)
+            | StorageAccess(_) => {}
+            Reassignment(reassgn) => {
+                for index in &reassgn.lhs_indices {
+                    if let ty::ProjectionKind::ArrayIndex { index, .. } = index {
+                        self.analyze_expression(index);
+                    }
+                }
+                self.analyze_expression(&reassgn.rhs);
+                self.change_variable(&reassgn.lhs_base_name);
+            }
+            CodeBlock(codeblock) => self.analyze_code_block(codeblock),
+            LazyOperator { lhs, rhs, .. } => {
+                self.analyze_expression(lhs);
+                self.analyze_expression(rhs);
+            }
+            ArrayIndex { prefix, index } => {
+                self.analyze_expression(prefix);
+                self.analyze_expression(index);
+            }
+            FunctionApplication {
+                arguments,
+                fn_ref,
+                contract_call_params,
+                contract_caller,
+                ..
+            } => {
+                for arg in contract_call_params.values() {
+                    self.analyze_expression(arg);
+                }
+                if let Some(contract_caller) = contract_caller {
+                    self.analyze_expression(contract_caller);
+                }
+                for (_, arg) in arguments {
+                    self.analyze_expression(arg);
+                }
+                let fn_decl = self.engines.de().get_function(fn_ref);
+                for ((_, arg), param) in arguments.iter().zip(&fn_decl.parameters) {
+                    if param.is_reference && param.is_mutable {
+                        self.change(arg);
+                    }
+                }
+            }
+            IntrinsicFunction(intrinsic) => {
+                for arg in &intrinsic.arguments {
+                    self.analyze_expression(arg);
+                }
+                if matches!(intrinsic.kind, sway_ast::Intrinsic::AddrOf) {
+                    for arg in &intrinsic.arguments {
+                        self.change(arg);
+                    }
+                }
+            }
+            Tuple { fields: exprs }
+            | Array {
+                elem_type: _,
+                contents: exprs,
+            } => {
+                for expr in exprs {
+                    self.analyze_expression(expr);
+                }
+            }
+            StructExpression { fields, .. } => {
+                for field in fields {
+                    self.analyze_expression(&field.value);
+                }
+            }
+            Ref(expr) => {
+                self.analyze_expression(expr);
+                self.change(expr);
+            }
+            StructFieldAccess { prefix: expr, .. }
+            | TupleElemAccess { prefix: expr, .. }
+            | ImplicitReturn(expr)
+            | Return(expr)
+            | EnumTag { exp: expr }
+            | UnsafeDowncast { exp: expr, .. }
+            | AbiCast { address: expr, .. }
+            | Deref(expr)
+            | MatchExp {
+                desugared: expr, ..
+            }
+            | ForLoop { desugared: expr } => self.analyze_expression(expr),
+            EnumInstantiation { contents, .. } => {
+                if let Some(expr) = contents {
+                    self.analyze_expression(expr);
+                }
+            }
+            IfExp {
+                condition,
+                then,
+                r#else,
+            } => {
+                self.analyze_expression(condition);
+                self.analyze_expression(then);
+                if let Some(else_exp) = r#else {
+                    self.analyze_expression(else_exp);
+                }
+            }
+            WhileLoop { condition, body } => {
+                self.analyze_expression(condition);
+                self.analyze_code_block(body);
+            }
+            AsmExpression { registers, .. } => {
+                for init in registers.iter().filter_map(|r| r.initializer.as_ref()) {
+                    self.analyze_expression(init);
+                    self.change(init);
+                }
+            }
+        }
+    }
+}
+
+// The local variable the expression is a place in, e.g. `a` for `a.b[0]`.
+fn base_variable(expr: &ty::TyExpression) -> Option<&Ident> {
+    use crate::ty::TyExpressionVariant::*;
+    match &expr.expression {
+        VariableExpression { name, .. } => Some(name),
+        StructFieldAccess { prefix, .. }
+        | TupleElemAccess { prefix, .. }
+        | ArrayIndex { prefix, .. } => base_variable(prefix),
+        _ => None,
+    }
+}
+
+// The `mut` keyword written right before the variable name, together with the whitespace up to
+// the name. Variables without one in the source code were not declared mutable by the user.
+fn mut_keyword_span(name: &Ident) -> Option<Span> {
+    let name_span = name.span();
+    let src = name_span.src();
+    let before_name = src[..name_span.start()].trim_end();
+    if before_name.len() == name_span.start() {
+        return None;
+    }
+    let mut_start = before_name.strip_suffix("mut")?.len();
+    if src[..mut_start]
+        .chars()
+        .next_back()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+    Span::new(
+        src.clone(),
+        mut_start,
+        name_span.start(),
+        name_span.source_id().copied(),
+    )
+}
@@ -21,18 +21,20 @@
 //!   #[foo(bar, bar)]
 
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use sway_ast::Literal;
+use sway_error::lint::{Lint, LintLevel};
 use sway_types::{
     constants::{
-        ALLOW_DEAD_CODE_NAME, ALLOW_DEPRECATED_NAME, CFG_EXPERIMENTAL_NEW_ENCODING,
-        CFG_PROGRAM_TYPE_ARG_NAME, CFG_TARGET_ARG_NAME,
+        ALLOW_DEPRECATED_NAME, CFG_EXPERIMENTAL_NEW_ENCODING, CFG_PROGRAM_TYPE_ARG_NAME,
+        CFG_TARGET_ARG_NAME,
     },
     Ident, Span, Spanned,
 };
 
 use std::{hash::Hash, sync::Arc};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct AttributeArg {
     pub name: Ident,
     pub value: Option<Literal>,
@@ -48,7 +50,7 @@ impl Spanned for AttributeArg {
 /// An attribute has a name (i.e "doc", "storage"),
 /// a vector of possible arguments and
 /// a span from its declaration.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Attribute {
     pub name: Ident,
     pub args: Vec<AttributeArg>,
@@ -56,7 +58,7 @@ pub struct Attribute {
 }
 
 /// Valid kinds of attributes supported by the compiler
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum AttributeKind {
     Doc,
     DocComment,
@@ -65,6 +67,8 @@ pub enum AttributeKind {
     Test,
     Payable,
     Allow,
+    Warn,
+    Deny,
     Cfg,
     Deprecated,
     Namespace,
@@ -77,7 +81,7 @@ impl AttributeKind {
         use AttributeKind::*;
         match self {
             Doc | DocComment | Storage | Inline | Test | Payable | Deprecated => (0, None),
-            Allow | Cfg | Namespace => (1, Some(1)),
+            Allow | Warn | Deny | Cfg | Namespace => (1, Some(1)),
         }
     }
 
@@ -86,10 +90,7 @@ impl AttributeKind {
         use AttributeKind::*;
         match self {
             Deprecated | Namespace | Doc | DocComment | Storage | Inline | Test | Payable => None,
-            Allow => Some(vec![
-                ALLOW_DEAD_CODE_NAME.to_string(),
-                ALLOW_DEPRECATED_NAME.to_string(),
-            ]),
+            Allow | Warn | Deny => Some(Lint::ALL.iter().map(|lint| lint.to_string()).collect()),
             Cfg => Some(vec![
                 CFG_TARGET_ARG_NAME.to_string(),
                 CFG_PROGRAM_TYPE_ARG_NAME.to_string(),
@@ -100,7 +101,7 @@ impl AttributeKind {
 }
 
 /// Stores the attributes associated with the type.
-#[derive(Default, Clone, Debug, Eq, PartialEq)]
+#[derive(Default, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct AttributesMap(Arc<IndexMap<AttributeKind, Vec<Attribute>>>);
 
 impl AttributesMap {
@@ -129,6 +130,32 @@ impl AttributesMap {
     pub fn inner(&self) -> &IndexMap<AttributeKind, Vec<Attribute>> {
         &self.0
     }
+
+    /// Returns the lint levels set by the `allow`, `warn` and `deny` attributes, in the order in
+    /// which they appear. Unknown lint names are skipped.
+    pub fn lint_levels(&self) -> Vec<(Lint, LintLevel)> {
+        let mut levels = [
+            (AttributeKind::Allow, LintLevel::Allow),
+            (AttributeKind::Warn, LintLevel::Warn),
+            (AttributeKind::Deny, LintLevel::Deny),
+        ]
+        .into_iter()
+        .flat_map(|(kind, level)| {
+            self.get(&kind)
+                .into_iter()
+                .flatten()
+                .flat_map(|attr| attr.args.iter())
+                .filter_map(move |arg| {
+                    Some((arg.span.start(), arg.name.as_str().parse().ok()?, level))
+                })
+        })
+        .collect::<Vec<_>>();
+        levels.sort_by_key(|(start, ..)| *start);
+        levels
+            .into_iter()
+            .map(|(_, lint, level)| (lint, level))
+            .collect()
+    }
 }
 
 impl std::ops::Deref for AttributesMap {
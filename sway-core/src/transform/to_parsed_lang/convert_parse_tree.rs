@@ -31,11 +31,11 @@ use sway_error::warning::{CompileWarning, Warning};
 use sway_types::{
     constants::{
         ALLOW_ATTRIBUTE_NAME, CFG_ATTRIBUTE_NAME, CFG_EXPERIMENTAL_NEW_ENCODING,
-        CFG_PROGRAM_TYPE_ARG_NAME, CFG_TARGET_ARG_NAME, DEPRECATED_ATTRIBUTE_NAME,
-        DOC_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME, INLINE_ATTRIBUTE_NAME,
-        NAMESPACE_ATTRIBUTE_NAME, PAYABLE_ATTRIBUTE_NAME, STORAGE_PURITY_ATTRIBUTE_NAME,
-        STORAGE_PURITY_READ_NAME, STORAGE_PURITY_WRITE_NAME, TEST_ATTRIBUTE_NAME,
-        VALID_ATTRIBUTE_NAMES,
+        CFG_PROGRAM_TYPE_ARG_NAME, CFG_TARGET_ARG_NAME, DENY_ATTRIBUTE_NAME,
+        DEPRECATED_ATTRIBUTE_NAME, DOC_ATTRIBUTE_NAME, DOC_COMMENT_ATTRIBUTE_NAME,
+        INLINE_ATTRIBUTE_NAME, NAMESPACE_ATTRIBUTE_NAME, PAYABLE_ATTRIBUTE_NAME,
+        STORAGE_PURITY_ATTRIBUTE_NAME, STORAGE_PURITY_READ_NAME, STORAGE_PURITY_WRITE_NAME,
+        TEST_ATTRIBUTE_NAME, VALID_ATTRIBUTE_NAMES, WARN_ATTRIBUTE_NAME,
     },
     integer_bits::IntegerBits,
 };
@@ -4539,6 +4539,8 @@ fn item_attrs_to_map(
                 TEST_ATTRIBUTE_NAME => Some(AttributeKind::Test),
                 PAYABLE_ATTRIBUTE_NAME => Some(AttributeKind::Payable),
                 ALLOW_ATTRIBUTE_NAME => Some(AttributeKind::Allow),
+                WARN_ATTRIBUTE_NAME => Some(AttributeKind::Warn),
+                DENY_ATTRIBUTE_NAME => Some(AttributeKind::Deny),
                 CFG_ATTRIBUTE_NAME => Some(AttributeKind::Cfg),
                 DEPRECATED_ATTRIBUTE_NAME => Some(AttributeKind::Deprecated),
                 NAMESPACE_ATTRIBUTE_NAME => Some(AttributeKind::Namespace),
@@ -4556,8 +4558,18 @@ fn item_attrs_to_map(
         }
     }
 
-    // Check attribute arguments
-    for (attribute_kind, attributes) in &attrs_map {
+    check_attribute_args(handler, &attrs_map);
+
+    Ok(AttributesMap::new(Arc::new(attrs_map)))
+}
+
+/// Checks the number and the values of the arguments of the attributes in `attrs_map`,
+/// warning about the unexpected ones.
+pub(crate) fn check_attribute_args(
+    handler: &Handler,
+    attrs_map: &IndexMap<AttributeKind, Vec<Attribute>>,
+) {
+    for (attribute_kind, attributes) in attrs_map {
         for attribute in attributes {
             // check attribute arguments length
             let (expected_min_len, expected_max_len) =
@@ -4594,8 +4606,6 @@ fn item_attrs_to_map(
             }
         }
     }
-
-    Ok(AttributesMap::new(Arc::new(attrs_map)))
 }
 
 fn error_if_self_param_is_not_allowed(
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use sway_error::handler::{ErrorEmitted, Handler};
 use sway_types::{Span, Spanned};
 
@@ -69,7 +70,7 @@ use crate::{
 /// - `data4` has a type ascription and has type arguments in the `TypeBinding`,
 ///     so, with the type from the value passed to `value`, all three are unified
 ///     together
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeBinding<T> {
     pub inner: T,
     pub type_arguments: TypeArgs,
@@ -94,7 +95,7 @@ pub struct TypeBinding<T> {
 /// ```
 /// So we can have type parameters in the `Prefix` or `Regular` variant but not
 /// in both.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TypeArgs {
     /// `Regular` variant indicates the type arguments are located after the suffix.
     Regular(Vec<TypeArgument>),
@@ -1,7 +1,8 @@
+use serde::{Deserialize, Serialize};
 use sway_types::{span::Span, Spanned};
 
 /// Describes a fixed length for types that needs it such as arrays and strings
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub struct Length {
     val: usize,
     span: Span,
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     fmt,
@@ -22,7 +23,7 @@ use crate::{
     types::*,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraitConstraint {
     pub trait_name: CallPath,
     pub type_arguments: Vec<TypeArgument>,
@@ -237,6 +238,7 @@ impl TraitConstraint {
                 handler.emit_err(CompileError::TraitNotFound {
                     name: trait_name.to_string(),
                     span: trait_name.span(),
+                    import_candidates: vec![],
                 });
             }
         }
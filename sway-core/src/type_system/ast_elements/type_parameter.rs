@@ -9,6 +9,7 @@ use crate::{
     semantic_analysis::*,
     type_system::priv_prelude::*,
 };
+use serde::{Deserialize, Serialize};
 
 use sway_error::{
     error::CompileError,
@@ -23,7 +24,7 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TypeParameter {
     pub type_id: TypeId,
     pub(crate) initial_type_id: TypeId,
@@ -6,6 +6,7 @@ use crate::{
 };
 use core::fmt::Write;
 use hashbrown::{hash_map::RawEntryMut, HashMap};
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
 use sway_error::{
     error::CompileError,
@@ -16,9 +17,11 @@ use sway_types::{integer_bits::IntegerBits, span::Span, ModuleId, SourceId};
 
 use super::unify::unifier::UnifyKind;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TypeEngine {
     slab: ConcurrentSlab<TypeSourceInfo>,
+    // The hashes of the keys depend on the engines, see `TypeEngine::restore`.
+    #[serde(skip)]
     id_map: RwLock<HashMap<TypeSourceInfo, TypeId>>,
 }
 
@@ -83,6 +86,45 @@ impl TypeEngine {
             });
     }
 
+    pub(crate) fn is_empty(&self) -> bool {
+        self.slab.is_empty()
+    }
+
+    /// Returns the entries used to reuse the [TypeId]s of the types that cannot change.
+    pub(crate) fn id_map_entries(&self) -> Vec<(TypeSourceInfo, TypeId)> {
+        let id_map = self.id_map.read().unwrap();
+        id_map
+            .iter()
+            .map(|(tsi, type_id)| (tsi.clone(), *type_id))
+            .collect()
+    }
+
+    /// Replaces the contents of the [TypeEngine] with the contents of `other` and the
+    /// `id_map_entries` returned by [TypeEngine::id_map_entries].
+    ///
+    /// The entries are hashed using `engines`, so the other engines must be restored first.
+    pub(crate) fn restore(
+        &self,
+        engines: &Engines,
+        other: TypeEngine,
+        id_map_entries: Vec<(TypeSourceInfo, TypeId)>,
+    ) {
+        self.slab.restore(other.slab);
+
+        let mut id_map = self.id_map.write().unwrap();
+        id_map.clear();
+        let hash_builder = id_map.hasher().clone();
+        for (tsi, type_id) in id_map_entries {
+            let ty_hash = make_hasher(&hash_builder, engines)(&tsi);
+            let raw_entry = id_map
+                .raw_entry_mut()
+                .from_hash(ty_hash, |x| x.eq(&tsi, engines));
+            if let RawEntryMut::Vacant(v) = raw_entry {
+                v.insert_with_hasher(ty_hash, tsi, type_id, make_hasher(&hash_builder, engines));
+            }
+        }
+    }
+
     pub fn replace(&self, id: TypeId, new_value: TypeSourceInfo) {
         self.slab.replace(id.index(), new_value);
     }
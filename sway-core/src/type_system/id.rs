@@ -1,4 +1,5 @@
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use sway_error::{
     error::CompileError,
     handler::{ErrorEmitted, Handler},
@@ -19,7 +20,7 @@ use std::{
 const EXTRACT_ANY_MAX_DEPTH: usize = 128;
 
 /// A identifier to uniquely refer to our type terms
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Ord, PartialOrd, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Ord, PartialOrd, Debug, Serialize, Deserialize)]
 pub struct TypeId(usize);
 
 impl DisplayWithEngines for TypeId {
@@ -5,11 +5,12 @@ use std::{
 };
 
 use crate::{engine_threading::*, type_system::priv_prelude::*};
+use serde::{Deserialize, Serialize};
 
 /// A list of types that serve as the list of type params for type substitution.
 /// Any types of the [TypeParam][TypeInfo::TypeParam] variant will point to an
 /// index in this list.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SubstList {
     list: Vec<TypeParameter>,
 }
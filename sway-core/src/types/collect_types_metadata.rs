@@ -9,13 +9,14 @@ use std::{
 };
 
 use crate::{type_system::TypeId, Engines, ExperimentalFlags};
+use serde::{Deserialize, Serialize};
 use sway_error::handler::{ErrorEmitted, Handler};
 use sway_types::{Ident, Span};
 
 /// If any types contained by this node are unresolved or have yet to be inferred, throw an
 /// error to signal to the user that more type information is needed.
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct LogId(usize);
 
 impl std::ops::Deref for LogId {
@@ -31,7 +32,7 @@ impl LogId {
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct MessageId(usize);
 
 impl std::ops::Deref for MessageId {
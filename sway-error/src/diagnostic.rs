@@ -9,6 +9,7 @@ pub struct Diagnostic {
     pub(crate) issue: Issue,
     pub(crate) hints: Vec<Hint>,
     pub(crate) help: Vec<String>,
+    pub(crate) suggestions: Vec<Suggestion>,
 }
 
 impl Diagnostic {
@@ -86,6 +87,11 @@ impl Diagnostic {
         String::from(" ")
     }
 
+    /// Edits to the source code which fix, or help fixing, the issue.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
     /// All the source files that are related to the diagnostic.
     /// This means the source file of the issue itself as well
     /// as source files of all the hints.
@@ -341,6 +347,112 @@ impl std::ops::Deref for Hint {
     }
 }
 
+/// How confident we are that a [Suggestion] fixes the issue the way the user intended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended and can be applied automatically,
+    /// e.g., by `forc check --fix`.
+    MachineApplicable,
+    /// The suggestion might be what the user intended, but it has to be reviewed before
+    /// being applied, e.g., because it is one of several candidates.
+    MaybeIncorrect,
+}
+
+/// An edit to the source code which fixes, or helps fixing, the issue of a diagnostic.
+///
+/// The edit replaces the code at `span` with the `replacement`. An empty `span` inserts the
+/// `replacement`, and an empty `replacement` removes the code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Suggestion {
+    pub span: Span,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+impl Suggestion {
+    pub fn replace(span: Span, replacement: String, applicability: Applicability) -> Self {
+        Self {
+            span,
+            replacement,
+            applicability,
+        }
+    }
+
+    pub fn remove(span: Span, applicability: Applicability) -> Self {
+        Self::replace(span, String::new(), applicability)
+    }
+
+    /// Inserts `line` as a new line right before the code at `span`, with the same indentation.
+    pub fn insert_line_before(span: &Span, line: &str, applicability: Applicability) -> Self {
+        let src = span.src();
+        let line_start = src[..span.start()].rfind('\n').map_or(0, |idx| idx + 1);
+        let indentation = src[line_start..span.start()]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect::<String>();
+        Self::replace(
+            Self::empty_span_at(span, span.start()),
+            format!("{line}\n{indentation}"),
+            applicability,
+        )
+    }
+
+    /// Inserts `use {path};` into the source file of `span`, after its last top-level `use`
+    /// statement or, if there are none, after the module kind, e.g., `library;`.
+    ///
+    /// Returns [None] if the module kind cannot be found in the source file.
+    pub fn import(span: &Span, path: &str, applicability: Applicability) -> Option<Self> {
+        const MODULE_KINDS: [&str; 4] = ["contract", "library", "predicate", "script"];
+        let src = span.src();
+
+        let mut module_kind_end = None;
+        let mut last_use_end = None;
+        let mut line_start = 0;
+        for line in src.split_inclusive('\n') {
+            let line_end = line_start + line.len();
+            if module_kind_end.is_none() {
+                let is_module_kind = MODULE_KINDS.iter().any(|kind| {
+                    line.strip_prefix(kind)
+                        .is_some_and(|rest| rest.trim_start().starts_with(';'))
+                });
+                if is_module_kind {
+                    module_kind_end = Some(line_end);
+                }
+            } else if line.starts_with("use ") || line.starts_with("pub use ") {
+                // The statement might span several lines and ends with the line of its `;`.
+                last_use_end = src[line_start..].find(';').map(|semicolon| {
+                    let semicolon = line_start + semicolon;
+                    src[semicolon..]
+                        .find('\n')
+                        .map_or(src.len(), |newline| semicolon + newline + 1)
+                });
+            }
+            line_start = line_end;
+        }
+
+        let (position, replacement) = match (last_use_end, module_kind_end) {
+            (Some(position), _) => (position, format!("use {path};\n")),
+            (None, Some(position)) => (position, format!("\nuse {path};\n")),
+            (None, None) => return None,
+        };
+        Some(Self::replace(
+            Self::empty_span_at(span, position),
+            replacement,
+            applicability,
+        ))
+    }
+
+    fn empty_span_at(span: &Span, position: usize) -> Span {
+        Span::new(
+            span.src().clone(),
+            position,
+            position,
+            span.source_id().copied(),
+        )
+        .expect("the position is within the source of the span")
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct SourcePath {
     path_buf: PathBuf,
@@ -1,9 +1,13 @@
 use crate::convert_parse_tree_error::ConvertParseTreeError;
-use crate::diagnostic::{Code, Diagnostic, Hint, Issue, Reason, ToDiagnostic};
+use crate::diagnostic::{
+    Applicability, Code, Diagnostic, Hint, Issue, Reason, Suggestion, ToDiagnostic,
+};
 use crate::formatting::*;
 use crate::lex_error::LexError;
+use crate::lint::Lint;
 use crate::parser_error::ParseError;
 use crate::type_error::TypeError;
+use crate::warning::Warning;
 
 use core::fmt;
 use sway_types::constants::STORAGE_PURITY_ATTRIBUTE_NAME;
@@ -59,7 +63,12 @@ pub enum CompileError {
     ModuleDepGraphCyclicReference { modules: Vec<BaseIdent> },
 
     #[error("Variable \"{var_name}\" does not exist in this scope.")]
-    UnknownVariable { var_name: Ident, span: Span },
+    UnknownVariable {
+        var_name: Ident,
+        span: Span,
+        /// Paths of public items named `var_name` that could be imported instead.
+        import_candidates: Vec<String>,
+    },
     #[error("Identifier \"{name}\" was used as a variable, but it is actually a {what_it_is}.")]
     NotAVariable {
         name: Ident,
@@ -88,6 +97,8 @@ pub enum CompileError {
          code that triggered this error."
     )]
     InternalOwned(String, Span),
+    #[error("{0}")]
+    InvalidIrPassPipeline(String, Span),
     #[error(
         "Predicate declaration contains no main function. Predicates require a main function."
     )]
@@ -343,7 +354,12 @@ pub enum CompileError {
     #[error("This is a {actually}, not a type alias")]
     DeclIsNotATypeAlias { actually: String, span: Span },
     #[error("Could not find symbol \"{name}\" in this scope.")]
-    SymbolNotFound { name: Ident, span: Span },
+    SymbolNotFound {
+        name: Ident,
+        span: Span,
+        /// Paths of public items named `name` that could be imported instead.
+        import_candidates: Vec<String>,
+    },
     #[error("Symbol \"{name}\" is private.")]
     ImportPrivateSymbol { name: Ident, span: Span },
     #[error("Module \"{name}\" is private.")]
@@ -377,7 +393,7 @@ pub enum CompileError {
         function_name: Ident,
     },
     #[error(
-        "Expected Module level doc comment. All other attributes are unsupported at this level."
+        "Expected Module level doc comment or lint level attribute. All other attributes are unsupported at this level."
     )]
     ExpectedModuleDocComment { span: Span },
     #[error(
@@ -457,7 +473,12 @@ pub enum CompileError {
     #[error("The enum variant `{ty}` is of type `unit`, so its constructor does not take arguments or parentheses. Try removing the ().")]
     UnitVariantWithParenthesesEnumInstantiator { span: Span, ty: String },
     #[error("Cannot find trait \"{name}\" in this scope.")]
-    TraitNotFound { name: String, span: Span },
+    TraitNotFound {
+        name: String,
+        span: Span,
+        /// Paths of public traits named `name` that could be imported instead.
+        import_candidates: Vec<String>,
+    },
     #[error("Trait \"{trait_name}\" is not imported when calling \"{function_name}\".\nThe import is needed because \"{function_name}\" uses \"{trait_name}\" in one of its trait constraints.")]
     TraitNotImportedAtFunctionApplication {
         trait_name: String,
@@ -637,7 +658,12 @@ pub enum CompileError {
         "Storage attribute access mismatch. Try giving the surrounding function more access by \
         adding \"#[{STORAGE_PURITY_ATTRIBUTE_NAME}({attrs})]\" to the function declaration."
     )]
-    StorageAccessMismatch { attrs: String, span: Span },
+    StorageAccessMismatch {
+        attrs: String,
+        span: Span,
+        /// The calling function, if it has no storage attribute yet.
+        fn_decl_span: Option<Span>,
+    },
     #[error(
         "The function \"{fn_name}\" in {interface_name} is pure, but this \
         implementation is not.  The \"{STORAGE_PURITY_ATTRIBUTE_NAME}\" annotation must be \
@@ -671,6 +697,8 @@ pub enum CompileError {
         storage_op: &'static str,
         attrs: String,
         span: Span,
+        /// The function, if it has no storage attribute yet.
+        fn_decl_span: Option<Span>,
     },
     #[error(
         "Parameter reference type or mutability mismatch between the trait function declaration and its implementation."
@@ -844,6 +872,12 @@ pub enum CompileError {
     UninitRegisterInAsmBlockBeingRead { span: Span },
     #[error("Expression of type \"{expression_type}\" cannot be dereferenced.")]
     ExpressionCannotBeDereferenced { expression_type: String, span: Span },
+    #[error("{warning}")]
+    DeniedLint {
+        lint: Lint,
+        warning: Warning,
+        span: Span,
+    },
 }
 
 impl std::convert::From<TypeError> for CompileError {
@@ -866,6 +900,7 @@ impl Spanned for CompileError {
             ParseError { span, .. } => span.clone(),
             Internal(_, span) => span.clone(),
             InternalOwned(_, span) => span.clone(),
+            InvalidIrPassPipeline(_, span) => span.clone(),
             NoPredicateMainFunction(span) => span.clone(),
             PredicateMainDoesNotReturnBool(span) => span.clone(),
             NoScriptMainFunction(span) => span.clone(),
@@ -1040,6 +1075,7 @@ impl Spanned for CompileError {
             SelfIsNotValidAsImplementingFor { span } => span.clone(),
             UninitRegisterInAsmBlockBeingRead { span } => span.clone(),
             ExpressionCannotBeDereferenced { span, .. } => span.clone(),
+            DeniedLint { span, .. } => span.clone(),
         }
     }
 }
@@ -1104,6 +1140,7 @@ impl ToDiagnostic for CompileError {
                         _ => unreachable!("We can have only the listed combinations: variable/constant shadows a non imported/imported constant.")
                     }
                 ],
+                suggestions: vec![],
             },
             ConstantShadowsVariable { name , variable_span } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Constants cannot shadow variables".to_string())),
@@ -1123,6 +1160,7 @@ impl ToDiagnostic for CompileError {
                     format!("Variables can shadow other variables, but constants cannot."),
                     format!("Consider renaming either the variable or the constant."),
                 ],
+                suggestions: vec![],
             },
             MultipleDefinitionsOfMatchArmVariable { match_value, match_type, first_definition, first_definition_is_struct_field, duplicate, duplicate_is_struct_field } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Match pattern variable is already defined".to_string())),
@@ -1180,6 +1218,7 @@ impl ToDiagnostic for CompileError {
                         (false, false) => "Consider renaming either of the variables.".to_string(),
                     },
                 ],
+                suggestions: vec![],
             },
             MatchArmVariableMismatchedType { match_value, match_type, variable, first_definition, expected, received } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Match pattern variable has mismatched type".to_string())),
@@ -1203,6 +1242,7 @@ impl ToDiagnostic for CompileError {
                 help: vec![
                     format!("In the same match arm, a variable must have the same type in all alternatives."),
                 ],
+                suggestions: vec![],
             },
             MatchArmVariableNotDefinedInAllAlternatives { match_value, match_type, variable, missing_in_alternatives} => Diagnostic {
                 reason: Some(Reason::new(code(1), "Match pattern variable is not defined in all alternatives".to_string())),
@@ -1235,6 +1275,7 @@ impl ToDiagnostic for CompileError {
                 help: vec![
                     format!("Consider removing the variable \"{variable}\" altogether, or adding it to all alternatives."),
                 ],
+                suggestions: vec![],
             },
             MatchStructPatternMissingFields { missing_fields, missing_fields_are_public, struct_name, struct_decl_span, total_number_of_fields, span } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Struct pattern has missing fields".to_string())),
@@ -1275,6 +1316,7 @@ impl ToDiagnostic for CompileError {
                     ),
                     "Alternatively, consider ignoring all the missing fields by ending the struct pattern with `..`.".to_string(),
                 ],
+                suggestions: vec![],
             },
             MatchStructPatternMustIgnorePrivateFields { private_fields, struct_name, struct_decl_span, all_fields_are_private, span } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Struct pattern must ignore inaccessible private fields".to_string())),
@@ -1310,6 +1352,7 @@ impl ToDiagnostic for CompileError {
                     ),
                 ],
                 help: vec![],
+                suggestions: vec![],
             },
             TraitNotImportedAtFunctionApplication { trait_name, function_name, function_call_site_span, trait_constraint_span, trait_candidates } => {
                 // Make candidates order deterministic.
@@ -1403,6 +1446,18 @@ impl ToDiagnostic for CompileError {
 
                         help
                     },
+                    suggestions: {
+                        // Several candidates leave the choice to the user.
+                        let applicability = if trait_candidates.len() == 1 {
+                            Applicability::MachineApplicable
+                        } else {
+                            Applicability::MaybeIncorrect
+                        };
+                        trait_candidates
+                            .iter()
+                            .filter_map(|trait_candidate| Suggestion::import(function_call_site_span, trait_candidate, applicability))
+                            .collect()
+                    },
                 }
             },
             // TODO-IG: Extend error messages to pointers, once typed pointers are defined and can be dereferenced.
@@ -1426,6 +1481,21 @@ impl ToDiagnostic for CompileError {
                     ),
                 ],
                 help: vec![],
+                suggestions: vec![],
+            },
+            DeniedLint { lint, warning, span } => Diagnostic {
+                reason: Some(Reason::new(code(1), "Denied lint".to_string())),
+                issue: Issue::error(
+                    source_engine,
+                    span.clone(),
+                    format!("{warning}")
+                ),
+                hints: vec![],
+                help: vec![
+                    format!("The \"{lint}\" lint is set to \"deny\", which turns its warnings into errors."),
+                    format!("Consider fixing the issue, or lowering the lint level with `#[warn({lint})]` or `#[allow({lint})]`."),
+                ],
+                suggestions: vec![],
             },
             StructInstantiationMissingFields { field_names, struct_name, span, struct_decl_span, total_number_of_fields } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Struct instantiation has missing fields".to_string())),
@@ -1453,6 +1523,7 @@ impl ToDiagnostic for CompileError {
                     ),
                 ],
                 help: vec![],
+                suggestions: vec![],
             },
             StructCannotBeInstantiated { struct_name, span, struct_decl_span, private_fields, constructors, all_fields_are_private, is_in_storage_declaration, struct_can_be_changed } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Struct cannot be instantiated due to inaccessible private fields".to_string())),
@@ -1604,7 +1675,8 @@ impl ToDiagnostic for CompileError {
                     };
 
                     help
-                }
+                },
+                suggestions: vec![],
             },
             StructFieldIsPrivate { field_name, struct_name, field_decl_span, struct_can_be_changed, usage_context } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Private struct field is inaccessible".to_string())),
@@ -1674,6 +1746,7 @@ impl ToDiagnostic for CompileError {
                         Diagnostic::help_none()
                     },
                 ],
+                suggestions: vec![],
             },
             StructFieldDoesNotExist { field_name, available_fields, is_public_struct_access, struct_name, struct_decl_span, struct_is_empty, usage_context } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Struct field does not exist".to_string())),
@@ -1734,6 +1807,7 @@ impl ToDiagnostic for CompileError {
                     hints
                 },
                 help: vec![],
+                suggestions: vec![],
             },
             NotIndexable { actually, span } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Type is not indexable".to_string())),
@@ -1749,6 +1823,7 @@ impl ToDiagnostic for CompileError {
                     format!("{}- arrays. E.g., `[u64;3]`.", Indent::Single),
                     format!("{}- references, direct or indirect, to arrays. E.g., `&[u64;3]` or `&&&[u64;3]`.", Indent::Single),
                 ],
+                suggestions: vec![],
             },
             FieldAccessOnNonStruct { actually, storage_variable, field_name, span } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Field access requires a struct".to_string())),
@@ -1785,7 +1860,8 @@ impl ToDiagnostic for CompileError {
                         format!("{}- structs. E.g., `my_struct.field`.", Indent::Single),
                         format!("{}- references, direct or indirect, to structs. E.g., `(&my_struct).field` or `(&&&my_struct).field`.", Indent::Single),
                     ]
-                }
+                },
+                suggestions: vec![],
             },
             StorageFieldDoesNotExist { field_name, available_fields, storage_decl_span } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Storage field does not exist".to_string())),
@@ -1824,6 +1900,7 @@ impl ToDiagnostic for CompileError {
                     hints
                 },
                 help: vec![],
+                suggestions: vec![],
             },
             TupleIndexOutOfBounds { index, count, tuple_type, span, prefix_span } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Tuple index is out of bounds".to_string())),
@@ -1840,6 +1917,7 @@ impl ToDiagnostic for CompileError {
                     ),
                 ],
                 help: vec![],
+                suggestions: vec![],
             },
             TupleElementAccessOnNonTuple { actually, span, index, index_span } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Tuple element access requires a tuple".to_string())),
@@ -1860,6 +1938,30 @@ impl ToDiagnostic for CompileError {
                     format!("{}- tuples. E.g., `my_tuple.1`.", Indent::Single),
                     format!("{}- references, direct or indirect, to tuples. E.g., `(&my_tuple).1` or `(&&&my_tuple).1`.", Indent::Single),
                 ],
+                suggestions: vec![],
+            },
+            StorageAccessMismatch { attrs, fn_decl_span, .. } | ImpureInPureContext { attrs, fn_decl_span, .. } => Diagnostic {
+                issue: Issue::error(source_engine, self.span(), format!("{}", self)),
+                suggestions: fn_decl_span
+                    .iter()
+                    .map(|fn_decl_span| Suggestion::insert_line_before(
+                        fn_decl_span,
+                        &format!("#[{STORAGE_PURITY_ATTRIBUTE_NAME}({attrs})]"),
+                        Applicability::MachineApplicable,
+                    ))
+                    .collect(),
+                ..Default::default()
+            },
+            UnknownVariable { span, import_candidates, .. }
+            | SymbolNotFound { span, import_candidates, .. }
+            | TraitNotFound { span, import_candidates, .. } => Diagnostic {
+                issue: Issue::error(source_engine, span.clone(), format!("{}", self)),
+                // Candidates are matched by name only, so the import might not be the intended one.
+                suggestions: import_candidates
+                    .iter()
+                    .filter_map(|candidate| Suggestion::import(span, candidate, Applicability::MaybeIncorrect))
+                    .collect(),
+                ..Default::default()
             },
            _ => Diagnostic {
                     // TODO: Temporary we use self here to achieve backward compatibility.
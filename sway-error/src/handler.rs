@@ -1,4 +1,5 @@
 use crate::{error::CompileError, warning::CompileWarning};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use core::cell::RefCell;
@@ -85,6 +86,16 @@ impl Handler {
         }
     }
 
+    /// Retain only the warnings for which `f` returns `true`.
+    pub fn retain_warnings(&self, f: impl FnMut(&CompileWarning) -> bool) {
+        self.inner.borrow_mut().warnings.retain(f);
+    }
+
+    /// Apply `f` to every error emitted so far.
+    pub fn update_errors(&self, f: impl FnMut(&mut CompileError)) {
+        self.inner.borrow_mut().errors.iter_mut().for_each(f);
+    }
+
     pub fn dedup(&self) {
         let mut inner = self.inner.borrow_mut();
         inner.errors = dedup_unsorted(inner.errors.clone());
@@ -93,7 +104,7 @@ impl Handler {
 }
 
 /// Proof that an error was emitted through a `Handler`.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ErrorEmitted {
     _priv: (),
 }
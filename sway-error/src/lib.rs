@@ -4,6 +4,7 @@ pub mod error;
 pub mod formatting;
 pub mod handler;
 pub mod lex_error;
+pub mod lint;
 pub mod parser_error;
 pub mod type_error;
 pub mod warning;
@@ -0,0 +1,209 @@
+//! Named lints and the levels they can be set to.
+//!
+//! Every [Warning] belongs to exactly one [Lint]. The level of a lint can be set for an item with
+//! the `#[allow(..)]`, `#[warn(..)]` and `#[deny(..)]` attributes, for a whole module with their
+//! inner `#![..]` forms, or for a whole package in the `[lints]` table of its manifest.
+
+use crate::warning::Warning;
+
+use core::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use sway_types::constants::{
+    ALLOW_DEAD_CODE_NAME, ALLOW_DEPRECATED_NAME, ALLOW_DIVISION_BY_ZERO_NAME,
+    ALLOW_UNCHECKED_ARITHMETIC_NAME, ALLOW_UNCHECKED_NARROWING_NAME,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Lint {
+    DeadCode,
+    Deprecated,
+    NonClassCaseNames,
+    NonSnakeCaseNames,
+    NonScreamingSnakeCaseNames,
+    UnusedReturnValue,
+    SimilarMethodFound,
+    Shadowing,
+    OverridingTraitImplementation,
+    UnreachableCode,
+    UnusedMut,
+    UnneededStorageAttributes,
+    UnbalancedStorageAccess,
+    UncheckedArithmetic,
+    UncheckedNarrowing,
+    DivisionByZero,
+    UnreachableMatchArm,
+    InvalidAttributes,
+    EffectAfterInteraction,
+    ModulePrivacy,
+}
+
+impl Lint {
+    pub const ALL: &'static [Lint] = &[
+        Lint::DeadCode,
+        Lint::Deprecated,
+        Lint::NonClassCaseNames,
+        Lint::NonSnakeCaseNames,
+        Lint::NonScreamingSnakeCaseNames,
+        Lint::UnusedReturnValue,
+        Lint::SimilarMethodFound,
+        Lint::Shadowing,
+        Lint::OverridingTraitImplementation,
+        Lint::UnreachableCode,
+        Lint::UnusedMut,
+        Lint::UnneededStorageAttributes,
+        Lint::UnbalancedStorageAccess,
+        Lint::UncheckedArithmetic,
+        Lint::UncheckedNarrowing,
+        Lint::DivisionByZero,
+        Lint::UnreachableMatchArm,
+        Lint::InvalidAttributes,
+        Lint::EffectAfterInteraction,
+        Lint::ModulePrivacy,
+    ];
+
+    /// The name used to refer to the lint in attributes and in the manifest.
+    pub fn name(&self) -> &'static str {
+        use Lint::*;
+        match self {
+            DeadCode => ALLOW_DEAD_CODE_NAME,
+            Deprecated => ALLOW_DEPRECATED_NAME,
+            NonClassCaseNames => "non_class_case_names",
+            NonSnakeCaseNames => "non_snake_case_names",
+            NonScreamingSnakeCaseNames => "non_screaming_snake_case_names",
+            UnusedReturnValue => "unused_return_value",
+            SimilarMethodFound => "similar_method_found",
+            Shadowing => "shadowing",
+            OverridingTraitImplementation => "overriding_trait_implementation",
+            UnreachableCode => "unreachable_code",
+            UnusedMut => "unused_mut",
+            UnneededStorageAttributes => "unneeded_storage_attributes",
+            UnbalancedStorageAccess => "unbalanced_storage_access",
+            UncheckedArithmetic => ALLOW_UNCHECKED_ARITHMETIC_NAME,
+            UncheckedNarrowing => ALLOW_UNCHECKED_NARROWING_NAME,
+            DivisionByZero => ALLOW_DIVISION_BY_ZERO_NAME,
+            UnreachableMatchArm => "unreachable_match_arm",
+            InvalidAttributes => "invalid_attributes",
+            EffectAfterInteraction => "effect_after_interaction",
+            ModulePrivacy => "module_privacy",
+        }
+    }
+
+    /// Opt-in lints are only checked if they are asked for, either by enabling the analysis
+    /// behind them, by setting their level to `warn` or `deny`, or by running `forc lint`.
+    pub fn is_opt_in(&self) -> bool {
+        matches!(
+            self,
+            Lint::UncheckedArithmetic | Lint::UncheckedNarrowing | Lint::DivisionByZero
+        )
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lint::ALL
+            .iter()
+            .find(|lint| lint.name() == s)
+            .copied()
+            .ok_or_else(|| format!("unknown lint \"{s}\""))
+    }
+}
+
+impl TryFrom<String> for Lint {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        name.parse()
+    }
+}
+
+impl From<Lint> for String {
+    fn from(lint: Lint) -> Self {
+        lint.name().to_string()
+    }
+}
+
+/// What to do with the warnings of a lint.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Drop the warnings.
+    Allow,
+    /// Report the warnings as warnings.
+    #[default]
+    Warn,
+    /// Report the warnings as errors.
+    Deny,
+}
+
+impl Warning {
+    /// The lint this warning belongs to.
+    pub fn lint(&self) -> Lint {
+        use Warning::*;
+        match self {
+            NonClassCaseStructName { .. }
+            | NonClassCaseTypeParameter { .. }
+            | NonClassCaseTraitName { .. }
+            | NonClassCaseEnumName { .. }
+            | NonClassCaseEnumVariantName { .. } => Lint::NonClassCaseNames,
+            NonSnakeCaseStructFieldName { .. } | NonSnakeCaseFunctionName { .. } => {
+                Lint::NonSnakeCaseNames
+            }
+            NonScreamingSnakeCaseConstName { .. } => Lint::NonScreamingSnakeCaseNames,
+            UnusedReturnValue { .. } => Lint::UnusedReturnValue,
+            SimilarMethodFound { .. } => Lint::SimilarMethodFound,
+            ShadowsOtherSymbol { .. }
+            | UninitializedAsmRegShadowsVariable { .. }
+            | ShadowingReservedRegister { .. } => Lint::Shadowing,
+            OverridingTraitImplementation => Lint::OverridingTraitImplementation,
+            DeadDeclaration
+            | DeadEnumDeclaration
+            | DeadFunctionDeclaration
+            | DeadStructDeclaration
+            | DeadTrait
+            | DeadEnumVariant { .. }
+            | DeadMethod
+            | StructFieldNeverRead
+            | DeadStorageDeclaration => Lint::DeadCode,
+            UnreachableCode => Lint::UnreachableCode,
+            UnusedMut { .. } => Lint::UnusedMut,
+            DeadStorageDeclarationForFunction { .. } => Lint::UnneededStorageAttributes,
+            StorageFieldWrittenButNeverRead | StorageFieldReadButNeverWritten => {
+                Lint::UnbalancedStorageAccess
+            }
+            ArithmeticOnUncheckedAbiArgument { .. } => Lint::UncheckedArithmetic,
+            UncheckedNarrowingConversion { .. } => Lint::UncheckedNarrowing,
+            PossibleDivisionByZero => Lint::DivisionByZero,
+            MatchExpressionUnreachableArm { .. } => Lint::UnreachableMatchArm,
+            UnrecognizedAttribute { .. }
+            | AttributeExpectedNumberOfArguments { .. }
+            | UnexpectedAttributeArgumentValue { .. } => Lint::InvalidAttributes,
+            EffectAfterInteraction { .. } => Lint::EffectAfterInteraction,
+            ModulePrivacyDisabled => Lint::ModulePrivacy,
+            UsingDeprecated { .. } => Lint::Deprecated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lint_names_round_trip() {
+        for lint in Lint::ALL {
+            assert_eq!(lint.name().parse::<Lint>().as_ref(), Ok(lint));
+        }
+        assert!("no_such_lint".parse::<Lint>().is_err());
+    }
+}
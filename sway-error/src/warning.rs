@@ -1,14 +1,17 @@
-use crate::diagnostic::{Code, Diagnostic, Hint, Issue, Reason, ToDiagnostic};
+use crate::diagnostic::{
+    Applicability, Code, Diagnostic, Hint, Issue, Reason, Suggestion, ToDiagnostic,
+};
 
 use core::fmt;
 
 use either::Either;
 
+use serde::{Deserialize, Serialize};
 use sway_types::{Ident, SourceId, Span, Spanned};
 
 // TODO: since moving to using Idents instead of strings,
 // the warning_content will usually contain a duplicate of the span.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CompileWarning {
     pub span: Span,
     pub warning_content: Warning,
@@ -30,7 +33,7 @@ impl CompileWarning {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Warning {
     NonClassCaseStructName {
         struct_name: Ident,
@@ -77,6 +80,11 @@ pub enum Warning {
     DeadStructDeclaration,
     DeadTrait,
     UnreachableCode,
+    UnusedMut {
+        name: Ident,
+        /// The `mut` keyword, together with the whitespace up to the variable name.
+        mut_span: Span,
+    },
     DeadEnumVariant {
         variant_name: Ident,
     },
@@ -89,6 +97,16 @@ pub enum Warning {
     DeadStorageDeclarationForFunction {
         unneeded_attrib: String,
     },
+    StorageFieldWrittenButNeverRead,
+    StorageFieldReadButNeverWritten,
+    ArithmeticOnUncheckedAbiArgument {
+        argument: String,
+    },
+    UncheckedNarrowingConversion {
+        from: String,
+        to: String,
+    },
+    PossibleDivisionByZero,
     MatchExpressionUnreachableArm {
         match_value: Span,
         match_type: String,
@@ -116,10 +134,15 @@ pub enum Warning {
         effect: String,
         effect_in_suggestion: String,
         block_name: Ident,
+        /// The calls from the contract entry point to the function with the effect.
+        call_chain: Vec<Ident>,
     },
     ModulePrivacyDisabled,
     UsingDeprecated {
         message: String,
+        /// The deprecated name as used, and the name suggested by the deprecated item to
+        /// replace it with, if any.
+        replacement: Option<(Span, String)>,
     },
 }
 
@@ -218,6 +241,9 @@ impl fmt::Display for Warning {
             DeadStructDeclaration => write!(f, "This struct is never used."),
             DeadFunctionDeclaration => write!(f, "This function is never called."),
             UnreachableCode => write!(f, "This code is unreachable."),
+            UnusedMut { name, .. } => {
+                write!(f, "Variable \"{name}\" is never changed and does not need to be mutable.")
+            }
             DeadEnumVariant { variant_name } => {
                 write!(f, "Enum variant {variant_name} is never constructed.")
             }
@@ -237,6 +263,32 @@ impl fmt::Display for Warning {
                 "This function's storage attributes declaration does not match its \
                  actual storage access pattern: '{unneeded_attrib}' attribute(s) can be removed."
             ),
+            StorageFieldWrittenButNeverRead => write!(
+                f,
+                "This storage field is written but never read by the contract."
+            ),
+            StorageFieldReadButNeverWritten => write!(
+                f,
+                "This storage field is read but never written by the contract, except by its \
+                 initializer. Consider making it a constant or a configurable."
+            ),
+            ArithmeticOnUncheckedAbiArgument { argument } => write!(
+                f,
+                "This arithmetic operation uses the ABI method argument \"{argument}\" without \
+                 checking its bounds first, and reverts if it overflows. Consider checking \
+                 \"{argument}\" against its expected range before using it."
+            ),
+            UncheckedNarrowingConversion { from, to } => write!(
+                f,
+                "This conversion from \"{from}\" to \"{to}\" silently truncates values which \
+                 do not fit into \"{to}\". Consider using \"{to}::try_from\" instead, or \
+                 checking the value's bounds first."
+            ),
+            PossibleDivisionByZero => write!(
+                f,
+                "The divisor of this operation could be zero, in which case it reverts. \
+                 Consider checking that it is not zero first."
+            ),
             MatchExpressionUnreachableArm { .. } => write!(f, "This match arm is unreachable."),
             UnrecognizedAttribute {attrib_name} => write!(f, "Unknown attribute: \"{attrib_name}\"."),
             AttributeExpectedNumberOfArguments {attrib_name, received_args, expected_min_len, expected_max_len } => write!(
@@ -257,13 +309,19 @@ impl fmt::Display for Warning {
                 "Unexpected attribute value: \"{received_value}\" for attribute: \"{attrib_name}\" expected value {}",
                 expected_values.iter().map(|v| format!("\"{v}\"")).collect::<Vec<_>>().join(" or ")
             ),
-            EffectAfterInteraction {effect, effect_in_suggestion, block_name} =>
+            EffectAfterInteraction {effect, effect_in_suggestion, block_name, call_chain} => {
                 write!(f, "{effect} after external contract interaction in function or method \"{block_name}\". \
-                          Consider {effect_in_suggestion} before calling another contract"),
+                          Consider {effect_in_suggestion} before calling another contract")?;
+                if call_chain.len() > 1 {
+                    let call_chain = call_chain.iter().map(|name| format!("\"{name}\"")).collect::<Vec<_>>();
+                    write!(f, ". Call chain: {}", call_chain.join(" -> "))?;
+                }
+                Ok(())
+            }
             ModulePrivacyDisabled => write!(f, "Module privacy rules will soon change to make modules private by default.
                                             You can enable the new behavior with the --experimental-private-modules flag, which will become the default behavior in a later release.
                                             More details are available in the related RFC: https://github.com/FuelLabs/sway-rfcs/blob/master/rfcs/0008-private-modules.md"),
-            UsingDeprecated { message } => write!(f, "{}", message),
+            UsingDeprecated { message, .. } => write!(f, "{}", message),
         }
     }
 }
@@ -296,6 +354,7 @@ impl ToDiagnostic for CompileWarning {
                     format!("In Sway, ABIs, structs, traits, and enums are CapitalCase."),
                     format!("Modules, variables, and functions are snake_case, while constants are SCREAMING_SNAKE_CASE."),
                 ],
+                suggestions: vec![],
             },
             MatchExpressionUnreachableArm { match_value, match_type, preceding_arms, unreachable_arm, is_last_arm, is_catch_all_arm } => Diagnostic {
                 reason: Some(Reason::new(code(1), "Match arm is unreachable".to_string())),
@@ -352,7 +411,26 @@ impl ToDiagnostic for CompileWarning {
                     vec![
                         format!("Consider removing the unreachable arm."),
                     ]
-                }
+                },
+                suggestions: vec![],
+            },
+            NonSnakeCaseStructFieldName { field_name: name } | NonSnakeCaseFunctionName { name } => Diagnostic {
+                issue: Issue::warning(source_engine, self.span(), format!("{}", self.warning_content)),
+                // Usages of the name are not renamed, so the fix has to be completed by hand.
+                suggestions: vec![Suggestion::replace(name.span(), to_snake_case(name.as_str()), Applicability::MaybeIncorrect)],
+                ..Default::default()
+            },
+            UnusedMut { mut_span, .. } => Diagnostic {
+                issue: Issue::warning(source_engine, self.span(), format!("{}", self.warning_content)),
+                suggestions: vec![Suggestion::remove(mut_span.clone(), Applicability::MachineApplicable)],
+                ..Default::default()
+            },
+            UsingDeprecated { replacement, .. } => Diagnostic {
+                issue: Issue::warning(source_engine, self.span(), format!("{}", self.warning_content)),
+                suggestions: replacement.iter()
+                    .map(|(span, replacement)| Suggestion::replace(span.clone(), replacement.clone(), Applicability::MachineApplicable))
+                    .collect(),
+                ..Default::default()
             },
            _ => Diagnostic {
                     // TODO: Temporary we use self here to achieve backward compatibility.
@@ -198,6 +198,19 @@ fn compute_dom_tree(
     Ok(Box::new(dom_tree))
 }
 
+/// Does `dominator` dominate `block`?  Both must be reachable, i.e., in the dominator tree.
+pub fn dominates(dom_tree: &DomTree, dominator: Block, mut block: Block) -> bool {
+    loop {
+        if block == dominator {
+            return true;
+        }
+        match dom_tree[&block].parent {
+            Some(parent) => block = parent,
+            None => return false,
+        }
+    }
+}
+
 pub const DOMFRONTS_NAME: &str = "dominance_frontiers";
 
 pub fn create_dom_fronts_pass() -> Pass {
@@ -0,0 +1,100 @@
+//! Natural loops analysis.
+//!
+//! A back edge is an edge from a block to one of its dominators, the loop header.  The natural
+//! loop of a back edge is made of the header and of the blocks which can reach the back edge
+//! without going through the header.  The loops of the back edges to a same header are merged.
+
+use sway_types::FxIndexSet;
+
+use crate::{
+    dominates, AnalysisResult, AnalysisResultT, AnalysisResults, Block, Context, DomTree, Function,
+    IrError, Pass, PassMutability, PostOrder, ScopedPass, DOMINATORS_NAME, POSTORDER_NAME,
+};
+
+/// A natural loop.
+#[derive(Clone)]
+pub struct Loop {
+    /// The single entry of the loop, which dominates all of its blocks.
+    pub header: Block,
+    /// The blocks of the loop, including the header and the blocks of the nested loops.
+    pub blocks: FxIndexSet<Block>,
+    /// The blocks which branch back to the header.
+    pub latches: Vec<Block>,
+    /// The index of the innermost loop which encloses this one.
+    pub parent: Option<usize>,
+}
+
+/// The natural loops of a function, with the nested loops before the loops enclosing them.
+#[derive(Clone, Default)]
+pub struct Loops(pub Vec<Loop>);
+impl AnalysisResultT for Loops {}
+
+pub const LOOPS_NAME: &str = "loops";
+
+pub fn create_loops_pass() -> Pass {
+    Pass {
+        name: LOOPS_NAME,
+        descr: "Natural loops computation",
+        deps: vec![POSTORDER_NAME, DOMINATORS_NAME],
+        runner: ScopedPass::FunctionPass(PassMutability::Analysis(compute_loops_pass)),
+    }
+}
+
+pub fn compute_loops_pass(
+    context: &Context,
+    analyses: &AnalysisResults,
+    function: Function,
+) -> Result<AnalysisResult, IrError> {
+    let po: &PostOrder = analyses.get_analysis_result(function);
+    let dom_tree: &DomTree = analyses.get_analysis_result(function);
+    Ok(Box::new(compute_loops(context, po, dom_tree)))
+}
+
+/// Compute the natural loops of a function.
+pub fn compute_loops(context: &Context, po: &PostOrder, dom_tree: &DomTree) -> Loops {
+    let mut loops = Vec::new();
+    for header in po.po_to_block.iter().rev() {
+        // Unreachable predecessors aren't in the dominator tree.
+        let reachable_preds = |block: &Block| {
+            block
+                .pred_iter(context)
+                .filter(|pred| po.block_to_po.contains_key(pred))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        let latches = reachable_preds(header)
+            .into_iter()
+            .filter(|pred| dominates(dom_tree, *header, *pred))
+            .collect::<Vec<_>>();
+        if latches.is_empty() {
+            continue;
+        }
+
+        let mut blocks = FxIndexSet::default();
+        blocks.insert(*header);
+        let mut worklist = latches.clone();
+        while let Some(block) = worklist.pop() {
+            if blocks.insert(block) {
+                worklist.extend(reachable_preds(&block));
+            }
+        }
+
+        loops.push(Loop {
+            header: *header,
+            blocks,
+            latches,
+            parent: None,
+        });
+    }
+
+    // A loop nested in another has fewer blocks, so sorting them by size puts the nested loops
+    // first, and the first loop after one which contains its header is its parent.
+    loops.sort_by_key(|lp| lp.blocks.len());
+    for idx in 0..loops.len() {
+        loops[idx].parent =
+            (idx + 1..loops.len()).find(|outer| loops[*outer].blocks.contains(&loops[idx].header));
+    }
+
+    Loops(loops)
+}
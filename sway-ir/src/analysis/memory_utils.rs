@@ -7,9 +7,9 @@ use rustc_hash::FxHashSet;
 use sway_types::{FxIndexMap, FxIndexSet};
 
 use crate::{
-    AnalysisResult, AnalysisResultT, AnalysisResults, BlockArgument, Context, FuelVmInstruction,
-    Function, InstOp, Instruction, IrError, LocalVar, Pass, PassMutability, ScopedPass, Type,
-    Value, ValueDatum,
+    AnalysisResult, AnalysisResultT, AnalysisResults, BlockArgument, ConstantValue, Context,
+    FuelVmInstruction, Function, InstOp, Instruction, IrError, LocalVar, Pass, PassMutability,
+    ScopedPass, Type, Value, ValueDatum,
 };
 
 pub const ESCAPED_SYMBOLS_NAME: &str = "escaped_symbols";
@@ -281,6 +281,216 @@ pub fn get_stored_symbols(context: &Context, val: Value) -> FxIndexSet<Symbol> {
     res
 }
 
+/// The symbol which `ptr` points into, if it can be traced back to a single local or function
+/// argument through `get_elem_ptr`s.  Unlike [get_symbol] this doesn't look through the
+/// arguments of the blocks other than the entry, which may be incomplete.
+pub fn get_traced_symbol(context: &Context, function: Function, mut ptr: Value) -> Option<Symbol> {
+    loop {
+        match &context.values[ptr.0].value {
+            ValueDatum::Instruction(Instruction {
+                op: InstOp::GetLocal(local),
+                ..
+            }) => return Some(Symbol::Local(*local)),
+            ValueDatum::Instruction(Instruction {
+                op: InstOp::GetElemPtr { base, .. },
+                ..
+            }) => ptr = *base,
+            ValueDatum::Argument(arg) if arg.block == function.get_entry_block(context) => {
+                return Some(Symbol::Arg(*arg))
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Symbols whose memory may be accessed by other functions or through pointers which can't be
+/// traced back to them: the escaped symbols and the symbols whose address is stored in memory.
+/// The function arguments may be accessed that way too, see [MemoryWrite::may_write].
+pub fn compute_exposed_symbols(
+    context: &Context,
+    function: Function,
+    escaped_symbols: &EscapedSymbols,
+) -> FxHashSet<Symbol> {
+    let mut exposed_symbols = escaped_symbols.clone();
+    for (_block, inst) in function.instruction_iter(context) {
+        if let InstOp::Store { stored_val, .. } = inst.get_instruction(context).unwrap().op {
+            exposed_symbols.extend(get_symbols(context, stored_val));
+        }
+    }
+    exposed_symbols
+}
+
+/// The memory which an instruction may write to.
+pub enum MemoryWrite {
+    Nothing,
+    /// The memory of these (traced) symbols.
+    Symbols(FxIndexSet<Symbol>),
+    /// The memory of any exposed symbol, as when calling another function.
+    Exposed,
+    /// Any memory at all.
+    Anything,
+}
+
+impl MemoryWrite {
+    /// Whether the memory of `symbol` may be written to.  `exposed_symbols` is the result of
+    /// [compute_exposed_symbols].
+    pub fn may_write(&self, symbol: &Symbol, exposed_symbols: &FxHashSet<Symbol>) -> bool {
+        match self {
+            MemoryWrite::Nothing => false,
+            // Two pointers passed in as arguments may point to the same memory.
+            MemoryWrite::Symbols(symbols) => match symbol {
+                Symbol::Arg(_) => symbols.iter().any(|sym| matches!(sym, Symbol::Arg(_))),
+                Symbol::Local(_) => symbols.contains(symbol),
+            },
+            MemoryWrite::Exposed => {
+                matches!(symbol, Symbol::Arg(_)) || exposed_symbols.contains(symbol)
+            }
+            MemoryWrite::Anything => true,
+        }
+    }
+}
+
+/// The memory which `inst` may write to.
+pub fn get_memory_write(context: &Context, function: Function, inst: Value) -> MemoryWrite {
+    if let Some(memory) = get_asm_block_memory(context, function, inst) {
+        return match memory.write {
+            Some(symbol) => MemoryWrite::Symbols(IndexSet::from_iter([symbol])),
+            None => MemoryWrite::Nothing,
+        };
+    }
+    if matches!(
+        inst.get_instruction(context).unwrap().op,
+        InstOp::AsmBlock(..) | InstOp::Call(..) | InstOp::ContractCall { .. }
+    ) {
+        return MemoryWrite::Exposed;
+    }
+
+    let mut symbols = IndexSet::default();
+    for ptr in get_stored_ptr_values(context, inst) {
+        match get_traced_symbol(context, function, ptr) {
+            Some(symbol) => symbols.insert(symbol),
+            None => return MemoryWrite::Anything,
+        };
+    }
+    if symbols.is_empty() {
+        MemoryWrite::Nothing
+    } else {
+        MemoryWrite::Symbols(symbols)
+    }
+}
+
+/// The memory accessed by an ASM block which has no side effects, other than writing to the
+/// memory which its return register points to.  See [get_asm_block_memory].
+pub struct AsmBlockMemory {
+    /// The locals which may be read.
+    pub reads: FxIndexSet<Symbol>,
+    /// The local which is written to, if any.
+    pub write: Option<Symbol>,
+    /// Whether the block may panic, e.g. on an arithmetic overflow.
+    pub may_fail: bool,
+}
+
+/// The registers which may be read without depending on the state of the VM.
+const CONSTANT_REGISTERS: [&str; 2] = ["zero", "one"];
+
+/// The ALU instructions, which only write their first register.
+const ALU_OPS: &[&str] = &[
+    "add", "addi", "and", "andi", "div", "divi", "eq", "exp", "expi", "gt", "lt", "mod", "modi",
+    "move", "movi", "mul", "muli", "not", "or", "ori", "sll", "slli", "srl", "srli", "sub", "subi",
+    "xor", "xori",
+];
+
+/// The ALU instructions which panic on overflows or divisions by zero.
+const FALLIBLE_ALU_OPS: &[&str] = &[
+    "add", "addi", "div", "divi", "exp", "expi", "mod", "modi", "mul", "muli", "sub", "subi",
+];
+
+/// The memory accessed by `inst` if it is an ASM block without side effects, other than the
+/// hashing of a local into another local which its return register points to.
+///
+/// Such a block only contains:
+///   1. ALU instructions, which may only write the return register, when that isn't an argument.
+///   2. `s256` and `k256` instructions, which hash a constant number of bytes of a local into the
+///      local which the return register points to.
+///
+/// The registers it reads are its initialized arguments, the `zero` and `one` registers and the
+/// return register once written.
+pub fn get_asm_block_memory(
+    context: &Context,
+    function: Function,
+    inst: Value,
+) -> Option<AsmBlockMemory> {
+    let InstOp::AsmBlock(asm, args) = &inst.get_instruction(context)?.op else {
+        return None;
+    };
+    let ret = asm.return_name.as_ref()?.as_str();
+    let initializer = |reg: &str| {
+        args.iter()
+            .find(|arg| arg.name.as_str() == reg)
+            .and_then(|arg| arg.initializer)
+    };
+    // The local which `reg` points to, if it is an argument.
+    let local = |reg: &str| match get_traced_symbol(context, function, initializer(reg)?)? {
+        symbol @ Symbol::Local(_) => Some(symbol),
+        Symbol::Arg(_) => None,
+    };
+
+    let mut memory = AsmBlockMemory {
+        reads: IndexSet::default(),
+        write: None,
+        may_fail: false,
+    };
+    let mut is_ret_written = false;
+    for asm_inst in &asm.body {
+        let op = asm_inst.op_name.as_str();
+        let regs = asm_inst
+            .args
+            .iter()
+            .map(|reg| reg.as_str())
+            .collect::<Vec<_>>();
+        let is_readable = |reg: &str| {
+            CONSTANT_REGISTERS.contains(&reg)
+                || initializer(reg).is_some()
+                || (reg == ret && is_ret_written)
+        };
+        match regs[..] {
+            [dst, ref srcs @ ..] if ALU_OPS.contains(&op) => {
+                if dst != ret || initializer(ret).is_some() || !srcs.iter().all(|r| is_readable(r))
+                {
+                    return None;
+                }
+                memory.may_fail |= FALLIBLE_ALU_OPS.contains(&op);
+                is_ret_written = true;
+            }
+            [] if op == "noop" => {}
+            [dst, src, len] if op == "s256" || op == "k256" => {
+                let dst_local = local(dst).filter(|_| dst == ret)?;
+                let src_local = local(src)?;
+                let len = match initializer(len)?.get_constant(context)?.value {
+                    ConstantValue::Uint(len) => len,
+                    _ => return None,
+                };
+                // Hashing within the locals can't fail.
+                let size = |symbol: Symbol| match symbol {
+                    Symbol::Local(local) => local.get_inner_type(context).size(context).in_bytes(),
+                    Symbol::Arg(_) => 0,
+                };
+                if size(dst_local) < 32 || size(src_local) < len {
+                    return None;
+                }
+                if memory.write.is_some_and(|write| write != dst_local) {
+                    return None;
+                }
+                memory.reads.insert(src_local);
+                memory.write = Some(dst_local);
+            }
+            _ => return None,
+        }
+    }
+    let is_ret_defined = CONSTANT_REGISTERS.contains(&ret) || initializer(ret).is_some();
+    (is_ret_defined || is_ret_written).then_some(memory)
+}
+
 /// Combine a series of GEPs into one.
 pub fn combine_indices(context: &Context, val: Value) -> Option<Vec<Value>> {
     match &context.values[val.0].value {
@@ -5,8 +5,8 @@ use std::{
 
 use anyhow::anyhow;
 use sway_ir::{
-    insert_after_each, register_known_passes, ExperimentalFlags, PassGroup, PassManager,
-    MODULEPRINTER_NAME, MODULEVERIFIER_NAME,
+    insert_after_each, register_known_passes, Context, ExperimentalFlags, Interpreter, MockHost,
+    PassGroup, PassManager, MODULEPRINTER_NAME, MODULEVERIFIER_NAME,
 };
 use sway_types::SourceEngine;
 
@@ -29,10 +29,7 @@ fn main() -> Result<(), anyhow::Error> {
     let mut ir = sway_ir::parser::parse(&input_str, &source_engine, ExperimentalFlags::default())?;
 
     // Perform optimisation passes in order.
-    let mut passes = PassGroup::default();
-    for pass in config.passes {
-        passes.append_pass(pass);
-    }
+    let mut passes = config.passes;
     if config.print_after_each {
         passes = insert_after_each(passes, MODULEPRINTER_NAME);
     }
@@ -41,12 +38,47 @@ fn main() -> Result<(), anyhow::Error> {
     }
     pass_mgr.run(&mut ir, &passes)?;
 
+    if config.time_passes {
+        for (name, stats) in pass_mgr.stats() {
+            eprintln!(
+                "{name:16} {:>12?} {:>5} runs {:>5} modifying",
+                stats.elapsed, stats.runs, stats.modifying_runs
+            );
+        }
+    }
+
+    // Run the requested function, printing what it did instead of the IR unless there is an
+    // output file.
+    if let Some(fn_name) = &config.run {
+        run_function(&ir, fn_name)?;
+        if config.output_path.is_none() {
+            return Ok(());
+        }
+    }
+
     // Write the output file or standard out.
     write_to_output(ir, &config.output_path)?;
 
     Ok(())
 }
 
+fn run_function(ir: &Context, fn_name: &str) -> Result<(), anyhow::Error> {
+    let function = ir
+        .module_iter()
+        .flat_map(|module| module.function_iter(ir))
+        .find(|function| function.get_name(ir) == fn_name)
+        .ok_or_else(|| anyhow!("Function '{fn_name}' not found."))?;
+
+    let mut host = MockHost::default();
+    let outcome = Interpreter::new(ir, &mut host).run(function, &[])?;
+    for (log_id, value) in &host.logs {
+        println!("log {log_id} {value}");
+    }
+    println!("{outcome}");
+
+    Ok(())
+}
+
 fn read_from_input(path_str: &Option<String>) -> std::io::Result<String> {
     let mut input = Vec::new();
     match path_str {
@@ -86,10 +118,12 @@ struct Config {
 
     verify_after_each: bool,
     print_after_each: bool,
-    _time_passes: bool,
+    time_passes: bool,
     _stats: bool,
 
-    passes: Vec<&'static str>,
+    run: Option<String>,
+
+    passes: PassGroup,
 }
 
 // This is a little clumsy in that it needs to consume items from the iterator carefully in each
@@ -122,6 +156,7 @@ impl<'a, I: Iterator<Item = String>> ConfigBuilder<'a, I> {
                 match opt.as_str() {
                     "-i" => self.build_input(),
                     "-o" => self.build_output(),
+                    "-run" | "--run" => self.build_run(),
                     "-verify-after-each" => {
                         self.cfg.verify_after_each = true;
                         self.build_root()
@@ -130,12 +165,19 @@ impl<'a, I: Iterator<Item = String>> ConfigBuilder<'a, I> {
                         self.cfg.print_after_each = true;
                         self.build_root()
                     }
+                    "-time-passes" => {
+                        self.cfg.time_passes = true;
+                        self.build_root()
+                    }
                     "-h" => {
                         print!(
-                            "Usage: opt [passname...] -i input_file -o output_file\n\n{}",
+                            "Usage: opt [passname | pipeline...] -i input_file -o output_file\n\n{}",
                             self.pass_mgr.help_text()
                         );
-                        print!("\n\nIn the absence of -i or -o options, input is taken from stdin and output is printed to stdout.\n");
+                        print!("\n\nA pipeline is a comma separated list of passes and groups, e.g., 'mem2reg,repeat(constcombine,simplifycfg),dce'.  A 'repeat(...)' group is run until none of its passes change the IR.\n");
+                        print!("\nIn the absence of -i or -o options, input is taken from stdin and output is printed to stdout.\n");
+                        println!("Use -time-passes to print the time spent in each pass.");
+                        println!("Use -run fn_name to interpret a function without arguments after the passes, printing its logs and result instead of the IR unless -o is given.");
                         exit(0);
                     }
 
@@ -173,15 +215,24 @@ impl<'a, I: Iterator<Item = String>> ConfigBuilder<'a, I> {
         }
     }
 
-    fn build_pass(mut self, name: &str) -> Result<Config, anyhow::Error> {
-        if let Some(pass) = self.pass_mgr.lookup_registered_pass(name) {
-            self.cfg.passes.push(pass.name);
-            self.build_root()
-        } else {
-            Err(anyhow!(
-                "Unrecognised pass name '{name}'.\n\n{}",
-                self.pass_mgr.help_text()
-            ))
+    fn build_run(mut self) -> Result<Config, anyhow::Error> {
+        match self.next {
+            None => Err(anyhow!("-run option requires an argument.")),
+            Some(fn_name) => {
+                self.cfg.run = Some(fn_name);
+                self.next = self.rest.next();
+                self.build_root()
+            }
+        }
+    }
+
+    fn build_pass(mut self, pipeline: &str) -> Result<Config, anyhow::Error> {
+        match self.pass_mgr.parse_pipeline(pipeline) {
+            Ok(group) => {
+                self.cfg.passes.append_group(group);
+                self.build_root()
+            }
+            Err(err) => Err(anyhow!("{err}\n\n{}", self.pass_mgr.help_text())),
         }
     }
 }
@@ -6,8 +6,11 @@
 #[derive(Debug)]
 pub enum IrError {
     FunctionLocalClobbered(String, String),
+    InterpreterFailure(String),
+    InterpreterUnsupported(String),
     InvalidMetadatum(String),
     InvalidPhi,
+    InvalidPassPipeline(String),
     MisplacedTerminator(String),
     MissingBlock(String),
     MissingTerminator(String),
@@ -79,9 +82,18 @@ impl fmt::Display for IrError {
                 f,
                 "Local storage for function {fn_str} already has an entry for variable {var_str}."
             ),
+            IrError::InterpreterFailure(why_str) => {
+                write!(f, "Interpreter failure: {why_str}.")
+            }
+            IrError::InterpreterUnsupported(what_str) => {
+                write!(f, "Unsupported by the interpreter: {what_str}.")
+            }
             IrError::InvalidMetadatum(why_str) => {
                 write!(f, "Unable to convert from invalid metadatum: {why_str}.")
             }
+            IrError::InvalidPassPipeline(why_str) => {
+                write!(f, "Invalid pass pipeline: {why_str}.")
+            }
             IrError::InvalidPhi => write!(
                 f,
                 "Phi instruction has invalid block or value reference list."
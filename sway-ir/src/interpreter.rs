@@ -0,0 +1,1347 @@
+//! An interpreter for Sway-IR.
+//!
+//! The [`Interpreter`] executes the functions of a [`Context`] directly, without lowering them to
+//! Fuel VM bytecode first.  This allows a program to be run before and after an optimization pass
+//! and the results to be compared.
+//!
+//! The memory model follows the Fuel VM, and the code generated for it, closely enough for this to
+//! be meaningful.  Every IR value is held in a word: copy types (unit, bool, integers up to 64 bits
+//! and pointers) by their value and all other types (`u256`, `b256`, strings, slices and
+//! aggregates) by the address of their bytes in memory.  Locals are allocated on a stack, a frame
+//! per call, which grows up from address zero, and `aloc` in ASM blocks allocates on a heap which
+//! grows down from the top of the memory.
+//!
+//! Whatever the VM provides beyond that, i.e., transaction fields, storage, logs, messages and
+//! calls to other contracts, is delegated to an [`InterpreterHost`].  [`MockHost`] is a host which
+//! keeps everything in memory, for tests and the `opt` tool.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt,
+};
+
+use rustc_hash::FxHashMap;
+use sway_types::u256::U256;
+
+use crate::{
+    asm::{AsmArg, AsmBlock, AsmInstruction},
+    block::Block,
+    constant::{Constant, ConstantValue},
+    context::Context,
+    error::IrError,
+    function::Function,
+    instruction::{
+        BinaryOpKind, BranchToWithArgs, FuelVmInstruction, InstOp, Predicate, Register, UnaryOpKind,
+    },
+    irtype::{Type, TypeContent},
+    local_var::LocalVar,
+    value::{Value, ValueDatum},
+};
+
+/// The number of instructions executed before running out of gas, unless set with
+/// [`Interpreter::with_step_limit`].
+pub const DEFAULT_STEP_LIMIT: u64 = 1_000_000;
+
+/// The size of the VM memory.  The heap grows down from here.
+const VM_MAX_RAM: u64 = 64 * 1024 * 1024;
+
+/// The maximum depth of nested calls.
+const MAX_CALL_DEPTH: usize = 256;
+
+/// A value passed to or returned from an interpreted function, or logged by it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExecValue {
+    /// The value of a copy type: unit, bool, an integer up to 64 bits or a pointer.
+    Word(u64),
+    /// The bytes of any other type, as laid out in memory.
+    Bytes(Vec<u8>),
+}
+
+impl fmt::Display for ExecValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecValue::Word(word) => write!(f, "{word}"),
+            ExecValue::Bytes(bytes) => write_hex(f, bytes),
+        }
+    }
+}
+
+/// How the execution of a function ended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExecutionOutcome {
+    /// The function returned a value.
+    Return(ExecValue),
+    /// The program reverted with a code.
+    Revert(u64),
+    /// The program returned data with `retd`.
+    ReturnData(Vec<u8>),
+    /// The VM panicked.
+    Panic(PanicReason),
+}
+
+impl fmt::Display for ExecutionOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutionOutcome::Return(value) => write!(f, "ret {value}"),
+            ExecutionOutcome::Revert(code) => write!(f, "revert {code}"),
+            ExecutionOutcome::ReturnData(bytes) => {
+                write!(f, "retd ")?;
+                write_hex(f, bytes)
+            }
+            ExecutionOutcome::Panic(reason) => write!(f, "panic {reason:?}"),
+        }
+    }
+}
+
+/// The reasons for which the interpreter panics, named after their Fuel VM equivalents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanicReason {
+    /// An integer operation overflowed or underflowed.
+    ArithmeticOverflow,
+    /// An integer was divided by zero.
+    ArithmeticError,
+    /// Memory outside of the stack and the heap was accessed, or either ran out of space.
+    MemoryOverflow,
+    /// The step limit was reached.
+    OutOfGas,
+}
+
+/// A call to another contract, as passed to [`InterpreterHost::contract_call`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractCallRequest {
+    /// The name of the called method, if known.
+    pub name: Option<String>,
+    /// The bytes of the call parameters: the contract ID, the function selector and the user
+    /// arguments.
+    pub params: Vec<u8>,
+    pub coins: u64,
+    pub asset_id: [u8; 32],
+    pub gas: u64,
+}
+
+/// The parts of the Fuel VM which the interpreter doesn't model itself.
+pub trait InterpreterHost {
+    /// Read a transaction field, as `gtf` does.
+    fn gtf(&mut self, index: u64, tx_field_id: u64) -> u64;
+
+    /// Read a VM register.  The stack and heap pointers are tracked by the interpreter.
+    fn read_register(&mut self, reg: Register) -> u64;
+
+    /// Log a value with its log ID.
+    fn log(&mut self, log_id: u64, value: ExecValue);
+
+    /// Send a message with some coins to a recipient, as `smo` does.
+    fn smo(&mut self, recipient: [u8; 32], message: Vec<u8>, coins: u64);
+
+    /// Read a storage slot, or `None` if it isn't set.
+    fn storage_read(&mut self, key: &[u8; 32]) -> Option<[u8; 32]>;
+
+    /// Write a storage slot, or clear it if `value` is `None`.  Returns whether the slot was set
+    /// before.
+    fn storage_write(&mut self, key: [u8; 32], value: Option<[u8; 32]>) -> bool;
+
+    /// Call another contract.  Returns the bytes of the returned value, which are padded with
+    /// zeroes as needed, or the code the call reverted with.
+    fn contract_call(&mut self, call: ContractCallRequest) -> Result<Vec<u8>, u64>;
+}
+
+/// An [`InterpreterHost`] which keeps its state in memory and records everything done to it.
+#[derive(Debug, Default)]
+pub struct MockHost {
+    /// The transaction fields, by index and field ID.  Missing fields are zero.
+    pub tx_fields: BTreeMap<(u64, u64), u64>,
+    /// The VM registers.  Missing registers are zero.
+    pub registers: FxHashMap<Register, u64>,
+    pub storage: BTreeMap<[u8; 32], [u8; 32]>,
+    pub logs: Vec<(u64, ExecValue)>,
+    pub messages: Vec<([u8; 32], Vec<u8>, u64)>,
+    pub contract_calls: Vec<ContractCallRequest>,
+    /// The results of the contract calls, in order.  Once they run out, calls return zeroes.
+    pub contract_call_results: VecDeque<Result<Vec<u8>, u64>>,
+}
+
+impl InterpreterHost for MockHost {
+    fn gtf(&mut self, index: u64, tx_field_id: u64) -> u64 {
+        self.tx_fields
+            .get(&(index, tx_field_id))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn read_register(&mut self, reg: Register) -> u64 {
+        self.registers.get(&reg).copied().unwrap_or(0)
+    }
+
+    fn log(&mut self, log_id: u64, value: ExecValue) {
+        self.logs.push((log_id, value));
+    }
+
+    fn smo(&mut self, recipient: [u8; 32], message: Vec<u8>, coins: u64) {
+        self.messages.push((recipient, message, coins));
+    }
+
+    fn storage_read(&mut self, key: &[u8; 32]) -> Option<[u8; 32]> {
+        self.storage.get(key).copied()
+    }
+
+    fn storage_write(&mut self, key: [u8; 32], value: Option<[u8; 32]>) -> bool {
+        match value {
+            Some(value) => self.storage.insert(key, value),
+            None => self.storage.remove(&key),
+        }
+        .is_some()
+    }
+
+    fn contract_call(&mut self, call: ContractCallRequest) -> Result<Vec<u8>, u64> {
+        self.contract_calls.push(call);
+        self.contract_call_results
+            .pop_front()
+            .unwrap_or_else(|| Ok(Vec::new()))
+    }
+}
+
+/// Executes the functions of a [`Context`].
+pub struct Interpreter<'a, H: InterpreterHost> {
+    context: &'a Context<'a>,
+    host: &'a mut H,
+    memory: Memory,
+    step_limit: u64,
+    steps: u64,
+    depth: usize,
+}
+
+impl<'a, H: InterpreterHost> Interpreter<'a, H> {
+    pub fn new(context: &'a Context<'a>, host: &'a mut H) -> Self {
+        Interpreter {
+            context,
+            host,
+            memory: Memory::default(),
+            step_limit: DEFAULT_STEP_LIMIT,
+            steps: 0,
+            depth: 0,
+        }
+    }
+
+    /// Set the number of instructions, including those in ASM blocks, which may be executed
+    /// before running out of gas.
+    pub fn with_step_limit(mut self, step_limit: u64) -> Self {
+        self.step_limit = step_limit;
+        self
+    }
+
+    /// Call `function` with `args` in a fresh memory.
+    ///
+    /// Returns an error if the IR is malformed or uses something the interpreter doesn't support,
+    /// otherwise how the execution ended.
+    pub fn run(
+        &mut self,
+        function: Function,
+        args: &[ExecValue],
+    ) -> Result<ExecutionOutcome, IrError> {
+        let context = self.context;
+        if args.len() != function.num_args(context) {
+            return Err(IrError::InterpreterFailure(format!(
+                "function {} takes {} arguments but {} were passed",
+                function.get_name(context),
+                function.num_args(context),
+                args.len()
+            )));
+        }
+
+        self.memory = Memory::default();
+        self.steps = 0;
+        self.depth = 0;
+
+        let result = args
+            .iter()
+            .map(|arg| match arg {
+                ExecValue::Word(word) => Ok(*word),
+                ExecValue::Bytes(bytes) => self.push_bytes(bytes),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|args| self.call(function, args))
+            .and_then(|ret| self.exec_value(function.get_return_type(context), ret));
+
+        match result {
+            Ok(value) => Ok(ExecutionOutcome::Return(value)),
+            Err(Halt::Outcome(outcome)) => Ok(outcome),
+            Err(Halt::Error(err)) => Err(err),
+        }
+    }
+
+    fn call(&mut self, function: Function, args: Vec<u64>) -> Result<u64, Halt> {
+        let context = self.context;
+        if self.depth == MAX_CALL_DEPTH {
+            return Err(failure("maximum call depth exceeded"));
+        }
+
+        let frame_base = self.memory.sp();
+        let mut frame = Frame::default();
+        for (_, local) in function.locals_iter(context) {
+            let ty = local.get_inner_type(context);
+            let addr = self.memory.push(ty.size(context).in_bytes())?;
+            if let Some(initializer) = local.get_initializer(context) {
+                let bytes = self.constant_bytes(initializer)?;
+                self.memory.write(addr, &bytes)?;
+            }
+            frame.locals.insert(*local, addr);
+        }
+        for ((_, arg), word) in function.args_iter(context).zip(args) {
+            frame.values.insert(*arg, word);
+        }
+
+        self.depth += 1;
+        let ret = self.run_blocks(&mut frame, function.get_entry_block(context))?;
+        self.depth -= 1;
+
+        // Values which aren't copy types live in memory, in the frame if they were created by the
+        // function, so they're copied to the caller's frame.
+        let ret_ty = function.get_return_type(context);
+        if is_copy_type(context, ret_ty) {
+            self.memory.pop(frame_base);
+            Ok(ret)
+        } else {
+            let bytes = self
+                .memory
+                .read(ret, ret_ty.size(context).in_bytes())?
+                .to_vec();
+            self.memory.pop(frame_base);
+            self.push_bytes(&bytes)
+        }
+    }
+
+    fn run_blocks(&mut self, frame: &mut Frame, mut block: Block) -> Result<u64, Halt> {
+        let context = self.context;
+        loop {
+            let mut next_block = None;
+            for inst_val in block.instruction_iter(context) {
+                self.step()?;
+                match self.execute(frame, inst_val)? {
+                    Step::Next => (),
+                    Step::Branch(to_block) => {
+                        next_block = Some(to_block);
+                        break;
+                    }
+                    Step::Return(ret) => return Ok(ret),
+                }
+            }
+            block =
+                next_block.ok_or_else(|| IrError::MissingTerminator(block.get_label(context)))?;
+        }
+    }
+
+    fn step(&mut self) -> Result<(), Halt> {
+        self.steps += 1;
+        if self.steps > self.step_limit {
+            Err(Halt::Outcome(ExecutionOutcome::Panic(
+                PanicReason::OutOfGas,
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn execute(&mut self, frame: &mut Frame, inst_val: Value) -> Result<Step, Halt> {
+        let context = self.context;
+        let inst = inst_val
+            .get_instruction(context)
+            .ok_or_else(|| failure("executing a value which isn't an instruction"))?;
+        let result = match &inst.op {
+            InstOp::AsmBlock(asm, args) => self.asm_block(frame, asm, args)?,
+            InstOp::UnaryOp {
+                op: UnaryOpKind::Not,
+                arg,
+            } => {
+                let arg_ty = value_type(context, arg)?;
+                let arg = self.value(frame, *arg)?;
+                if is_copy_type(context, arg_ty) {
+                    !arg
+                } else {
+                    let bytes = self.memory.read_b256(arg)?.map(|byte| !byte);
+                    self.push_bytes(&bytes)?
+                }
+            }
+            InstOp::BinaryOp { op, arg1, arg2 } => {
+                let arg_ty = value_type(context, arg1)?;
+                let lhs = self.value(frame, *arg1)?;
+                let rhs = self.value(frame, *arg2)?;
+                if is_copy_type(context, arg_ty) {
+                    word_binary_op(*op, lhs, rhs).map_err(panic)?
+                } else {
+                    let bytes = self.b256_binary_op(*op, lhs, rhs)?;
+                    self.push_bytes(&bytes)?
+                }
+            }
+            InstOp::BitCast(val, _)
+            | InstOp::CastPtr(val, _)
+            | InstOp::IntToPtr(val, _)
+            | InstOp::PtrToInt(val, _) => self.value(frame, *val)?,
+            InstOp::Branch(to_block) => return self.branch(frame, to_block),
+            InstOp::Call(function, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.value(frame, *arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.call(*function, args)?
+            }
+            InstOp::Cmp(pred, lhs, rhs) => {
+                let arg_ty = value_type(context, lhs)?;
+                let lhs = self.value(frame, *lhs)?;
+                let rhs = self.value(frame, *rhs)?;
+                let ordering = if is_copy_type(context, arg_ty) {
+                    lhs.cmp(&rhs)
+                } else {
+                    self.memory
+                        .read_b256(lhs)?
+                        .cmp(&self.memory.read_b256(rhs)?)
+                };
+                compare(*pred, ordering)
+            }
+            InstOp::ConditionalBranch {
+                cond_value,
+                true_block,
+                false_block,
+            } => {
+                let to_block = if self.value(frame, *cond_value)? != 0 {
+                    true_block
+                } else {
+                    false_block
+                };
+                return self.branch(frame, to_block);
+            }
+            InstOp::ContractCall {
+                return_type,
+                name,
+                params,
+                coins,
+                asset_id,
+                gas,
+            } => {
+                let params_ty = value_type(context, params)?;
+                let params = self.value(frame, *params)?;
+                let params = match params_ty.get_pointee_type(context) {
+                    Some(ty) => self
+                        .memory
+                        .read(params, ty.size(context).in_bytes())?
+                        .to_vec(),
+                    None => params.to_be_bytes().to_vec(),
+                };
+                let asset_id = self.value(frame, *asset_id)?;
+                let call = ContractCallRequest {
+                    name: name.clone(),
+                    params,
+                    coins: self.value(frame, *coins)?,
+                    asset_id: self.memory.read_b256(asset_id)?,
+                    gas: self.value(frame, *gas)?,
+                };
+                let mut bytes = self
+                    .host
+                    .contract_call(call)
+                    .map_err(|code| Halt::Outcome(ExecutionOutcome::Revert(code)))?;
+                bytes.resize(return_type.size(context).in_bytes() as usize, 0);
+                if is_copy_type(context, *return_type) {
+                    word_from_bytes(&bytes)
+                } else {
+                    self.push_bytes(&bytes)?
+                }
+            }
+            InstOp::FuelVm(fuel_vm_instr) => self.fuel_vm_instruction(frame, fuel_vm_instr)?,
+            InstOp::GetLocal(local) => *frame
+                .locals
+                .get(local)
+                .ok_or_else(|| failure("local variable from another function"))?,
+            InstOp::GetElemPtr { base, indices, .. } => {
+                let base_ty = value_type(context, base)?
+                    .get_pointee_type(context)
+                    .ok_or_else(|| failure("get_elem_ptr from a non-pointer"))?;
+                let base = self.value(frame, *base)?;
+                let indices = indices
+                    .iter()
+                    .map(|idx| self.value(frame, *idx))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let offset = base_ty
+                    .get_indexed_offset(context, &indices)
+                    .ok_or_else(|| failure("get_elem_ptr with invalid indices"))?;
+                base.checked_add(offset)
+                    .ok_or(panic(PanicReason::MemoryOverflow))?
+            }
+            InstOp::Load(src_val_ptr) => {
+                let ty = value_type(context, &inst_val)?;
+                let src = self.value(frame, *src_val_ptr)?;
+                if is_copy_type(context, ty) {
+                    self.memory
+                        .read_copy_type(src, ty.size(context).in_bytes())?
+                } else {
+                    let bytes = self.memory.read(src, ty.size(context).in_bytes())?.to_vec();
+                    self.push_bytes(&bytes)?
+                }
+            }
+            InstOp::MemCopyBytes {
+                dst_val_ptr,
+                src_val_ptr,
+                byte_len,
+            } => {
+                let dst = self.value(frame, *dst_val_ptr)?;
+                let src = self.value(frame, *src_val_ptr)?;
+                self.memory.copy(dst, src, *byte_len)?;
+                0
+            }
+            InstOp::MemCopyVal {
+                dst_val_ptr,
+                src_val_ptr,
+            } => {
+                let len = pointee_size(context, dst_val_ptr)?;
+                let dst = self.value(frame, *dst_val_ptr)?;
+                let src = self.value(frame, *src_val_ptr)?;
+                self.memory.copy(dst, src, len)?;
+                0
+            }
+            InstOp::Nop => 0,
+            InstOp::Ret(ret_val, _) => return Ok(Step::Return(self.value(frame, *ret_val)?)),
+            InstOp::Store {
+                dst_val_ptr,
+                stored_val,
+            } => {
+                let ty = value_type(context, stored_val)?;
+                let dst = self.value(frame, *dst_val_ptr)?;
+                let stored = self.value(frame, *stored_val)?;
+                let size = ty.size(context).in_bytes();
+                if is_copy_type(context, ty) {
+                    self.memory.write_copy_type(dst, size, stored)?;
+                } else {
+                    self.memory.copy(dst, stored, size)?;
+                }
+                0
+            }
+        };
+        frame.values.insert(inst_val, result);
+        Ok(Step::Next)
+    }
+
+    fn branch(&mut self, frame: &mut Frame, to_block: &BranchToWithArgs) -> Result<Step, Halt> {
+        // All the arguments are evaluated before any parameter is set, as a block may pass its own
+        // parameters to itself in a different order.
+        let args = to_block
+            .args
+            .iter()
+            .map(|arg| self.value(frame, *arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (param, arg) in to_block.block.arg_iter(self.context).zip(args) {
+            frame.values.insert(*param, arg);
+        }
+        Ok(Step::Branch(to_block.block))
+    }
+
+    fn fuel_vm_instruction(
+        &mut self,
+        frame: &mut Frame,
+        fuel_vm_instr: &FuelVmInstruction,
+    ) -> Result<u64, Halt> {
+        Ok(match fuel_vm_instr {
+            FuelVmInstruction::Gtf { index, tx_field_id } => {
+                let index = self.value(frame, *index)?;
+                self.host.gtf(index, *tx_field_id)
+            }
+            FuelVmInstruction::Log {
+                log_val,
+                log_ty,
+                log_id,
+            } => {
+                let log_val = self.value(frame, *log_val)?;
+                let log_id = self.value(frame, *log_id)?;
+                let value = self.logged_value(*log_ty, log_val)?;
+                self.host.log(log_id, value);
+                0
+            }
+            FuelVmInstruction::ReadRegister(reg) => self.read_register(*reg),
+            FuelVmInstruction::Revert(code) => {
+                let code = self.value(frame, *code)?;
+                return Err(Halt::Outcome(ExecutionOutcome::Revert(code)));
+            }
+            FuelVmInstruction::Smo {
+                recipient,
+                message,
+                message_size,
+                coins,
+            } => {
+                let recipient = self.value(frame, *recipient)?;
+                let recipient = self.memory.read_b256(recipient)?;
+                let message = self.value(frame, *message)?;
+                let message_size = self.value(frame, *message_size)?;
+                let message = self.memory.read(message, message_size)?.to_vec();
+                let coins = self.value(frame, *coins)?;
+                self.host.smo(recipient, message, coins);
+                0
+            }
+            FuelVmInstruction::StateClear {
+                key,
+                number_of_slots,
+            } => {
+                let key = self.value(frame, *key)?;
+                let key = self.memory.read_b256(key)?;
+                let mut all_set = true;
+                for idx in 0..self.value(frame, *number_of_slots)? {
+                    all_set &= self.host.storage_write(nth_slot_key(&key, idx), None);
+                }
+                all_set as u64
+            }
+            FuelVmInstruction::StateLoadQuadWord {
+                load_val,
+                key,
+                number_of_slots,
+            } => {
+                let dst = self.value(frame, *load_val)?;
+                let key = self.value(frame, *key)?;
+                let key = self.memory.read_b256(key)?;
+                let mut all_set = true;
+                for idx in 0..self.value(frame, *number_of_slots)? {
+                    let slot = self.host.storage_read(&nth_slot_key(&key, idx));
+                    all_set &= slot.is_some();
+                    self.memory
+                        .write(slot_addr(dst, idx)?, &slot.unwrap_or_default())?;
+                }
+                all_set as u64
+            }
+            FuelVmInstruction::StateLoadWord(key) => {
+                let key = self.value(frame, *key)?;
+                let key = self.memory.read_b256(key)?;
+                self.host
+                    .storage_read(&key)
+                    .map_or(0, |slot| word_from_bytes(&slot[..8]))
+            }
+            FuelVmInstruction::StateStoreQuadWord {
+                stored_val,
+                key,
+                number_of_slots,
+            } => {
+                let src = self.value(frame, *stored_val)?;
+                let key = self.value(frame, *key)?;
+                let key = self.memory.read_b256(key)?;
+                let mut all_set = true;
+                for idx in 0..self.value(frame, *number_of_slots)? {
+                    let slot = self.memory.read_b256(slot_addr(src, idx)?)?;
+                    all_set &= self.host.storage_write(nth_slot_key(&key, idx), Some(slot));
+                }
+                all_set as u64
+            }
+            FuelVmInstruction::StateStoreWord { stored_val, key } => {
+                let stored = self.value(frame, *stored_val)?;
+                let key = self.value(frame, *key)?;
+                let key = self.memory.read_b256(key)?;
+                // The rest of the slot is cleared, as `sww` does.
+                let mut slot = [0; 32];
+                slot[..8].copy_from_slice(&stored.to_be_bytes());
+                self.host.storage_write(key, Some(slot)) as u64
+            }
+            FuelVmInstruction::WideUnaryOp {
+                op: UnaryOpKind::Not,
+                result,
+                arg,
+            } => {
+                let result = self.value(frame, *result)?;
+                let arg = self.value(frame, *arg)?;
+                let bytes = self.memory.read_b256(arg)?.map(|byte| !byte);
+                self.memory.write(result, &bytes)?;
+                0
+            }
+            FuelVmInstruction::WideBinaryOp {
+                op,
+                result,
+                arg1,
+                arg2,
+            } => {
+                let result = self.value(frame, *result)?;
+                let lhs = self.value(frame, *arg1)?;
+                let rhs = self.value(frame, *arg2)?;
+                let bytes = self.b256_binary_op(*op, lhs, rhs)?;
+                self.memory.write(result, &bytes)?;
+                0
+            }
+            FuelVmInstruction::WideModularOp {
+                op,
+                result,
+                arg1,
+                arg2,
+                arg3,
+            } => {
+                let result = self.value(frame, *result)?;
+                let lhs = self.value(frame, *arg1)?;
+                let lhs = U256::from_be_bytes(&self.memory.read_b256(lhs)?);
+                let rhs = self.value(frame, *arg2)?;
+                let rhs = U256::from_be_bytes(&self.memory.read_b256(rhs)?);
+                let modulus = self.value(frame, *arg3)?;
+                let modulus = U256::from_be_bytes(&self.memory.read_b256(modulus)?);
+                if modulus == U256::from(0) {
+                    return Err(panic(PanicReason::ArithmeticError));
+                }
+                let lhs = &lhs % &modulus;
+                let rhs = &rhs % &modulus;
+                // Like the backend, `wide mod` is a modular addition (WQAM).
+                let value = match op {
+                    BinaryOpKind::Mod => add_mod(&lhs, &rhs, &modulus),
+                    _ => return Err(unsupported(format!("wide modular {op:?}"))),
+                };
+                self.memory.write(result, &value.to_be_bytes())?;
+                0
+            }
+            FuelVmInstruction::WideCmpOp { op, arg1, arg2 } => {
+                let lhs = self.value(frame, *arg1)?;
+                let rhs = self.value(frame, *arg2)?;
+                let ordering = self
+                    .memory
+                    .read_b256(lhs)?
+                    .cmp(&self.memory.read_b256(rhs)?);
+                compare(*op, ordering)
+            }
+            FuelVmInstruction::JmpMem => return Err(unsupported("jmp_mem")),
+            FuelVmInstruction::Retd { ptr, len } => {
+                let ptr = self.value(frame, *ptr)?;
+                let len = self.value(frame, *len)?;
+                let bytes = self.memory.read(ptr, len)?.to_vec();
+                return Err(Halt::Outcome(ExecutionOutcome::ReturnData(bytes)));
+            }
+        })
+    }
+
+    fn asm_block(
+        &mut self,
+        frame: &mut Frame,
+        asm: &AsmBlock,
+        args: &[AsmArg],
+    ) -> Result<u64, Halt> {
+        let mut regs = FxHashMap::default();
+        for arg in args {
+            let word = match arg.initializer {
+                Some(initializer) => self.value(frame, initializer)?,
+                None => 0,
+            };
+            regs.insert(arg.name.as_str().to_owned(), word);
+        }
+        for asm_instr in &asm.body {
+            self.step()?;
+            self.asm_instruction(&mut regs, asm_instr)?;
+        }
+        match &asm.return_name {
+            Some(name) => self.asm_register(&regs, name.as_str()),
+            None => Ok(0),
+        }
+    }
+
+    fn asm_instruction(
+        &mut self,
+        regs: &mut FxHashMap<String, u64>,
+        asm_instr: &AsmInstruction,
+    ) -> Result<(), Halt> {
+        let op_name = asm_instr.op_name.as_str();
+        let arg = |idx: usize| {
+            asm_instr
+                .args
+                .get(idx)
+                .map(|arg| arg.as_str())
+                .ok_or_else(|| failure(format!("missing argument for '{op_name}'")))
+        };
+        let imm = || {
+            asm_instr
+                .immediate
+                .as_ref()
+                .ok_or_else(|| failure(format!("missing immediate for '{op_name}'")))
+                .and_then(|imm| parse_immediate(imm.as_str()))
+        };
+
+        let result = match op_name {
+            "add" | "sub" | "mul" | "div" | "mod" | "exp" | "and" | "or" | "xor" | "sll"
+            | "srl" | "eq" | "gt" | "lt" => {
+                let lhs = self.asm_register(regs, arg(1)?)?;
+                let rhs = self.asm_register(regs, arg(2)?)?;
+                Some(asm_alu_op(op_name, lhs, rhs)?)
+            }
+            "addi" | "subi" | "muli" | "divi" | "modi" | "expi" | "andi" | "ori" | "xori"
+            | "slli" | "srli" => {
+                let lhs = self.asm_register(regs, arg(1)?)?;
+                Some(asm_alu_op(&op_name[..op_name.len() - 1], lhs, imm()?)?)
+            }
+            "not" => Some(!self.asm_register(regs, arg(1)?)?),
+            "move" => Some(self.asm_register(regs, arg(1)?)?),
+            "movi" => Some(imm()?),
+            "lw" => {
+                let base = self.asm_register(regs, arg(1)?)?;
+                let addr = offset_addr(base, imm()?, 8)?;
+                Some(self.memory.read_copy_type(addr, 8)?)
+            }
+            "lb" => {
+                let base = self.asm_register(regs, arg(1)?)?;
+                let addr = offset_addr(base, imm()?, 1)?;
+                Some(self.memory.read_copy_type(addr, 1)?)
+            }
+            "sw" | "sb" => {
+                let (size, word) = if op_name == "sw" { (8, 8) } else { (1, 1) };
+                let base = self.asm_register(regs, arg(0)?)?;
+                let addr = offset_addr(base, imm()?, word)?;
+                let value = self.asm_register(regs, arg(1)?)?;
+                self.memory.write_copy_type(addr, size, value)?;
+                None
+            }
+            "mcp" | "mcpi" => {
+                let dst = self.asm_register(regs, arg(0)?)?;
+                let src = self.asm_register(regs, arg(1)?)?;
+                let len = if op_name == "mcp" {
+                    self.asm_register(regs, arg(2)?)?
+                } else {
+                    imm()?
+                };
+                self.memory.copy(dst, src, len)?;
+                None
+            }
+            "mcl" | "mcli" => {
+                let dst = self.asm_register(regs, arg(0)?)?;
+                let len = if op_name == "mcl" {
+                    self.asm_register(regs, arg(1)?)?
+                } else {
+                    imm()?
+                };
+                self.memory.write(dst, &vec![0; len as usize])?;
+                None
+            }
+            "meq" => {
+                let lhs = self.asm_register(regs, arg(1)?)?;
+                let rhs = self.asm_register(regs, arg(2)?)?;
+                let len = self.asm_register(regs, arg(3)?)?;
+                Some((self.memory.read(lhs, len)? == self.memory.read(rhs, len)?) as u64)
+            }
+            "aloc" => {
+                let size = self.asm_register(regs, arg(0)?)?;
+                self.memory.aloc(size)?;
+                None
+            }
+            "gtf" => {
+                let index = self.asm_register(regs, arg(1)?)?;
+                Some(self.host.gtf(index, imm()?))
+            }
+            "rvrt" => {
+                let code = self.asm_register(regs, arg(0)?)?;
+                return Err(Halt::Outcome(ExecutionOutcome::Revert(code)));
+            }
+            "noop" => None,
+            _ => return Err(unsupported(format!("ASM instruction '{op_name}'"))),
+        };
+
+        if let Some(result) = result {
+            let dst = arg(0)?;
+            if constant_register(dst).is_some() {
+                return Err(failure(format!(
+                    "'{op_name}' writes to reserved register {dst}"
+                )));
+            }
+            regs.insert(dst.to_owned(), result);
+        }
+        Ok(())
+    }
+
+    fn asm_register(&mut self, regs: &FxHashMap<String, u64>, name: &str) -> Result<u64, Halt> {
+        match constant_register(name) {
+            Some(AsmConstantRegister::Word(word)) => Ok(word),
+            Some(AsmConstantRegister::Vm(reg)) => Ok(self.read_register(reg)),
+            None => regs
+                .get(name)
+                .copied()
+                .ok_or_else(|| failure(format!("unknown ASM register {name}"))),
+        }
+    }
+
+    fn read_register(&mut self, reg: Register) -> u64 {
+        match reg {
+            Register::Sp => self.memory.sp(),
+            Register::Hp => self.memory.hp(),
+            _ => self.host.read_register(reg),
+        }
+    }
+
+    /// Get the word holding `val`, materializing it first if it's a constant.
+    fn value(&mut self, frame: &mut Frame, val: Value) -> Result<u64, Halt> {
+        if let Some(word) = frame.values.get(&val) {
+            return Ok(*word);
+        }
+        let word = match &self.context.values[val.0].value {
+            ValueDatum::Constant(constant) | ValueDatum::Configurable(constant) => {
+                self.constant_word(constant)?
+            }
+            ValueDatum::Argument(_) | ValueDatum::Instruction(_) => {
+                return Err(failure("value used before it is defined"))
+            }
+        };
+        frame.values.insert(val, word);
+        Ok(word)
+    }
+
+    fn constant_word(&mut self, constant: &Constant) -> Result<u64, Halt> {
+        if is_copy_type(self.context, constant.ty) {
+            Ok(match &constant.value {
+                ConstantValue::Undef | ConstantValue::Unit => 0,
+                ConstantValue::Bool(b) => *b as u64,
+                ConstantValue::Uint(n) => *n,
+                ConstantValue::Reference(referenced) => {
+                    let bytes = self.constant_bytes(referenced)?;
+                    self.push_bytes(&bytes)?
+                }
+                _ => return Err(failure("malformed copy type constant")),
+            })
+        } else {
+            let bytes = self.constant_bytes(constant)?;
+            self.push_bytes(&bytes)
+        }
+    }
+
+    /// Lay out a constant in memory.
+    fn constant_bytes(&mut self, constant: &Constant) -> Result<Vec<u8>, Halt> {
+        let context = self.context;
+        let size = constant.ty.size(context).in_bytes() as usize;
+        let mut bytes = match &constant.value {
+            ConstantValue::Undef | ConstantValue::Unit => Vec::new(),
+            ConstantValue::Bool(b) => vec![*b as u8],
+            ConstantValue::Uint(n) if size == 1 => vec![*n as u8],
+            ConstantValue::Uint(n) => n.to_be_bytes().to_vec(),
+            ConstantValue::U256(n) | ConstantValue::B256(n) => n.to_be_bytes().to_vec(),
+            ConstantValue::String(s) => s.clone(),
+            ConstantValue::Array(elems) => elems
+                .iter()
+                .map(|elem| self.constant_bytes(elem))
+                .collect::<Result<Vec<_>, _>>()?
+                .concat(),
+            ConstantValue::Struct(fields) => {
+                let mut bytes = vec![0; size];
+                for (idx, field) in fields.iter().enumerate() {
+                    let (offset, field_ty) = constant
+                        .ty
+                        .get_struct_field_offset_and_type(context, idx as u64)
+                        .ok_or_else(|| failure("malformed struct constant"))?;
+                    let field_bytes = self.constant_bytes(field)?;
+                    // Union variants, i.e., enum values, are left padded.
+                    let offset = if field_ty.is_union(context) {
+                        offset + field_ty.size(context).in_bytes() - field_bytes.len() as u64
+                    } else {
+                        offset
+                    } as usize;
+                    bytes[offset..offset + field_bytes.len()].copy_from_slice(&field_bytes);
+                }
+                bytes
+            }
+            ConstantValue::Reference(referenced) => {
+                let bytes = self.constant_bytes(referenced)?;
+                self.push_bytes(&bytes)?.to_be_bytes().to_vec()
+            }
+        };
+        bytes.resize(size, 0);
+        Ok(bytes)
+    }
+
+    /// Convert the word holding a value of type `ty` to an [`ExecValue`].
+    fn exec_value(&self, ty: Type, word: u64) -> Result<ExecValue, Halt> {
+        if is_copy_type(self.context, ty) {
+            Ok(ExecValue::Word(word))
+        } else {
+            let size = ty.size(self.context).in_bytes();
+            Ok(ExecValue::Bytes(self.memory.read(word, size)?.to_vec()))
+        }
+    }
+
+    fn logged_value(&self, log_ty: Type, word: u64) -> Result<ExecValue, Halt> {
+        let context = self.context;
+        // Pointers, as which demoted values are logged, log the data they point to, as do slices.
+        let ty = match log_ty.get_pointee_type(context) {
+            Some(pointee_ty) => pointee_ty,
+            None if is_copy_type(context, log_ty) => return Ok(ExecValue::Word(word)),
+            None => log_ty,
+        };
+        if ty.is_slice(context) || ty.is_string_slice(context) {
+            let ptr = self.memory.read_copy_type(word, 8)?;
+            let len = self.memory.read_copy_type(offset_addr(word, 1, 8)?, 8)?;
+            Ok(ExecValue::Bytes(self.memory.read(ptr, len)?.to_vec()))
+        } else {
+            let size = ty.size(context).in_bytes();
+            Ok(ExecValue::Bytes(self.memory.read(word, size)?.to_vec()))
+        }
+    }
+
+    /// Perform a binary operation on the `u256` or `b256` at `lhs`, and the one at `rhs` or, for
+    /// shifts, the number of bits in `rhs`.
+    fn b256_binary_op(&self, op: BinaryOpKind, lhs: u64, rhs: u64) -> Result<[u8; 32], Halt> {
+        let lhs = self.memory.read_b256(lhs)?;
+        Ok(match op {
+            BinaryOpKind::Lsh => shift_left(&lhs, rhs),
+            BinaryOpKind::Rsh => shift_right(&lhs, rhs),
+            BinaryOpKind::And | BinaryOpKind::Or | BinaryOpKind::Xor => {
+                let rhs = self.memory.read_b256(rhs)?;
+                let mut bytes = lhs;
+                for (byte, rhs_byte) in bytes.iter_mut().zip(rhs) {
+                    match op {
+                        BinaryOpKind::And => *byte &= rhs_byte,
+                        BinaryOpKind::Or => *byte |= rhs_byte,
+                        _ => *byte ^= rhs_byte,
+                    }
+                }
+                bytes
+            }
+            BinaryOpKind::Add
+            | BinaryOpKind::Sub
+            | BinaryOpKind::Mul
+            | BinaryOpKind::Div
+            | BinaryOpKind::Mod => {
+                let lhs = U256::from_be_bytes(&lhs);
+                let rhs = U256::from_be_bytes(&self.memory.read_b256(rhs)?);
+                match op {
+                    BinaryOpKind::Add => {
+                        lhs.checked_add(&rhs).ok_or(PanicReason::ArithmeticOverflow)
+                    }
+                    BinaryOpKind::Sub => {
+                        lhs.checked_sub(&rhs).ok_or(PanicReason::ArithmeticOverflow)
+                    }
+                    BinaryOpKind::Mul => {
+                        lhs.checked_mul(&rhs).ok_or(PanicReason::ArithmeticOverflow)
+                    }
+                    BinaryOpKind::Div => lhs.checked_div(&rhs).ok_or(PanicReason::ArithmeticError),
+                    _ if rhs == U256::from(0) => Err(PanicReason::ArithmeticError),
+                    _ => Ok(&lhs % &rhs),
+                }
+                .map_err(panic)?
+                .to_be_bytes()
+            }
+        })
+    }
+
+    fn push_bytes(&mut self, bytes: &[u8]) -> Result<u64, Halt> {
+        let addr = self.memory.push(bytes.len() as u64)?;
+        self.memory.write(addr, bytes)?;
+        Ok(addr)
+    }
+}
+
+/// Why the execution stopped before the called function returned.
+enum Halt {
+    Outcome(ExecutionOutcome),
+    Error(IrError),
+}
+
+impl From<IrError> for Halt {
+    fn from(err: IrError) -> Self {
+        Halt::Error(err)
+    }
+}
+
+fn panic(reason: PanicReason) -> Halt {
+    Halt::Outcome(ExecutionOutcome::Panic(reason))
+}
+
+fn failure<S: Into<String>>(why: S) -> Halt {
+    Halt::Error(IrError::InterpreterFailure(why.into()))
+}
+
+fn unsupported<S: Into<String>>(what: S) -> Halt {
+    Halt::Error(IrError::InterpreterUnsupported(what.into()))
+}
+
+enum Step {
+    Next,
+    Branch(Block),
+    Return(u64),
+}
+
+/// The values and the local variables of a function call.
+#[derive(Default)]
+struct Frame {
+    values: FxHashMap<Value, u64>,
+    locals: FxHashMap<LocalVar, u64>,
+}
+
+/// The VM memory, with the stack growing up from address zero and the heap growing down from
+/// [`VM_MAX_RAM`].
+#[derive(Default)]
+struct Memory {
+    stack: Vec<u8>,
+    heap: Vec<u8>,
+}
+
+impl Memory {
+    fn sp(&self) -> u64 {
+        self.stack.len() as u64
+    }
+
+    fn hp(&self) -> u64 {
+        VM_MAX_RAM - self.heap.len() as u64
+    }
+
+    /// Allocate `size` bytes, rounded up to a whole number of words, on the stack.
+    fn push(&mut self, size: u64) -> Result<u64, Halt> {
+        let addr = self.sp();
+        let size = crate::size_bytes_round_up_to_word_alignment!(size);
+        if size > self.hp() - addr {
+            return Err(panic(PanicReason::MemoryOverflow));
+        }
+        self.stack.resize((addr + size) as usize, 0);
+        Ok(addr)
+    }
+
+    /// Free the stack from `sp` up.
+    fn pop(&mut self, sp: u64) {
+        self.stack.truncate(sp as usize);
+    }
+
+    /// Allocate `size` bytes on the heap.
+    fn aloc(&mut self, size: u64) -> Result<(), Halt> {
+        if size > self.hp() - self.sp() {
+            return Err(panic(PanicReason::MemoryOverflow));
+        }
+        self.heap.splice(0..0, vec![0; size as usize]);
+        Ok(())
+    }
+
+    /// Find `len` bytes from `addr`, returning whether they are on the heap and where they start.
+    fn locate(&self, addr: u64, len: u64) -> Result<(bool, usize), Halt> {
+        let end = addr
+            .checked_add(len)
+            .ok_or(panic(PanicReason::MemoryOverflow))?;
+        if end <= self.sp() {
+            Ok((false, addr as usize))
+        } else if addr >= self.hp() && end <= VM_MAX_RAM {
+            Ok((true, (addr - self.hp()) as usize))
+        } else {
+            Err(panic(PanicReason::MemoryOverflow))
+        }
+    }
+
+    fn read(&self, addr: u64, len: u64) -> Result<&[u8], Halt> {
+        let (on_heap, start) = self.locate(addr, len)?;
+        let mem = if on_heap { &self.heap } else { &self.stack };
+        Ok(&mem[start..start + len as usize])
+    }
+
+    fn write(&mut self, addr: u64, bytes: &[u8]) -> Result<(), Halt> {
+        let (on_heap, start) = self.locate(addr, bytes.len() as u64)?;
+        let mem = if on_heap {
+            &mut self.heap
+        } else {
+            &mut self.stack
+        };
+        mem[start..start + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn copy(&mut self, dst: u64, src: u64, len: u64) -> Result<(), Halt> {
+        let bytes = self.read(src, len)?.to_vec();
+        self.write(dst, &bytes)
+    }
+
+    fn read_b256(&self, addr: u64) -> Result<[u8; 32], Halt> {
+        let mut bytes = [0; 32];
+        bytes.copy_from_slice(self.read(addr, 32)?);
+        Ok(bytes)
+    }
+
+    /// Read a copy type value, which is either a byte or a word.
+    fn read_copy_type(&self, addr: u64, size: u64) -> Result<u64, Halt> {
+        Ok(word_from_bytes(self.read(addr, size)?))
+    }
+
+    /// Write a copy type value, which is either a byte or a word.
+    fn write_copy_type(&mut self, addr: u64, size: u64, value: u64) -> Result<(), Halt> {
+        if size == 1 {
+            self.write(addr, &[value as u8])
+        } else {
+            self.write(addr, &value.to_be_bytes())
+        }
+    }
+}
+
+/// The registers which may be used in ASM blocks without being declared.
+enum AsmConstantRegister {
+    Word(u64),
+    Vm(Register),
+}
+
+fn constant_register(name: &str) -> Option<AsmConstantRegister> {
+    Some(match name {
+        "zero" => AsmConstantRegister::Word(0),
+        "one" => AsmConstantRegister::Word(1),
+        "of" => AsmConstantRegister::Vm(Register::Of),
+        "pc" => AsmConstantRegister::Vm(Register::Pc),
+        "ssp" => AsmConstantRegister::Vm(Register::Ssp),
+        "sp" => AsmConstantRegister::Vm(Register::Sp),
+        "fp" => AsmConstantRegister::Vm(Register::Fp),
+        "hp" => AsmConstantRegister::Vm(Register::Hp),
+        "err" => AsmConstantRegister::Vm(Register::Error),
+        "ggas" => AsmConstantRegister::Vm(Register::Ggas),
+        "cgas" => AsmConstantRegister::Vm(Register::Cgas),
+        "bal" => AsmConstantRegister::Vm(Register::Bal),
+        "is" => AsmConstantRegister::Vm(Register::Is),
+        "ret" => AsmConstantRegister::Vm(Register::Ret),
+        "retl" => AsmConstantRegister::Vm(Register::Retl),
+        "flag" => AsmConstantRegister::Vm(Register::Flag),
+        _ => return None,
+    })
+}
+
+fn parse_immediate(imm: &str) -> Result<u64, Halt> {
+    let digits = imm.strip_prefix('i').unwrap_or(imm);
+    match digits.strip_prefix("0x") {
+        Some(hex_digits) => u64::from_str_radix(hex_digits, 16),
+        None => digits.parse(),
+    }
+    .map_err(|_| failure(format!("malformed immediate '{imm}'")))
+}
+
+fn asm_alu_op(op_name: &str, lhs: u64, rhs: u64) -> Result<u64, Halt> {
+    let op = match op_name {
+        "add" => BinaryOpKind::Add,
+        "sub" => BinaryOpKind::Sub,
+        "mul" => BinaryOpKind::Mul,
+        "div" => BinaryOpKind::Div,
+        "mod" => BinaryOpKind::Mod,
+        "and" => BinaryOpKind::And,
+        "or" => BinaryOpKind::Or,
+        "xor" => BinaryOpKind::Xor,
+        "sll" => BinaryOpKind::Lsh,
+        "srl" => BinaryOpKind::Rsh,
+        "exp" => {
+            return u32::try_from(rhs)
+                .ok()
+                .and_then(|rhs| lhs.checked_pow(rhs))
+                .ok_or(panic(PanicReason::ArithmeticOverflow))
+        }
+        "eq" => return Ok((lhs == rhs) as u64),
+        "gt" => return Ok((lhs > rhs) as u64),
+        "lt" => return Ok((lhs < rhs) as u64),
+        _ => unreachable!("not an ALU instruction"),
+    };
+    word_binary_op(op, lhs, rhs).map_err(panic)
+}
+
+fn word_binary_op(op: BinaryOpKind, lhs: u64, rhs: u64) -> Result<u64, PanicReason> {
+    match op {
+        BinaryOpKind::Add => lhs.checked_add(rhs).ok_or(PanicReason::ArithmeticOverflow),
+        BinaryOpKind::Sub => lhs.checked_sub(rhs).ok_or(PanicReason::ArithmeticOverflow),
+        BinaryOpKind::Mul => lhs.checked_mul(rhs).ok_or(PanicReason::ArithmeticOverflow),
+        BinaryOpKind::Div => lhs.checked_div(rhs).ok_or(PanicReason::ArithmeticError),
+        BinaryOpKind::Mod => lhs.checked_rem(rhs).ok_or(PanicReason::ArithmeticError),
+        BinaryOpKind::And => Ok(lhs & rhs),
+        BinaryOpKind::Or => Ok(lhs | rhs),
+        BinaryOpKind::Xor => Ok(lhs ^ rhs),
+        // Shifting by a word or more clears the value, as in the VM.
+        BinaryOpKind::Lsh => Ok(u32::try_from(rhs)
+            .ok()
+            .and_then(|rhs| lhs.checked_shl(rhs))
+            .unwrap_or(0)),
+        BinaryOpKind::Rsh => Ok(u32::try_from(rhs)
+            .ok()
+            .and_then(|rhs| lhs.checked_shr(rhs))
+            .unwrap_or(0)),
+    }
+}
+
+fn shift_left(bytes: &[u8; 32], bits: u64) -> [u8; 32] {
+    let mut shifted = [0; 32];
+    if bits < 256 {
+        let (byte_shift, bit_shift) = ((bits / 8) as usize, bits % 8);
+        for (idx, shifted_byte) in shifted.iter_mut().enumerate().take(32 - byte_shift) {
+            let src = idx + byte_shift;
+            *shifted_byte = bytes[src] << bit_shift;
+            if bit_shift > 0 && src + 1 < 32 {
+                *shifted_byte |= bytes[src + 1] >> (8 - bit_shift);
+            }
+        }
+    }
+    shifted
+}
+
+fn shift_right(bytes: &[u8; 32], bits: u64) -> [u8; 32] {
+    let mut shifted = [0; 32];
+    if bits < 256 {
+        let (byte_shift, bit_shift) = ((bits / 8) as usize, bits % 8);
+        for (idx, shifted_byte) in shifted.iter_mut().enumerate().skip(byte_shift) {
+            let src = idx - byte_shift;
+            *shifted_byte = bytes[src] >> bit_shift;
+            if bit_shift > 0 && src > 0 {
+                *shifted_byte |= bytes[src - 1] << (8 - bit_shift);
+            }
+        }
+    }
+    shifted
+}
+
+/// `(lhs + rhs) % modulus`, for `lhs` and `rhs` less than `modulus`, without overflowing.
+fn add_mod(lhs: &U256, rhs: &U256, modulus: &U256) -> U256 {
+    let rhs_complement = modulus.checked_sub(rhs).unwrap();
+    if lhs >= &rhs_complement {
+        lhs.checked_sub(&rhs_complement).unwrap()
+    } else {
+        lhs.checked_add(rhs).unwrap()
+    }
+}
+
+/// The key of the storage slot `idx` slots after `key`.
+fn nth_slot_key(key: &[u8; 32], idx: u64) -> [u8; 32] {
+    let mut slot_key = *key;
+    let mut carry = idx;
+    for byte in slot_key.iter_mut().rev() {
+        if carry == 0 {
+            break;
+        }
+        let sum = *byte as u64 + (carry & 0xff);
+        *byte = sum as u8;
+        carry = (carry >> 8) + (sum >> 8);
+    }
+    slot_key
+}
+
+fn slot_addr(base: u64, idx: u64) -> Result<u64, Halt> {
+    offset_addr(base, idx, 32)
+}
+
+fn offset_addr(base: u64, idx: u64, elem_size: u64) -> Result<u64, Halt> {
+    idx.checked_mul(elem_size)
+        .and_then(|offset| base.checked_add(offset))
+        .ok_or(panic(PanicReason::MemoryOverflow))
+}
+
+fn compare(pred: Predicate, ordering: std::cmp::Ordering) -> u64 {
+    (match pred {
+        Predicate::Equal => ordering.is_eq(),
+        Predicate::LessThan => ordering.is_lt(),
+        Predicate::GreaterThan => ordering.is_gt(),
+    }) as u64
+}
+
+fn word_from_bytes(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(0, |word, byte| (word << 8) | *byte as u64)
+}
+
+/// Copy types are held by value, all others by the address of their bytes.
+fn is_copy_type(context: &Context, ty: Type) -> bool {
+    match ty.get_content(context) {
+        TypeContent::Never | TypeContent::Unit | TypeContent::Bool | TypeContent::Pointer(_) => {
+            true
+        }
+        TypeContent::Uint(width) => *width <= 64,
+        _ => false,
+    }
+}
+
+fn value_type(context: &Context, val: &Value) -> Result<Type, Halt> {
+    val.get_type(context)
+        .ok_or_else(|| failure("value without a type"))
+}
+
+fn pointee_size(context: &Context, ptr_val: &Value) -> Result<u64, Halt> {
+    value_type(context, ptr_val)?
+        .get_pointee_type(context)
+        .map(|ty| ty.size(context).in_bytes())
+        .ok_or_else(|| failure("memory access through a non-pointer"))
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    write!(f, "0x")?;
+    for byte in bytes {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
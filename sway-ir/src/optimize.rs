@@ -23,6 +23,8 @@ pub mod dce;
 pub use dce::*;
 pub mod inline;
 pub use inline::*;
+pub mod licm;
+pub use licm::*;
 pub mod mem2reg;
 pub use mem2reg::*;
 pub mod memcpyopt;
@@ -31,12 +33,16 @@ pub mod misc_demotion;
 pub use misc_demotion::*;
 pub mod ret_demotion;
 pub use ret_demotion::*;
+pub mod sccp;
+pub use sccp::*;
 pub mod simplify_cfg;
 pub use simplify_cfg::*;
 pub mod sroa;
 pub use sroa::*;
 pub mod fn_dedup;
 pub use fn_dedup::*;
+pub mod gvn;
+pub use gvn::*;
 
 mod target_fuel;
 
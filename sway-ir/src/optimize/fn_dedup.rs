@@ -12,8 +12,9 @@ use std::hash::{Hash, Hasher};
 use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 
 use crate::{
-    build_call_graph, callee_first_order, AnalysisResults, Block, Context, Function, InstOp,
-    Instruction, IrError, Module, Pass, PassMutability, ScopedPass, Value,
+    build_call_graph, callee_first_order, AnalysisResults, Block, Constant, ConstantValue, Context,
+    Function, InstOp, Instruction, IrError, LocalVar, Module, Pass, PassMutability, ScopedPass,
+    Type, TypeContent, Value,
 };
 
 pub const FNDEDUP_NAME: &str = "fndedup";
@@ -27,6 +28,17 @@ pub fn create_fn_dedup_pass() -> Pass {
     }
 }
 
+pub const FNDEDUP_AGGRESSIVE_NAME: &str = "fndedup_aggressive";
+
+pub fn create_fn_dedup_aggressive_pass() -> Pass {
+    Pass {
+        name: FNDEDUP_AGGRESSIVE_NAME,
+        descr: "Deduplicate functions, ignoring the names of locals and ASM block arguments.",
+        deps: vec![],
+        runner: ScopedPass::ModulePass(PassMutability::Transform(dedup_fns_aggressive)),
+    }
+}
+
 // Functions that are equivalent are put in the same set.
 struct EqClass {
     // Map a function hash to its equivalence class.
@@ -35,15 +47,62 @@ struct EqClass {
     function_hash_map: FxHashMap<Function, u64>,
 }
 
-fn hash_fn(context: &Context, function: Function, eq_class: &mut EqClass) -> u64 {
+// Types and constants are hashed by their content rather than by their index in the context, so
+// that the hash of a function doesn't depend on the order in which the IR was built.
+fn hash_type(context: &Context, ty: Type, state: &mut FxHasher) {
+    let content = ty.get_content(context);
+    std::mem::discriminant(content).hash(state);
+    match content {
+        TypeContent::Array(elem_ty, len) => {
+            hash_type(context, *elem_ty, state);
+            len.hash(state);
+        }
+        TypeContent::Union(field_tys) | TypeContent::Struct(field_tys) => {
+            field_tys.len().hash(state);
+            for field_ty in field_tys {
+                hash_type(context, *field_ty, state);
+            }
+        }
+        TypeContent::Pointer(pointee_ty) => hash_type(context, *pointee_ty, state),
+        TypeContent::Uint(nbits) => nbits.hash(state),
+        TypeContent::StringArray(len) => len.hash(state),
+        TypeContent::Never
+        | TypeContent::Unit
+        | TypeContent::Bool
+        | TypeContent::B256
+        | TypeContent::StringSlice
+        | TypeContent::Slice => (),
+    }
+}
+
+fn hash_constant(context: &Context, constant: &Constant, state: &mut FxHasher) {
+    hash_type(context, constant.ty, state);
+    match &constant.value {
+        ConstantValue::Array(elems) | ConstantValue::Struct(elems) => {
+            elems.len().hash(state);
+            for elem in elems {
+                hash_constant(context, elem, state);
+            }
+        }
+        ConstantValue::Reference(pointee) => hash_constant(context, pointee, state),
+        value => value.hash(state),
+    }
+}
+
+fn hash_fn(
+    context: &Context,
+    function: Function,
+    eq_class: &mut EqClass,
+    localise_names: bool,
+) -> u64 {
     let state = &mut FxHasher::default();
 
     // A unique, but only in this function, ID for values.
     let localised_value_id: &mut FxHashMap<Value, u64> = &mut FxHashMap::default();
     // A unique, but only in this function, ID for blocks.
     let localised_block_id: &mut FxHashMap<Block, u64> = &mut FxHashMap::default();
-    // TODO: We could do a similar localised ID'ing of local variable names
-    // and ASM block arguments too, thereby slightly relaxing the equality check.
+    // When `localise_names` is set, a unique, but only in this function, ID for local variables.
+    let localised_local_id: &mut FxHashMap<LocalVar, u64> = &mut FxHashMap::default();
 
     fn get_localised_id<T: Eq + Hash>(t: T, map: &mut FxHashMap<T, u64>) -> u64 {
         let cur_count = map.len();
@@ -60,20 +119,25 @@ fn hash_fn(context: &Context, function: Function, eq_class: &mut EqClass) -> u64
             crate::ValueDatum::Argument(_) | crate::ValueDatum::Instruction(_) => {
                 get_localised_id(v, localised_value_id).hash(hasher)
             }
-            crate::ValueDatum::Configurable(c) | crate::ValueDatum::Constant(c) => c.hash(hasher),
+            crate::ValueDatum::Configurable(c) | crate::ValueDatum::Constant(c) => {
+                hash_constant(context, c, hasher)
+            }
         }
     }
 
     // Start with the function return type.
-    function.get_return_type(context).hash(state);
+    hash_type(context, function.get_return_type(context), state);
 
-    // ... and local variables.
-    for (local_name, local_var) in function.locals_iter(context) {
-        local_name.hash(state);
-        if let Some(init) = local_var.get_initializer(context) {
-            init.hash(state);
+    // ... and local variables.  Localised locals are instead hashed where they're first used, as
+    // the locals are iterated in the order of their names.
+    if !localise_names {
+        for (local_name, local_var) in function.locals_iter(context) {
+            local_name.hash(state);
+            if let Some(init) = local_var.get_initializer(context) {
+                hash_constant(context, init, state);
+            }
+            hash_type(context, local_var.get_type(context), state);
         }
-        local_var.get_type(context).hash(state);
     }
 
     // Process every block, first its arguments and then the instructions.
@@ -81,7 +145,7 @@ fn hash_fn(context: &Context, function: Function, eq_class: &mut EqClass) -> u64
         get_localised_id(block, localised_block_id).hash(state);
         for &arg in block.arg_iter(context) {
             get_localised_id(arg, localised_value_id).hash(state);
-            arg.get_argument(context).unwrap().ty.hash(state);
+            hash_type(context, arg.get_argument(context).unwrap().ty, state);
         }
         for inst in block.instruction_iter(context) {
             get_localised_id(inst, localised_value_id).hash(state);
@@ -94,21 +158,34 @@ fn hash_fn(context: &Context, function: Function, eq_class: &mut EqClass) -> u64
             // Hash non-value inputs.
             match &inst.op {
                 crate::InstOp::AsmBlock(asm_block, args) => {
+                    // The declared arguments are local to the ASM block, so when localising names
+                    // they're identified by their position.  Any other names are registers.
+                    let localised_arg_id = args
+                        .iter()
+                        .enumerate()
+                        .filter(|_| localise_names)
+                        .map(|(idx, arg)| (arg.name.as_str(), idx))
+                        .collect::<FxHashMap<_, _>>();
+                    let hash_name =
+                        |name: &str, state: &mut FxHasher| match localised_arg_id.get(name) {
+                            Some(idx) => idx.hash(state),
+                            None => name.hash(state),
+                        };
                     for arg in args
                         .iter()
                         .map(|arg| &arg.name)
                         .chain(asm_block.args_names.iter())
                     {
-                        arg.as_str().hash(state);
+                        hash_name(arg.as_str(), state);
                     }
                     if let Some(return_name) = &asm_block.return_name {
-                        return_name.as_str().hash(state);
+                        hash_name(return_name.as_str(), state);
                     }
-                    asm_block.return_type.hash(state);
+                    hash_type(context, asm_block.return_type, state);
                     for asm_inst in &asm_block.body {
                         asm_inst.op_name.as_str().hash(state);
                         for arg in &asm_inst.args {
-                            arg.as_str().hash(state);
+                            hash_name(arg.as_str(), state);
                         }
                         if let Some(imm) = &asm_inst.immediate {
                             imm.as_str().hash(state);
@@ -117,7 +194,7 @@ fn hash_fn(context: &Context, function: Function, eq_class: &mut EqClass) -> u64
                 }
                 crate::InstOp::UnaryOp { op, .. } => op.hash(state),
                 crate::InstOp::BinaryOp { op, .. } => op.hash(state),
-                crate::InstOp::BitCast(_, ty) => ty.hash(state),
+                crate::InstOp::BitCast(_, ty) => hash_type(context, *ty, state),
                 crate::InstOp::Branch(b) => {
                     get_localised_id(b.block, localised_block_id).hash(state)
                 }
@@ -133,7 +210,7 @@ fn hash_fn(context: &Context, function: Function, eq_class: &mut EqClass) -> u64
                         }
                     }
                 }
-                crate::InstOp::CastPtr(_, ty) => ty.hash(state),
+                crate::InstOp::CastPtr(_, ty) => hash_type(context, *ty, state),
                 crate::InstOp::Cmp(p, _, _) => p.hash(state),
                 crate::InstOp::ConditionalBranch {
                     cond_value: _,
@@ -148,10 +225,13 @@ fn hash_fn(context: &Context, function: Function, eq_class: &mut EqClass) -> u64
                 }
                 crate::InstOp::FuelVm(fuel_vm_inst) => match fuel_vm_inst {
                     crate::FuelVmInstruction::Gtf { tx_field_id, .. } => tx_field_id.hash(state),
-                    crate::FuelVmInstruction::Log { log_ty, .. } => log_ty.hash(state),
+                    crate::FuelVmInstruction::Log { log_ty, .. } => {
+                        hash_type(context, *log_ty, state)
+                    }
                     crate::FuelVmInstruction::ReadRegister(reg) => reg.hash(state),
                     crate::FuelVmInstruction::Revert(_)
                     | crate::FuelVmInstruction::JmpMem
+                    | crate::FuelVmInstruction::Retd { .. }
                     | crate::FuelVmInstruction::Smo { .. }
                     | crate::FuelVmInstruction::StateClear { .. }
                     | crate::FuelVmInstruction::StateLoadQuadWord { .. }
@@ -162,22 +242,30 @@ fn hash_fn(context: &Context, function: Function, eq_class: &mut EqClass) -> u64
                     crate::FuelVmInstruction::WideBinaryOp { op, .. } => op.hash(state),
                     crate::FuelVmInstruction::WideModularOp { op, .. } => op.hash(state),
                     crate::FuelVmInstruction::WideCmpOp { op, .. } => op.hash(state),
-                    crate::FuelVmInstruction::Retd { ptr, len } => {
-                        ptr.hash(state);
-                        len.hash(state);
-                    }
                 },
+                crate::InstOp::GetLocal(local) if localise_names => {
+                    let known = localised_local_id.contains_key(local);
+                    get_localised_id(*local, localised_local_id).hash(state);
+                    if !known {
+                        if let Some(init) = local.get_initializer(context) {
+                            hash_constant(context, init, state);
+                        }
+                        hash_type(context, local.get_type(context), state);
+                    }
+                }
                 crate::InstOp::GetLocal(local) => function
                     .lookup_local_name(context, local)
                     .unwrap()
                     .hash(state),
-                crate::InstOp::GetElemPtr { elem_ptr_ty, .. } => elem_ptr_ty.hash(state),
-                crate::InstOp::IntToPtr(_, ty) => ty.hash(state),
+                crate::InstOp::GetElemPtr { elem_ptr_ty, .. } => {
+                    hash_type(context, *elem_ptr_ty, state)
+                }
+                crate::InstOp::IntToPtr(_, ty) => hash_type(context, *ty, state),
                 crate::InstOp::Load(_) => (),
                 crate::InstOp::MemCopyBytes { byte_len, .. } => byte_len.hash(state),
                 crate::InstOp::MemCopyVal { .. } | crate::InstOp::Nop => (),
-                crate::InstOp::PtrToInt(_, ty) => ty.hash(state),
-                crate::InstOp::Ret(_, ty) => ty.hash(state),
+                crate::InstOp::PtrToInt(_, ty) => hash_type(context, *ty, state),
+                crate::InstOp::Ret(_, ty) => hash_type(context, *ty, state),
                 crate::InstOp::Store { .. } => (),
             }
         }
@@ -190,6 +278,43 @@ pub fn dedup_fns(
     context: &mut Context,
     _: &AnalysisResults,
     module: Module,
+) -> Result<bool, IrError> {
+    dedup_fns_in_module(context, module, false)
+}
+
+/// Like [dedup_fns] but functions which differ only in the names of their local variables or of
+/// their ASM block arguments are also considered equal.
+pub fn dedup_fns_aggressive(
+    context: &mut Context,
+    _: &AnalysisResults,
+    module: Module,
+) -> Result<bool, IrError> {
+    dedup_fns_in_module(context, module, true)
+}
+
+/// Fingerprint every function in `module`.
+///
+/// The fingerprint of a function covers its own body and those of the functions it calls,
+/// directly or not, but not the names of its locals and ASM block arguments, nor the order in
+/// which the IR was built.  Comparing fingerprints across compilations tells whether the code a
+/// function runs may have changed.
+pub fn fingerprint_fns(context: &Context, module: Module) -> FxHashMap<Function, u64> {
+    let eq_class = &mut EqClass {
+        hash_set_map: FxHashMap::default(),
+        function_hash_map: FxHashMap::default(),
+    };
+    let cg = build_call_graph(context, &context.modules.get(module.0).unwrap().functions);
+    for function in callee_first_order(&cg) {
+        let hash = hash_fn(context, function, eq_class, true);
+        eq_class.function_hash_map.insert(function, hash);
+    }
+    std::mem::take(&mut eq_class.function_hash_map)
+}
+
+fn dedup_fns_in_module(
+    context: &mut Context,
+    module: Module,
+    localise_names: bool,
 ) -> Result<bool, IrError> {
     let mut modified = false;
     let eq_class = &mut EqClass {
@@ -199,7 +324,7 @@ pub fn dedup_fns(
     let cg = build_call_graph(context, &context.modules.get(module.0).unwrap().functions);
     let callee_first = callee_first_order(&cg);
     for function in callee_first {
-        let hash = hash_fn(context, function, eq_class);
+        let hash = hash_fn(context, function, eq_class, localise_names);
         eq_class
             .hash_set_map
             .entry(hash)
@@ -0,0 +1,242 @@
+//! ## Global Value Numbering
+//!
+//! This optimization removes redundant pure computations.  The dominator tree is walked in
+//! preorder and every pure instruction is given a key made of its opcode and the value numbers of
+//! its operands.  An instruction whose key is already available from a dominating instruction is
+//! replaced with that instruction.
+//!
+//! Loads are also eliminated when they read from the same pointer as an earlier load and nothing
+//! in between may have written to that memory.  This is only tracked within a block and into
+//! successors whose only predecessor is that block.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use sway_utils::mapped_stack::MappedStack;
+
+use crate::{
+    get_traced_symbol, memory_utils, AnalysisResults, BinaryOpKind, Block, Context, DomTree,
+    EscapedSymbols, Function, InstOp, IrError, LocalVar, Pass, PassMutability, Predicate,
+    ScopedPass, Symbol, Type, UnaryOpKind, Value, ValueDatum, DOMINATORS_NAME,
+    ESCAPED_SYMBOLS_NAME,
+};
+
+pub const GVN_NAME: &str = "gvn";
+
+pub fn create_gvn_pass() -> Pass {
+    Pass {
+        name: GVN_NAME,
+        descr: "Global value numbering / common subexpression elimination.",
+        deps: vec![DOMINATORS_NAME, ESCAPED_SYMBOLS_NAME],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(global_value_numbering)),
+    }
+}
+
+/// A pure computation, with its operands replaced by their value numbers.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Expr {
+    UnaryOp(UnaryOpKind, Value),
+    BinaryOp(BinaryOpKind, Value, Value),
+    BitCast(Value, Type),
+    CastPtr(Value, Type),
+    Cmp(Predicate, Value, Value),
+    GetLocal(LocalVar),
+    GetElemPtr(Value, Type, Vec<Value>),
+    IntToPtr(Value, Type),
+    PtrToInt(Value, Type),
+}
+
+struct ValueNumbering<'a> {
+    dom_tree: &'a DomTree,
+    function: Function,
+    exposed_symbols: FxHashSet<Symbol>,
+    /// The equal constants, indexed by the hash of their content.
+    constants: FxHashMap<u64, Vec<Value>>,
+    /// The value number of the constants.
+    constant_numbers: FxHashMap<Value, Value>,
+    /// The value number of the redundant values, i.e., the value replacing them.
+    numbers: FxHashMap<Value, Value>,
+    /// The available pure computations.
+    available: MappedStack<Expr, Value>,
+    /// The redundant instructions to delete.
+    deletes: Vec<(Block, Value)>,
+}
+
+/// Remove the instructions which recompute a value which is already available, and return true if
+/// the function was modified.
+pub fn global_value_numbering(
+    context: &mut Context,
+    analyses: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let dom_tree: &DomTree = analyses.get_analysis_result(function);
+    let escaped_symbols: &EscapedSymbols = analyses.get_analysis_result(function);
+
+    let mut vn = ValueNumbering {
+        dom_tree,
+        function,
+        exposed_symbols: memory_utils::compute_exposed_symbols(context, function, escaped_symbols),
+        constants: FxHashMap::default(),
+        constant_numbers: FxHashMap::default(),
+        numbers: FxHashMap::default(),
+        available: MappedStack::default(),
+        deletes: Vec::new(),
+    };
+    vn.visit_block(
+        context,
+        function.get_entry_block(context),
+        FxHashMap::default(),
+    );
+
+    if vn.deletes.is_empty() {
+        return Ok(false);
+    }
+
+    let ValueNumbering {
+        numbers, deletes, ..
+    } = vn;
+    function.replace_values(context, &numbers, None);
+    for (block, inst) in deletes {
+        block.remove_instruction(context, inst);
+    }
+
+    Ok(true)
+}
+
+impl<'a> ValueNumbering<'a> {
+    /// Number the instructions of `block` and then of the blocks it dominates.  `loads` maps the
+    /// pointers which were loaded from, and whose memory is unchanged since, to those loads.
+    fn visit_block(&mut self, context: &Context, block: Block, mut loads: FxHashMap<Value, Value>) {
+        let mut pushed = Vec::new();
+        for inst in block.instruction_iter(context) {
+            if let Some(expr) = self.expr(context, inst) {
+                match self.available.get(&expr) {
+                    Some(available) => {
+                        self.numbers.insert(inst, *available);
+                        self.deletes.push((block, inst));
+                    }
+                    None => {
+                        self.available.push(expr.clone(), inst);
+                        pushed.push(expr);
+                    }
+                }
+                continue;
+            }
+
+            if let InstOp::Load(ptr) = inst.get_instruction(context).unwrap().op {
+                let ptr = self.number(context, ptr);
+                match loads.get(&ptr) {
+                    Some(available) => {
+                        self.numbers.insert(inst, *available);
+                        self.deletes.push((block, inst));
+                    }
+                    None if get_traced_symbol(context, self.function, ptr).is_some() => {
+                        loads.insert(ptr, inst);
+                    }
+                    None => (),
+                }
+                continue;
+            }
+
+            let write = memory_utils::get_memory_write(context, self.function, inst);
+            loads.retain(|ptr, _| {
+                let symbol = get_traced_symbol(context, self.function, *ptr).unwrap();
+                !write.may_write(&symbol, &self.exposed_symbols)
+            });
+        }
+
+        for child in self.dom_tree[&block].children.clone() {
+            // The loads remain available only if there is no other way into the child.
+            let child_loads = if child.num_predecessors(context) == 1 {
+                loads.clone()
+            } else {
+                FxHashMap::default()
+            };
+            self.visit_block(context, child, child_loads);
+        }
+
+        for expr in pushed {
+            self.available.pop(&expr);
+        }
+    }
+
+    /// The value number of `value`, i.e., the first equivalent value found.
+    fn number(&mut self, context: &Context, value: Value) -> Value {
+        if let Some(number) = self
+            .numbers
+            .get(&value)
+            .or_else(|| self.constant_numbers.get(&value))
+        {
+            return *number;
+        }
+        let ValueDatum::Constant(constant) = &context.values[value.0].value else {
+            return value;
+        };
+
+        // Equal constants are distinct values, give them the same number.
+        use std::hash::{Hash, Hasher};
+        let mut state = rustc_hash::FxHasher::default();
+        constant.hash(&mut state);
+        let equal_constants = self.constants.entry(state.finish()).or_default();
+        let number = equal_constants
+            .iter()
+            .find(|other| {
+                let ValueDatum::Constant(other) = &context.values[other.0].value else {
+                    unreachable!("Only constants are numbered by their content.")
+                };
+                constant.eq(context, other)
+            })
+            .copied()
+            .unwrap_or_else(|| {
+                equal_constants.push(value);
+                value
+            });
+        self.constant_numbers.insert(value, number);
+        number
+    }
+
+    /// The key of `inst` if it is a pure computation.
+    fn expr(&mut self, context: &Context, inst: Value) -> Option<Expr> {
+        let expr = match &inst.get_instruction(context).unwrap().op {
+            InstOp::UnaryOp { op, arg } => Expr::UnaryOp(*op, self.number(context, *arg)),
+            InstOp::BinaryOp { op, arg1, arg2 } => {
+                let mut arg1 = self.number(context, *arg1);
+                let mut arg2 = self.number(context, *arg2);
+                let is_commutative = matches!(
+                    op,
+                    BinaryOpKind::Add
+                        | BinaryOpKind::Mul
+                        | BinaryOpKind::And
+                        | BinaryOpKind::Or
+                        | BinaryOpKind::Xor
+                );
+                if is_commutative && arg2.0 < arg1.0 {
+                    std::mem::swap(&mut arg1, &mut arg2);
+                }
+                Expr::BinaryOp(*op, arg1, arg2)
+            }
+            InstOp::BitCast(value, ty) => Expr::BitCast(self.number(context, *value), *ty),
+            InstOp::CastPtr(value, ty) => Expr::CastPtr(self.number(context, *value), *ty),
+            InstOp::Cmp(pred, lhs, rhs) => Expr::Cmp(
+                *pred,
+                self.number(context, *lhs),
+                self.number(context, *rhs),
+            ),
+            InstOp::GetLocal(local) => Expr::GetLocal(*local),
+            InstOp::GetElemPtr {
+                base,
+                elem_ptr_ty,
+                indices,
+            } => Expr::GetElemPtr(
+                self.number(context, *base),
+                *elem_ptr_ty,
+                indices
+                    .iter()
+                    .map(|idx| self.number(context, *idx))
+                    .collect(),
+            ),
+            InstOp::IntToPtr(value, ty) => Expr::IntToPtr(self.number(context, *value), *ty),
+            InstOp::PtrToInt(value, ty) => Expr::PtrToInt(self.number(context, *value), *ty),
+            _otherwise => return None,
+        };
+        Some(expr)
+    }
+}
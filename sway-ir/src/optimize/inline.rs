@@ -43,6 +43,17 @@ pub fn create_inline_in_module_pass() -> Pass {
     }
 }
 
+pub const INLINE_MODULE_SIZE_NAME: &str = "inline_module_size";
+
+pub fn create_inline_in_module_for_size_pass() -> Pass {
+    Pass {
+        name: INLINE_MODULE_SIZE_NAME,
+        descr: "inline function calls in a module, only where it reduces the code size.",
+        deps: vec![],
+        runner: ScopedPass::ModulePass(PassMutability::Transform(inline_in_module_for_size)),
+    }
+}
+
 /// This is a copy of sway_core::inline::Inline.
 /// TODO: Reuse: Depend on sway_core? Move it to sway_types?
 #[derive(Debug)]
@@ -83,30 +94,62 @@ fn metadata_to_inline(context: &Context, md_idx: Option<MetadataIndex>) -> Optio
     })
 }
 
+/// Count how often each function in the module is called.
+fn count_calls(context: &Context, module: Module) -> HashMap<Function, u64> {
+    module
+        .function_iter(context)
+        .fold(HashMap::new(), |mut counts, func| {
+            for (_block, ins) in func.instruction_iter(context) {
+                if let Some(Instruction {
+                    op: InstOp::Call(callee, _args),
+                    ..
+                }) = ins.get_instruction(context)
+                {
+                    counts
+                        .entry(*callee)
+                        .and_modify(|count| *count += 1)
+                        .or_insert(1);
+                }
+            }
+            counts
+        })
+}
+
+/// Whether inlining is required, regardless of the heuristic.
+///
+/// See https://github.com/FuelLabs/sway/pull/4899
+fn must_inline(ctx: &Context, func: &Function) -> bool {
+    func.args_iter(ctx).any(|(_name, arg_val)| {
+        arg_val.get_type(ctx).is_some_and(|ty| {
+            ty.is_ptr(ctx) || !(ty.is_unit(ctx) | ty.is_bool(ctx) | ty.is_uint(ctx))
+        })
+    })
+}
+
+/// Inline the calls to the functions in the module, callees first, which pass the heuristic.
+fn inline_in_module_with<F: Fn(&Context, &Function, &Value) -> bool>(
+    context: &mut Context,
+    module: Module,
+    inline_heuristic: F,
+) -> Result<bool, IrError> {
+    let cg =
+        call_graph::build_call_graph(context, &module.function_iter(context).collect::<Vec<_>>());
+    let functions = call_graph::callee_first_order(&cg);
+    let mut modified = false;
+
+    for function in functions {
+        modified |= inline_some_function_calls(context, &function, &inline_heuristic)?;
+    }
+    Ok(modified)
+}
+
 pub fn inline_in_module(
     context: &mut Context,
     _: &AnalysisResults,
     module: Module,
 ) -> Result<bool, IrError> {
     // Inspect ALL calls and count how often each function is called.
-    let call_counts: HashMap<Function, u64> =
-        module
-            .function_iter(context)
-            .fold(HashMap::new(), |mut counts, func| {
-                for (_block, ins) in func.instruction_iter(context) {
-                    if let Some(Instruction {
-                        op: InstOp::Call(callee, _args),
-                        ..
-                    }) = ins.get_instruction(context)
-                    {
-                        counts
-                            .entry(*callee)
-                            .and_modify(|count| *count += 1)
-                            .or_insert(1);
-                    }
-                }
-                counts
-            });
+    let call_counts = count_calls(context, module);
 
     let inline_heuristic = |ctx: &Context, func: &Function, _call_site: &Value| {
         let attributed_inline = metadata_to_inline(ctx, func.get_metadata(ctx));
@@ -132,27 +175,53 @@ pub fn inline_in_module(
             return true;
         }
 
-        // See https://github.com/FuelLabs/sway/pull/4899
-        if func.args_iter(ctx).any(|(_name, arg_val)| {
-            arg_val.get_type(ctx).map_or(false, |ty| {
-                ty.is_ptr(ctx) || !(ty.is_unit(ctx) | ty.is_bool(ctx) | ty.is_uint(ctx))
-            })
-        }) {
+        must_inline(ctx, func)
+    };
+
+    inline_in_module_with(context, module, inline_heuristic)
+}
+
+/// Inline function calls in a module only where it's expected to reduce the code size.
+///
+/// A function is inlined into all of its callers when its copies are expected to be no larger
+/// than the calls to it plus the function itself, as the function is then removed by DCE.  The
+/// sizes are estimated by counting IR instructions.
+pub fn inline_in_module_for_size(
+    context: &mut Context,
+    _: &AnalysisResults,
+    module: Module,
+) -> Result<bool, IrError> {
+    // The approximate cost of a call, on top of passing its arguments: setting the return address,
+    // the jump and copying the return value.
+    const CALL_OVERHEAD: usize = 3;
+    // The approximate cost of a function's prologue and epilogue, saving and restoring registers
+    // and the return address.
+    const FUNCTION_OVERHEAD: usize = 8;
+
+    let call_counts = count_calls(context, module);
+
+    let inline_heuristic = |ctx: &Context, func: &Function, _call_site: &Value| {
+        if let Some(Inline::Never) = metadata_to_inline(ctx, func.get_metadata(ctx)) {
+            return false;
+        }
+
+        let num_calls = call_counts.get(func).copied().unwrap_or(0) as usize;
+        if num_calls == 1 {
             return true;
         }
 
-        false
-    };
+        let inlined_size = num_calls * func.num_instructions(ctx);
+        let called_size = num_calls * (func.num_args(ctx) + CALL_OVERHEAD)
+            + func.num_instructions(ctx)
+            + FUNCTION_OVERHEAD;
+        if inlined_size <= called_size {
+            return true;
+        }
 
-    let cg =
-        call_graph::build_call_graph(context, &module.function_iter(context).collect::<Vec<_>>());
-    let functions = call_graph::callee_first_order(&cg);
-    let mut modified = false;
+        must_inline(ctx, func)
+    };
 
-    for function in functions {
-        modified |= inline_some_function_calls(context, &function, inline_heuristic)?;
-    }
-    Ok(modified)
+    inline_in_module_with(context, module, inline_heuristic)
 }
 
 pub fn inline_in_main(
@@ -0,0 +1,318 @@
+//! ## Loop Invariant Code Motion
+//!
+//! This optimization moves the instructions which compute the same value in every iteration of a
+//! loop to its preheader, a block which is executed once before entering the loop.  The loops are
+//! processed from the innermost, so that an instruction may be moved out of several nested loops.
+//!
+//! An instruction is invariant when its operands are defined outside of the loop.  The invariant
+//! instructions which are moved are:
+//!   1. The pure instructions which can't fail, from anywhere in the loop.
+//!   2. The arithmetic, which fails on overflows and divisions by zero, only from the header and
+//!      before anything with side effects.  The header is executed whenever the loop is entered,
+//!      but the rest of the loop may not be.
+//!   3. The loads from locals or arguments which nothing in the loop may write to.
+//!   4. The ASM blocks without side effects, e.g. those hashing the storage keys, with the same
+//!      restrictions as the arithmetic if they may fail.  Nothing in the loop may write to the
+//!      locals they read, and nothing else may access the local they write to before them.
+
+use rustc_hash::FxHashSet;
+use sway_types::FxIndexSet;
+
+use crate::{
+    get_traced_symbol, memory_utils, AnalysisResults, BinaryOpKind, Block, Context, EscapedSymbols,
+    Function, InsertionPosition, InstOp, Instruction, InstructionInserter, IrError, Loop, Loops,
+    Pass, PassMutability, ScopedPass, Symbol, Value, ValueDatum, ESCAPED_SYMBOLS_NAME, LOOPS_NAME,
+};
+
+pub const LICM_NAME: &str = "licm";
+
+pub fn create_licm_pass() -> Pass {
+    Pass {
+        name: LICM_NAME,
+        descr: "Loop invariant code motion.",
+        deps: vec![LOOPS_NAME, ESCAPED_SYMBOLS_NAME],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(hoist_loop_invariants)),
+    }
+}
+
+/// Move the loop invariant instructions out of the loops and return true if the function was
+/// modified.
+pub fn hoist_loop_invariants(
+    context: &mut Context,
+    analyses: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    // The preheaders we create are added to the enclosing loops, so we need our own copy.
+    let mut loops: Loops = analyses.get_analysis_result::<Loops, _>(function).clone();
+    let escaped_symbols: &EscapedSymbols = analyses.get_analysis_result(function);
+    let exposed_symbols = memory_utils::compute_exposed_symbols(context, function, escaped_symbols);
+
+    let mut modified = false;
+    for idx in 0..loops.0.len() {
+        // A loop back to the entry block can't have a preheader.
+        if loops.0[idx].header == function.get_entry_block(context) {
+            continue;
+        }
+
+        let invariants = find_invariants(context, function, &loops, idx, &exposed_symbols);
+        if invariants.is_empty() {
+            continue;
+        }
+
+        let preheader = get_or_create_preheader(context, function, &mut loops, idx)?;
+        for inst in invariants {
+            let block = inst.get_instruction(context).unwrap().parent;
+            block.remove_instruction(context, inst);
+            inst.get_instruction_mut(context).unwrap().parent = preheader;
+            let terminator_pos = preheader.num_instructions(context) - 1;
+            InstructionInserter::new(context, preheader, InsertionPosition::At(terminator_pos))
+                .insert(inst);
+        }
+        modified = true;
+    }
+
+    Ok(modified)
+}
+
+/// The invariant instructions of the loop which may be moved to its preheader, in the order in
+/// which they must be inserted there.
+fn find_invariants(
+    context: &Context,
+    function: Function,
+    loops: &Loops,
+    idx: usize,
+    exposed_symbols: &FxHashSet<Symbol>,
+) -> Vec<Value> {
+    let lp = &loops.0[idx];
+
+    let insts = lp
+        .blocks
+        .iter()
+        .flat_map(|block| block.instruction_iter(context))
+        .collect::<Vec<_>>();
+    let writes = insts
+        .iter()
+        .map(|inst| memory_utils::get_memory_write(context, function, *inst))
+        .collect::<Vec<_>>();
+    let may_write = |symbol: &Symbol| {
+        writes
+            .iter()
+            .any(|write| write.may_write(symbol, exposed_symbols))
+    };
+
+    let mut invariants = Vec::new();
+    let mut invariants_set = FxHashSet::default();
+    loop {
+        let num_invariants = invariants.len();
+        for block in &lp.blocks {
+            let mut is_before_side_effects = *block == lp.header;
+            for inst in block.instruction_iter(context) {
+                if invariants_set.contains(&inst) {
+                    continue;
+                }
+
+                let op = &inst.get_instruction(context).unwrap().op;
+                let asm_memory = memory_utils::get_asm_block_memory(context, function, inst);
+                let may_fail = matches!(
+                    op,
+                    InstOp::BinaryOp {
+                        op: BinaryOpKind::Add
+                            | BinaryOpKind::Sub
+                            | BinaryOpKind::Mul
+                            | BinaryOpKind::Div
+                            | BinaryOpKind::Mod,
+                        ..
+                    }
+                ) || asm_memory.as_ref().is_some_and(|memory| memory.may_fail);
+                let may_move = match op {
+                    InstOp::BinaryOp { .. } if may_fail => is_before_side_effects,
+                    InstOp::UnaryOp { .. }
+                    | InstOp::BinaryOp { .. }
+                    | InstOp::BitCast(..)
+                    | InstOp::CastPtr(..)
+                    | InstOp::Cmp(..)
+                    | InstOp::GetLocal(_)
+                    | InstOp::GetElemPtr { .. }
+                    | InstOp::IntToPtr(..)
+                    | InstOp::PtrToInt(..) => true,
+                    InstOp::Load(ptr) => get_traced_symbol(context, function, *ptr)
+                        .is_some_and(|symbol| !may_write(&symbol)),
+                    InstOp::AsmBlock(..) => asm_memory.is_some_and(|memory| {
+                        (!memory.may_fail || is_before_side_effects)
+                            && !memory.reads.iter().any(&may_write)
+                            && memory.write.iter().all(|symbol| {
+                                may_move_write(
+                                    context,
+                                    function,
+                                    lp,
+                                    (&insts, &writes),
+                                    inst,
+                                    symbol,
+                                    exposed_symbols,
+                                )
+                            })
+                    }),
+                    _otherwise => false,
+                };
+
+                let is_invariant = may_move
+                    && op.get_operands().iter().all(|opd| {
+                        invariants_set.contains(opd) || !is_defined_in(context, &lp.blocks, *opd)
+                    });
+                if is_invariant {
+                    invariants.push(inst);
+                    invariants_set.insert(inst);
+                } else if may_fail || op.may_have_side_effect() {
+                    is_before_side_effects = false;
+                }
+            }
+        }
+
+        // Moving an instruction may make those using it invariant too.
+        if invariants.len() == num_invariants {
+            break;
+        }
+    }
+
+    invariants
+}
+
+/// Whether the write of the ASM block `asm` to the local `symbol` may be moved to the preheader.
+///
+/// Nothing else in the loop may write to the local.  The reads of the local which follow `asm` in
+/// its block, or use its result, see its value either way.  Any other read in the loop may happen
+/// before `asm` and those after the loop may happen without it, unless it is in the header.
+fn may_move_write(
+    context: &Context,
+    function: Function,
+    lp: &Loop,
+    (insts, writes): (&[Value], &[memory_utils::MemoryWrite]),
+    asm: Value,
+    symbol: &Symbol,
+    exposed_symbols: &FxHashSet<Symbol>,
+) -> bool {
+    let asm_block = asm.get_instruction(context).unwrap().parent;
+    let following = asm_block
+        .instruction_iter(context)
+        .skip_while(|inst| *inst != asm)
+        .collect::<FxHashSet<_>>();
+    insts
+        .iter()
+        .zip(writes)
+        .all(|(inst, write)| *inst == asm || !write.may_write(symbol, exposed_symbols))
+        && function.instruction_iter(context).all(|(block, inst)| {
+            following.contains(&inst)
+                || (asm_block == lp.header && !lp.blocks.contains(&block))
+                || !may_read(context, function, inst, asm, symbol, exposed_symbols)
+        })
+}
+
+/// Whether `inst` may read the memory of the local `symbol` other than through the result of the
+/// ASM block `asm`.
+fn may_read(
+    context: &Context,
+    function: Function,
+    inst: Value,
+    asm: Value,
+    symbol: &Symbol,
+    exposed_symbols: &FxHashSet<Symbol>,
+) -> bool {
+    if let Some(memory) = memory_utils::get_asm_block_memory(context, function, inst) {
+        return memory.reads.contains(symbol);
+    }
+    // The values passed to calls and ASM blocks may be pointers, even if they aren't typed so.
+    let is_call = matches!(
+        inst.get_instruction(context).unwrap().op,
+        InstOp::Call(..) | InstOp::AsmBlock(..)
+    );
+    memory_utils::get_loaded_ptr_values(context, inst)
+        .into_iter()
+        .filter(|ptr| *ptr != asm)
+        .filter(|ptr| is_call || ptr.get_type(context).is_some_and(|ty| ty.is_ptr(context)))
+        .any(|ptr| match get_traced_symbol(context, function, ptr) {
+            Some(traced) => traced == *symbol,
+            None => exposed_symbols.contains(symbol),
+        })
+}
+
+/// Is `value` an instruction or a block argument of one of `blocks`?
+fn is_defined_in(context: &Context, blocks: &FxIndexSet<Block>, value: Value) -> bool {
+    match &context.values[value.0].value {
+        ValueDatum::Instruction(Instruction { parent, .. }) => blocks.contains(parent),
+        ValueDatum::Argument(arg) => blocks.contains(&arg.block),
+        ValueDatum::Constant(_) | ValueDatum::Configurable(_) => false,
+    }
+}
+
+/// The block from which the loop is entered, which is created if there isn't a single one which
+/// unconditionally branches to the header.
+fn get_or_create_preheader(
+    context: &mut Context,
+    function: Function,
+    loops: &mut Loops,
+    idx: usize,
+) -> Result<Block, IrError> {
+    let header = loops.0[idx].header;
+    let entering_blocks = header
+        .pred_iter(context)
+        .filter(|pred| !loops.0[idx].blocks.contains(*pred))
+        .cloned()
+        .collect::<Vec<_>>();
+    if let [pred] = entering_blocks[..] {
+        if matches!(
+            pred.get_terminator(context),
+            Some(Instruction {
+                op: InstOp::Branch(_),
+                ..
+            })
+        ) {
+            return Ok(pred);
+        }
+    }
+
+    let preheader = function.create_block_before(
+        context,
+        &header,
+        Some(format!("{}_preheader", header.get_label(context))),
+    )?;
+    let arg_tys = header
+        .arg_iter(context)
+        .map(|arg| arg.get_type(context).unwrap())
+        .collect::<Vec<_>>();
+    let args = arg_tys
+        .into_iter()
+        .map(|ty| {
+            let idx = preheader.new_arg(context, ty);
+            preheader.get_arg(context, idx).unwrap()
+        })
+        .collect::<Vec<_>>();
+    for pred in entering_blocks {
+        // Both edges of a conditional branch may enter the loop, with different arguments.
+        let branches = match &mut pred.get_terminator_mut(context).unwrap().op {
+            InstOp::Branch(branch) => vec![branch],
+            InstOp::ConditionalBranch {
+                true_block,
+                false_block,
+                ..
+            } => vec![true_block, false_block],
+            _otherwise => unreachable!("Only branches have successors."),
+        };
+        for branch in branches {
+            if branch.block == header {
+                branch.block = preheader;
+            }
+        }
+        header.remove_pred(context, &pred);
+        preheader.add_pred(context, &pred);
+    }
+    preheader.append(context).branch(header, args);
+
+    // The preheader belongs to the loops enclosing this one.
+    let mut parent = loops.0[idx].parent;
+    while let Some(parent_idx) = parent {
+        loops.0[parent_idx].blocks.insert(preheader);
+        parent = loops.0[parent_idx].parent;
+    }
+
+    Ok(preheader)
+}
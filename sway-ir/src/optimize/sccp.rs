@@ -0,0 +1,416 @@
+//! ## Sparse Conditional Constant Propagation
+//!
+//! This optimization finds the values which are constant on every path which may actually be
+//! executed.  Every value starts as undefined and is lowered to a constant or to overdefined as the
+//! function is evaluated, starting from the entry block and only following the branches which may
+//! be taken.  The block arguments are the meet of the values passed along the taken edges into
+//! their block, so a constant flows through the loops and joins which always pass it.
+//!
+//! Calls to pure functions, which have no side effects and don't read configurables, are
+//! evaluated when all of their arguments are constant, by propagating those constants through the
+//! callee.  The call is then replaced by the returned constant, if there is one.
+//!
+//! The constant values replace the instructions and block arguments, and the conditional branches
+//! on a constant are replaced with a branch to the taken successor.  The blocks which become
+//! unreachable are left for `simplifycfg` to remove.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    compute_post_order, fold_binary_op, fold_cmp, fold_unary_op, get_traced_symbol,
+    AnalysisResults, Block, BranchToWithArgs, Constant, ConstantValue, Context, Function, InstOp,
+    Instruction, IrError, Pass, PassMutability, ScopedPass, Symbol, Value, ValueDatum,
+};
+
+pub const SCCP_NAME: &str = "sccp";
+
+pub fn create_sccp_pass() -> Pass {
+    Pass {
+        name: SCCP_NAME,
+        descr: "Sparse conditional constant propagation.",
+        deps: vec![],
+        runner: ScopedPass::FunctionPass(PassMutability::Transform(sccp)),
+    }
+}
+
+/// The maximum depth of the nested calls which are evaluated.
+const MAX_CALL_DEPTH: usize = 4;
+
+#[derive(Clone)]
+enum LatticeValue {
+    /// Not known yet, i.e., no definition has been evaluated.
+    Undefined,
+    Constant(Constant),
+    /// May have more than one value.
+    Overdefined,
+}
+
+impl LatticeValue {
+    fn meet(self, context: &Context, other: &LatticeValue) -> LatticeValue {
+        match (self, other) {
+            (LatticeValue::Undefined, other) => other.clone(),
+            (this, LatticeValue::Undefined) => this,
+            (LatticeValue::Constant(c1), LatticeValue::Constant(c2)) if c1.eq(context, c2) => {
+                LatticeValue::Constant(c1)
+            }
+            _otherwise => LatticeValue::Overdefined,
+        }
+    }
+
+    fn is_same(&self, context: &Context, other: &LatticeValue) -> bool {
+        match (self, other) {
+            (LatticeValue::Undefined, LatticeValue::Undefined)
+            | (LatticeValue::Overdefined, LatticeValue::Overdefined) => true,
+            (LatticeValue::Constant(c1), LatticeValue::Constant(c2)) => c1.eq(context, c2),
+            _otherwise => false,
+        }
+    }
+}
+
+/// The lattice values of a function and its blocks which may be executed.
+#[derive(Default)]
+struct Propagation {
+    values: FxHashMap<Value, LatticeValue>,
+    executable: FxHashSet<Block>,
+    /// The branches out of each executable block which may be taken.
+    taken: FxHashMap<Block, Vec<BranchToWithArgs>>,
+}
+
+impl Propagation {
+    fn value_of(&self, context: &Context, value: Value) -> LatticeValue {
+        match &context.values[value.0].value {
+            ValueDatum::Constant(constant) => LatticeValue::Constant(constant.clone()),
+            ValueDatum::Configurable(_) => LatticeValue::Overdefined,
+            ValueDatum::Argument(_) | ValueDatum::Instruction(_) => self
+                .values
+                .get(&value)
+                .cloned()
+                .unwrap_or(LatticeValue::Undefined),
+        }
+    }
+
+    /// Lower the lattice value of `value` and return true if it has changed.
+    fn update(&mut self, context: &Context, value: Value, lattice_value: LatticeValue) -> bool {
+        let old = self.value_of(context, value);
+        let new = old.clone().meet(context, &lattice_value);
+        if new.is_same(context, &old) {
+            false
+        } else {
+            self.values.insert(value, new);
+            true
+        }
+    }
+}
+
+struct Solver<'a, 'eng> {
+    context: &'a Context<'eng>,
+    /// The functions being evaluated, outermost first.
+    active: Vec<Function>,
+    /// Whether each function which was called is pure.
+    purity: FxHashMap<Function, bool>,
+    /// The results of the calls which were evaluated, with their arguments.
+    calls: FxHashMap<Function, Vec<(Vec<Constant>, LatticeValue)>>,
+}
+
+/// Propagate the constants of `function` and return true if it was modified.
+pub fn sccp(
+    context: &mut Context,
+    _: &AnalysisResults,
+    function: Function,
+) -> Result<bool, IrError> {
+    let mut solver = Solver {
+        context,
+        active: vec![function],
+        purity: FxHashMap::default(),
+        calls: FxHashMap::default(),
+    };
+    let num_args = function.num_args(context);
+    let propagation = solver.solve(function, vec![LatticeValue::Overdefined; num_args]);
+
+    // Block arguments aren't removed, so only those which are still used are replaced.
+    let used_values = function
+        .instruction_iter(context)
+        .flat_map(|(_, inst)| inst.get_instruction(context).unwrap().op.get_operands())
+        .collect::<FxHashSet<_>>();
+
+    // The constant instructions and block arguments, and the branches which may be folded.
+    let mut constants = Vec::new();
+    let mut folded_branches = Vec::new();
+    for block in function.block_iter(context) {
+        if !propagation.executable.contains(&block) {
+            continue;
+        }
+        let args = block
+            .arg_iter(context)
+            .filter(|arg| used_values.contains(*arg))
+            .map(|arg| (*arg, None));
+        let insts = block
+            .instruction_iter(context)
+            .map(|inst| (inst, Some(block)));
+        for (value, parent) in args.chain(insts) {
+            if let LatticeValue::Constant(constant) = propagation.value_of(context, value) {
+                constants.push((value, parent, constant));
+            }
+        }
+
+        if let Some(Instruction {
+            op:
+                InstOp::ConditionalBranch {
+                    cond_value,
+                    true_block,
+                    false_block,
+                },
+            ..
+        }) = block.get_terminator(context)
+        {
+            if let LatticeValue::Constant(Constant {
+                value: ConstantValue::Bool(cond),
+                ..
+            }) = propagation.value_of(context, *cond_value)
+            {
+                let (taken, not_taken) = if cond {
+                    (true_block, false_block)
+                } else {
+                    (false_block, true_block)
+                };
+                folded_branches.push((block, taken.clone(), not_taken.block));
+            }
+        }
+    }
+
+    if constants.is_empty() && folded_branches.is_empty() {
+        return Ok(false);
+    }
+
+    for (block, taken, not_taken) in folded_branches {
+        if not_taken != taken.block {
+            not_taken.remove_pred(context, &block);
+        }
+        block.get_terminator_mut(context).unwrap().op = InstOp::Branch(taken);
+    }
+
+    let mut replacements = FxHashMap::default();
+    for (value, parent, constant) in constants {
+        replacements.insert(value, Value::new_constant(context, constant));
+        if let Some(block) = parent {
+            block.remove_instruction(context, value);
+        }
+    }
+    function.replace_values(context, &replacements, None);
+
+    Ok(true)
+}
+
+impl<'a, 'eng> Solver<'a, 'eng> {
+    /// Evaluate `function` with the lattice values of its arguments, until a fixed point is
+    /// reached.
+    fn solve(&mut self, function: Function, args: Vec<LatticeValue>) -> Propagation {
+        let context = self.context;
+        let entry = function.get_entry_block(context);
+        let po = compute_post_order(context, &function);
+
+        let mut propagation = Propagation::default();
+        for (arg, lattice_value) in entry.arg_iter(context).zip(args) {
+            propagation.update(context, *arg, lattice_value);
+        }
+        propagation.executable.insert(entry);
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in po.po_to_block.iter().rev() {
+                if !propagation.executable.contains(block) {
+                    continue;
+                }
+
+                // The arguments receive the values passed along the edges which may be taken.
+                if *block != entry {
+                    for (idx, arg) in block.arg_iter(context).enumerate() {
+                        let lattice_value = block
+                            .pred_iter(context)
+                            .filter_map(|pred| propagation.taken.get(pred))
+                            .flatten()
+                            .filter(|branch| branch.block == *block)
+                            .fold(LatticeValue::Undefined, |acc, branch| {
+                                acc.meet(context, &propagation.value_of(context, branch.args[idx]))
+                            });
+                        changed |= propagation.update(context, *arg, lattice_value);
+                    }
+                }
+
+                for inst in block.instruction_iter(context) {
+                    let lattice_value = self.evaluate(&propagation, inst);
+                    changed |= propagation.update(context, inst, lattice_value);
+                }
+
+                let taken = self.taken_branches(&propagation, *block);
+                for branch in &taken {
+                    changed |= propagation.executable.insert(branch.block);
+                }
+                let num_taken = propagation.taken.get(block).map_or(0, |taken| taken.len());
+                if taken.len() != num_taken {
+                    propagation.taken.insert(*block, taken);
+                    changed = true;
+                }
+            }
+        }
+
+        propagation
+    }
+
+    fn evaluate(&mut self, propagation: &Propagation, inst: Value) -> LatticeValue {
+        let context = self.context;
+        let fold = |operands: &[Value], f: &dyn Fn(&[&Constant]) -> Option<Constant>| {
+            let operands = operands
+                .iter()
+                .map(|opd| propagation.value_of(context, *opd))
+                .collect::<Vec<_>>();
+            let mut constants = Vec::new();
+            for operand in &operands {
+                match operand {
+                    LatticeValue::Undefined => return LatticeValue::Undefined,
+                    LatticeValue::Constant(constant) => constants.push(constant),
+                    LatticeValue::Overdefined => return LatticeValue::Overdefined,
+                }
+            }
+            f(&constants).map_or(LatticeValue::Overdefined, LatticeValue::Constant)
+        };
+
+        match &inst.get_instruction(context).unwrap().op {
+            InstOp::UnaryOp { op, arg } => fold(&[*arg], &|cs| fold_unary_op(context, op, cs[0])),
+            InstOp::BinaryOp { op, arg1, arg2 } => {
+                fold(&[*arg1, *arg2], &|cs| fold_binary_op(op, cs[0], cs[1]))
+            }
+            InstOp::Cmp(pred, lhs, rhs) => fold(&[*lhs, *rhs], &|cs| {
+                fold_cmp(context, pred, cs[0], cs[1]).map(|b| Constant::new_bool(context, b))
+            }),
+            InstOp::Call(callee, args) => {
+                let mut constants = Vec::new();
+                for arg in args {
+                    match propagation.value_of(context, *arg) {
+                        LatticeValue::Undefined => return LatticeValue::Undefined,
+                        LatticeValue::Constant(constant) => constants.push(constant),
+                        LatticeValue::Overdefined => return LatticeValue::Overdefined,
+                    }
+                }
+                self.evaluate_call(*callee, constants)
+            }
+            _otherwise => LatticeValue::Overdefined,
+        }
+    }
+
+    /// The branches out of `block` which may be taken.
+    fn taken_branches(&self, propagation: &Propagation, block: Block) -> Vec<BranchToWithArgs> {
+        match block.get_terminator(self.context).map(|term| &term.op) {
+            Some(InstOp::Branch(branch)) => vec![branch.clone()],
+            Some(InstOp::ConditionalBranch {
+                cond_value,
+                true_block,
+                false_block,
+            }) => match propagation.value_of(self.context, *cond_value) {
+                LatticeValue::Undefined => Vec::new(),
+                LatticeValue::Constant(Constant {
+                    value: ConstantValue::Bool(true),
+                    ..
+                }) => vec![true_block.clone()],
+                LatticeValue::Constant(Constant {
+                    value: ConstantValue::Bool(false),
+                    ..
+                }) => vec![false_block.clone()],
+                _otherwise => vec![true_block.clone(), false_block.clone()],
+            },
+            _otherwise => Vec::new(),
+        }
+    }
+
+    /// The constant returned by a call to `callee` with constant arguments, if it is pure.
+    fn evaluate_call(&mut self, callee: Function, args: Vec<Constant>) -> LatticeValue {
+        let context = self.context;
+        if self.active.len() > MAX_CALL_DEPTH
+            || self.active.contains(&callee)
+            || !self.is_pure(callee)
+        {
+            return LatticeValue::Overdefined;
+        }
+
+        let cached = self.calls.get(&callee).and_then(|results| {
+            results.iter().find(|(cached_args, _)| {
+                cached_args
+                    .iter()
+                    .zip(&args)
+                    .all(|(cached_arg, arg)| cached_arg.eq(context, arg))
+            })
+        });
+        if let Some((_, result)) = cached {
+            return result.clone();
+        }
+
+        self.active.push(callee);
+        let propagation = self.solve(
+            callee,
+            args.iter().cloned().map(LatticeValue::Constant).collect(),
+        );
+        self.active.pop();
+
+        let result = callee
+            .instruction_iter(context)
+            .filter(|(block, _)| propagation.executable.contains(block))
+            .filter_map(
+                |(_, inst)| match &inst.get_instruction(context).unwrap().op {
+                    InstOp::Ret(value, _) => Some(*value),
+                    _otherwise => None,
+                },
+            )
+            .fold(LatticeValue::Undefined, |acc, value| {
+                acc.meet(context, &propagation.value_of(context, value))
+            });
+        // A callee which never returns has no result to propagate either.
+        let result = match result {
+            LatticeValue::Constant(_) => result,
+            _otherwise => LatticeValue::Overdefined,
+        };
+
+        self.calls
+            .entry(callee)
+            .or_default()
+            .push((args, result.clone()));
+        result
+    }
+
+    /// Does `function` only compute its result, i.e., it has no side effects other than writing
+    /// to its own locals, and it doesn't read configurables?
+    fn is_pure(&mut self, function: Function) -> bool {
+        let context = self.context;
+        if let Some(is_pure) = self.purity.get(&function) {
+            return *is_pure;
+        }
+
+        // Recursive functions are assumed to be impure.
+        self.purity.insert(function, false);
+        let mut is_pure = true;
+        for (_, inst) in function.instruction_iter(context) {
+            let op = &inst.get_instruction(context).unwrap().op;
+            let is_pure_inst = op
+                .get_operands()
+                .iter()
+                .all(|opd| !opd.is_configurable(context))
+                && match op {
+                    InstOp::Call(callee, _) => self.is_pure(*callee),
+                    InstOp::Store { dst_val_ptr, .. }
+                    | InstOp::MemCopyBytes { dst_val_ptr, .. }
+                    | InstOp::MemCopyVal { dst_val_ptr, .. } => matches!(
+                        get_traced_symbol(context, function, *dst_val_ptr),
+                        Some(Symbol::Local(_))
+                    ),
+                    InstOp::Ret(..) => true,
+                    _otherwise => !op.may_have_side_effect(),
+                };
+            if !is_pure_inst {
+                is_pure = false;
+                break;
+            }
+        }
+        self.purity.insert(function, is_pure);
+        is_pure
+    }
+}
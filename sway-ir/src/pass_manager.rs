@@ -1,19 +1,24 @@
 use crate::{
     create_arg_demotion_pass, create_const_combine_pass, create_const_demotion_pass,
     create_dce_pass, create_dom_fronts_pass, create_dominators_pass, create_escaped_symbols_pass,
-    create_fn_dedup_pass, create_func_dce_pass, create_inline_in_main_pass,
-    create_inline_in_module_pass, create_mem2reg_pass, create_memcpyopt_pass,
-    create_misc_demotion_pass, create_module_printer_pass, create_module_verifier_pass,
-    create_postorder_pass, create_ret_demotion_pass, create_simplify_cfg_pass, create_sroa_pass,
-    Context, Function, IrError, Module, CONSTCOMBINE_NAME, DCE_NAME, FNDEDUP_NAME, FUNC_DCE_NAME,
-    INLINE_MODULE_NAME, MEM2REG_NAME, SIMPLIFYCFG_NAME,
+    create_fn_dedup_aggressive_pass, create_fn_dedup_pass, create_func_dce_pass, create_gvn_pass,
+    create_inline_in_main_pass, create_inline_in_module_for_size_pass,
+    create_inline_in_module_pass, create_licm_pass, create_loops_pass, create_mem2reg_pass,
+    create_memcpyopt_pass, create_misc_demotion_pass, create_module_printer_pass,
+    create_module_verifier_pass, create_postorder_pass, create_ret_demotion_pass, create_sccp_pass,
+    create_simplify_cfg_pass, create_sroa_pass, Context, Function, IrError, Module,
+    CONSTCOMBINE_NAME, DCE_NAME, FNDEDUP_AGGRESSIVE_NAME, FNDEDUP_NAME, FUNC_DCE_NAME, GVN_NAME,
+    INLINE_MODULE_NAME, INLINE_MODULE_SIZE_NAME, LICM_NAME, MEM2REG_NAME, SCCP_NAME,
+    SIMPLIFYCFG_NAME,
 };
 use downcast_rs::{impl_downcast, Downcast};
 use rustc_hash::FxHashMap;
 use std::{
     any::{type_name, TypeId},
     collections::hash_map,
+    time::{Duration, Instant},
 };
+use sway_types::FxIndexMap;
 
 /// Result of an analysis. Specific result must be downcasted to.
 pub trait AnalysisResultT: Downcast {}
@@ -141,10 +146,22 @@ impl AnalysisResults {
     }
 }
 
+/// Statistics about the runs of a pass.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PassStats {
+    /// The number of times the pass was run.
+    pub runs: usize,
+    /// The number of runs in which the pass modified the IR.
+    pub modifying_runs: usize,
+    /// The total time spent in the pass, not including its dependencies.
+    pub elapsed: Duration,
+}
+
 #[derive(Default)]
 pub struct PassManager {
     passes: FxHashMap<&'static str, Pass>,
     analyses: AnalysisResults,
+    stats: FxIndexMap<&'static str, PassStats>,
 }
 
 impl PassManager {
@@ -188,6 +205,7 @@ impl PassManager {
 
         // To please the borrow checker, get current pass again.
         let pass_t = self.passes.get(pass).expect("Unregistered pass");
+        let start = Instant::now();
 
         for m in ir.module_iter() {
             match &pass_t.runner {
@@ -229,18 +247,105 @@ impl PassManager {
                 }
             }
         }
+
+        let stats = self.stats.entry(pass).or_default();
+        stats.runs += 1;
+        stats.modifying_runs += usize::from(modified);
+        stats.elapsed += start.elapsed();
+
         Ok(modified)
     }
 
     /// Run the passes specified in `config`.
     pub fn run(&mut self, ir: &mut Context, passes: &PassGroup) -> Result<bool, IrError> {
         let mut modified = false;
-        for pass in passes.flatten_pass_group() {
-            modified |= self.actually_run(ir, pass)?;
+        for pass_or_group in &passes.0 {
+            modified |= match pass_or_group {
+                PassOrGroup::Pass(pass) => self.actually_run(ir, pass)?,
+                PassOrGroup::Group(group) => self.run(ir, group)?,
+                PassOrGroup::Repeat(group, max_iterations) => {
+                    let mut modified = false;
+                    for _ in 0..*max_iterations {
+                        if !self.run(ir, group)? {
+                            break;
+                        }
+                        modified = true;
+                    }
+                    modified
+                }
+            };
         }
         Ok(modified)
     }
 
+    /// Get the statistics of the passes which were run, in the order in which they were first run.
+    pub fn stats(&self) -> impl Iterator<Item = (&'static str, &PassStats)> {
+        self.stats.iter().map(|(name, stats)| (*name, stats))
+    }
+
+    /// Parse a textual pass pipeline, e.g., `mem2reg,repeat(constcombine,simplifycfg),dce`.
+    ///
+    /// The pipeline is a comma separated list of registered pass names, of the predefined groups
+    /// `o1` and `oz`, and of `repeat(...)` groups which are run until none of their passes modify
+    /// the IR.  The number of iterations of a repeated group is capped at
+    /// [REPEAT_MAX_ITERATIONS], unless specified as in `repeat<5>(...)`.
+    pub fn parse_pipeline(&self, pipeline: &str) -> Result<PassGroup, IrError> {
+        let mut tokens = tokenize_pipeline(pipeline)?.into_iter().peekable();
+        let group = self.parse_pipeline_group(&mut tokens)?;
+        match tokens.next() {
+            None => Ok(group),
+            Some(token) => Err(IrError::InvalidPassPipeline(format!(
+                "unexpected '{token}'"
+            ))),
+        }
+    }
+
+    fn parse_pipeline_group(
+        &self,
+        tokens: &mut std::iter::Peekable<std::vec::IntoIter<String>>,
+    ) -> Result<PassGroup, IrError> {
+        let mut group = PassGroup::default();
+        loop {
+            let name = tokens
+                .next()
+                .ok_or_else(|| IrError::InvalidPassPipeline("expected a pass name".to_owned()))?;
+            match name.as_str() {
+                "repeat" => {
+                    let mut max_iterations = REPEAT_MAX_ITERATIONS;
+                    if tokens.next_if_eq("<").is_some() {
+                        max_iterations = tokens
+                            .next()
+                            .and_then(|count| count.parse().ok())
+                            .ok_or_else(|| {
+                                IrError::InvalidPassPipeline(
+                                    "expected a number of iterations after 'repeat<'".to_owned(),
+                                )
+                            })?;
+                        expect_pipeline_token(tokens, ">")?;
+                    }
+                    expect_pipeline_token(tokens, "(")?;
+                    let repeated = self.parse_pipeline_group(tokens)?;
+                    expect_pipeline_token(tokens, ")")?;
+                    group.append_repeat(repeated, max_iterations);
+                }
+                "o1" => group.append_group(create_o1_pass_group()),
+                "oz" => group.append_group(create_oz_pass_group()),
+                name => match self.lookup_registered_pass(name) {
+                    Some(pass) => group.append_pass(pass.name),
+                    None => {
+                        return Err(IrError::InvalidPassPipeline(format!(
+                            "unrecognised pass name '{name}'"
+                        )))
+                    }
+                },
+            }
+
+            if tokens.next_if_eq(",").is_none() {
+                return Ok(group);
+            }
+        }
+    }
+
     /// Get reference to a registered pass.
     pub fn lookup_registered_pass(&self, name: &str) -> Option<&Pass> {
         self.passes.get(name)
@@ -258,6 +363,49 @@ impl PassManager {
     }
 }
 
+/// Split a textual pass pipeline into names, numbers and punctuation.
+fn tokenize_pipeline(pipeline: &str) -> Result<Vec<String>, IrError> {
+    let mut tokens = Vec::new();
+    let mut chars = pipeline.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '(' | ')' | ',' | '<' | '>' => tokens.push(c.to_string()),
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    word.push(c);
+                }
+                tokens.push(word);
+            }
+            c => {
+                return Err(IrError::InvalidPassPipeline(format!(
+                    "unexpected character '{c}'"
+                )))
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn expect_pipeline_token(
+    tokens: &mut std::iter::Peekable<std::vec::IntoIter<String>>,
+    expected: &str,
+) -> Result<(), IrError> {
+    match tokens.next() {
+        Some(token) if token == expected => Ok(()),
+        Some(token) => Err(IrError::InvalidPassPipeline(format!(
+            "expected '{expected}' but found '{token}'"
+        ))),
+        None => Err(IrError::InvalidPassPipeline(format!(
+            "expected '{expected}' at the end"
+        ))),
+    }
+}
+
+/// The default maximum number of iterations of a repeated group.
+pub const REPEAT_MAX_ITERATIONS: usize = 10;
+
 /// A group of passes.
 /// Can contain sub-groups.
 #[derive(Default)]
@@ -267,24 +415,12 @@ pub struct PassGroup(Vec<PassOrGroup>);
 pub enum PassOrGroup {
     Pass(&'static str),
     Group(PassGroup),
+    /// A group which is run again until none of its passes modify the IR, at most the given
+    /// number of times.
+    Repeat(PassGroup, usize),
 }
 
 impl PassGroup {
-    // Flatten a group of passes into an ordered list.
-    fn flatten_pass_group(&self) -> Vec<&'static str> {
-        let mut output = Vec::<&str>::new();
-        fn inner(output: &mut Vec<&str>, input: &PassGroup) {
-            for pass_or_group in &input.0 {
-                match pass_or_group {
-                    PassOrGroup::Pass(pass) => output.push(pass),
-                    PassOrGroup::Group(pg) => inner(output, pg),
-                }
-            }
-        }
-        inner(&mut output, self);
-        output
-    }
-
     /// Append a pass to this group.
     pub fn append_pass(&mut self, pass: &'static str) {
         self.0.push(PassOrGroup::Pass(pass));
@@ -294,6 +430,12 @@ impl PassGroup {
     pub fn append_group(&mut self, group: PassGroup) {
         self.0.push(PassOrGroup::Group(group));
     }
+
+    /// Append a pass group which is repeated until it no longer modifies the IR, or until it was
+    /// run `max_iterations` times.
+    pub fn append_repeat(&mut self, group: PassGroup, max_iterations: usize) {
+        self.0.push(PassOrGroup::Repeat(group, max_iterations));
+    }
 }
 
 /// A convenience utility to register known passes.
@@ -302,14 +444,17 @@ pub fn register_known_passes(pm: &mut PassManager) {
     pm.register(create_postorder_pass());
     pm.register(create_dominators_pass());
     pm.register(create_dom_fronts_pass());
+    pm.register(create_loops_pass());
     pm.register(create_escaped_symbols_pass());
     pm.register(create_module_printer_pass());
     pm.register(create_module_verifier_pass());
     // Optimization passes.
     pm.register(create_fn_dedup_pass());
+    pm.register(create_fn_dedup_aggressive_pass());
     pm.register(create_mem2reg_pass());
     pm.register(create_sroa_pass());
     pm.register(create_inline_in_module_pass());
+    pm.register(create_inline_in_module_for_size_pass());
     pm.register(create_inline_in_main_pass());
     pm.register(create_const_combine_pass());
     pm.register(create_simplify_cfg_pass());
@@ -320,6 +465,19 @@ pub fn register_known_passes(pm: &mut PassManager) {
     pm.register(create_ret_demotion_pass());
     pm.register(create_misc_demotion_pass());
     pm.register(create_memcpyopt_pass());
+    pm.register(create_gvn_pass());
+    pm.register(create_licm_pass());
+    pm.register(create_sccp_pass());
+}
+
+/// Constant folding and CFG simplification, which enable each other and so are repeated until
+/// neither changes anything.
+fn create_constant_folding_pass_group() -> PassGroup {
+    let mut folding = PassGroup::default();
+    folding.append_pass(CONSTCOMBINE_NAME);
+    folding.append_pass(SCCP_NAME);
+    folding.append_pass(SIMPLIFYCFG_NAME);
+    folding
 }
 
 pub fn create_o1_pass_group() -> PassGroup {
@@ -329,21 +487,168 @@ pub fn create_o1_pass_group() -> PassGroup {
     o1.append_pass(MEM2REG_NAME);
     o1.append_pass(INLINE_MODULE_NAME);
     o1.append_pass(FNDEDUP_NAME);
-    o1.append_pass(CONSTCOMBINE_NAME);
-    o1.append_pass(SIMPLIFYCFG_NAME);
-    o1.append_pass(CONSTCOMBINE_NAME);
-    o1.append_pass(SIMPLIFYCFG_NAME);
+    o1.append_repeat(create_constant_folding_pass_group(), REPEAT_MAX_ITERATIONS);
+    o1.append_pass(LICM_NAME);
+    o1.append_pass(GVN_NAME);
+    o1.append_repeat(create_constant_folding_pass_group(), REPEAT_MAX_ITERATIONS);
     o1.append_pass(FUNC_DCE_NAME);
     o1.append_pass(DCE_NAME);
 
     o1
 }
 
-/// Utility to insert a pass after every pass in the given group
+pub fn create_oz_pass_group() -> PassGroup {
+    // Like O1, but with passes which favour the code size over the execution speed.
+    let mut oz = PassGroup::default();
+    oz.append_pass(MEM2REG_NAME);
+    // Deduplicate before inlining too, so that the inliner counts the calls to the duplicates
+    // together.
+    oz.append_pass(FNDEDUP_AGGRESSIVE_NAME);
+    oz.append_pass(INLINE_MODULE_SIZE_NAME);
+    oz.append_pass(FNDEDUP_AGGRESSIVE_NAME);
+    oz.append_repeat(create_constant_folding_pass_group(), REPEAT_MAX_ITERATIONS);
+    oz.append_pass(GVN_NAME);
+    oz.append_repeat(create_constant_folding_pass_group(), REPEAT_MAX_ITERATIONS);
+    oz.append_pass(FUNC_DCE_NAME);
+    oz.append_pass(DCE_NAME);
+
+    oz
+}
+
+/// Utility to insert a pass after every pass in the given group, including those of its
+/// sub-groups.
 pub fn insert_after_each(pg: PassGroup, pass: &'static str) -> PassGroup {
     PassGroup(
         pg.0.into_iter()
-            .flat_map(|p_o_g| vec![p_o_g, PassOrGroup::Pass(pass)])
+            .flat_map(|p_o_g| match p_o_g {
+                PassOrGroup::Pass(_) => vec![p_o_g, PassOrGroup::Pass(pass)],
+                PassOrGroup::Group(group) => {
+                    vec![PassOrGroup::Group(insert_after_each(group, pass))]
+                }
+                PassOrGroup::Repeat(group, max_iterations) => vec![PassOrGroup::Repeat(
+                    insert_after_each(group, pass),
+                    max_iterations,
+                )],
+            })
             .collect(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExperimentalFlags;
+    use sway_types::SourceEngine;
+
+    fn pass_manager() -> PassManager {
+        let mut pass_mgr = PassManager::default();
+        register_known_passes(&mut pass_mgr);
+        pass_mgr
+    }
+
+    fn describe(group: &PassGroup) -> String {
+        group
+            .0
+            .iter()
+            .map(|pass_or_group| match pass_or_group {
+                PassOrGroup::Pass(pass) => pass.to_string(),
+                PassOrGroup::Group(group) => format!("[{}]", describe(group)),
+                PassOrGroup::Repeat(group, max_iterations) => {
+                    format!("repeat<{max_iterations}>[{}]", describe(group))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn parse_pipeline() {
+        let pass_mgr = pass_manager();
+        let parse = |pipeline| describe(&pass_mgr.parse_pipeline(pipeline).unwrap());
+
+        assert_eq!(parse("dce"), "dce");
+        assert_eq!(
+            parse("mem2reg, repeat(constcombine,simplifycfg) ,dce"),
+            "mem2reg repeat<10>[constcombine simplifycfg] dce"
+        );
+        assert_eq!(
+            parse("repeat<3>(sccp,repeat(dce))"),
+            "repeat<3>[sccp repeat<10>[dce]]"
+        );
+        assert!(parse("o1").starts_with("[mem2reg "));
+    }
+
+    #[test]
+    fn parse_invalid_pipeline() {
+        let pass_mgr = pass_manager();
+        let error = |pipeline| pass_mgr.parse_pipeline(pipeline).err().unwrap().to_string();
+
+        assert_eq!(
+            error("dce,nope"),
+            "Invalid pass pipeline: unrecognised pass name 'nope'."
+        );
+        assert_eq!(
+            error("repeat(dce"),
+            "Invalid pass pipeline: expected ')' at the end."
+        );
+        assert_eq!(
+            error("repeat<x>(dce)"),
+            "Invalid pass pipeline: expected a number of iterations after 'repeat<'."
+        );
+        assert_eq!(error("dce)"), "Invalid pass pipeline: unexpected ')'.");
+        assert_eq!(
+            error("dce;"),
+            "Invalid pass pipeline: unexpected character ';'."
+        );
+        assert_eq!(error(""), "Invalid pass pipeline: expected a pass name.");
+    }
+
+    #[test]
+    fn repeat_until_fixed_point() {
+        let source_engine = SourceEngine::default();
+        let parse_ir = || {
+            crate::parse(
+                "script {
+                    entry fn main() -> u64 {
+                        entry():
+                        v0 = const bool true
+                        cbr v0, block0(), block1()
+
+                        block0():
+                        v1 = const u64 1
+                        ret u64 v1
+
+                        block1():
+                        v2 = const u64 2
+                        ret u64 v2
+                    }
+                }",
+                &source_engine,
+                ExperimentalFlags::default(),
+            )
+            .unwrap()
+        };
+
+        // The branch is folded, then the dead block is removed, then nothing changes.
+        let mut pass_mgr = pass_manager();
+        let pipeline = pass_mgr
+            .parse_pipeline("repeat(constcombine,simplifycfg)")
+            .unwrap();
+        assert!(pass_mgr.run(&mut parse_ir(), &pipeline).unwrap());
+        let stats = pass_mgr.stats().collect::<FxHashMap<_, _>>();
+        assert_eq!(stats[CONSTCOMBINE_NAME].runs, 2);
+        assert_eq!(stats[CONSTCOMBINE_NAME].modifying_runs, 1);
+        assert_eq!(stats[SIMPLIFYCFG_NAME].runs, 2);
+        assert_eq!(stats[SIMPLIFYCFG_NAME].modifying_runs, 1);
+
+        // The iterations are capped.
+        let mut pass_mgr = pass_manager();
+        let pipeline = pass_mgr
+            .parse_pipeline("repeat<1>(constcombine,simplifycfg)")
+            .unwrap();
+        assert!(pass_mgr.run(&mut parse_ir(), &pipeline).unwrap());
+        let stats = pass_mgr.stats().collect::<FxHashMap<_, _>>();
+        assert_eq!(stats[CONSTCOMBINE_NAME].runs, 1);
+        assert_eq!(stats[SIMPLIFYCFG_NAME].runs, 1);
+    }
+}
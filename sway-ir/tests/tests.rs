@@ -1,11 +1,20 @@
-use std::path::PathBuf;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 use sway_ir::{
     create_arg_demotion_pass, create_const_combine_pass, create_const_demotion_pass,
     create_dce_pass, create_dom_fronts_pass, create_dominators_pass, create_escaped_symbols_pass,
-    create_mem2reg_pass, create_memcpyopt_pass, create_misc_demotion_pass, create_postorder_pass,
-    create_ret_demotion_pass, create_simplify_cfg_pass, optimize as opt, register_known_passes,
-    Context, ExperimentalFlags, PassGroup, PassManager, DCE_NAME, MEM2REG_NAME, SROA_NAME,
+    create_fn_dedup_aggressive_pass, create_gvn_pass, create_inline_in_module_for_size_pass,
+    create_licm_pass, create_loops_pass, create_mem2reg_pass, create_memcpyopt_pass,
+    create_misc_demotion_pass, create_postorder_pass, create_ret_demotion_pass, create_sccp_pass,
+    create_simplify_cfg_pass, optimize as opt, register_known_passes, Context, ExecValue,
+    ExecutionOutcome, ExperimentalFlags, Function, InstOp, Instruction, Interpreter, IrError,
+    MockHost, PanicReason, PassGroup, PassManager, Type, ARGDEMOTION_NAME, CONSTCOMBINE_NAME,
+    CONSTDEMOTION_NAME, DCE_NAME, FNDEDUP_AGGRESSIVE_NAME, FNDEDUP_NAME, FUNC_DCE_NAME, GVN_NAME,
+    INLINE_MAIN_NAME, INLINE_MODULE_NAME, INLINE_MODULE_SIZE_NAME, LICM_NAME, MEM2REG_NAME,
+    MEMCPYOPT_NAME, MISCDEMOTION_NAME, RETDEMOTION_NAME, SCCP_NAME, SIMPLIFYCFG_NAME, SROA_NAME,
 };
 use sway_types::SourceEngine;
 
@@ -43,26 +52,30 @@ fn run_tests<F: Fn(&str, &mut Context) -> bool>(sub_dir: &str, opt_fn: F) {
 
         let output = sway_ir::printer::to_string(&ir);
 
-        let chkr = filecheck::CheckerBuilder::new()
-            .text(&input)
-            .unwrap()
-            .finish();
-        if chkr.is_empty() {
-            println!("{output}");
-            panic!("No filecheck directives found in test: {}", path.display());
-        }
+        check_output(&path, &input, &output);
+    }
+}
 
-        match chkr.explain(&output, filecheck::NO_VARIABLES) {
-            Ok((success, report)) if !success => {
-                println!("--- FILECHECK FAILED FOR {}", path.display());
-                println!("{report}");
-                panic!()
-            }
-            Err(e) => {
-                panic!("filecheck directive error while checking: {e}");
-            }
-            _ => (),
+fn check_output(path: &Path, input: &str, output: &str) {
+    let chkr = filecheck::CheckerBuilder::new()
+        .text(input)
+        .unwrap()
+        .finish();
+    if chkr.is_empty() {
+        println!("{output}");
+        panic!("No filecheck directives found in test: {}", path.display());
+    }
+
+    match chkr.explain(output, filecheck::NO_VARIABLES) {
+        Ok((success, report)) if !success => {
+            println!("--- FILECHECK FAILED FOR {}", path.display());
+            println!("{report}");
+            panic!()
         }
+        Err(e) => {
+            panic!("filecheck directive error while checking: {e}");
+        }
+        _ => (),
     }
 }
 
@@ -269,6 +282,83 @@ fn sroa() {
 
 // -------------------------------------------------------------------------------------------------
 
+#[allow(clippy::needless_collect)]
+#[test]
+fn fn_dedup_aggressive() {
+    run_tests("fn_dedup_aggressive", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        let pass = pass_mgr.register(create_fn_dedup_aggressive_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[allow(clippy::needless_collect)]
+#[test]
+fn inline_size() {
+    run_tests("inline_size", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        let pass = pass_mgr.register(create_inline_in_module_for_size_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[allow(clippy::needless_collect)]
+#[test]
+fn gvn() {
+    run_tests("gvn", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        pass_mgr.register(create_postorder_pass());
+        pass_mgr.register(create_dominators_pass());
+        pass_mgr.register(create_escaped_symbols_pass());
+        let pass = pass_mgr.register(create_gvn_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[allow(clippy::needless_collect)]
+#[test]
+fn licm() {
+    run_tests("licm", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        pass_mgr.register(create_postorder_pass());
+        pass_mgr.register(create_dominators_pass());
+        pass_mgr.register(create_loops_pass());
+        pass_mgr.register(create_escaped_symbols_pass());
+        let pass = pass_mgr.register(create_licm_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[allow(clippy::needless_collect)]
+#[test]
+fn sccp() {
+    run_tests("sccp", |_first_line, ir: &mut Context| {
+        let mut pass_mgr = PassManager::default();
+        let mut pass_group = PassGroup::default();
+        let pass = pass_mgr.register(create_sccp_pass());
+        pass_group.append_pass(pass);
+        pass_mgr.run(ir, &pass_group).unwrap()
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+
 #[test]
 fn serialize() {
     // This isn't running a pass, it's just confirming that the IR can be loaded and printed, and
@@ -277,3 +367,354 @@ fn serialize() {
 }
 
 // -------------------------------------------------------------------------------------------------
+
+#[test]
+fn fingerprint_fns() {
+    // Fingerprint the functions in `ir` by name.
+    fn fingerprints(ir: &str) -> HashMap<String, u64> {
+        let source_engine = SourceEngine::default();
+        let ir = sway_ir::parser::parse(ir, &source_engine, ExperimentalFlags::default()).unwrap();
+        let module = ir.module_iter().next().unwrap();
+        sway_ir::fingerprint_fns(&ir, module)
+            .into_iter()
+            .map(|(function, fingerprint)| (function.get_name(&ir).to_string(), fingerprint))
+            .collect()
+    }
+
+    let helper = |value: u64| {
+        format!(
+            "
+    fn helper_1(a: u64) -> {{ u64, bool }} {{
+        local {{ u64, bool }} pair
+
+        entry(a: u64):
+        v0 = get_local ptr {{ u64, bool }}, pair
+        v1 = const u64 0
+        v2 = get_elem_ptr v0, ptr u64, v1
+        v3 = const u64 {value}
+        v4 = add a, v3
+        store v4 to v2
+        v5 = load v0
+        ret {{ u64, bool }} v5
+    }}"
+        )
+    };
+    let tests = "
+    fn uses_helper() -> bool {
+        entry():
+        v0 = const u64 1
+        v1 = call helper_1(v0)
+        v2 = const bool true
+        ret bool v2
+    }
+
+    fn standalone() -> b256 {
+        entry():
+        v0 = const b256 0x0101010101010101010101010101010101010101010101010101010101010101
+        ret b256 v0
+    }";
+
+    let original = fingerprints(&format!("script {{{}{tests}\n}}", helper(2)));
+    // Declaring an unrelated function first changes the order in which types are created.
+    let unrelated = "
+    fn unrelated(a: u256) -> { b256, u256 } {
+        entry(a: u256):
+        v0 = const { b256, u256 } { b256 0x0000000000000000000000000000000000000000000000000000000000000000, u256 0x0000000000000000000000000000000000000000000000000000000000000000 }
+        ret { b256, u256 } v0
+    }";
+    let reordered = fingerprints(&format!("script {{{unrelated}{}{tests}\n}}", helper(2)));
+    for name in ["helper_1", "uses_helper", "standalone"] {
+        assert_eq!(original[name], reordered[name], "{name}");
+    }
+
+    // Changing a callee changes the fingerprint of its callers, but not of other functions.
+    let changed = fingerprints(&format!("script {{{}{tests}\n}}", helper(3)));
+    assert_ne!(original["helper_1"], changed["helper_1"]);
+    assert_ne!(original["uses_helper"], changed["uses_helper"]);
+    assert_eq!(original["standalone"], changed["standalone"]);
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// -------------------------------------------------------------------------------------------------
+// Utilities for running functions in the IR interpreter.
+
+// Run `function` with `args`, returning the outcome preceded by everything it logged, or `None` if
+// it ran out of gas, as the number of instructions it runs is up to the optimizations.
+fn interpret(
+    ir: &Context,
+    function: Function,
+    args: &[ExecValue],
+) -> Result<Option<String>, IrError> {
+    let mut host = MockHost::default();
+    let outcome = Interpreter::new(ir, &mut host).run(function, args)?;
+    if matches!(outcome, ExecutionOutcome::Panic(PanicReason::OutOfGas)) {
+        return Ok(None);
+    }
+
+    let mut output = host
+        .logs
+        .iter()
+        .map(|(log_id, value)| format!("log {log_id} {value}\n"))
+        .collect::<String>();
+    output.push_str(&format!("{outcome}\n"));
+    for (key, value) in &host.storage {
+        output.push_str(&format!("storage {key:?} {value:?}\n"));
+    }
+    for (recipient, data, coins) in &host.messages {
+        output.push_str(&format!("smo {recipient:?} {data:?} {coins}\n"));
+    }
+    Ok(Some(output))
+}
+
+// Whether a value of `ty` holds an address, which an optimization is free to change.
+fn holds_address(ir: &Context, ty: Type) -> bool {
+    ty.is_ptr(ir)
+        || ty.is_slice(ir)
+        || ty.is_string_slice(ir)
+        || ty
+            .get_array_elem_type(ir)
+            .is_some_and(|elem_ty| holds_address(ir, elem_ty))
+        || ty
+            .get_field_types(ir)
+            .into_iter()
+            .any(|field_ty| holds_address(ir, field_ty))
+}
+
+// Run each function which takes only integers and bools and doesn't return an address, with
+// arbitrary but fixed arguments.  Returns the results by function name and signature.  Programs
+// which convert pointers to integers are skipped entirely, as their results may depend on the stack
+// layout.
+fn interpret_functions(ir: &Context) -> Vec<(String, String, Result<Option<String>, IrError>)> {
+    let functions = ir
+        .module_iter()
+        .flat_map(|module| module.function_iter(ir))
+        .collect::<Vec<_>>();
+    let converts_pointers = functions.iter().any(|function| {
+        function.instruction_iter(ir).any(|(_, inst)| {
+            matches!(
+                inst.get_instruction(ir),
+                Some(Instruction {
+                    op: InstOp::PtrToInt(..),
+                    ..
+                })
+            )
+        })
+    });
+    if converts_pointers {
+        return Vec::new();
+    }
+
+    functions
+        .into_iter()
+        .filter(|function| {
+            !holds_address(ir, function.get_return_type(ir))
+                && function.args_iter(ir).all(|(_, arg)| {
+                    arg.get_type(ir)
+                        .is_some_and(|ty| ty.is_uint(ir) || ty.is_bool(ir))
+                })
+        })
+        .map(|function| {
+            let args = (0..function.num_args(ir))
+                .map(|idx| ExecValue::Word(idx as u64 % 2 + 1))
+                .collect::<Vec<_>>();
+            let signature = format!(
+                "({}) -> {}",
+                function
+                    .args_iter(ir)
+                    .map(|(_, arg)| arg.get_type(ir).unwrap().as_string(ir))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                function.get_return_type(ir).as_string(ir)
+            );
+            (
+                function.get_name(ir).to_owned(),
+                signature,
+                interpret(ir, function, &args),
+            )
+        })
+        .collect()
+}
+
+// -------------------------------------------------------------------------------------------------
+
+#[test]
+fn interpret_main() {
+    // Run `main` in each test and FileCheck its logs and outcome.
+    let source_engine = SourceEngine::default();
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let dir: PathBuf = format!("{manifest_dir}/tests/interpret").into();
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let input = std::fs::read_to_string(&path).unwrap();
+
+        let ir = sway_ir::parser::parse(&input, &source_engine, ExperimentalFlags::default())
+            .unwrap_or_else(|parse_err| panic!("{}: {parse_err}", path.display()));
+        let ir = ir
+            .verify()
+            .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+
+        let main = ir
+            .module_iter()
+            .flat_map(|module| module.function_iter(&ir))
+            .find(|function| function.get_name(&ir) == "main")
+            .unwrap_or_else(|| panic!("{}: no main function", path.display()));
+        let output = interpret(&ir, main, &[])
+            .unwrap_or_else(|err| panic!("{}: main can't be interpreted: {err}", path.display()))
+            .unwrap_or_else(|| panic!("{}: main ran out of gas", path.display()));
+
+        check_output(&path, &input, &output);
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+
+// The test programs which can't be run, as they convert between pointers and integers, only have
+// functions which take or return addresses or never return, aren't complete, or use ASM
+// instructions the interpreter doesn't support.
+const NOT_RUNNABLE: [&str; 9] = [
+    "demote_misc/demote_ptr_to_int.ir",
+    "gvn/loads.ir",
+    "inline/int_to_ptr.ir",
+    "licm/storage_vec_key.ir",
+    "licm/while_loop.ir",
+    "memcpyopt/ret_value.ir",
+    "serialize/entry.ir",
+    "serialize/intrinsic_addr_of.ir",
+    "serialize/str_slice.ir",
+];
+
+// The functions which can't be run, as they call functions without passing their arguments.
+const NOT_RUNNABLE_FUNCTIONS: [(&str, &str); 2] = [
+    ("inline/by_block_and_instr_count.ir", "main"),
+    ("simplify_cfg/dead_blocks.ir", "main"),
+];
+
+#[test]
+fn passes_preserve_behaviour() {
+    // Every test program, before and after each transform pass, and the O1 and Oz pipelines for the
+    // interpreter tests, must behave identically when run.
+    let single_passes = [
+        ARGDEMOTION_NAME,
+        CONSTCOMBINE_NAME,
+        CONSTDEMOTION_NAME,
+        DCE_NAME,
+        FNDEDUP_AGGRESSIVE_NAME,
+        FNDEDUP_NAME,
+        FUNC_DCE_NAME,
+        GVN_NAME,
+        INLINE_MAIN_NAME,
+        INLINE_MODULE_NAME,
+        INLINE_MODULE_SIZE_NAME,
+        LICM_NAME,
+        MEM2REG_NAME,
+        MEMCPYOPT_NAME,
+        MISCDEMOTION_NAME,
+        RETDEMOTION_NAME,
+        SCCP_NAME,
+        SIMPLIFYCFG_NAME,
+        SROA_NAME,
+    ]
+    .map(|name| {
+        let mut group = PassGroup::default();
+        group.append_pass(name);
+        (name, group)
+    });
+    let pipelines = [
+        ("o1", sway_ir::create_o1_pass_group()),
+        ("oz", sway_ir::create_oz_pass_group()),
+    ];
+
+    let source_engine = SourceEngine::default();
+    let parse = |path: &Path, input: &str| {
+        let ir = sway_ir::parser::parse(input, &source_engine, ExperimentalFlags::default())
+            .unwrap_or_else(|parse_err| panic!("{}: {parse_err}", path.display()));
+        ir.verify()
+            .unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+    };
+
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let mut dirs = std::fs::read_dir(format!("{manifest_dir}/tests"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    dirs.sort();
+    for dir in dirs {
+        let is_interpret_dir = dir.ends_with("interpret");
+        let mut paths = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ir"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        for path in paths {
+            let input = std::fs::read_to_string(&path).unwrap();
+            let ir = parse(&path, &input);
+            let test_name = path
+                .strip_prefix(dir.parent().unwrap())
+                .unwrap()
+                .to_str()
+                .unwrap();
+            if NOT_RUNNABLE.contains(&test_name) {
+                continue;
+            }
+
+            let expected = interpret_functions(&ir)
+                .into_iter()
+                .filter(|(name, _, _)| !NOT_RUNNABLE_FUNCTIONS.contains(&(test_name, name)))
+                .filter_map(|(name, signature, result)| {
+                    let result = result.unwrap_or_else(|err| {
+                        panic!("{}: '{name}' can't be interpreted: {err}", path.display())
+                    });
+                    // Whether it runs out of gas is up to the optimizations.
+                    result.map(|result| (name, signature, result))
+                })
+                .collect::<Vec<_>>();
+            assert!(
+                !expected.is_empty(),
+                "{}: no function can be interpreted",
+                path.display()
+            );
+
+            let groups = single_passes
+                .iter()
+                .chain(pipelines.iter().filter(|_| is_interpret_dir));
+            for (group_name, group) in groups {
+                let mut ir = parse(&path, &input);
+                let mut pass_mgr = PassManager::default();
+                register_known_passes(&mut pass_mgr);
+                if let Err(err) = pass_mgr.run(&mut ir, group) {
+                    panic!("{}: {group_name} failed: {err}", path.display());
+                }
+                let ir = ir.verify().unwrap_or_else(|err| {
+                    panic!("{}: invalid IR after {group_name}: {err}", path.display())
+                });
+
+                let actual = interpret_functions(&ir);
+                for (name, signature, expected_result) in &expected {
+                    // The pass may have removed or changed the signature of the function.
+                    let Some((_, _, actual_result)) =
+                        actual.iter().find(|(actual_name, actual_signature, _)| {
+                            actual_name == name && actual_signature == signature
+                        })
+                    else {
+                        continue;
+                    };
+                    let actual_result = actual_result.as_ref().unwrap_or_else(|err| {
+                        panic!(
+                            "{}: '{name}' can't be interpreted after {group_name}: {err}",
+                            path.display()
+                        )
+                    });
+                    assert_eq!(
+                        actual_result.as_ref(),
+                        Some(expected_result),
+                        "{}: '{name}' behaves differently after {group_name}",
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+}
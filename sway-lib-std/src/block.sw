@@ -132,7 +132,7 @@ pub fn block_header_hash(block_height: u32) -> Result<b256, BlockHashError> {
 #[test(should_revert)]
 fn test_block_header_hash_err_current_height() {
     // Get the header hash of the current block. Each time this test runs, the block height will be 1. calling BHSH with a height >= current height will fail.
-    let mut hash = block_header_hash(height());
+    let hash = block_header_hash(height());
     let correct_error = match hash {
         Ok(_) => false,
         Err(BlockHashError::BlockHeightTooHigh) => true,
@@ -1007,7 +1007,7 @@ fn test_pop() {
 
 #[test()]
 fn test_len() {
-    let (mut bytes, _, _, _) = setup();
+    let (bytes, _, _, _) = setup();
     assert(bytes.len() == 3);
 }
 
@@ -1146,7 +1146,7 @@ fn test_from_vec_u8() {
 
 #[test()]
 fn test_into_vec_u8() {
-    let (mut bytes, a, b, c) = setup();
+    let (bytes, a, b, c) = setup();
     assert(bytes.len() == 3);
 
     let vec: Vec<u8> = bytes.into();
@@ -1181,7 +1181,7 @@ fn test_bytes_limits() {
 
 #[test()]
 fn test_split_at() {
-    let (mut original, _a, _b, _c) = setup();
+    let (original, _a, _b, _c) = setup();
     assert(original.len() == 3);
     let index = 1;
     let (left, right) = original.split_at(index);
@@ -1193,7 +1193,7 @@ fn test_split_at() {
 
 #[test()]
 fn test_split_at_0() {
-    let (mut original, _a, _b, _c) = setup();
+    let (original, _a, _b, _c) = setup();
     assert(original.len() == 3);
     let index = 0;
     let (left, right) = original.split_at(index);
@@ -1205,7 +1205,7 @@ fn test_split_at_0() {
 
 #[test()]
 fn test_split_at_len() {
-    let (mut original, _a, _b, _c) = setup();
+    let (original, _a, _b, _c) = setup();
     assert(original.len() == 3);
     let index = 3;
     let (left, right) = original.split_at(index);
@@ -1296,7 +1296,7 @@ fn test_append_to_empty_bytes() {
 #[test()]
 fn test_eq() {
     let (mut bytes, _a, _b, _c) = setup();
-    let (mut bytes2, _a, _b, _c) = setup();
+    let (bytes2, _a, _b, _c) = setup();
     assert(bytes == bytes2);
 
     let d = 5u8;
@@ -1324,7 +1324,7 @@ fn test_as_raw_slice() {
     let slice_1 = asm(ptr: (__addr_of(val), 32)) {
         ptr: raw_slice
     };
-    let mut bytes = Bytes::from(slice_1);
+    let bytes = Bytes::from(slice_1);
     let slice_2 = bytes.as_raw_slice();
     assert(slice_1.ptr() == slice_2.ptr());
     assert(slice_1.number_of_bytes() == slice_2.number_of_bytes());
@@ -1337,7 +1337,7 @@ fn test_from_raw_slice() {
     let slice_1 = asm(ptr: (__addr_of(val), 32)) {
         ptr: raw_slice
     };
-    let mut bytes = Bytes::from(slice_1);
+    let bytes = Bytes::from(slice_1);
     let slice_2 = bytes.as_raw_slice();
     assert(slice_1.ptr() == slice_2.ptr());
     assert(slice_1.number_of_bytes() == slice_2.number_of_bytes());
@@ -275,9 +275,6 @@ pub fn call_with_function_selector_vec(
     single_value_type_arg: bool,
     call_params: CallParams,
 ) {
-    let mut function_selector = function_selector;
-    let mut calldata = calldata;
-
     call_with_function_selector(
         target,
         Bytes::from(function_selector),
@@ -282,7 +282,7 @@ impl Hash for String {
 
 #[test]
 fn string_test_as_bytes() {
-    let mut string = String::new();
+    let string = String::new();
 
     let bytes = string.as_bytes();
     assert(bytes.len() == 0);
@@ -299,7 +299,7 @@ fn string_test_as_bytes() {
 
 #[test]
 fn string_test_capacity() {
-    let mut string = String::new();
+    let string = String::new();
 
     assert(string.capacity() == 0);
 
@@ -337,7 +337,7 @@ fn string_test_from() {
     bytes.push(3u8);
     bytes.push(4u8);
 
-    let mut string_from_bytes = String::from(bytes);
+    let string_from_bytes = String::from(bytes);
     let bytes = string_from_bytes.as_bytes();
     assert(bytes.len() == 5);
     assert(bytes.capacity() == string_from_bytes.capacity());
@@ -357,7 +357,7 @@ fn string_test_from_raw_slice() {
     bytes.push(4u8);
 
     let raw_slice = bytes.as_raw_slice();
-    let mut string_from_slice = String::from(raw_slice);
+    let string_from_slice = String::from(raw_slice);
     let bytes = string_from_slice.as_bytes();
     assert(bytes.len() == 5);
     assert(bytes.get(0).unwrap() == 0u8);
@@ -375,7 +375,7 @@ fn string_test_from_ascii() {
     bytes.push(3u8);
     bytes.push(4u8);
 
-    let mut string_from_ascii = String::from_ascii(bytes);
+    let string_from_ascii = String::from_ascii(bytes);
     assert(bytes.capacity() == string_from_ascii.capacity());
     let bytes = string_from_ascii.as_bytes();
     assert(bytes.get(0).unwrap() == 0u8);
@@ -385,7 +385,7 @@ fn string_test_from_ascii() {
 
 #[test]
 fn string_test_from_ascii_str() {
-    let mut string_from_ascii = String::from_ascii_str("ABCDEF");
+    let string_from_ascii = String::from_ascii_str("ABCDEF");
     assert(string_from_ascii.capacity() == 6);
     let bytes = string_from_ascii.as_bytes();
     assert(bytes.get(0).unwrap() == 65u8);
@@ -399,7 +399,7 @@ fn string_test_from_ascii_str() {
 
 #[test]
 fn string_test_into_bytes() {
-    let mut string = String::new();
+    let string = String::new();
 
     let bytes: Bytes = string.into();
     assert(bytes.len() == 0);
@@ -417,7 +417,7 @@ fn string_test_into_bytes() {
     bytes.push(0u8);
     bytes.push(1u8);
     let string = String::from_ascii(bytes);
-    let mut bytes: Bytes = string.into();
+    let bytes: Bytes = string.into();
     assert(bytes.len() == 2);
     assert(bytes.capacity() == string.capacity());
     assert(bytes.get(1).unwrap() == 1u8);
@@ -425,7 +425,7 @@ fn string_test_into_bytes() {
 
 #[test]
 fn string_test_into_raw_slice() {
-    let mut string = String::new();
+    let string = String::new();
 
     let raw_slice: raw_slice = string.into();
     assert(raw_slice.number_of_bytes() == 0);
@@ -441,18 +441,18 @@ fn string_test_into_raw_slice() {
     bytes.push(0u8);
     bytes.push(1u8);
     let string = String::from_ascii(bytes);
-    let mut raw_slice = string.as_raw_slice();
+    let raw_slice = string.as_raw_slice();
     assert(raw_slice.number_of_bytes() == 2);
     assert(raw_slice.ptr().add_uint_offset(1).read_byte() == 1u8);
 
-    let mut raw_slice = string.as_raw_slice();
+    let raw_slice = string.as_raw_slice();
     assert(raw_slice.number_of_bytes() == 2);
     assert(raw_slice.ptr().read_byte() == 0u8);
 }
 
 #[test]
 fn string_test_is_empty() {
-    let mut string = String::new();
+    let string = String::new();
 
     assert(string.is_empty());
 
@@ -473,7 +473,7 @@ fn string_test_is_empty() {
 
 #[test]
 fn string_test_new() {
-    let mut string = String::new();
+    let string = String::new();
 
     assert(string.is_empty());
     assert(string.capacity() == 0);
@@ -484,7 +484,7 @@ fn string_test_with_capacity() {
     let mut iterator = 0;
 
     while iterator < 16 {
-        let mut string = String::with_capacity(iterator);
+        let string = String::with_capacity(iterator);
         assert(string.capacity() == iterator);
         iterator += 1;
     }
@@ -494,7 +494,7 @@ fn string_test_with_capacity() {
 
     string.clear();
     assert(string.capacity() == 0);
-    let mut string = String::with_capacity(4);
+    let string = String::with_capacity(4);
 
     assert(string.capacity() == 4);
 }
@@ -548,7 +548,7 @@ impl Root for U128 {
     fn sqrt(self) -> Self {
         let zero = Self::from((0, 0));
         let mut x0 = self >> 1;
-        let mut s = self;
+        let s = self;
 
         if x0 != zero {
             let mut x1 = (x0 + s / x0) >> 1;
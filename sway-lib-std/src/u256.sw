@@ -573,12 +573,12 @@ impl core::ops::Multiply for U256 {
                 let result_d_c = self.d.overflowing_mul(other.c);
                 let result_d_d = self.d.overflowing_mul(other.d);
 
-                let (overflow_of_c_to_b_1, mut c): (u64, u64) = result_d_d.upper().overflowing_add(result_c_d.lower()).into();
-                let (mut overflow_of_c_to_b_2, c): (u64, u64) = c.overflowing_add(result_d_c.lower()).into();
+                let (overflow_of_c_to_b_1, c): (u64, u64) = result_d_d.upper().overflowing_add(result_c_d.lower()).into();
+                let (overflow_of_c_to_b_2, c): (u64, u64) = c.overflowing_add(result_d_c.lower()).into();
 
                 let (overflow_of_b_to_a_0, overflow_of_c_to_b_2): (u64, u64) = overflow_of_c_to_b_1.overflowing_add(overflow_of_c_to_b_2).into();
 
-                let (overflow_of_b_to_a_1, mut b): (u64, u64) = result_b_d.lower().overflowing_add(result_c_d.upper()).into();
+                let (overflow_of_b_to_a_1, b): (u64, u64) = result_b_d.lower().overflowing_add(result_c_d.upper()).into();
                 let (overflow_of_b_to_a_2, b): (u64, u64) = b.overflowing_add(result_d_c.upper()).into();
                 let (overflow_of_b_to_a_3, b): (u64, u64) = b.overflowing_add(overflow_of_c_to_b_2).into();
 
@@ -598,12 +598,12 @@ impl core::ops::Multiply for U256 {
                 let result_d_c = other.d.overflowing_mul(self.c);
                 let result_d_d = other.d.overflowing_mul(self.d);
 
-                let (overflow_of_c_to_b_1, mut c): (u64, u64) = result_d_d.upper().overflowing_add(result_c_d.lower()).into();
-                let (mut overflow_of_c_to_b_2, c): (u64, u64) = c.overflowing_add(result_d_c.lower()).into();
+                let (overflow_of_c_to_b_1, c): (u64, u64) = result_d_d.upper().overflowing_add(result_c_d.lower()).into();
+                let (overflow_of_c_to_b_2, c): (u64, u64) = c.overflowing_add(result_d_c.lower()).into();
 
                 let (overflow_of_b_to_a_0, overflow_of_c_to_b_2): (u64, u64) = overflow_of_c_to_b_1.overflowing_add(overflow_of_c_to_b_2).into();
 
-                let (overflow_of_b_to_a_1, mut b): (u64, u64) = result_b_d.lower().overflowing_add(result_c_d.upper()).into();
+                let (overflow_of_b_to_a_1, b): (u64, u64) = result_b_d.lower().overflowing_add(result_c_d.upper()).into();
                 let (overflow_of_b_to_a_2, b): (u64, u64) = b.overflowing_add(result_d_c.upper()).into();
                 let (overflow_of_b_to_a_3, b): (u64, u64) = b.overflowing_add(overflow_of_c_to_b_2).into();
 
@@ -620,13 +620,13 @@ impl core::ops::Multiply for U256 {
                 let result_d_c = self.d.overflowing_mul(other.c);
                 let result_d_d = self.d.overflowing_mul(other.d);
 
-                let (overflow_of_c_to_b_1, mut c): (u64, u64) = result_d_d.upper().overflowing_add(result_c_d.lower()).into();
+                let (overflow_of_c_to_b_1, c): (u64, u64) = result_d_d.upper().overflowing_add(result_c_d.lower()).into();
 
-                let (mut overflow_of_c_to_b_2, c): (u64, u64) = c.overflowing_add(result_d_c.lower()).into();
+                let (overflow_of_c_to_b_2, c): (u64, u64) = c.overflowing_add(result_d_c.lower()).into();
 
                 let (overflow_of_b_to_a_0, overflow_of_c_to_b_2): (u64, u64) = overflow_of_c_to_b_1.overflowing_add(overflow_of_c_to_b_2).into();
 
-                let (overflow_of_b_to_a_1, mut b): (u64, u64) = result_c_c.lower().overflowing_add(result_c_d.upper()).into();
+                let (overflow_of_b_to_a_1, b): (u64, u64) = result_c_c.lower().overflowing_add(result_c_d.upper()).into();
                 let (overflow_of_b_to_a_2, b): (u64, u64) = b.overflowing_add(result_d_c.upper()).into();
                 let (overflow_of_b_to_a_3, b): (u64, u64) = b.overflowing_add(overflow_of_c_to_b_2).into();
 
@@ -30,7 +30,10 @@ pub(crate) fn import_code_action(
     diagnostics: &mut impl Iterator<Item = (Range, DiagnosticData)>,
 ) -> Option<Vec<CodeActionOrCommand>> {
     // Find a diagnostic that has the attached metadata indicating we should try to suggest an auto-import.
-    let symbol_name = diagnostics.find_map(|(_, diag)| diag.unknown_symbol_name)?;
+    // If the compiler already suggested the imports, they are offered by `suggestion_code_action`.
+    let symbol_name = diagnostics
+        .filter(|(_, diag)| diag.suggestions.is_empty())
+        .find_map(|(_, diag)| diag.unknown_symbol_name)?;
 
     // Check if there are any matching call paths to import using the name from the diagnostic data.
     let call_paths = get_call_paths_for_name(ctx, &symbol_name)?;
@@ -1,11 +1,13 @@
 mod auto_import;
 mod qualify;
+mod suggestion;
 
 use crate::capabilities::{code_actions::CodeActionContext, diagnostic::DiagnosticData};
 use lsp_types::CodeActionOrCommand;
 
 use self::auto_import::import_code_action;
 use self::qualify::qualify_code_action;
+use self::suggestion::suggestion_code_action;
 
 /// Returns a list of [CodeActionOrCommand] based on the relavent compiler diagnostics.
 pub(crate) fn code_actions(ctx: &CodeActionContext) -> Option<Vec<CodeActionOrCommand>> {
@@ -19,8 +21,9 @@ pub(crate) fn code_actions(ctx: &CodeActionContext) -> Option<Vec<CodeActionOrCo
         None
     });
 
-    import_code_action(ctx, &mut diagnostics_with_data.clone())
+    suggestion_code_action(ctx, &mut diagnostics_with_data.clone())
         .into_iter()
+        .chain(import_code_action(ctx, &mut diagnostics_with_data.clone()))
         .chain(qualify_code_action(ctx, &mut diagnostics_with_data.clone()))
         .reduce(|mut combined, mut curr| {
             combined.append(&mut curr);
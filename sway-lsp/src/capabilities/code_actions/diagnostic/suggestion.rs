@@ -0,0 +1,44 @@
+use crate::capabilities::{code_actions::CodeActionContext, diagnostic::DiagnosticData};
+use lsp_types::{
+    CodeAction as LspCodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit,
+    WorkspaceEdit,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Returns a list of [CodeActionOrCommand] quick fixes, one for each of the edits suggested by the
+/// compiler in the diagnostics.
+pub(crate) fn suggestion_code_action(
+    ctx: &CodeActionContext,
+    diagnostics: &mut impl Iterator<Item = (Range, DiagnosticData)>,
+) -> Option<Vec<CodeActionOrCommand>> {
+    let actions = diagnostics
+        .flat_map(|(_, diag)| diag.suggestions)
+        .map(|suggestion| {
+            let text_edit = TextEdit {
+                range: suggestion.range,
+                new_text: suggestion.replacement,
+            };
+
+            let changes = HashMap::from([(ctx.uri.clone(), vec![text_edit])]);
+
+            CodeActionOrCommand::CodeAction(LspCodeAction {
+                title: suggestion.title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }),
+                is_preferred: suggestion.is_machine_applicable.then_some(true),
+                data: Some(Value::String(ctx.uri.to_string())),
+                ..Default::default()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if !actions.is_empty() {
+        return Some(actions);
+    }
+
+    None
+}
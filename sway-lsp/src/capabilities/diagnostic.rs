@@ -3,9 +3,10 @@ use std::path::PathBuf;
 
 use lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, Position, Range};
 use serde::{Deserialize, Serialize};
+use sway_error::diagnostic::{Applicability, Suggestion, ToDiagnostic};
 use sway_error::warning::CompileWarning;
 use sway_error::{error::CompileError, warning::Warning};
-use sway_types::{LineCol, SourceEngine, Spanned};
+use sway_types::{LineCol, SourceEngine, Span, Spanned};
 
 pub(crate) type DiagnosticMap = HashMap<PathBuf, Diagnostics>;
 
@@ -15,8 +16,14 @@ pub struct Diagnostics {
     pub errors: Vec<Diagnostic>,
 }
 
-fn get_error_diagnostic(error: &CompileError) -> Diagnostic {
-    let data = serde_json::to_value(DiagnosticData::try_from(error.clone()).ok()).ok();
+fn get_error_diagnostic(error: &CompileError, source_engine: &SourceEngine) -> Diagnostic {
+    let data = DiagnosticData::try_from(error.clone())
+        .unwrap_or_default()
+        .with_suggestions(
+            &error.span(),
+            error.to_diagnostic(source_engine).suggestions(),
+        );
+    let data = serde_json::to_value(data).ok();
 
     Diagnostic {
         range: get_range(error.span().line_col()),
@@ -27,12 +34,21 @@ fn get_error_diagnostic(error: &CompileError) -> Diagnostic {
     }
 }
 
-fn get_warning_diagnostic(warning: &CompileWarning) -> Diagnostic {
+fn get_warning_diagnostic(warning: &CompileWarning, source_engine: &SourceEngine) -> Diagnostic {
+    let data = DiagnosticData::try_from(warning.clone())
+        .unwrap_or_default()
+        .with_suggestions(
+            &warning.span(),
+            warning.to_diagnostic(source_engine).suggestions(),
+        );
+    let data = serde_json::to_value(data).ok();
+
     Diagnostic {
         range: get_range(warning.span().line_col()),
         severity: Some(DiagnosticSeverity::WARNING),
         message: warning.to_friendly_warning_string(),
         tags: get_warning_diagnostic_tags(&warning.warning_content),
+        data,
         ..Default::default()
     }
 }
@@ -44,7 +60,7 @@ pub fn get_diagnostics(
 ) -> DiagnosticMap {
     let mut diagnostics = DiagnosticMap::new();
     for warning in warnings {
-        let diagnostic = get_warning_diagnostic(warning);
+        let diagnostic = get_warning_diagnostic(warning, source_engine);
         if let Some(source_id) = warning.span().source_id() {
             let path = source_engine.get_path(source_id);
             diagnostics
@@ -55,7 +71,7 @@ pub fn get_diagnostics(
         }
     }
     for error in errors {
-        let diagnostic = get_error_diagnostic(error);
+        let diagnostic = get_error_diagnostic(error, source_engine);
         if let Some(source_id) = error.span().source_id() {
             let path = source_engine.get_path(source_id);
             diagnostics.entry(path).or_default().errors.push(diagnostic);
@@ -86,6 +102,7 @@ fn get_warning_diagnostic_tags(warning: &Warning) -> Option<Vec<DiagnosticTag>>
         | Warning::DeadTrait
         | Warning::MatchExpressionUnreachableArm { .. }
         | Warning::UnreachableCode
+        | Warning::UnusedMut { .. }
         | Warning::UnusedReturnValue { .. } => Some(vec![DiagnosticTag::UNNECESSARY]),
         _ => None,
     }
@@ -95,6 +112,49 @@ fn get_warning_diagnostic_tags(warning: &Warning) -> Option<Vec<DiagnosticTag>>
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct DiagnosticData {
     pub unknown_symbol_name: Option<String>,
+    /// The edits suggested by the compiler to fix the issue, in the file of the diagnostic.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<SuggestionData>,
+}
+
+impl DiagnosticData {
+    /// Adds the `suggestions` which edit the same file as the diagnostic. Edits to other files
+    /// cannot be offered as quick fixes of the diagnostic.
+    fn with_suggestions(mut self, span: &Span, suggestions: &[Suggestion]) -> Self {
+        self.suggestions = suggestions
+            .iter()
+            .filter(|suggestion| suggestion.span.source_id() == span.source_id())
+            .map(SuggestionData::from)
+            .collect();
+        self
+    }
+}
+
+/// A [Suggestion] of the compiler, converted to the positions used by the LSP.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuggestionData {
+    pub title: String,
+    pub range: Range,
+    pub replacement: String,
+    pub is_machine_applicable: bool,
+}
+
+impl From<&Suggestion> for SuggestionData {
+    fn from(suggestion: &Suggestion) -> Self {
+        let title = if suggestion.replacement.is_empty() {
+            format!("Remove `{}`", suggestion.span.as_str().trim())
+        } else if suggestion.span.as_str().is_empty() {
+            format!("Insert `{}`", suggestion.replacement.trim())
+        } else {
+            format!("Replace with `{}`", suggestion.replacement)
+        };
+        SuggestionData {
+            title,
+            range: get_range(suggestion.span.line_col()),
+            replacement: suggestion.replacement.clone(),
+            is_machine_applicable: suggestion.applicability == Applicability::MachineApplicable,
+        }
+    }
 }
 
 impl TryFrom<CompileWarning> for DiagnosticData {
@@ -112,12 +172,15 @@ impl TryFrom<CompileError> for DiagnosticData {
         match value {
             CompileError::SymbolNotFound { name, .. } => Ok(DiagnosticData {
                 unknown_symbol_name: Some(name.to_string()),
+                ..Default::default()
             }),
             CompileError::TraitNotFound { name, .. } => Ok(DiagnosticData {
                 unknown_symbol_name: Some(name),
+                ..Default::default()
             }),
             CompileError::UnknownVariable { var_name, .. } => Ok(DiagnosticData {
                 unknown_symbol_name: Some(var_name.to_string()),
+                ..Default::default()
             }),
             _ => anyhow::bail!("Not implemented"),
         }
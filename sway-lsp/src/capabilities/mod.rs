@@ -11,6 +11,8 @@ pub mod on_enter;
 pub mod rename;
 pub mod runnable;
 pub mod semantic_tokens;
+pub mod signature_help;
+pub mod workspace_symbol;
 
 pub(crate) use code_actions::code_actions;
 pub(crate) use on_enter::on_enter;
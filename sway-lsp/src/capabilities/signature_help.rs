@@ -0,0 +1,437 @@
+use crate::{
+    core::{
+        session::Session,
+        token::{Token, TypedAstToken},
+    },
+    utils::markdown,
+};
+use lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, Position,
+    SignatureHelp, SignatureInformation, Url,
+};
+use std::sync::Arc;
+use sway_core::{
+    language::ty,
+    transform::{AttributeKind, AttributesMap},
+    Engines, TypeArgument, TypeParameter,
+};
+use sway_types::Spanned;
+
+/// Returns the signature of the function, method or ABI method being called at the given
+/// position, with the parameter the cursor is in highlighted.
+pub fn signature_help(
+    session: Arc<Session>,
+    url: &Url,
+    position: Position,
+) -> Option<SignatureHelp> {
+    let text = session.get_text_document(url).ok()?.get_text();
+    let call = CallContext::at_position(&text, position)?;
+    let engines = session.engines.read();
+    let signatures = resolve_callee(&session, &engines, url, &call);
+    let first = signatures.first()?;
+    let active_parameter = call.argument_index + first.skipped_self as u32;
+    Some(SignatureHelp {
+        signatures: signatures
+            .into_iter()
+            .map(|signature| {
+                let active_parameter = call.argument_index + signature.skipped_self as u32;
+                SignatureInformation {
+                    active_parameter: Some(active_parameter),
+                    ..signature.information
+                }
+            })
+            .collect(),
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    })
+}
+
+/// The call surrounding the cursor, found by scanning the text of the document backwards.
+///
+/// The text is used rather than the typed tree, as the call is usually incomplete and fails to
+/// compile while its arguments are being typed.
+#[derive(Debug, PartialEq)]
+struct CallContext {
+    /// The name of the called function or method.
+    name: String,
+    /// The position of the start of the name.
+    name_position: Position,
+    /// Whether the call uses the method syntax, `receiver.method(..)`.
+    is_method_call: bool,
+    /// The index of the argument that the cursor is in.
+    argument_index: u32,
+}
+
+impl CallContext {
+    fn at_position(text: &str, position: Position) -> Option<CallContext> {
+        let chars = text_before_position(text, position);
+
+        // Find the unclosed opening parenthesis of the call, counting the arguments before the
+        // cursor.
+        let mut depth = 0;
+        let mut argument_index = 0;
+        let mut in_string = false;
+        let mut open_paren = None;
+        for (i, &c) in chars.iter().enumerate().rev() {
+            if in_string {
+                in_string = c != '"';
+                continue;
+            }
+            match c {
+                '"' => in_string = true,
+                ')' | ']' | '}' => depth += 1,
+                '(' | '[' | '{' if depth > 0 => depth -= 1,
+                '(' => {
+                    open_paren = Some(i);
+                    break;
+                }
+                '[' | '{' | ';' => return None,
+                ',' if depth == 0 => argument_index += 1,
+                _ => {}
+            }
+        }
+
+        // Skip any type arguments, as in `foo::<T>(..)`, to find the end of the name.
+        let mut end = skip_whitespace_back(&chars, open_paren?);
+        if end > 0 && chars[end - 1] == '>' {
+            let mut depth = 0;
+            while end > 0 {
+                end -= 1;
+                match chars[end] {
+                    '>' => depth += 1,
+                    '<' => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    break;
+                }
+            }
+            end = skip_whitespace_back(&chars, end);
+            if end < 2 || chars[end - 2..end] != [':', ':'] {
+                return None;
+            }
+            end = skip_whitespace_back(&chars, end - 2);
+        }
+        let start = chars[..end]
+            .iter()
+            .rposition(|c| !(c.is_alphanumeric() || *c == '_'))
+            .map_or(0, |i| i + 1);
+        let name: String = chars[start..end].iter().collect();
+        if name.is_empty()
+            || name.starts_with(|c: char| c.is_ascii_digit())
+            || sway_parse::RESERVED_KEYWORDS.contains(name.as_str())
+        {
+            return None;
+        }
+        let before_name = skip_whitespace_back(&chars, start);
+        let is_method_call = before_name > 0 && chars[before_name - 1] == '.';
+
+        let line_start = chars[..start]
+            .iter()
+            .rposition(|c| *c == '\n')
+            .map_or(0, |i| i + 1);
+        let name_position = Position::new(
+            chars[..start].iter().filter(|c| **c == '\n').count() as u32,
+            chars[line_start..start]
+                .iter()
+                .map(|c| c.len_utf16() as u32)
+                .sum(),
+        );
+        Some(CallContext {
+            name,
+            name_position,
+            is_method_call,
+            argument_index,
+        })
+    }
+}
+
+/// Returns the characters of the text before the position.
+fn text_before_position(text: &str, position: Position) -> Vec<char> {
+    let mut chars = Vec::new();
+    for (line_index, line) in text.split('\n').enumerate() {
+        if line_index < position.line as usize {
+            chars.extend(line.chars());
+            chars.push('\n');
+            continue;
+        }
+        let mut utf16_len = 0;
+        for c in line.chars() {
+            if utf16_len >= position.character {
+                break;
+            }
+            utf16_len += c.len_utf16() as u32;
+            chars.push(c);
+        }
+        break;
+    }
+    chars
+}
+
+/// Returns the index following the last non-whitespace character before `end`.
+fn skip_whitespace_back(chars: &[char], end: usize) -> usize {
+    chars[..end]
+        .iter()
+        .rposition(|c| !c.is_whitespace())
+        .map_or(0, |i| i + 1)
+}
+
+/// The signature of a callee.
+struct Signature {
+    information: SignatureInformation,
+    /// Whether the callee's `self` parameter is passed as the receiver of a method call, so
+    /// that the arguments start at its second parameter.
+    skipped_self: bool,
+}
+
+/// Resolves the callee of the call to its declarations.
+///
+/// If the program compiled with the call, the callee is the declaration that the compiler
+/// resolved it to, including trait methods resolved through the trait map and ABI methods.
+/// Otherwise, the declarations of functions with the callee's name are used.
+fn resolve_callee(
+    session: &Session,
+    engines: &Engines,
+    url: &Url,
+    call: &CallContext,
+) -> Vec<Signature> {
+    let token_map = session.token_map();
+    let resolved = token_map
+        .token_at_position(url, call.name_position)
+        .filter(|item| item.key().name == call.name)
+        .and_then(|item| {
+            let token = item.value();
+            if let Some(TypedAstToken::TypedExpression(ty::TyExpression {
+                expression: ty::TyExpressionVariant::FunctionApplication { fn_ref, .. },
+                ..
+            })) = &token.typed
+            {
+                let fn_decl = engines.de().get_function(fn_ref);
+                return Some(fn_decl_signature(engines, &fn_decl, call));
+            }
+            let decl_ident = token.declared_token_ident(engines)?;
+            let decl = token_map.try_get(&decl_ident).try_unwrap()?;
+            decl_signature(engines, decl.value(), call)
+        });
+    if let Some(signature) = resolved {
+        return vec![signature];
+    }
+
+    let mut signatures: Vec<Signature> = Vec::new();
+    for item in token_map.tokens_for_name(&call.name) {
+        let (ident, token) = item.pair();
+        if token.declared_token_ident(engines).as_ref() != Some(ident) {
+            continue;
+        }
+        let Some(signature) = decl_signature(engines, token, call) else {
+            continue;
+        };
+        if signatures
+            .iter()
+            .all(|s| s.information.label != signature.information.label)
+        {
+            signatures.push(signature);
+        }
+    }
+    signatures.sort_by(|a, b| a.information.label.cmp(&b.information.label));
+    signatures
+}
+
+/// Returns the signature of the function declared by the token, if any.
+fn decl_signature(engines: &Engines, token: &Token, call: &CallContext) -> Option<Signature> {
+    match token.typed.as_ref()? {
+        TypedAstToken::TypedFunctionDeclaration(fn_decl) => {
+            Some(fn_decl_signature(engines, fn_decl, call))
+        }
+        TypedAstToken::TypedDeclaration(ty::TyDecl::FunctionDecl(ty::FunctionDecl {
+            decl_id,
+            ..
+        })) => {
+            let fn_decl = engines.de().get_function(decl_id);
+            Some(fn_decl_signature(engines, &fn_decl, call))
+        }
+        TypedAstToken::TypedTraitFn(trait_fn) => Some(signature(
+            engines,
+            &trait_fn.name.to_string(),
+            &[],
+            &trait_fn.parameters,
+            &trait_fn.return_type,
+            &trait_fn.attributes,
+            call,
+        )),
+        _ => None,
+    }
+}
+
+fn fn_decl_signature(
+    engines: &Engines,
+    fn_decl: &ty::TyFunctionDecl,
+    call: &CallContext,
+) -> Signature {
+    // Only list the type parameters declared by the function itself, not those of its impl.
+    let type_parameters: Vec<_> = fn_decl
+        .type_parameters
+        .iter()
+        .filter(|type_param| {
+            let span = type_param.name_ident.span();
+            span.source_id() == fn_decl.span.source_id()
+                && span.start() >= fn_decl.span.start()
+                && span.end() <= fn_decl.span.end()
+        })
+        .cloned()
+        .collect();
+    signature(
+        engines,
+        fn_decl.name.as_str(),
+        &type_parameters,
+        &fn_decl.parameters,
+        &fn_decl.return_type,
+        &fn_decl.attributes,
+        call,
+    )
+}
+
+/// Formats the signature as `fn name<T>(param: Type) -> ReturnType`, with the doc comment of the
+/// declaration as its documentation.
+fn signature(
+    engines: &Engines,
+    name: &str,
+    type_parameters: &[TypeParameter],
+    parameters: &[ty::TyFunctionParameter],
+    return_type: &TypeArgument,
+    attributes: &AttributesMap,
+    call: &CallContext,
+) -> Signature {
+    let type_name = |type_argument: &TypeArgument| {
+        let span = type_argument.span.as_str();
+        if span.is_empty() {
+            engines.help_out(type_argument.type_id).to_string()
+        } else {
+            span.to_string()
+        }
+    };
+
+    let mut label = format!("fn {name}");
+    if !type_parameters.is_empty() {
+        let names: Vec<_> = type_parameters
+            .iter()
+            .map(|type_param| type_param.name_ident.as_str())
+            .collect();
+        label.push_str(&format!("<{}>", names.join(", ")));
+    }
+    label.push('(');
+    let mut parameter_information = Vec::new();
+    for (i, param) in parameters.iter().enumerate() {
+        if i > 0 {
+            label.push_str(", ");
+        }
+        let param_label = if param.is_self() {
+            match (param.is_reference, param.is_mutable) {
+                (true, true) => "ref mut self".to_string(),
+                (false, true) => "mut self".to_string(),
+                _ => "self".to_string(),
+            }
+        } else {
+            format!("{}: {}", param.name, type_name(&param.type_argument))
+        };
+        let start = label.encode_utf16().count() as u32;
+        label.push_str(&param_label);
+        let end = label.encode_utf16().count() as u32;
+        parameter_information.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: None,
+        });
+    }
+    label.push(')');
+    if !engines.te().get(return_type.type_id).is_unit() {
+        label.push_str(&format!(" -> {}", type_name(return_type)));
+    }
+
+    let doc_comment = attributes
+        .get(&AttributeKind::DocComment)
+        .map(|attributes| {
+            attributes
+                .iter()
+                .filter_map(|attribute| attribute.args.first())
+                .fold(String::new(), |mut doc_comment, arg| {
+                    let line = arg.name.as_str();
+                    doc_comment.push_str(line.strip_prefix(' ').unwrap_or(line));
+                    doc_comment.push('\n');
+                    doc_comment
+                })
+        })
+        .unwrap_or_default();
+    let documentation = (!doc_comment.is_empty()).then(|| {
+        Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: markdown::format_docs(&doc_comment),
+        })
+    });
+
+    Signature {
+        information: SignatureInformation {
+            label,
+            documentation,
+            parameters: Some(parameter_information),
+            active_parameter: None,
+        },
+        skipped_self: call.is_method_call && parameters.first().is_some_and(|p| p.is_self()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call_at_end(text: &str) -> Option<CallContext> {
+        let lines: Vec<_> = text.split('\n').collect();
+        let position = Position::new(
+            lines.len() as u32 - 1,
+            lines.last().unwrap().encode_utf16().count() as u32,
+        );
+        CallContext::at_position(text, position)
+    }
+
+    #[test]
+    fn call_context_at_position() {
+        assert_eq!(
+            call_at_end("fn main() {\n    let x = foo(1, bar(2, 3), "),
+            Some(CallContext {
+                name: "foo".to_string(),
+                name_position: Position::new(1, 12),
+                is_method_call: false,
+                argument_index: 2,
+            })
+        );
+        assert_eq!(
+            call_at_end("    abi(MyAbi, id).transfer(\"a, b\", "),
+            Some(CallContext {
+                name: "transfer".to_string(),
+                name_position: Position::new(0, 19),
+                is_method_call: true,
+                argument_index: 1,
+            })
+        );
+        assert_eq!(
+            call_at_end("    Vec::new().push(Foo { a: 1, b: 2 }"),
+            Some(CallContext {
+                name: "push".to_string(),
+                name_position: Position::new(0, 15),
+                is_method_call: true,
+                argument_index: 0,
+            })
+        );
+        assert_eq!(
+            call_at_end("    baz::<u8, u16>("),
+            Some(CallContext {
+                name: "baz".to_string(),
+                name_position: Position::new(0, 4),
+                is_method_call: false,
+                argument_index: 0,
+            })
+        );
+        assert_eq!(call_at_end("    let x = (1, "), None);
+        assert_eq!(call_at_end("    if (a"), None);
+        assert_eq!(call_at_end("    foo(1);\n    let x = 2"), None);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{
+    capabilities::document_symbol::symbol_kind,
+    core::{session::Session, token::SymbolKind},
+    utils::document::get_url_from_path,
+};
+use lsp_types::{Location, SymbolInformation};
+use std::{collections::HashSet, sync::Arc};
+
+/// The maximum number of symbols returned for a single query.
+const MAX_SYMBOLS: usize = 128;
+
+/// Returns the declarations of the sessions' workspaces and their dependencies whose name
+/// fuzzy matches the query, with the best matches first.
+pub fn workspace_symbols(sessions: &[Arc<Session>], query: &str) -> Vec<SymbolInformation> {
+    let mut seen = HashSet::new();
+    let mut matches = Vec::new();
+    for session in sessions {
+        let engines = session.engines.read();
+        for item in session.token_map().iter() {
+            let (ident, token) = item.pair();
+            if !is_searchable(&token.kind) {
+                continue;
+            }
+            let Some(score) = fuzzy_match(query, &ident.name) else {
+                continue;
+            };
+            // Only the declaration of a symbol is listed, not its uses.
+            if token.declared_token_ident(&engines).as_ref() != Some(ident) {
+                continue;
+            }
+            let Some(url) = ident
+                .path
+                .as_ref()
+                .and_then(|path| get_url_from_path(path).ok())
+                .and_then(|url| session.sync.to_workspace_url(url))
+            else {
+                continue;
+            };
+            // Dependencies such as `std` are shared by the sessions.
+            if !seen.insert((
+                url.clone(),
+                ident.range.start.line,
+                ident.range.start.character,
+            )) {
+                continue;
+            }
+            #[allow(deprecated)]
+            let symbol = SymbolInformation {
+                name: ident.name.clone(),
+                kind: symbol_kind(&token.kind),
+                location: Location::new(url, ident.range),
+                tags: None,
+                container_name: None,
+                deprecated: None,
+            };
+            matches.push((score, symbol));
+        }
+    }
+    matches.sort_by(|(a_score, a), (b_score, b)| {
+        a_score
+            .cmp(b_score)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.location.uri.as_str().cmp(b.location.uri.as_str()))
+            .then_with(|| a.location.range.start.cmp(&b.location.range.start))
+    });
+    matches
+        .into_iter()
+        .take(MAX_SYMBOLS)
+        .map(|(_, symbol)| symbol)
+        .collect()
+}
+
+/// Whether symbols of this kind can be searched for.
+fn is_searchable(kind: &SymbolKind) -> bool {
+    matches!(
+        kind,
+        SymbolKind::Function
+            | SymbolKind::Const
+            | SymbolKind::Struct
+            | SymbolKind::Enum
+            | SymbolKind::Variant
+            | SymbolKind::Trait
+            | SymbolKind::TypeAlias
+            | SymbolKind::Field
+    )
+}
+
+/// Matches the query against a name, ignoring case, if the characters of the query appear in the
+/// name in the same order.
+///
+/// Returns a score that is lower for better matches: names starting with the query come first,
+/// followed by names containing it, followed by the remaining matches ordered by how spread out
+/// the matched characters are. Shorter names are preferred among equal matches.
+fn fuzzy_match(query: &str, name: &str) -> Option<(u8, usize, usize)> {
+    let query = query.to_lowercase();
+    let name_lower = name.to_lowercase();
+    if name_lower.starts_with(&query) {
+        return Some((0, 0, name.len()));
+    }
+    if name_lower.contains(&query) {
+        return Some((1, 0, name.len()));
+    }
+    let mut gaps = 0;
+    let mut name_chars = name_lower.chars();
+    for query_char in query.chars() {
+        loop {
+            let name_char = name_chars.next()?;
+            if name_char == query_char {
+                break;
+            }
+            gaps += 1;
+        }
+    }
+    Some((2, gaps, name.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_match_orders_matches() {
+        assert_eq!(fuzzy_match("", "anything"), Some((0, 0, 8)));
+        assert_eq!(fuzzy_match("Trans", "transfer"), Some((0, 0, 8)));
+        assert_eq!(fuzzy_match("fer", "transfer"), Some((1, 0, 8)));
+        assert_eq!(fuzzy_match("tfr", "transfer"), Some((2, 5, 8)));
+        assert_eq!(fuzzy_match("rft", "transfer"), None);
+        assert!(
+            fuzzy_match("gsf", "get_storage_field") < fuzzy_match("gsf", "get_some_other_field")
+        );
+    }
+}
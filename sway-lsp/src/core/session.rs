@@ -15,6 +15,7 @@ use crate::{
     traverse::{
         dependency, lexed_tree, parsed_tree::ParsedTree, typed_tree::TypedTree, ParseContext,
     },
+    utils::document::get_url_from_path,
 };
 use dashmap::DashMap;
 use forc_pkg as pkg;
@@ -147,6 +148,39 @@ impl Session {
         Some(token_ranges)
     }
 
+    /// Returns the locations of all references of the token at the given position, across all
+    /// files of the workspace and its dependencies.
+    ///
+    /// The declaration of the token is only included if `include_declaration` is set.
+    pub fn token_references(
+        &self,
+        url: &Url,
+        position: Position,
+        include_declaration: bool,
+    ) -> Option<Vec<Location>> {
+        let engines = self.engines.read();
+        let t = self.token_map.token_at_position(url, position)?;
+        let decl_ident = t.value().declared_token_ident(&engines);
+        let mut locations: Vec<_> = self
+            .token_map
+            .iter()
+            .all_references_of_token(t.value(), &engines)
+            .filter_map(|item| {
+                let ident = item.key();
+                if !include_declaration && Some(ident) == decl_ident.as_ref() {
+                    return None;
+                }
+                let url = get_url_from_path(ident.path.as_ref()?).ok()?;
+                self.sync
+                    .to_workspace_url(url)
+                    .map(|url| Location::new(url, ident.range))
+            })
+            .collect();
+        locations
+            .sort_by(|a, b| (a.uri.as_str(), a.range.start).cmp(&(b.uri.as_str(), b.range.start)));
+        Some(locations)
+    }
+
     pub fn token_definition_response(
         &self,
         uri: Url,
@@ -621,4 +655,47 @@ mod tests {
             if test_dir == dir
         ));
     }
+
+    #[test]
+    fn compile_restores_and_stores_cached_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg_dir = dir.path().join("pkg");
+        let core_dir = get_absolute_path("sway-lib-core");
+        std::fs::create_dir_all(pkg_dir.join("src")).unwrap();
+        std::fs::write(
+            pkg_dir.join("Forc.toml"),
+            format!(
+                "[project]\nauthors = [\"Fuel Labs <contact@fuel.sh>\"]\nentry = \"lib.sw\"\n\
+                 license = \"Apache-2.0\"\nname = \"pkg\"\nimplicit-std = false\n\n\
+                 [dependencies]\ncore = {{ path = \"{core_dir}\" }}\n"
+            ),
+        )
+        .unwrap();
+        std::fs::write(pkg_dir.join("src").join("lib.sw"), "library;\n").unwrap();
+        let uri = get_url(pkg_dir.to_str().unwrap());
+
+        let forc_dir = dir.path().join(".forc");
+        forc_util::with_user_forc_directory(&forc_dir, || {
+            // The number of modules of `core` reused from the engines rather than compiled.
+            let core_reused_modules = |engines: &Engines| {
+                let lsp_mode = Some(LspConfig {
+                    optimized_build: false,
+                });
+                let results = compile(&uri, engines, None, lsp_mode, Default::default()).unwrap();
+                let (core, _) = results.first().unwrap();
+                core.as_ref().unwrap().metrics.reused_modules
+            };
+
+            let engines = Engines::default();
+            assert_eq!(core_reused_modules(&engines), 0);
+
+            // A new session restores the dependencies cached by the first one.
+            assert_ne!(core_reused_modules(&Engines::default()), 0);
+
+            // Recompiling with the same engines stores the dependencies missing from the cache.
+            std::fs::remove_dir_all(forc_util::dependency_cache_directory()).unwrap();
+            assert_ne!(core_reused_modules(&engines), 0);
+            assert_ne!(core_reused_modules(&Engines::default()), 0);
+        });
+    }
 }
@@ -95,6 +95,43 @@ pub async fn handle_goto_definition(
     }
 }
 
+pub async fn handle_references(
+    state: &ServerState,
+    params: lsp_types::ReferenceParams,
+) -> Result<Option<Vec<lsp_types::Location>>> {
+    let _ = state.wait_for_parsing().await;
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document_position.text_document.uri)
+        .await
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position.position;
+            Ok(session.token_references(&uri, position, params.context.include_declaration))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
+pub async fn handle_workspace_symbol(
+    state: &ServerState,
+    params: lsp_types::WorkspaceSymbolParams,
+) -> Result<Option<Vec<lsp_types::SymbolInformation>>> {
+    let _ = state.wait_for_parsing().await;
+    let sessions: Vec<_> = state
+        .sessions
+        .iter()
+        .map(|item| item.value().clone())
+        .collect();
+    Ok(Some(capabilities::workspace_symbol::workspace_symbols(
+        &sessions,
+        &params.query,
+    )))
+}
+
 pub async fn handle_completion(
     state: &ServerState,
     params: lsp_types::CompletionParams,
@@ -145,6 +182,28 @@ pub async fn handle_hover(
     }
 }
 
+pub async fn handle_signature_help(
+    state: &ServerState,
+    params: lsp_types::SignatureHelpParams,
+) -> Result<Option<lsp_types::SignatureHelp>> {
+    match state
+        .sessions
+        .uri_and_session_from_workspace(&params.text_document_position_params.text_document.uri)
+        .await
+    {
+        Ok((uri, session)) => {
+            let position = params.text_document_position_params.position;
+            Ok(capabilities::signature_help::signature_help(
+                session, &uri, position,
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{}", err.to_string());
+            Ok(None)
+        }
+    }
+}
+
 pub async fn handle_prepare_rename(
     state: &ServerState,
     params: lsp_types::TextDocumentPositionParams,
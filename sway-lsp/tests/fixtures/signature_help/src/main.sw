@@ -0,0 +1,37 @@
+contract;
+
+/// Adds two numbers.
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+struct Counter {
+    value: u64,
+}
+
+impl Counter {
+    /// Increments the counter by the given amount.
+    fn increment(ref mut self, amount: u64) {
+        self.value = self.value + amount;
+    }
+
+    fn first<T>(self, x: T, y: u64) -> T {
+        x
+    }
+}
+
+abi MyAbi {
+    /// Transfers coins to the recipient.
+    fn transfer(amount: u64, to: b256);
+}
+
+impl MyAbi for Contract {
+    fn transfer(amount: u64, to: b256) {
+        let _ = add(amount, 1);
+        let mut counter = Counter { value: 0 };
+        counter.increment(add(1, 2));
+        let _ = counter.first(true, 2);
+        let caller = abi(MyAbi, 0x0000000000000000000000000000000000000000000000000000000000000000);
+        caller.transfer(1, to);
+    }
+}
@@ -7,7 +7,9 @@ use pretty_assertions::assert_eq;
 use serde_json::json;
 use std::collections::HashMap;
 use sway_lsp::{
-    capabilities::diagnostic::DiagnosticData, handlers::request, server_state::ServerState,
+    capabilities::diagnostic::{DiagnosticData, SuggestionData},
+    handlers::request,
+    server_state::ServerState,
 };
 
 fn create_code_action(
@@ -507,6 +509,7 @@ pub(crate) async fn code_action_auto_import_struct_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("EvmAddress".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -550,6 +553,7 @@ pub(crate) async fn code_action_auto_import_struct_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("DeepStruct".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -597,6 +601,7 @@ pub(crate) async fn code_action_auto_import_enum_request(server: &ServerState, u
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("AuthError".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -640,6 +645,7 @@ pub(crate) async fn code_action_auto_import_enum_request(server: &ServerState, u
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("DeepEnum".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -688,6 +694,7 @@ pub(crate) async fn code_action_auto_import_function_request(server: &ServerStat
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("deep_fun".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -736,6 +743,7 @@ pub(crate) async fn code_action_auto_import_constant_request(server: &ServerStat
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("TEST_CONST".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -781,6 +789,7 @@ pub(crate) async fn code_action_auto_import_trait_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("TryFrom".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -824,6 +833,7 @@ pub(crate) async fn code_action_auto_import_trait_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("DeepTrait".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -870,6 +880,7 @@ pub(crate) async fn code_action_auto_import_alias_request(server: &ServerState,
             range,
             DiagnosticData {
                 unknown_symbol_name: Some("A".to_string()),
+                ..Default::default()
             },
         ),
     );
@@ -909,3 +920,136 @@ pub(crate) async fn code_action_auto_import_alias_request(server: &ServerState,
     let actual = send_request(server, &params).await;
     assert_eq!(expected, actual);
 }
+
+pub(crate) async fn code_action_suggestion_request(server: &ServerState, uri: &Url) {
+    let range = Range {
+        start: Position {
+            line: 34,
+            character: 5,
+        },
+        end: Position {
+            line: 34,
+            character: 12,
+        },
+    };
+    let insert_range = Range {
+        start: Position {
+            line: 34,
+            character: 0,
+        },
+        end: Position {
+            line: 34,
+            character: 0,
+        },
+    };
+
+    let params = create_code_action_params(
+        uri.clone(),
+        range,
+        create_diagnostic_from_data(
+            range,
+            DiagnosticData {
+                suggestions: vec![
+                    SuggestionData {
+                        title: "Insert `#[allow(dead_code)]`".to_string(),
+                        range: insert_range,
+                        replacement: "#[allow(dead_code)]\n".to_string(),
+                        is_machine_applicable: true,
+                    },
+                    SuggestionData {
+                        title: "Replace with `TryInto`".to_string(),
+                        range,
+                        replacement: "TryInto".to_string(),
+                        is_machine_applicable: false,
+                    },
+                ],
+                ..Default::default()
+            },
+        ),
+    );
+    let mut preferred = create_code_action(
+        uri.clone(),
+        "Insert `#[allow(dead_code)]`".to_string(),
+        create_changes_map(uri, insert_range, "#[allow(dead_code)]\n"),
+        None,
+        Some(CodeActionKind::QUICKFIX),
+    );
+    if let CodeActionOrCommand::CodeAction(action) = &mut preferred {
+        action.is_preferred = Some(true);
+    }
+    let expected = vec![
+        preferred,
+        create_code_action(
+            uri.clone(),
+            "Replace with `TryInto`".to_string(),
+            create_changes_map(uri, range, "TryInto"),
+            None,
+            Some(CodeActionKind::QUICKFIX),
+        ),
+    ];
+
+    let actual = send_request(server, &params).await;
+    assert_eq!(expected, actual);
+}
+
+pub(crate) async fn code_action_import_suggestion_request(server: &ServerState, uri: &Url) {
+    // The import suggested by the compiler replaces the one found in the token map.
+    let range = Range {
+        start: Position {
+            line: 8,
+            character: 12,
+        },
+        end: Position {
+            line: 8,
+            character: 22,
+        },
+    };
+    let insert_range = Range {
+        start: Position {
+            line: 6,
+            character: 0,
+        },
+        end: Position {
+            line: 6,
+            character: 0,
+        },
+    };
+    let call_path = "std::vm::evm::evm_address::EvmAddress";
+    let replacement = format!("use {call_path};\n");
+
+    let params = create_code_action_params(
+        uri.clone(),
+        range,
+        create_diagnostic_from_data(
+            range,
+            DiagnosticData {
+                unknown_symbol_name: Some("EvmAddress".to_string()),
+                suggestions: vec![SuggestionData {
+                    title: format!("Insert `use {call_path};`"),
+                    range: insert_range,
+                    replacement: replacement.clone(),
+                    is_machine_applicable: false,
+                }],
+            },
+        ),
+    );
+    let expected = vec![
+        create_code_action(
+            uri.clone(),
+            format!("Insert `use {call_path};`"),
+            create_changes_map(uri, insert_range, &replacement),
+            None,
+            Some(CodeActionKind::QUICKFIX),
+        ),
+        create_code_action(
+            uri.clone(),
+            format!("Qualify as `{}`", call_path),
+            create_changes_for_qualify(uri, 8, 12, 22, call_path),
+            None,
+            Some(CodeActionKind::QUICKFIX),
+        ),
+    ];
+
+    let actual = send_request(server, &params).await;
+    assert_eq!(expected, actual);
+}
@@ -281,6 +281,160 @@ pub(crate) async fn highlight_request(server: &ServerState, uri: &Url) {
     assert_eq!(expected, response.unwrap());
 }
 
+pub(crate) async fn references_request(server: &ServerState, uri: &Url) {
+    let references = |include_declaration| ReferenceParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            position: Position {
+                line: 45,
+                character: 37,
+            },
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+        context: ReferenceContext {
+            include_declaration,
+        },
+    };
+    let declaration = Location::new(
+        uri.clone(),
+        Range::new(Position::new(10, 4), Position::new(10, 10)),
+    );
+    let reference = Location::new(
+        uri.clone(),
+        Range::new(Position::new(45, 35), Position::new(45, 41)),
+    );
+
+    let response = request::handle_references(server, references(true))
+        .await
+        .unwrap();
+    assert_eq!(vec![declaration, reference.clone()], response.unwrap());
+
+    let response = request::handle_references(server, references(false))
+        .await
+        .unwrap();
+    assert_eq!(vec![reference], response.unwrap());
+}
+
+pub(crate) async fn workspace_symbol_request(server: &ServerState, uri: &Url) {
+    let symbols = |query: &str| WorkspaceSymbolParams {
+        partial_result_params: Default::default(),
+        work_done_progress_params: Default::default(),
+        query: query.to_string(),
+    };
+
+    let response = request::handle_workspace_symbol(server, symbols("field_"))
+        .await
+        .unwrap()
+        .unwrap();
+    let names: Vec<_> = response.iter().take(2).map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["field_a", "field_b"]);
+    assert_eq!(response[0].kind, SymbolKind::FIELD);
+    assert_eq!(
+        response[0].location,
+        Location::new(
+            uri.clone(),
+            Range::new(Position::new(35, 4), Position::new(35, 11))
+        )
+    );
+
+    // Matches the characters of the query in order, ignoring case.
+    let response = request::handle_workspace_symbol(server, symbols("numorstr"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(response[0].name, "NumberOrString");
+    assert_eq!(response[0].kind, SymbolKind::ENUM);
+
+    // Declarations of dependencies are included.
+    let response = request::handle_workspace_symbol(server, symbols("StorageKey"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(response.iter().any(|s| s.name == "StorageKey"
+        && s.kind == SymbolKind::STRUCT
+        && s.location
+            .uri
+            .path()
+            .ends_with("sway-lib-core/src/storage.sw")));
+}
+
+pub(crate) async fn signature_help_request(server: &ServerState, uri: &Url) {
+    let signature_help = |line, character| async move {
+        let params = SignatureHelpParams {
+            context: None,
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position: Position { line, character },
+            },
+            work_done_progress_params: Default::default(),
+        };
+        request::handle_signature_help(server, params)
+            .await
+            .unwrap()
+            .unwrap()
+    };
+    let docs = |value: &str| {
+        Some(Documentation::MarkupContent(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: value.to_string(),
+        }))
+    };
+
+    // A function call.
+    let response = signature_help(29, 28).await;
+    assert_eq!(
+        response.signatures[0].label,
+        "fn add(a: u64, b: u64) -> u64"
+    );
+    assert_eq!(
+        response.signatures[0].parameters,
+        Some(vec![
+            ParameterInformation {
+                label: ParameterLabel::LabelOffsets([7, 13]),
+                documentation: None,
+            },
+            ParameterInformation {
+                label: ParameterLabel::LabelOffsets([15, 21]),
+                documentation: None,
+            },
+        ])
+    );
+    assert_eq!(
+        response.signatures[0].documentation,
+        docs("Adds two numbers.")
+    );
+    assert_eq!(response.active_parameter, Some(1));
+
+    // A method call, whose receiver is the `self` parameter.
+    let response = signature_help(31, 35).await;
+    assert_eq!(
+        response.signatures[0].label,
+        "fn increment(ref mut self, amount: u64)"
+    );
+    assert_eq!(response.active_parameter, Some(1));
+
+    // A generic method.
+    let response = signature_help(32, 36).await;
+    assert_eq!(
+        response.signatures[0].label,
+        "fn first<T>(self, x: T, y: u64) -> T"
+    );
+    assert_eq!(response.active_parameter, Some(2));
+
+    // An ABI method.
+    let response = signature_help(34, 27).await;
+    assert_eq!(
+        response.signatures[0].label,
+        "fn transfer(amount: u64, to: b256)"
+    );
+    assert_eq!(
+        response.signatures[0].documentation,
+        docs("Transfers coins to the recipient.")
+    );
+    assert_eq!(response.active_parameter, Some(1));
+}
+
 pub(crate) async fn code_lens_empty_request(server: &ServerState, uri: &Url) {
     let params = CodeLensParams {
         text_document: TextDocumentIdentifier { uri: uri.clone() },
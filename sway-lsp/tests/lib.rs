@@ -1804,6 +1804,21 @@ lsp_capability_test!(
     lsp::highlight_request,
     doc_comments_dir().join("src/main.sw")
 );
+lsp_capability_test!(
+    references,
+    lsp::references_request,
+    doc_comments_dir().join("src/main.sw")
+);
+lsp_capability_test!(
+    workspace_symbol,
+    lsp::workspace_symbol_request,
+    doc_comments_dir().join("src/main.sw")
+);
+lsp_capability_test!(
+    signature_help,
+    lsp::signature_help_request,
+    test_fixtures_dir().join("signature_help/src/main.sw")
+);
 lsp_capability_test!(
     code_action_abi,
     code_actions::code_action_abi_request,
@@ -1864,6 +1879,16 @@ lsp_capability_test!(
     code_actions::code_action_auto_import_alias_request,
     test_fixtures_dir().join("auto_import/src/main.sw")
 );
+lsp_capability_test!(
+    code_action_suggestion,
+    code_actions::code_action_suggestion_request,
+    test_fixtures_dir().join("auto_import/src/main.sw")
+);
+lsp_capability_test!(
+    code_action_import_suggestion,
+    code_actions::code_action_import_suggestion_request,
+    test_fixtures_dir().join("auto_import/src/main.sw")
+);
 lsp_capability_test!(
     code_lens,
     lsp::code_lens_request,
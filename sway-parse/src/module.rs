@@ -28,9 +28,16 @@ impl Parse for ModuleKind {
 
 impl ParseToEnd for Annotated<Module> {
     fn parse_to_end<'a, 'e>(mut parser: Parser<'a, '_>) -> ParseResult<(Self, ParserConsumed<'a>)> {
-        // Parse the attribute list.
+        // Parse the attribute list, made of inner doc comments and inner `#![..]` attributes.
         let mut attribute_list = Vec::new();
-        while let Some(DocComment { .. }) = parser.peek() {
+        loop {
+            if let Some(attr) = parser.guarded_parse::<HashBangToken, AttributeDecl>()? {
+                attribute_list.push(attr);
+                continue;
+            }
+            let Some(DocComment { .. }) = parser.peek() else {
+                break;
+            };
             let doc_comment = parser.parse::<DocComment>()?;
             // TODO: Use a Literal instead of an Ident when Attribute args
             // start supporting them and remove `Ident::new_no_trim`.
@@ -143,4 +150,27 @@ mod tests {
         )
         "###);
     }
+
+    #[test]
+    fn parse_module_inner_attributes() {
+        let module = parse_to_end::<Annotated<Module>>(
+            r#"
+            //! A library.
+            #![allow(dead_code)]
+            #![deny(deprecated)]
+            library;
+        "#,
+        );
+        assert!(module
+            .attribute_list
+            .iter()
+            .all(|attr_decl| matches!(attr_decl.hash_kind, AttributeHashKind::Inner(_))));
+        let names = module
+            .attribute_list
+            .iter()
+            .flat_map(|attr_decl| attr_decl.attribute.get().into_iter())
+            .map(|attr| attr.name.as_str().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![DOC_COMMENT_ATTRIBUTE_NAME, "allow", "deny"]);
+    }
 }
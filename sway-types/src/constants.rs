@@ -44,6 +44,13 @@ pub const PAYABLE_ATTRIBUTE_NAME: &str = "payable";
 pub const ALLOW_ATTRIBUTE_NAME: &str = "allow";
 pub const ALLOW_DEAD_CODE_NAME: &str = "dead_code";
 pub const ALLOW_DEPRECATED_NAME: &str = "deprecated";
+pub const ALLOW_UNCHECKED_ARITHMETIC_NAME: &str = "unchecked_arithmetic";
+pub const ALLOW_UNCHECKED_NARROWING_NAME: &str = "unchecked_narrowing";
+pub const ALLOW_DIVISION_BY_ZERO_NAME: &str = "division_by_zero";
+
+/// The valid attribute strings for setting lint levels, next to `allow`.
+pub const WARN_ATTRIBUTE_NAME: &str = "warn";
+pub const DENY_ATTRIBUTE_NAME: &str = "deny";
 
 /// The valid attribute strings related to conditional compilation.
 pub const CFG_ATTRIBUTE_NAME: &str = "cfg";
@@ -64,6 +71,8 @@ pub const VALID_ATTRIBUTE_NAMES: &[&str] = &[
     INLINE_ATTRIBUTE_NAME,
     PAYABLE_ATTRIBUTE_NAME,
     ALLOW_ATTRIBUTE_NAME,
+    WARN_ATTRIBUTE_NAME,
+    DENY_ATTRIBUTE_NAME,
     CFG_ATTRIBUTE_NAME,
     DEPRECATED_ATTRIBUTE_NAME,
     NAMESPACE_ATTRIBUTE_NAME,
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{span::Span, Spanned};
 
@@ -93,9 +93,14 @@ pub type Ident = BaseIdent;
 
 impl Serialize for Ident {
     // Serialize an `Ident` struct with two fields: `to_string` and `span`.
+    // Non human-readable formats get all the fields instead, so that the `Ident` can be restored.
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         use serde::ser::SerializeStruct;
 
+        if !serializer.is_human_readable() {
+            return (&self.name_override_opt, &self.span, self.is_raw_ident).serialize(serializer);
+        }
+
         let mut state = serializer.serialize_struct("Ident", 2)?;
         state.serialize_field("to_string", &self.to_string())?;
         state.serialize_field("span", &self.span)?;
@@ -103,6 +108,17 @@ impl Serialize for Ident {
     }
 }
 
+impl<'de> Deserialize<'de> for Ident {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (name_override_opt, span, is_raw_ident) = Deserialize::deserialize(deserializer)?;
+        Ok(Ident {
+            name_override_opt,
+            span,
+            is_raw_ident,
+        })
+    }
+}
+
 impl Hash for Ident {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.as_str().hash(state);
@@ -1,4 +1,5 @@
 use crate::{ModuleId, SourceId};
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeSet, HashMap},
     path::PathBuf,
@@ -14,7 +15,7 @@ use std::{
 /// The Source Engine is designed to be thread-safe. Its internal structures are
 /// secured by the RwLock mechanism. This allows its functions to be invoked using
 /// a straightforward non-mutable reference, ensuring safe concurrent access.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SourceEngine {
     next_source_id: RwLock<u32>,
     path_to_source_map: RwLock<HashMap<PathBuf, SourceId>>,
@@ -107,4 +108,20 @@ impl SourceEngine {
         v.sort();
         v
     }
+
+    /// Returns `true` if no source ID has been created yet.
+    pub fn is_empty(&self) -> bool {
+        self.source_to_path_map.read().unwrap().is_empty()
+    }
+
+    /// Replaces the contents of the Source Engine with the contents of `other`.
+    pub fn restore(&self, other: SourceEngine) {
+        *self.next_source_id.write().unwrap() = other.next_source_id.into_inner().unwrap();
+        *self.path_to_source_map.write().unwrap() = other.path_to_source_map.into_inner().unwrap();
+        *self.source_to_path_map.write().unwrap() = other.source_to_path_map.into_inner().unwrap();
+        *self.next_module_id.write().unwrap() = other.next_module_id.into_inner().unwrap();
+        *self.path_to_module_map.write().unwrap() = other.path_to_module_map.into_inner().unwrap();
+        *self.module_to_sources_map.write().unwrap() =
+            other.module_to_sources_map.into_inner().unwrap();
+    }
 }
@@ -1,16 +1,68 @@
-use serde::Serialize;
+use serde::{de, ser, Deserialize, Serialize};
 
 use crate::SourceId;
 
 use {
     lazy_static::lazy_static,
-    std::{cmp, fmt, hash::Hash, sync::Arc},
+    std::{cell::RefCell, cmp, collections::HashMap, fmt, hash::Hash, sync::Arc},
 };
 
 lazy_static! {
     static ref DUMMY_SPAN: Span = Span::new(Arc::from(""), 0, 0, None).unwrap();
 }
 
+thread_local! {
+    static SPAN_SOURCES: RefCell<Option<SpanSources>> = const { RefCell::new(None) };
+}
+
+/// The source code referenced by the spans (de)serialized to and from non human-readable formats.
+#[derive(Default)]
+struct SpanSources {
+    sources: Vec<Arc<str>>,
+    indices: HashMap<*const u8, usize>,
+}
+
+impl SpanSources {
+    fn index_of(&mut self, src: &Arc<str>) -> usize {
+        *self
+            .indices
+            .entry(Arc::as_ptr(src) as *const u8)
+            .or_insert_with(|| {
+                self.sources.push(src.clone());
+                self.sources.len() - 1
+            })
+    }
+}
+
+fn replace_span_sources(sources: Option<SpanSources>) -> Option<SpanSources> {
+    SPAN_SOURCES.with(|cell| cell.replace(sources))
+}
+
+/// Calls `f`, collecting the source code referenced by the spans it serializes to non
+/// human-readable formats, and returns the collected sources along with the result of `f`.
+///
+/// Spans serialized to non human-readable formats, e.g. `bincode`, only store the index of their
+/// source code within the collected sources, so that each source file is stored only once.
+/// Serializing such a span outside of this function fails.
+pub fn collect_span_sources<T>(f: impl FnOnce() -> T) -> (T, Vec<Arc<str>>) {
+    let outer = replace_span_sources(Some(SpanSources::default()));
+    let result = f();
+    let collected = replace_span_sources(outer).unwrap_or_default();
+    (result, collected.sources)
+}
+
+/// Calls `f`, resolving the source code of the spans it deserializes from non human-readable
+/// formats within `sources`, as returned by [collect_span_sources].
+pub fn with_span_sources<T>(sources: Vec<Arc<str>>, f: impl FnOnce() -> T) -> T {
+    let outer = replace_span_sources(Some(SpanSources {
+        sources,
+        indices: HashMap::new(),
+    }));
+    let result = f();
+    replace_span_sources(outer);
+    result
+}
+
 pub struct Position<'a> {
     input: &'a str,
     pos: usize,
@@ -73,13 +125,48 @@ impl Eq for Span {}
 
 impl Serialize for Span {
     // Serialize a tuple two fields: `start` and `end`.
+    // Non human-readable formats also get the index of the source code within the sources
+    // collected by `collect_span_sources` and the `source_id`, so that the span can be restored.
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         use serde::ser::SerializeTuple;
 
-        let mut state = serializer.serialize_tuple(2)?;
-        state.serialize_element(&self.start)?;
-        state.serialize_element(&self.end)?;
-        state.end()
+        if serializer.is_human_readable() {
+            let mut state = serializer.serialize_tuple(2)?;
+            state.serialize_element(&self.start)?;
+            state.serialize_element(&self.end)?;
+            return state.end();
+        }
+
+        let src_index = SPAN_SOURCES
+            .with(|cell| cell.borrow_mut().as_mut().map(|s| s.index_of(&self.src)))
+            .ok_or_else(|| {
+                ser::Error::custom("spans can only be serialized within `collect_span_sources`")
+            })?;
+        (src_index, self.start, self.end, self.source_id).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Span {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return Err(de::Error::custom(
+                "spans can only be deserialized from non human-readable formats",
+            ));
+        }
+
+        let (src_index, start, end, source_id) =
+            <(usize, usize, usize, Option<SourceId>)>::deserialize(deserializer)?;
+        let src = SPAN_SOURCES
+            .with(|cell| {
+                cell.borrow()
+                    .as_ref()
+                    .and_then(|s| s.sources.get(src_index).cloned())
+            })
+            .ok_or_else(|| de::Error::custom("unknown span source"))?;
+        if src.is_empty() && source_id.is_none() {
+            return Ok(Span::dummy());
+        }
+        Span::new(src, start, end, source_id).ok_or_else(|| de::Error::custom("invalid span"))
     }
 }
 
@@ -2,9 +2,10 @@ use std::ops::{Not, Shl, Shr};
 
 use num_bigint::{BigUint, ParseBigIntError, TryFromBigIntError};
 use num_traits::Zero;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Serialize, Deserialize)]
 pub struct U256(BigUint);
 
 impl U256 {
@@ -39,6 +40,10 @@ impl U256 {
         other.0.is_zero().not().then(|| Self(&self.0 / &other.0))
     }
 
+    pub fn checked_rem(&self, other: &U256) -> Option<U256> {
+        other.0.is_zero().not().then(|| Self(&self.0 % &other.0))
+    }
+
     pub fn shr(&self, other: &u64) -> U256 {
         U256((&self.0).shr(other))
     }
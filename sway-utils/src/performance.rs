@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PerformanceMetric {
@@ -12,6 +13,9 @@ pub struct PerformanceData {
     pub bytecode_size: usize,
     pub metrics: Vec<PerformanceMetric>,
     pub reused_modules: u64,
+    /// The number of registers spilled to the stack in each function.
+    #[serde(default)]
+    pub spills: BTreeMap<String, usize>,
 }
 
 #[macro_export]
@@ -16,6 +16,9 @@ pub enum FormatterError {
     CommentError,
     #[error("Error while formatting newline sequences")]
     NewlineSequenceError,
+    /// Inner attributes such as `#![allow(dead_code)]` are formatted now, so this is no longer
+    /// returned.
+    #[deprecated(note = "inner attributes are formatted and no longer cause an error")]
     #[error("Cannot format raw hashbang attribute,\nIf this is intended to be a doc comment try using the `//!` syntax instead")]
     HashBangAttributeError,
     #[error("Error while formatting file with syntax errors")]
@@ -115,10 +115,10 @@ impl Format for AttributeDecl {
         // invariant: attribute lists cannot be empty
         // `#`
         let hash_type_token_span = match &self.hash_kind {
-            AttributeHashKind::Inner(_) => Err(FormatterError::HashBangAttributeError),
-            AttributeHashKind::Outer(hash_token) => Ok(hash_token.span()),
+            AttributeHashKind::Inner(hash_bang_token) => hash_bang_token.span(),
+            AttributeHashKind::Outer(hash_token) => hash_token.span(),
         };
-        write!(formatted_code, "{}", hash_type_token_span?.as_str())?;
+        write!(formatted_code, "{}", hash_type_token_span.as_str())?;
         // `[`
         Self::open_square_bracket(formatted_code, formatter)?;
         let mut regular_attrs = regular_attrs.iter().peekable();
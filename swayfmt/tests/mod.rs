@@ -34,6 +34,21 @@ library;
     )
 }
 
+#[test]
+fn module_lint_attributes_persist() {
+    check(
+        r#"
+//! this is a module level doc comment
+#![allow( dead_code )]
+library;
+        "#,
+        r#"//! this is a module level doc comment
+#![allow(dead_code)]
+library;
+"#,
+    )
+}
+
 #[test]
 fn conserve_pub_mod() {
     check(
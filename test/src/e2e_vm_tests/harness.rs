@@ -332,7 +332,11 @@ pub(crate) async fn compile_and_run_unit_tests(
         }) {
             Ok(Ok(built_tests)) => {
                 let test_filter = None;
-                let tested = built_tests.run(forc_test::TestRunnerCount::Auto, test_filter)?;
+                let tested = built_tests.run(
+                    forc_test::TestRunnerCount::Auto,
+                    test_filter,
+                    forc_test::TestRunOpts::default(),
+                )?;
                 match tested {
                     forc_test::Tested::Package(tested_pkg) => Ok(vec![*tested_pkg]),
                     forc_test::Tested::Workspace(tested_pkgs) => Ok(tested_pkgs),
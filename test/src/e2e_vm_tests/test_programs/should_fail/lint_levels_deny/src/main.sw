@@ -0,0 +1,12 @@
+#![deny(non_snake_case_names)]
+script;
+
+fn never_called() {}
+
+#[warn(dead_code)]
+fn never_called_either() {}
+
+#[allow(dead_code)]
+fn NotSnakeCase() {}
+
+fn main() {}
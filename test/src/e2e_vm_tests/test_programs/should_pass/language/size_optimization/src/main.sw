@@ -0,0 +1,98 @@
+script;
+
+use std::hash::*;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+#[inline(never)]
+fn scale(p: Point, k: u64) -> Point {
+    Point {
+        x: p.x * k + 1,
+        y: p.y * k + 2,
+    }
+}
+
+#[inline(never)]
+fn shift(p: Point, k: u64) -> Point {
+    Point {
+        x: p.x * k + 1,
+        y: p.y * k + 3,
+    }
+}
+
+// Hides the value from the optimizer, so that the calls below are not evaluated at compile time.
+fn opaque(value: u64) -> u64 {
+    asm(r: value) {
+        r: u64
+    }
+}
+
+// `mix` and `mix_more` mostly compile to the same instructions, which are outlined.  Identical
+// functions would be deduplicated instead.
+#[inline(never)]
+fn mix(a: u64, b: u64, c: u64) -> u64 {
+    let x = (a ^ b) + (b & c) * 3 + (a | c) * 5;
+    let y = (x ^ a) + (x & b) * 7 + (x | c) * 11;
+    let z = (y ^ b) + (y & c) * 13 + (y | a) * 17;
+    (z ^ x) + (z & y) + (z | a)
+}
+
+#[inline(never)]
+fn mix_more(a: u64, b: u64, c: u64) -> u64 {
+    let x = (a ^ b) + (b & c) * 3 + (a | c) * 5;
+    let y = (x ^ a) + (x & b) * 7 + (x | c) * 11;
+    let z = (y ^ b) + (y & c) * 13 + (y | a) * 17;
+    (z ^ x) + (z & y) + (z | a) + 1
+}
+
+#[inline(never)]
+fn squares(n: u64) -> Vec<u64> {
+    let mut v = Vec::new();
+    let mut i = 0;
+    while i < n {
+        v.push(i * i);
+        i += 1;
+    }
+    v
+}
+
+#[inline(never)]
+fn sum(v: Vec<u64>) -> u64 {
+    let mut total = 0;
+    let mut i = 0;
+    while i < v.len() {
+        total += v.get(i).unwrap();
+        i += 1;
+    }
+    total
+}
+
+#[inline(never)]
+fn hash_point(p: Point) -> b256 {
+    sha256((p.x, p.y))
+}
+
+#[inline(never)]
+fn hash_point_again(p: Point) -> b256 {
+    sha256((p.x, p.y))
+}
+
+fn main() -> u64 {
+    let p = scale(Point { x: 3, y: 4 }, 5);
+    assert(p.x == 16 && p.y == 22);
+    let q = shift(p, 2);
+    assert(q.x == 33 && q.y == 47);
+
+    assert(hash_point(p) == hash_point_again(Point { x: 16, y: 22 }));
+    assert(hash_point(p) != hash_point(q));
+
+    let s = "a string literal";
+    assert(s.len() == 16);
+
+    assert(mix(opaque(1), opaque(2), opaque(3)) + 1 == mix_more(opaque(1), opaque(2), opaque(3)));
+
+    sum(squares(10)) + q.x + q.y
+}
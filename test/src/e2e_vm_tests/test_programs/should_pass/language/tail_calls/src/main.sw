@@ -0,0 +1,42 @@
+script;
+
+// Hides `v` from the optimizer, so the calls below aren't evaluated at compile time.
+fn opaque(v: u64) -> u64 {
+    asm(r: v) {
+        r: u64
+    }
+}
+
+#[inline(never)]
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+// The outer call to `add` is a tail call, the inner one isn't.
+#[inline(never)]
+fn triple(a: u64) -> u64 {
+    add(a, add(a, a))
+}
+
+#[inline(never)]
+fn step3(a: u64, b: u64) -> u64 {
+    add(a * 2, b)
+}
+
+#[inline(never)]
+fn step2(a: u64, b: u64) -> u64 {
+    step3(b, a + 1)
+}
+
+#[inline(never)]
+fn step1(a: u64) -> u64 {
+    step2(a, a * 3)
+}
+
+// `a` and `b` are kept in registers across the later calls.
+fn main() -> u64 {
+    let a = triple(opaque(5));
+    let b = step1(opaque(7));
+    let c = step1(a);
+    a + b + c
+}
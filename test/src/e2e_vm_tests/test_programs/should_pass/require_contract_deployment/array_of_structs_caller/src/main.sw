@@ -4,9 +4,9 @@ use array_of_structs_abi::{Id, TestContract, Wrapper};
 use std::hash::*;
 
 #[cfg(experimental_new_encoding = false)]
-const CONTRACT_ID = 0xe2a4f86301f8b57ff2c93ce68366669fc2f0926dccd26f9f6550b049cb324a2c;
+const CONTRACT_ID = 0xa42625795e1ee0683e927ce222b01c07225ffe0b40bf35d4e836a478ea889ba5;
 #[cfg(experimental_new_encoding = true)]
-const CONTRACT_ID = 0x18912cbce488c8786f2f6ccf7e1e0cab6341f025f3fe2ecb053de11b93ba9ee8;
+const CONTRACT_ID = 0xdc61397c4864fa0565e9721ab2c9da58aef0c17b1ce91d40583270d9c403d3b3;
 
 fn main() -> u64 {
     let addr = abi(TestContract, CONTRACT_ID);
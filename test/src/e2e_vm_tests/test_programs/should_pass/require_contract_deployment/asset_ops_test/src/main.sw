@@ -7,14 +7,14 @@ use std::constants::DEFAULT_SUB_ID;
 use test_fuel_coin_abi::*;
 
 #[cfg(experimental_new_encoding = false)]
-const FUEL_COIN_CONTRACT_ID = 0x542c6e67e5e8768a2c119a80ddcbd1f8d01110ced16fda37e4aa77ebb6d6cdb9;
+const FUEL_COIN_CONTRACT_ID = 0x6d7542c34afa285c36b3a3ff7f1991d6b5e00f6857a1c7d987830f6b8ae406aa;
 #[cfg(experimental_new_encoding = true)]
-const FUEL_COIN_CONTRACT_ID = 0x819fafa79acf18c418188f02ebd7eb20c03597a9b986bb73284df6b4d501c86f;
+const FUEL_COIN_CONTRACT_ID = 0xf8614975510d23bb9020ce3775c4ed85a8abbd0d0bfc935df0e03a6ea4061742;
 
 #[cfg(experimental_new_encoding = false)]
 const BALANCE_CONTRACT_ID = 0xe50966cd6b1da8fe006e3e876e08f3df6948ce426e1a7cfe49fba411b0a11f89;
 #[cfg(experimental_new_encoding = true)]
-const BALANCE_CONTRACT_ID = 0x3fb29b6614aca92e5036d803e4e90578ac33812c4ff7a3eb6d1857d9e79b826b;
+const BALANCE_CONTRACT_ID = 0x5b37f39ceda82f7dccf7e1253c3ddf1565a964a0171741eed51d15991084c82b;
 
 fn main() -> bool {
     let default_gas = 1_000_000_000_000;
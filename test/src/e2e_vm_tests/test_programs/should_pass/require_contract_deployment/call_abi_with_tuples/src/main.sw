@@ -5,7 +5,7 @@ use abi_with_tuples::*;
 #[cfg(experimental_new_encoding = false)]
 const CONTRACT_ID = 0x1200d031e9c10f8d9bd9dd556a98a0c88e74a4da991047556f78b1bcc1be2ab6;
 #[cfg(experimental_new_encoding = true)]
-const CONTRACT_ID = 0xa6f9dd4eb91fadf782b0a62924f09b798dd4d374f692bb91070caf098c0bc769;
+const CONTRACT_ID = 0x7a826b2ed44b01b9efe00051b161cae94dfefdf1836e0ecaf49b1c2bf18c221e;
 
 fn main() -> bool {
     let the_abi = abi(MyContract, CONTRACT_ID);
@@ -2,9 +2,9 @@ script;
 use basic_storage_abi::{BasicStorage, Quad};
 
 #[cfg(experimental_new_encoding = false)]
-const CONTRACT_ID = 0xd956f6bb7ee577561325f16f51534c001061342972a0bef9c2dcfc6d83919491;
+const CONTRACT_ID = 0x4a94385c2cc73c0d88a4fbf5eecb0a04802ce5f818a9533629a9ef0404b52251;
 #[cfg(experimental_new_encoding = true)]
-const CONTRACT_ID = 0x21c57ccaa695c1744898bc14753a7772d150640e8337f537d23e248a77bdb679;
+const CONTRACT_ID = 0xfa4619756aa2d1ab4ba55a6b6ccaeefbd4595fbaf045451b932fbb2e2db39650;
 
 fn main() -> u64 {
     let addr = abi(BasicStorage, CONTRACT_ID);
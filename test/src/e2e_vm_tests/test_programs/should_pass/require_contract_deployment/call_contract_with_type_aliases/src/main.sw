@@ -3,9 +3,9 @@ script;
 use contract_with_type_aliases_abi::*;
 
 #[cfg(experimental_new_encoding = false)]
-const CONTRACT_ID = 0xbd74e82536dd497dc73b8810ed5750b2b3b5b97a08d31e89b4135cb5360d447d;
+const CONTRACT_ID = 0x2295166ad91d27a85ab3cab4f37daa0ba262ecd408d6e6207233b1c356cf3c3c;
 #[cfg(experimental_new_encoding = true)]
-const CONTRACT_ID = 0x694fb5392d964ff8fcf7f224870f7f60f43721a9770000317660702a3b0a30d8;
+const CONTRACT_ID = 0x87e65ad852d1b7cb82296cda97a4e3b25997b9b440f76af5cc8c5619069e981c;
 
 fn main() {
     let caller = abi(MyContract, CONTRACT_ID);
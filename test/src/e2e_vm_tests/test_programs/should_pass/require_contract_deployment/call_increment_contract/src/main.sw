@@ -3,9 +3,9 @@ script;
 use increment_abi::Incrementor;
 
 #[cfg(experimental_new_encoding = false)]
-const CONTRACT_ID = 0xa1aa9555466ef3c61914e5426973e2257cb4dcd8311ffbbe0e8850a9742f312d;
+const CONTRACT_ID = 0x29c815b10459090dcd69d12e92384ef14719b211b900eecb0d07de94ebd5e351;
 #[cfg(experimental_new_encoding = true)]
-const CONTRACT_ID = 0xfc785a275345b2344f3dafe03cc910d36691de6456e4d3277d5b2b0fe85479b2;
+const CONTRACT_ID = 0x91f130eb0ce09e4eecbe9f6e62328ae9f6cc65193b59fe3abba227e6dea73780;
 
 fn main() -> bool {
     let the_abi = abi(Incrementor, CONTRACT_ID);
@@ -3,9 +3,9 @@ script;
 use storage_enum_abi::*;
 
 #[cfg(experimental_new_encoding = false)]
-const CONTRACT_ID = 0x039f59a5f7ab74f3c75eedaedeabdbff9b8bc5310f44ff10b0344fc316026e7d;
+const CONTRACT_ID = 0x8fc7be4d9759f8be13c939d7316545e16ab78efb98fc443c180f82ade27c37c0;
 #[cfg(experimental_new_encoding = true)]
-const CONTRACT_ID = 0xc12257fb772806169eb2fa2322e68bc33e3c6b53a9942acd03bc837cae7abd66;
+const CONTRACT_ID = 0x8751f30788bc3ecbb4d908e4000451828a1bfdd4e33a36abca20639483aef1d8;
 
 fn main() -> u64 {
     let caller = abi(StorageEnum, CONTRACT_ID);
@@ -5,9 +5,9 @@ use std::constants::BASE_ASSET_ID;
 use context_testing_abi::*;
 
 #[cfg(experimental_new_encoding = false)]
-const CONTRACT_ID = 0x65dae4fedb02e2d70cdb56e2b82d23a2baa69a6acdbf01cc1271c7c1a1abe2cc;
+const CONTRACT_ID = 0x13a9442d6bef5af5803f8aa3b91d0b6520ebfc3ea9dbeeb6104b457b1bb60e5f;
 #[cfg(experimental_new_encoding = true)]
-const CONTRACT_ID = 0x441f75894772629af8addf3bac4b04f92800aa9e509b1a438f9c82d7f5dd7972;
+const CONTRACT_ID = 0x15884d1cb2e6499497b8f5adc011d2bb961110a23e8b78f25008996550a317ff;
 
 fn main() -> bool {
     let gas: u64 = u64::max();
@@ -5,7 +5,7 @@ use nested_struct_args_abi::*;
 #[cfg(experimental_new_encoding = false)]
 const CONTRACT_ID = 0x0fd8fed83ef774a35708706495b49f93254cc5ded343c3bd4416a70c8eb47e01;
 #[cfg(experimental_new_encoding = true)]
-const CONTRACT_ID = 0x4db0b0649c279387dcb6e1b84ac83c3da1b806ab0f539233d4328c0598ed4a70;
+const CONTRACT_ID = 0xf297a47108f19eb981bfee33d9b4c0690932f915c6905e858709c2c9d58dabeb;
 
 fn main() -> bool {
     let caller = abi(NestedStructArgs, CONTRACT_ID);
@@ -4,9 +4,9 @@ use storage_access_abi::*;
 use std::hash::*;
 
 #[cfg(experimental_new_encoding = false)]
-const CONTRACT_ID = 0xcd976bf8d7f3a9b54416c215ee0c732cbae4f9221e281fbc6c6aa8f428f03eb1;
+const CONTRACT_ID = 0xc51ee11f8ffd2c75dda0ae18cf7c07f2f56a58f0d26d8566b2161d26bf4a5149;
 #[cfg(experimental_new_encoding = true)]
-const CONTRACT_ID = 0x3c3c2e05ccf4448075f6008331dc11ccbd434bef6c866e1e4157bb218d2de4ff;
+const CONTRACT_ID = 0x2dd2fa26aef236103247449161c38c9c3f6f09abc6d787b2dbf945020751ff7c;
 
 fn main() -> bool {
     let caller = abi(StorageAccess, CONTRACT_ID);
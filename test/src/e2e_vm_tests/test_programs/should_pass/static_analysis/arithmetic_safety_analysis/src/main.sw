@@ -0,0 +1,62 @@
+contract;
+
+const MAX_AMOUNT: u64 = 1000;
+
+abi Vault {
+    fn deposit(amount: u64, fee: u64) -> u64;
+    fn checked_deposit(amount: u64) -> u64;
+    fn share(total: u64, parts: u64) -> u64;
+    fn checked_share(total: u64, parts: u64) -> u64;
+    fn to_byte(value: u64) -> u8;
+    fn allowed(amount: u64) -> u64;
+}
+
+fn truncate(value: u64) -> u8 {
+    asm(r1: value) {
+        r1: u8
+    }
+}
+
+fn checked_truncate(value: u64) -> u8 {
+    require(value <= 255, 0);
+    asm(r1: value) {
+        r1: u8
+    }
+}
+
+fn average(total: u64, count: u64) -> u64 {
+    total / (count + 1)
+}
+
+impl Vault for Contract {
+    fn deposit(amount: u64, fee: u64) -> u64 {
+        let net = amount;
+        net - fee
+    }
+
+    fn checked_deposit(amount: u64) -> u64 {
+        if amount > MAX_AMOUNT {
+            revert(0);
+        }
+        amount * 2 + average(amount, 4)
+    }
+
+    fn share(total: u64, parts: u64) -> u64 {
+        require(total < MAX_AMOUNT, 0);
+        total / parts + total % 10
+    }
+
+    fn checked_share(total: u64, parts: u64) -> u64 {
+        require(total < MAX_AMOUNT && parts != 0, 0);
+        total / parts
+    }
+
+    fn to_byte(value: u64) -> u8 {
+        truncate(value) + checked_truncate(value)
+    }
+
+    #[allow(unchecked_arithmetic)]
+    fn allowed(amount: u64) -> u64 {
+        amount + 1
+    }
+}
@@ -0,0 +1,56 @@
+library;
+
+use std::storage::storage_api::write;
+
+pub const ADDR: b256 = 0x3dba0a4455b598b7655a7fb430883d96c9527ef275b49739e7b0ad12f8280eae;
+
+abi TestAbi {
+    #[storage(write)]
+    fn deposit();
+
+    #[storage(write)]
+    fn withdraw();
+
+    #[storage(write)]
+    fn settle();
+}
+
+#[storage(write)]
+fn write_balance() {
+    write(ADDR, 0, 42);
+}
+
+#[storage(write)]
+pub fn update_balance() {
+    write_balance();
+}
+
+#[storage(write)]
+pub fn pay_out() {
+    // interaction
+    abi(TestAbi, ADDR).withdraw();
+    // effect -- reached from "withdraw" and reported with the whole call chain
+    update_balance();
+}
+
+pub struct Ledger {}
+
+impl Ledger {
+    #[storage(write)]
+    fn credit(self) {
+        write_balance();
+    }
+}
+
+pub trait Pay {
+    #[storage(write)]
+    fn pay(self);
+}
+
+impl Pay for Ledger {
+    #[storage(write)]
+    fn pay(self) {
+        // effect hidden in an inherent method called from a trait method
+        self.credit();
+    }
+}
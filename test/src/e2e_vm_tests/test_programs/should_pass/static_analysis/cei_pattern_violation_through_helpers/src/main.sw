@@ -0,0 +1,29 @@
+contract;
+
+mod helpers;
+
+use helpers::*;
+
+impl TestAbi for Contract {
+    #[storage(write)]
+    fn deposit() {
+        // interaction
+        abi(TestAbi, ADDR).deposit();
+        // effect hidden in the helpers -- therefore violation of CEI
+        update_balance();
+    }
+
+    #[storage(write)]
+    fn withdraw() {
+        // both the interaction and the effect happen in a library function
+        pay_out();
+    }
+
+    #[storage(write)]
+    fn settle() {
+        // interaction
+        abi(TestAbi, ADDR).settle();
+        // effect hidden in trait and impl methods -- therefore violation of CEI
+        Ledger {}.pay();
+    }
+}
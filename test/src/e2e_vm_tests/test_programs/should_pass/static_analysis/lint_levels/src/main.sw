@@ -0,0 +1,31 @@
+//! Lint levels set for the whole module, for single items and for the whole package.
+#![allow(dead_code)]
+script;
+
+struct NeverUsed {}
+
+#[warn(dead_code)]
+fn never_called() {}
+
+fn NotSnakeCase() {}
+
+#[allow(non_snake_case_names)]
+fn AlsoNotSnakeCase() {}
+
+fn returns_value() -> u64 {
+    42
+}
+
+fn divide(a: u64, b: u64) -> u64 {
+    a / b
+}
+
+#[allow(division_by_zero)]
+fn divide_unchecked(a: u64, b: u64) -> u64 {
+    a / b
+}
+
+fn main() -> u64 {
+    returns_value();
+    divide(10, 2) + divide_unchecked(10, 2)
+}
@@ -0,0 +1,57 @@
+contract;
+
+use std::{hash::Hash, storage::storage_map::*};
+
+storage {
+    // read and written
+    counter: u64 = 0,
+    // only written
+    last_caller_tag: u64 = 0,
+    // only read
+    fee: u64 = 10,
+    // only written, through a helper taking the storage key
+    audit_log: StorageMap<u64, u64> = StorageMap {},
+    // only read, but explicitly allowed
+    #[allow(dead_code)]
+    owner_tag: u64 = 42,
+    // read and written in different entry points
+    balances: StorageMap<u64, u64> = StorageMap {},
+}
+
+abi Counter {
+    #[storage(read, write)]
+    fn increment(tag: u64) -> u64;
+
+    #[storage(read)]
+    fn balance_of(account: u64) -> u64;
+
+    #[storage(read, write)]
+    fn deposit(account: u64, amount: u64);
+}
+
+#[storage(write)]
+fn record(log: StorageKey<StorageMap<u64, u64>>, key: u64, value: u64) {
+    log.insert(key, value);
+}
+
+impl Counter for Contract {
+    #[storage(read, write)]
+    fn increment(tag: u64) -> u64 {
+        let counter = storage.counter;
+        let new_value = counter.read() + storage.fee.read() + storage.owner_tag.read();
+        counter.write(new_value);
+        storage.last_caller_tag.write(tag);
+        new_value
+    }
+
+    #[storage(read)]
+    fn balance_of(account: u64) -> u64 {
+        storage.balances.get(account).try_read().unwrap_or(0)
+    }
+
+    #[storage(read, write)]
+    fn deposit(account: u64, amount: u64) {
+        storage.balances.insert(account, amount);
+        record(storage.audit_log, account, amount);
+    }
+}
@@ -0,0 +1,58 @@
+script;
+
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+fn increment(ref mut value: u64) {
+    value += 1;
+}
+
+impl Point {
+    fn move_right(ref mut self) {
+        self.x += 1;
+    }
+}
+
+fn main() -> u64 {
+    let mut never_changed = 1;
+    let mut reassigned = 2;
+    reassigned = 3;
+
+    let mut field_reassigned = Point { x: 0, y: 0 };
+    field_reassigned.y = 1;
+
+    let mut passed_by_ref_mut = 4;
+    increment(passed_by_ref_mut);
+
+    let mut method_receiver = Point { x: 0, y: 0 };
+    method_receiver.move_right();
+
+    let mut array = [1, 2, 3];
+    array[0] = 4;
+
+    let mut shadowed = 5;
+    {
+        let mut shadowed = 6;
+        shadowed = 7;
+        let _ = shadowed;
+    }
+
+    let mut counter = 0;
+    while counter < 10 {
+        counter += 1;
+    }
+
+    let mut in_asm = 8;
+    let _ = asm(r: in_asm) {
+        r: u64
+    };
+
+    let mut v = Vec::new();
+    v.push(1);
+
+    let (mut destructured, _) = (9, 10);
+
+    never_changed + reassigned + field_reassigned.y + passed_by_ref_mut + method_receiver.x + array[0] + shadowed + counter + in_asm + v.len() + destructured
+}
@@ -0,0 +1,34 @@
+library;
+
+struct Point {
+    x: u8,
+    y: u64,
+}
+
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+#[test]
+fn add_is_commutative(a: u32, b: u32) {
+    assert(add(a.as_u64(), b.as_u64()) == add(b.as_u64(), a.as_u64()));
+}
+
+#[test(runs = 32)]
+fn point_fields(p: Point, flag: bool) {
+    assert(p.x.as_u64() <= 255);
+    if flag {
+        assert(p.y == p.y);
+    }
+}
+
+#[test]
+fn array_and_b256(bytes: [u8; 4], id: b256) {
+    assert(bytes[3].as_u64() < 256);
+    assert(id == id);
+}
+
+#[test(should_revert, runs = 8)]
+fn always_reverts(a: u64) {
+    assert(a != a);
+}
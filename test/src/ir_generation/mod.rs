@@ -40,7 +40,9 @@ impl Checker {
     /// # ::check-ir-optimized::
     ///
     /// Optimized IR chekcer can be configured with `pass: <PASSNAME or o1>`. When
-    /// `o1` is chosen, all the configured passes are chosen automatically.
+    /// `o1` is chosen, all the configured passes are chosen automatically. Any pass
+    /// pipeline is accepted too, e.g. `pass: repeat(constcombine,simplifycfg)`, and
+    /// the lines are run in order.
     ///
     /// ```
     /// // ::check-ir-optimized::
@@ -347,20 +349,18 @@ pub(super) async fn run(
                                 panic!("No optimization passes were specified for ::check-ir-optimized::. Use `// pass: <PASSNAME>` in the very next line.");
                             }
 
+                            let mut pass_mgr = PassManager::default();
+                            register_known_passes(&mut pass_mgr);
+
+                            // Every pass line is a pipeline, usually a single pass or `o1`.
                             let mut group = PassGroup::default();
                             for pass in passes {
-                                if pass == "o1" {
-                                    group = sway_ir::create_o1_pass_group();
-                                } else {
-                                    // pass needs a 'static str
-                                    let pass = Box::leak(Box::new(pass));
-                                    group.append_pass(pass.as_str());
-                                }
+                                let pipeline = pass_mgr
+                                    .parse_pipeline(&pass)
+                                    .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+                                group.append_group(pipeline);
                             }
 
-                            let mut pass_mgr = PassManager::default();
-                            register_known_passes(&mut pass_mgr);
-
                             // Parse the IR again avoiding mutating the original ir
                             let mut ir = sway_ir::parser::parse(
                                 &ir_output,
@@ -534,6 +534,8 @@ fn compile_core(
         offline_mode: true,
         terse_mode: true,
         disable_tests: false,
+        watch: false,
+        fix: false,
         locked: false,
         ipfs_node: None,
         experimental_new_encoding: experimental.new_encoding,
@@ -0,0 +1,26 @@
+script;
+
+fn select(flag: bool, a: u64, b: u64) -> u64 {
+    if flag { a } else { b }
+}
+
+fn scale<T>(value: u64, factor: u64) -> u64 {
+    if factor == 0 { value } else { value * factor }
+}
+
+fn main() -> u64 {
+    let x = 3;
+    let y = if x > 2 { 10 } else { 20 };
+    scale::<u8>(select(true, y, 7), 4)
+}
+
+// check: fn main
+
+// ::check-ir-optimized::
+// pass: mem2reg
+// pass: sccp
+
+// check: fn main
+// not: call
+// check: $(v=$VAL) = const u64 40
+// check: ret u64 $v